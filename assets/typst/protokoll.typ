@@ -0,0 +1,87 @@
+// MZProtokoll – Typst-Vorlage
+//
+// Diese Vorlage wird jedem Typst-Export vorangestellt. Eine angepasste Kopie
+// lässt sich über `typst_vorlage` in einstellungen.toml einbinden; sie muss die
+// Funktion `protokoll(daten)` bereitstellen. Felder von `daten`:
+//   sprache ("de" oder "en"), beschriftung (Dictionary mit seite, von, art, notiz,
+//   kuemmerer, bis, naechstes in der PDF-Sprache),
+//   projekt, titel, meta (str), info (Array aus (Bezeichnung, Inhalt)),
+//   eintraege (Array aus Dictionaries mit punkt, art, farbe, notiz, kuemmerer,
+//   bis, todo, wortbeitrag), naechstes (none oder Dictionary mit termin, agenda)
+
+#let protokoll(daten) = {
+  set document(title: daten.titel)
+  set page(
+    paper: "a4",
+    margin: (x: 15mm, top: 15mm, bottom: 20mm),
+    header: context if counter(page).get().first() > 1 [
+      #set text(8pt, fill: luma(110))
+      #daten.projekt #h(1fr) #daten.titel
+    ],
+    footer: context [
+      #set text(8pt)
+      #h(1fr) #daten.beschriftung.seite #counter(page).display() #daten.beschriftung.von #counter(page).final().first()
+    ],
+  )
+  set text(lang: daten.sprache, size: 9pt)
+  set par(justify: false)
+  show link: set text(fill: rgb("#1f5fbf"))
+
+  if daten.projekt != "" {
+    text(10pt, fill: luma(90), daten.projekt)
+    linebreak()
+  }
+  text(16pt, weight: "bold", daten.titel)
+  if daten.meta != "" {
+    linebreak()
+    daten.meta
+  }
+  line(length: 100%, stroke: 0.5pt)
+
+  grid(
+    columns: (28%, 1fr),
+    row-gutter: 5pt,
+    ..daten.info.map(((name, inhalt)) => (text(weight: "bold", name), inhalt)).flatten(),
+  )
+  line(length: 100%, stroke: 0.5pt)
+
+  // Zusammenhängende Tabellenzeilen sammeln; Wortbeiträge unterbrechen die Tabelle
+  let b = daten.beschriftung
+  let zeilen = ()
+  let tabelle(zeilen) = table(
+    columns: (auto, auto, 1fr, auto, auto),
+    stroke: (x, y) => (bottom: 0.3pt + luma(180)),
+    inset: 4pt,
+    table.header(..([], b.art, b.notiz, b.kuemmerer, b.bis).map(t => text(weight: "bold", t))),
+    ..zeilen,
+  )
+  for e in daten.eintraege {
+    if e.wortbeitrag {
+      if zeilen.len() > 0 {
+        tabelle(zeilen)
+        zeilen = ()
+      }
+      par[*#(e.kuemmerer):* #e.notiz]
+    } else {
+      let stark(inhalt) = if e.todo { strong(inhalt) } else { inhalt }
+      zeilen += (
+        stark(e.punkt),
+        text(fill: e.farbe, weight: "bold", e.art),
+        stark(e.notiz),
+        stark(e.kuemmerer),
+        stark(e.bis),
+      )
+    }
+  }
+  if zeilen.len() > 0 {
+    tabelle(zeilen)
+  }
+
+  if daten.naechstes != none {
+    heading(level: 2, b.naechstes)
+    if daten.naechstes.termin != "" [#daten.naechstes.termin]
+    if daten.naechstes.agenda.len() > 0 {
+      list(..daten.naechstes.agenda)
+    }
+  }
+}
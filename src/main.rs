@@ -26,6 +26,33 @@ fn url_oeffnen(url: &str) {
     let _ = std::process::Command::new("xdg-open").arg(url).spawn();
 }
 
+/// Zeigt eine Desktop-Benachrichtigung (`notify-send` unter Linux, Mitteilung unter
+/// macOS). Gibt `false` zurück, wenn keine angezeigt werden konnte (z. B. unter Windows).
+fn desktop_benachrichtigung(titel: &str, text: &str) -> bool {
+    let mut kommando = if cfg!(target_os = "macos") {
+        let mut kommando = std::process::Command::new("osascript");
+        kommando.arg("-e").arg(format!("display notification {:?} with title {:?}", text, titel));
+        kommando
+    } else if cfg!(windows) {
+        return false;
+    } else {
+        let mut kommando = std::process::Command::new("notify-send");
+        kommando.args(["--app-name=MZProtokoll", titel, text]);
+        kommando
+    };
+    kommando.status().is_ok_and(|s| s.success())
+}
+
+/// Kodiert einen Text für URLs (Prozent-Kodierung nach RFC 3986).
+fn url_kodieren(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
 /// Erstellt eine fette Schrift mit der angegebenen Größe (in Punkten).
 fn fette_schrift(groesse: f32) -> egui::FontId {
     egui::FontId::new(groesse, egui::FontFamily::Name("Bold".into()))
@@ -65,7 +92,942 @@ fn omarchy_farben_laden() -> Option<HashMap<String, egui::Color32>> {
     Some(colors)
 }
 
+/// Relative Leuchtdichte einer Farbe nach WCAG 2.x (0 = schwarz, 1 = weiß).
+fn leuchtdichte(farbe: egui::Color32) -> f32 {
+    let kanal = |c: u8| {
+        let c = c as f32 / 255.0;
+        if c <= 0.03928 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    };
+    0.2126 * kanal(farbe.r()) + 0.7152 * kanal(farbe.g()) + 0.0722 * kanal(farbe.b())
+}
+
+/// Kontrastverhältnis zweier Farben nach WCAG (1:1 bis 21:1).
+fn kontrast(a: egui::Color32, b: egui::Color32) -> f32 {
+    let (la, lb) = (leuchtdichte(a), leuchtdichte(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Mischt zwei Farben linear (`anteil` = Gewicht von `b`, 0–1).
+fn farben_mischen(a: egui::Color32, b: egui::Color32, anteil: f32) -> egui::Color32 {
+    let m = |x: u8, y: u8| (x as f32 + (y as f32 - x as f32) * anteil).round() as u8;
+    egui::Color32::from_rgb(m(a.r(), b.r()), m(a.g(), b.g()), m(a.b(), b.b()))
+}
+
+/// Mindestkontrast von Art-Farben und Linien gegenüber dem Hintergrund
+/// (WCAG-Grenze für grafische Elemente und große Schrift).
+const MINDESTKONTRAST: f32 = 3.0;
+
+/// Aus der Theme-Palette abgeleitete Farben der Eintragstabelle.
+/// Ohne Palette (Hell/Dunkel) gelten die festen Standardfarben.
+#[derive(Clone, Default)]
+struct TabellenFarben {
+    /// Linie unter der Kopfzeile (`None` = Standardgrau).
+    kopflinie: Option<egui::Color32>,
+    /// Hintergrund jeder zweiten Zeile (`None` = keine Zebra-Streifen).
+    zebra: Option<egui::Color32>,
+    /// Rasterlinien zwischen den Zeilen (`None` = keine Linien).
+    raster: Option<egui::Color32>,
+    /// Art-Farben in der Reihenfolge von `Art::all()` (leer = `Art::color`).
+    art: Vec<egui::Color32>,
+}
+
+impl TabellenFarben {
+    /// Leitet die Tabellenfarben aus einer Omarchy-Palette ab. Art-Farben folgen den
+    /// ANSI-Farben des Terminals; unterschreitet eine Farbe `MINDESTKONTRAST`
+    /// gegenüber dem Hintergrund, bleibt die Standardfarbe bzw. der Vordergrund.
+    fn aus_palette(farben: &HashMap<String, egui::Color32>) -> Self {
+        let Some(&hintergrund) = farben.get("background") else {
+            return Self::default();
+        };
+        let vordergrund = farben.get("foreground").copied().unwrap_or_else(|| {
+            if leuchtdichte(hintergrund) > 0.5 { egui::Color32::BLACK } else { egui::Color32::WHITE }
+        });
+        let lesbar = |kandidaten: &[Option<egui::Color32>]| {
+            kandidaten
+                .iter()
+                .flatten()
+                .copied()
+                .find(|&f| kontrast(f, hintergrund) >= MINDESTKONTRAST)
+                .unwrap_or(vordergrund)
+        };
+        let art = Art::all()
+            .iter()
+            .map(|art| {
+                let schluessel = match art {
+                    Art::Abgebrochen => "color1",
+                    Art::Fertig => "color2",
+                    Art::Idee => "color3",
+                    Art::Entscheidung => "color4",
+                    Art::Agenda => "color5",
+                    Art::Wortbeitrag => "color6",
+                    Art::Frage => "color13",
+                    Art::Todo => "color11",
+                    Art::Info | Art::Leer => "color8",
+                };
+                lesbar(&[farben.get(schluessel).copied(), Some(art.color())])
+            })
+            .collect();
+        Self {
+            kopflinie: Some(lesbar(&[farben.get("color8").copied(), Some(farben_mischen(hintergrund, vordergrund, 0.5))])),
+            zebra: Some(farben_mischen(hintergrund, vordergrund, 0.05)),
+            raster: Some(farben_mischen(hintergrund, vordergrund, 0.15)),
+            art,
+        }
+    }
+
+    /// Übernimmt eigene Art-Farben aus den Einstellungen; sie haben Vorrang vor
+    /// Palette und Standardfarben.
+    fn anpassen(&mut self, einstellungen: &Einstellungen, projekt: &str) {
+        self.art = Art::all()
+            .iter()
+            .map(|a| einstellungen.art_farbe(projekt, a).unwrap_or_else(|| self.art(a)))
+            .collect();
+    }
+
+    /// Farbe einer Eintragsart im aktuellen Theme.
+    fn art(&self, art: &Art) -> egui::Color32 {
+        Art::all()
+            .iter()
+            .position(|a| a == art)
+            .and_then(|i| self.art.get(i))
+            .copied()
+            .unwrap_or_else(|| art.color())
+    }
+}
+
+/// Beschriftung einer Art aus der vorberechneten Liste `art_labels`
+/// (Reihenfolge `Art::all()`); ohne Eintrag gilt `Art::label`.
+fn art_beschriftung<'a>(labels: &'a [String], art: &'a Art) -> &'a str {
+    Art::all()
+        .iter()
+        .position(|a| a == art)
+        .and_then(|i| labels.get(i))
+        .map(|l| l.as_str())
+        .unwrap_or_else(|| art.label())
+}
+
+/// Gibt das Basisverzeichnis von MZProtokoll zurück
+/// (`~/.config/mzprotokoll` unter Linux, `%APPDATA%\mzprotokoll` unter Windows).
+fn konfig_basis() -> Option<std::path::PathBuf> {
+    #[cfg(windows)]
+    let basis = std::env::var("APPDATA").ok().map(std::path::PathBuf::from)?;
+    #[cfg(not(windows))]
+    let basis = std::path::PathBuf::from(std::env::var("HOME").ok()?).join(".config");
+    Some(basis.join("mzprotokoll"))
+}
+
+/// Name des aktiven Profils aus der Datei `aktives_profil` im Basisverzeichnis
+/// (leer = Standardprofil).
+fn aktives_profil() -> String {
+    konfig_basis()
+        .and_then(|d| std::fs::read_to_string(d.join("aktives_profil")).ok())
+        .map(|name| name.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Aktiviert ein Profil (leer = Standardprofil) und legt sein Verzeichnis an.
+fn aktives_profil_setzen(name: &str) -> std::io::Result<()> {
+    let basis = konfig_basis().ok_or_else(|| std::io::Error::other("Kein Konfigurationsverzeichnis"))?;
+    std::fs::create_dir_all(basis.join("profile").join(name))?;
+    std::fs::write(basis.join("aktives_profil"), name)
+}
+
+/// Namen aller angelegten Profile (Unterordner von `profile/`), alphabetisch.
+fn profile_auflisten() -> Vec<String> {
+    let mut namen: Vec<String> = konfig_basis()
+        .and_then(|d| std::fs::read_dir(d.join("profile")).ok())
+        .map(|eintraege| {
+            eintraege
+                .flatten()
+                .filter(|e| e.path().is_dir())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    namen.sort_by_key(|n| n.to_lowercase());
+    namen
+}
+
+/// Gibt das Konfigurationsverzeichnis des aktiven Profils zurück: beim Standardprofil
+/// das Basisverzeichnis selbst, sonst `profile/<Name>`. Einstellungen, Benutzerprofil,
+/// Vorlagen, Beschlussregister und Versandstände sind so je Profil getrennt.
+fn konfig_verzeichnis() -> Option<std::path::PathBuf> {
+    let basis = konfig_basis()?;
+    let profil = aktives_profil();
+    if profil.is_empty() {
+        Some(basis)
+    } else {
+        Some(basis.join("profile").join(profil))
+    }
+}
+
+/// Standard-Zuordnung Protokollfeld → XML-Elementname für den XML-Export.
+/// Jeder Eintrag kann in `einstellungen.toml` über `xml.<feld> = "Name"` überschrieben
+/// werden; ein leerer Name blendet das Feld im Export aus.
+const XML_STANDARD_MAPPING: &[(&str, &str)] = &[
+    ("wurzel", "protokoll"),
+    ("projekt", "projekt"),
+    ("titel", "titel"),
+    ("datum", "datum"),
+    ("protokoll_datum", "protokoll_datum"),
+    ("ort", "ort"),
+    ("protokollfuehrer", "protokollfuehrer"),
+    ("teilnehmer", "teilnehmer"),
+    ("zur_kenntnis", "zur_kenntnis"),
+    ("person", "person"),
+    ("ueber_meeting", "ueber_meeting"),
+    ("status", "status"),
+    ("klassifizierung", "klassifizierung"),
+    ("eintraege", "eintraege"),
+    ("eintrag", "eintrag"),
+    ("punkt", "punkt"),
+    ("art", "art"),
+    ("notiz", "notiz"),
+    ("kuemmerer", "kuemmerer"),
+    ("bis", "bis"),
+    ("beschluss_nr", "beschluss_nr"),
+    ("erstellt_am", "erstellt_am"),
+    ("erstellt_von", "erstellt_von"),
+    ("pdf_datei", "pdf_datei"),
+];
+
+/// Standardpunkte der Checkliste „Protokoll versandfertig", falls in
+/// `einstellungen.toml` keine eigenen Punkte (`checkliste.<n>`) hinterlegt sind.
+const STANDARD_CHECKLISTE: &[&str] = &[
+    "Rechtschreibung geprüft",
+    "Freigabe erteilt",
+    "TODOs vollständig",
+    "Verteiler gepflegt",
+];
+
+/// Standard-Betreff für den E-Mail-Versand (Platzhalter siehe `email_platzhalter_ersetzen`).
+const STANDARD_EMAIL_BETREFF: &str = "Protokoll: {titel} ({datum})";
+
+/// Standard-Text für den E-Mail-Versand.
+const STANDARD_EMAIL_TEXT: &str = "Hallo zusammen,\n\nanbei das Protokoll „{titel}“ vom {datum}.\n\nOffene TODOs:\n{offene_todos}\n\n{signatur}";
+
+/// Dauerhafte Benutzereinstellungen aus `einstellungen.toml` im Konfigurationsverzeichnis.
+/// Das Format entspricht der Omarchy-Farbdatei: eine Zeile `schluessel = "wert"` je Einstellung.
+struct Einstellungen {
+    /// `true` = beim PDF-Export zusätzlich eine XML-Begleitdatei (gleicher Name, `.xml`) schreiben.
+    xml_begleitdatei: bool,
+    /// Überschriebene XML-Elementnamen (Schlüssel ohne `xml.`-Präfix).
+    xml_mapping: HashMap<String, String>,
+    /// Feste Werte für Vorlagen-Platzhalter (Schlüssel `platzhalter.<name>`),
+    /// z. B. `platzhalter.projektleiter = "Anna Beispiel"`.
+    platzhalter: HashMap<String, String>,
+    /// `true` = beim PDF-Export die Anlage „Offene-Punkte-Liste" anhängen.
+    pdf_offene_punkte: bool,
+    /// `true` = Links als eigene Seite „Quellen & Referenzen" am PDF-Ende statt direkt
+    /// unter der Tabelle (im Exportdialog umschaltbar).
+    pdf_quellenseite: bool,
+    /// Punkte der Versand-Checkliste (Schlüssel `checkliste.<n>`, nach `n` sortiert).
+    checkliste: Vec<String>,
+    /// `true` = dem PDF ein Deckblatt voranstellen.
+    pdf_deckblatt: bool,
+    /// Pfad zu einem Logo (PNG/JPEG) für das Deckblatt (leer = kein Logo).
+    pdf_logo: String,
+    /// Kopfzeile ab Seite 2 mit den Platzhaltern `{projekt}`, `{titel}`, `{datum}`,
+    /// `{ort}` und `{klassifizierung}` (leer = keine Kopfzeile).
+    pdf_kopfzeile: String,
+    /// Ausrichtung der Kopfzeile (`links`, `mitte` oder `rechts`).
+    pdf_kopfzeile_ausrichtung: genpdf::Alignment,
+    /// Vorausgewähltes Dichte-Profil im PDF-Exportdialog.
+    pdf_profil: PdfProfil,
+    /// Vorausgewählte Sprache der PDF-Beschriftungen (`pdf_sprache = "de"|"en"`).
+    pdf_sprache: PdfSprache,
+    /// Spaltengewichte der PDF-Tabelle, sofern das Protokoll keine eigenen mitbringt.
+    pdf_spalten: PdfSpalten,
+    /// Stärke der Trennlinien im PDF in mm.
+    pdf_trennlinie_staerke: f64,
+    /// Farbe der Trennlinien im PDF.
+    pdf_trennlinie_farbe: genpdf::style::Color,
+    /// `true` = erfasste Bearbeitungszeit als `**Bearbeitungszeit:**` in der Datei speichern.
+    zeiterfassung_speichern: bool,
+    /// `true` = beim Start im Touch-Modus (große Bedienelemente, einspaltige Einträge).
+    touch_modus: bool,
+    /// Kopffeld, das nach dem Start und nach „Neu" den Fokus erhält (`erst_fokus`,
+    /// `None` = keins).
+    erst_fokus: Option<KopfFeld>,
+    /// Automatische Nummerierung der Agenda-TOPs (`top_nummerierung`).
+    top_nummerierung: TopNummerierung,
+    /// Backend der PDF-Erzeugung (`pdf_backend`).
+    pdf_backend: PdfBackendArt,
+    /// Kommando des HTML-Backends mit den Platzhaltern `{html}` und `{pdf}`
+    /// (`pdf_html_kommando`), ohne Shell an Leerzeichen zerlegt.
+    pdf_html_kommando: String,
+    /// Eigene Typst-Vorlage (`typst_vorlage`); leer = mitgelieferte Vorlage.
+    typst_vorlage: String,
+    /// Typst-Compiler mit den Platzhaltern `{typ}` und `{pdf}` (`typst_kommando`).
+    typst_kommando: String,
+    /// Beim Typst-Export zusätzlich das PDF kompilieren (`typst_kompilieren`).
+    typst_kompilieren: bool,
+    /// Kind-Einträge als `(Art, Notiz)`, die unter jedem Agenda-TOP einer Vorlage angelegt
+    /// werden, je Projekt (Schlüssel wie bei `email_betreff`), z. B. `top_vorbelegung`
+    /// oder `projekt.Vorstand.top_vorbelegung`.
+    top_vorbelegung: HashMap<String, Vec<(Art, String)>>,
+    /// Organisations-Präfixe für Kürzel als `(Präfix, Beschreibung)`, z. B. `EXT` für
+    /// Externe (`kuerzel_praefixe = "EXT: Externe, KD: Kunde"`). Nur diese Präfixe
+    /// werden in Kürzeln wie `EXT-AB` erkannt und abgesetzt dargestellt.
+    kuerzel_praefixe: Vec<(String, String)>,
+    /// Schema für automatisch erzeugte Kürzel (`kuerzel_schema`) mit den Platzhaltern
+    /// `{initialen}`, `{vorname}` und `{nachname}`, optional mit Länge (`{nachname:2}`).
+    kuerzel_schema: String,
+    /// Feiertage für Arbeitstage und Fälligkeiten (`bundesland`, `feiertage_zusaetzlich`).
+    feiertage: Feiertagskalender,
+    /// Wege der Erinnerung „Protokoll finalisieren bis …" nach dem Meeting
+    /// (`finalisierung_erinnerung`, leer = keine Erinnerung).
+    finalisierung_erinnerung: Vec<Erinnerungsweg>,
+    /// Frist für das Finalisieren in Arbeitstagen nach dem Meeting.
+    finalisierung_frist: u32,
+    /// Verzeichnis, das beim Start nach heute begonnenen Protokollen durchsucht wird
+    /// (leer = aktuelles Arbeitsverzeichnis).
+    arbeitsverzeichnis: String,
+    /// `true` = ein weiterer Programmstart übergibt seine Datei an die laufende Instanz
+    /// (`einzelinstanz`, abschaltbar mit `"false"` oder je Start mit `--neue-instanz`).
+    einzelinstanz: bool,
+    /// Änderungen bei bekanntem Speicherpfad automatisch in die Datei schreiben
+    /// (`auto_speichern`).
+    auto_speichern: bool,
+    /// Ruhezeit in Sekunden nach der letzten Änderung, bevor automatisch gespeichert
+    /// wird (`auto_speichern_sekunden`, mindestens 1).
+    auto_speichern_sekunden: u64,
+    /// Zielverzeichnis für „Schnell exportieren" (leer = Ordner der geöffneten Datei).
+    export_verzeichnis: String,
+    /// Dateiname ohne Endung für „Schnell exportieren" mit den Platzhaltern
+    /// `{projekt}`, `{titel}` und `{datum}` (JJJJ-MM-TT).
+    export_namensmuster: String,
+    /// Multiplikator der Scrollweite für Mausräder (Ereignisse in Zeilen).
+    scroll_faktor_mausrad: f32,
+    /// Multiplikator der Scrollweite für Touchpads (Ereignisse in Punkten).
+    scroll_faktor_touchpad: f32,
+    /// Scrollweite einer Mausrad-Raste in Punkten.
+    mausrad_zeilenhoehe: f32,
+    /// `true` = Mausrad-Scrollen über mehrere Frames glätten (egui-Standard).
+    scroll_glaetten: bool,
+    /// Wochen, die die Fälligkeits-Zeitleiste unter der Tabelle abdeckt (0 = keine Zeitleiste).
+    zeitleiste_wochen: u32,
+    /// Eigene Beschriftungen und Farben der Arten (global und je Projekt).
+    art_anpassungen: Vec<ArtAnpassung>,
+    /// vdir-Kalender für „In Kalender eintragen", z. B.
+    /// `~/.local/share/calendars/arbeit/default` (leer = nicht eingerichtet).
+    kalender_vdir: String,
+    /// URL der CalDAV-Aufgabenliste für „Aufgaben synchronisieren", z. B.
+    /// `https://cloud.example.org/remote.php/dav/calendars/anna/aufgaben/` (leer = aus).
+    caldav_aufgaben_url: String,
+    /// Benutzername für CalDAV; das Passwort liegt im Schlüsselbund (Konto `caldav`).
+    caldav_benutzer: String,
+    /// Matrix-Homeserver, z. B. `https://matrix.example.org` (leer = keine Matrix-Anbindung).
+    matrix_homeserver: String,
+    /// Raum-ID für Zusammenfassungen, z. B. `!abcdef:example.org`; das Zugangstoken
+    /// liegt im Schlüsselbund (Konto `matrix`).
+    matrix_raum: String,
+    /// Redmine-Server, z. B. `https://redmine.example.org` (leer = keine Redmine-Anbindung);
+    /// der API-Key liegt im Schlüsselbund (Konto `redmine`).
+    redmine_url: String,
+    /// Redmine-Projektkennung für neue Tickets je Projekt (Schlüssel wie bei
+    /// `email_betreff`), z. B. `redmine_projekt` oder `projekt.Website.redmine_projekt`.
+    redmine_projekt: HashMap<String, String>,
+    /// Betreff-Vorlagen für den E-Mail-Versand je Projekt (Schlüssel in Kleinbuchstaben,
+    /// `""` = global), z. B. `email_betreff` oder `projekt.Website.email_betreff`.
+    email_betreff: HashMap<String, String>,
+    /// Text-Vorlagen für den E-Mail-Versand (`\n` für Zeilenumbrüche), wie `email_betreff`.
+    email_text: HashMap<String, String>,
+    /// OpenAI-kompatibler Chat-Endpunkt eines lokalen Sprachmodells für „Zusammenfassung
+    /// erzeugen", z. B. `http://localhost:11434/v1/chat/completions` (leer = regelbasiert).
+    llm_endpunkt: String,
+    /// Modellname für `llm_endpunkt`.
+    llm_modell: String,
+    /// Angebotene Status-Zustände (`status_zustaende`, kommagetrennt); Entwurf und
+    /// Freigegeben sind immer enthalten.
+    status_zustaende: Vec<Status>,
+    /// Wurzel der Ablage für „Archivieren" (leer = nicht eingerichtet).
+    archiv_verzeichnis: String,
+    /// Unterordner im Archiv mit den Platzhaltern `{projekt}`, `{jahr}` und `{monat}`.
+    archiv_schema: String,
+    /// Schritte, die beim Statuswechsel auf „Freigegeben" nacheinander ablaufen
+    /// (`freigabe_pipeline`, kommagetrennt, z. B. `pruefen, archiv, webhook, schreibschutz`;
+    /// leer = keine Pipeline).
+    freigabe_pipeline: Vec<PipelineSchritt>,
+    /// URL, an die der Pipeline-Schritt `webhook` die Freigabe als JSON sendet.
+    freigabe_webhook: String,
+    /// Kommando, das Geheimnisse vor dem Schlüsselbund liefert, z. B.
+    /// `pass show mzprotokoll/{konto}` (leer = nur Schlüsselbund).
+    passwort_kommando: String,
+    /// Exportprofile in Anzeigereihenfolge; „Kundenfassung" ist eingebaut.
+    export_profile: Vec<ExportProfil>,
+    /// Umwandlung nach PDF/A mit den Platzhaltern `{pdf}` (Quelle) und `{ziel}`
+    /// (`pdfa_kommando`), ohne Shell an Leerzeichen zerlegt.
+    pdfa_kommando: String,
+    /// Programm für den Passwortschutz (`qpdf_programm`); die Argumente samt Passwort
+    /// erhält es über eine Argumentdatei.
+    qpdf_programm: String,
+}
+
+/// Abweichende Beschriftung bzw. Farbe einer Art, z. B. `art.ENTSCHEIDUNG.label = "BESCHLUSS"`
+/// oder projektbezogen `projekt.Website.art.ENTSCHEIDUNG.farbe = "#8e44ad"`.
+struct ArtAnpassung {
+    /// Projekt, für das die Anpassung gilt (`None` = alle Projekte).
+    projekt: Option<String>,
+    art: Art,
+    label: Option<String>,
+    farbe: Option<egui::Color32>,
+}
+
+impl Einstellungen {
+    /// Liest die Einstellungen ein. Fehlt die Datei, gelten die Standardwerte.
+    fn laden() -> Self {
+        let mut einstellungen = Self {
+            xml_begleitdatei: false,
+            xml_mapping: HashMap::new(),
+            platzhalter: HashMap::new(),
+            pdf_offene_punkte: false,
+            pdf_quellenseite: false,
+            checkliste: STANDARD_CHECKLISTE.iter().map(|p| p.to_string()).collect(),
+            pdf_deckblatt: false,
+            pdf_logo: String::new(),
+            pdf_kopfzeile: "{projekt} – {titel} – {datum}".to_string(),
+            pdf_kopfzeile_ausrichtung: genpdf::Alignment::Left,
+            pdf_profil: PdfProfil::Standard,
+            pdf_sprache: PdfSprache::Deutsch,
+            pdf_spalten: PdfSpalten(PDF_SPALTENGEWICHTE),
+            pdf_trennlinie_staerke: 0.3,
+            pdf_trennlinie_farbe: genpdf::style::Color::Greyscale(180),
+            touch_modus: false,
+            erst_fokus: Some(KopfFeld::Titel),
+            top_nummerierung: TopNummerierung::Tops,
+            top_vorbelegung: HashMap::new(),
+            kuerzel_praefixe: Vec::new(),
+            kuerzel_schema: "{initialen}".to_string(),
+            pdf_backend: PdfBackendArt::Genpdf,
+            pdf_html_kommando: "weasyprint {html} {pdf}".to_string(),
+            typst_vorlage: String::new(),
+            typst_kommando: "typst compile {typ} {pdf}".to_string(),
+            typst_kompilieren: false,
+            feiertage: Feiertagskalender::default(),
+            finalisierung_erinnerung: Vec::new(),
+            finalisierung_frist: 2,
+            arbeitsverzeichnis: String::new(),
+            einzelinstanz: true,
+            auto_speichern: false,
+            auto_speichern_sekunden: 3,
+            zeiterfassung_speichern: false,
+            export_verzeichnis: String::new(),
+            export_namensmuster: "MZProtokoll_{titel}__{datum}".to_string(),
+            scroll_faktor_mausrad: 10.0,
+            scroll_faktor_touchpad: 1.5,
+            mausrad_zeilenhoehe: 40.0,
+            scroll_glaetten: true,
+            zeitleiste_wochen: 8,
+            art_anpassungen: Vec::new(),
+            kalender_vdir: String::new(),
+            caldav_aufgaben_url: String::new(),
+            caldav_benutzer: String::new(),
+            matrix_homeserver: String::new(),
+            matrix_raum: String::new(),
+            redmine_url: String::new(),
+            redmine_projekt: HashMap::new(),
+            email_betreff: HashMap::new(),
+            email_text: HashMap::new(),
+            llm_endpunkt: String::new(),
+            llm_modell: String::new(),
+            status_zustaende: vec![Status::Entwurf, Status::Freigegeben],
+            archiv_verzeichnis: String::new(),
+            archiv_schema: "{projekt}/{jahr}/{monat}".to_string(),
+            freigabe_pipeline: Vec::new(),
+            freigabe_webhook: String::new(),
+            passwort_kommando: String::new(),
+            export_profile: vec![ExportProfil::kundenfassung()],
+            pdfa_kommando: "gs -dPDFA=2 -dBATCH -dNOPAUSE -dQUIET -dPDFACompatibilityPolicy=1 \
+                            -sColorConversionStrategy=RGB -sDEVICE=pdfwrite -sOutputFile={ziel} {pdf}"
+                .to_string(),
+            qpdf_programm: "qpdf".to_string(),
+        };
+        let Some(pfad) = konfig_verzeichnis().map(|d| d.join("einstellungen.toml")) else {
+            return einstellungen;
+        };
+        let Ok(content) = std::fs::read_to_string(pfad) else {
+            return einstellungen;
+        };
+        let mut checkliste: Vec<(u32, String)> = Vec::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.starts_with('#') {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                let key = key.trim();
+                let value = value.trim().trim_matches('"');
+                if let Some(nr) = key.strip_prefix("checkliste.").and_then(|n| n.parse().ok()) {
+                    checkliste.push((nr, value.to_string()));
+                } else if key == "xml_begleitdatei" {
+                    einstellungen.xml_begleitdatei = value == "true";
+                } else if key == "pdf_offene_punkte" {
+                    einstellungen.pdf_offene_punkte = value == "true";
+                } else if key == "pdf_quellenseite" {
+                    einstellungen.pdf_quellenseite = value == "true";
+                } else if key == "zeiterfassung_speichern" {
+                    einstellungen.zeiterfassung_speichern = value == "true";
+                } else if key == "touch_modus" {
+                    einstellungen.touch_modus = value == "true";
+                } else if key == "arbeitsverzeichnis" {
+                    einstellungen.arbeitsverzeichnis = value.to_string();
+                } else if key == "einzelinstanz" {
+                    einstellungen.einzelinstanz = value != "false";
+                } else if key == "auto_speichern" {
+                    einstellungen.auto_speichern = value == "true";
+                } else if key == "auto_speichern_sekunden" {
+                    if let Ok(sekunden) = value.trim().parse::<u64>() {
+                        einstellungen.auto_speichern_sekunden = sekunden.max(1);
+                    }
+                } else if key == "export_verzeichnis" {
+                    einstellungen.export_verzeichnis = value.to_string();
+                } else if key == "export_namensmuster" {
+                    if !value.is_empty() {
+                        einstellungen.export_namensmuster = value.to_string();
+                    }
+                } else if key == "scroll_faktor_mausrad" || key == "scroll_faktor_touchpad" || key == "mausrad_zeilenhoehe" {
+                    if let Ok(wert) = value.replace(',', ".").parse::<f32>() {
+                        let wert = wert.clamp(0.1, 200.0);
+                        match key {
+                            "scroll_faktor_mausrad" => einstellungen.scroll_faktor_mausrad = wert,
+                            "scroll_faktor_touchpad" => einstellungen.scroll_faktor_touchpad = wert,
+                            _ => einstellungen.mausrad_zeilenhoehe = wert,
+                        }
+                    }
+                } else if key == "kalender_vdir" {
+                    einstellungen.kalender_vdir = value.to_string();
+                } else if key == "caldav_aufgaben_url" {
+                    einstellungen.caldav_aufgaben_url = value.to_string();
+                } else if key == "caldav_benutzer" {
+                    einstellungen.caldav_benutzer = value.to_string();
+                } else if key == "matrix_homeserver" {
+                    einstellungen.matrix_homeserver = value.trim_end_matches('/').to_string();
+                } else if key == "matrix_raum" {
+                    einstellungen.matrix_raum = value.to_string();
+                } else if key == "redmine_url" {
+                    einstellungen.redmine_url = value.trim_end_matches('/').to_string();
+                } else if key == "llm_endpunkt" {
+                    einstellungen.llm_endpunkt = value.to_string();
+                } else if key == "llm_modell" {
+                    einstellungen.llm_modell = value.to_string();
+                } else if key == "status_zustaende" {
+                    let genannt: Vec<Status> = value.split(',').filter_map(Status::parsen).collect();
+                    einstellungen.status_zustaende = Status::alle()
+                        .iter()
+                        .copied()
+                        .filter(|s| matches!(s, Status::Entwurf | Status::Freigegeben) || genannt.contains(s))
+                        .collect();
+                } else if key == "archiv_verzeichnis" {
+                    einstellungen.archiv_verzeichnis = value.to_string();
+                } else if key == "archiv_schema" {
+                    if !value.is_empty() {
+                        einstellungen.archiv_schema = value.to_string();
+                    }
+                } else if key == "freigabe_pipeline" {
+                    einstellungen.freigabe_pipeline = value.split(',').filter_map(PipelineSchritt::parsen).collect();
+                } else if key == "freigabe_webhook" {
+                    einstellungen.freigabe_webhook = value.to_string();
+                } else if key == "passwort_kommando" {
+                    einstellungen.passwort_kommando = value.to_string();
+                } else if let Some((name, feld)) = key.strip_prefix("exportprofil.").and_then(|rest| rest.rsplit_once('.')) {
+                    let profile = &mut einstellungen.export_profile;
+                    let index = match profile.iter().position(|p| p.name == name) {
+                        Some(i) => i,
+                        None => {
+                            profile.push(ExportProfil::neu(name));
+                            profile.len() - 1
+                        }
+                    };
+                    profile[index].feld_setzen(feld, value);
+                } else if key == "pdfa_kommando" {
+                    if !value.trim().is_empty() {
+                        einstellungen.pdfa_kommando = value.trim().to_string();
+                    }
+                } else if key == "qpdf_programm" {
+                    if !value.trim().is_empty() {
+                        einstellungen.qpdf_programm = value.trim().to_string();
+                    }
+                } else if key == "zeitleiste_wochen" {
+                    if let Ok(wochen) = value.parse::<u32>() {
+                        einstellungen.zeitleiste_wochen = wochen.min(52);
+                    }
+                } else if key == "scroll_glaetten" {
+                    einstellungen.scroll_glaetten = value != "false";
+                } else if key == "pdf_deckblatt" {
+                    einstellungen.pdf_deckblatt = value == "true";
+                } else if key == "pdf_logo" {
+                    einstellungen.pdf_logo = value.to_string();
+                } else if key == "erst_fokus" {
+                    einstellungen.erst_fokus = KopfFeld::parsen(value);
+                } else if key == "top_nummerierung" {
+                    einstellungen.top_nummerierung = TopNummerierung::parsen(value);
+                } else if key == "bundesland" {
+                    einstellungen.feiertage.bundesland = value.trim().to_uppercase();
+                } else if key == "feiertage_zusaetzlich" {
+                    einstellungen.feiertage.zusaetzlich = value
+                        .split(',')
+                        .filter_map(|tag| {
+                            // TT.MM. (jedes Jahr) oder TT.MM.JJJJ (einmalig)
+                            let mut teile = tag.trim().split('.');
+                            let tag = teile.next()?.trim().parse().ok()?;
+                            let monat = teile.next()?.trim().parse().ok()?;
+                            let jahr = teile.next().map(str::trim).filter(|j| !j.is_empty()).and_then(|j| j.parse().ok());
+                            Some((tag, monat, jahr))
+                        })
+                        .collect();
+                } else if key == "kuerzel_praefixe" {
+                    einstellungen.kuerzel_praefixe = value
+                        .split(',')
+                        .filter_map(|teil| {
+                            let (praefix, beschreibung) = teil.split_once(':').unwrap_or((teil, ""));
+                            let praefix = praefix.trim().trim_end_matches('-').to_uppercase();
+                            (!praefix.is_empty()).then(|| (praefix, beschreibung.trim().to_string()))
+                        })
+                        .collect();
+                } else if key == "kuerzel_schema" {
+                    if !value.trim().is_empty() {
+                        einstellungen.kuerzel_schema = value.trim().to_string();
+                    }
+                } else if key == "pdf_backend" {
+                    einstellungen.pdf_backend = PdfBackendArt::parsen(value);
+                } else if key == "pdf_html_kommando" {
+                    if !value.trim().is_empty() {
+                        einstellungen.pdf_html_kommando = value.trim().to_string();
+                    }
+                } else if key == "typst_vorlage" {
+                    einstellungen.typst_vorlage = value.trim().to_string();
+                } else if key == "typst_kommando" {
+                    if !value.trim().is_empty() {
+                        einstellungen.typst_kommando = value.trim().to_string();
+                    }
+                } else if key == "typst_kompilieren" {
+                    einstellungen.typst_kompilieren = value == "true";
+                } else if key == "finalisierung_erinnerung" {
+                    einstellungen.finalisierung_erinnerung = value.split(',').filter_map(Erinnerungsweg::parsen).collect();
+                } else if key == "finalisierung_frist" {
+                    if let Ok(tage) = value.parse() {
+                        einstellungen.finalisierung_frist = tage;
+                    }
+                } else if key == "pdf_spalten" {
+                    if let Some(spalten) = PdfSpalten::parsen(value) {
+                        einstellungen.pdf_spalten = spalten;
+                    }
+                } else if key == "pdf_profil" {
+                    einstellungen.pdf_profil = match value {
+                        "kompakt" => PdfProfil::Kompakt,
+                        "lesefreundlich" => PdfProfil::Lesefreundlich,
+                        _ => PdfProfil::Standard,
+                    };
+                } else if key == "pdf_sprache" {
+                    if let Some(sprache) = PdfSprache::parsen(value) {
+                        einstellungen.pdf_sprache = sprache;
+                    }
+                } else if key == "pdf_trennlinie_staerke" {
+                    if let Ok(staerke) = value.replace(',', ".").parse::<f64>() {
+                        einstellungen.pdf_trennlinie_staerke = staerke.clamp(0.05, 3.0);
+                    }
+                } else if key == "pdf_trennlinie_farbe" {
+                    if let Some(farbe) = hex_farbe_parsen(value) {
+                        einstellungen.pdf_trennlinie_farbe =
+                            genpdf::style::Color::Rgb(farbe.r(), farbe.g(), farbe.b());
+                    }
+                } else if key == "pdf_kopfzeile" {
+                    einstellungen.pdf_kopfzeile = value.to_string();
+                } else if key == "pdf_kopfzeile_ausrichtung" {
+                    einstellungen.pdf_kopfzeile_ausrichtung = match value {
+                        "mitte" => genpdf::Alignment::Center,
+                        "rechts" => genpdf::Alignment::Right,
+                        _ => genpdf::Alignment::Left,
+                    };
+                } else if let Some(projekt) = key.strip_suffix("redmine_projekt").and_then(|rest| {
+                    // redmine_projekt global oder projekt.<Name>.redmine_projekt je Projekt
+                    if rest.is_empty() {
+                        return Some(String::new());
+                    }
+                    let name = rest.strip_prefix("projekt.")?.strip_suffix('.')?;
+                    Some(name.trim().to_lowercase())
+                }) {
+                    einstellungen.redmine_projekt.insert(projekt, value.trim().to_string());
+                } else if let Some(projekt) = key.strip_suffix("top_vorbelegung").and_then(|rest| {
+                    // top_vorbelegung global oder projekt.<Name>.top_vorbelegung je Projekt
+                    if rest.is_empty() {
+                        return Some(String::new());
+                    }
+                    let name = rest.strip_prefix("projekt.")?.strip_suffix('.')?;
+                    Some(name.trim().to_lowercase())
+                }) {
+                    // „ENTSCHEIDUNG, TODO: Maßnahmen festlegen“ – Art, optional mit Notiz
+                    let kinder = value
+                        .split(',')
+                        .filter_map(|teil| {
+                            let (art, notiz) = teil.split_once(':').unwrap_or((teil, ""));
+                            let art = art_parsen(&art.trim().to_uppercase());
+                            (!matches!(art, Art::Leer | Art::Agenda)).then(|| (art, notiz.trim().to_string()))
+                        })
+                        .collect();
+                    einstellungen.top_vorbelegung.insert(projekt, kinder);
+                } else if let Some((projekt, feld)) = key
+                    .strip_suffix("email_betreff")
+                    .map(|rest| (rest, "betreff"))
+                    .or_else(|| key.strip_suffix("email_text").map(|rest| (rest, "text")))
+                    .and_then(|(rest, feld)| {
+                        // email_… global oder projekt.<Name>.email_… je Projekt
+                        if rest.is_empty() {
+                            return Some((String::new(), feld));
+                        }
+                        let name = rest.strip_prefix("projekt.")?.strip_suffix('.')?;
+                        Some((name.trim().to_lowercase(), feld))
+                    })
+                {
+                    let vorlage = value.replace("\\n", "\n");
+                    match feld {
+                        "betreff" => einstellungen.email_betreff.insert(projekt, vorlage),
+                        _ => einstellungen.email_text.insert(projekt, vorlage),
+                    };
+                } else if let Some((projekt, art_schluessel)) = key
+                    .strip_prefix("art.")
+                    .map(|rest| (None, rest))
+                    .or_else(|| {
+                        let rest = key.strip_prefix("projekt.")?;
+                        let pos = rest.rfind(".art.")?;
+                        Some((Some(rest[..pos].to_string()), &rest[pos + 5..]))
+                    })
+                {
+                    // art.<ART>.label / art.<ART>.farbe, optional mit projekt.<Name>. davor
+                    if let Some((art_name, eigenschaft)) = art_schluessel.split_once('.') {
+                        let art = art_parsen(art_name);
+                        if art != Art::Leer && !value.is_empty() {
+                            let mut anpassung = ArtAnpassung { projekt, art, label: None, farbe: None };
+                            match eigenschaft {
+                                "label" => anpassung.label = Some(value.to_uppercase()),
+                                "farbe" => anpassung.farbe = hex_farbe_parsen(value),
+                                _ => {}
+                            }
+                            if anpassung.label.is_some() || anpassung.farbe.is_some() {
+                                einstellungen.art_anpassungen.push(anpassung);
+                            }
+                        }
+                    }
+                } else if let Some(feld) = key.strip_prefix("xml.") {
+                    einstellungen.xml_mapping.insert(feld.to_string(), value.to_string());
+                } else if let Some(name) = key.strip_prefix("platzhalter.") {
+                    einstellungen.platzhalter.insert(name.to_string(), value.to_string());
+                }
+            }
+        }
+        if !checkliste.is_empty() {
+            checkliste.sort_by_key(|(nr, _)| *nr);
+            einstellungen.checkliste = checkliste.into_iter().map(|(_, punkt)| punkt).collect();
+        }
+        einstellungen
+    }
+
+    /// Sucht eine Art-Anpassung: die des Projekts vor der globalen.
+    fn art_anpassung<T>(&self, projekt: &str, art: &Art, wert: impl Fn(&ArtAnpassung) -> Option<T>) -> Option<T> {
+        let projekt = projekt.trim().to_lowercase();
+        let passend = |nur_projekt: bool| {
+            self.art_anpassungen.iter().filter(|a| a.art == *art).find_map(|a| match &a.projekt {
+                Some(p) if nur_projekt && p.trim().to_lowercase() == projekt => wert(a),
+                None if !nur_projekt => wert(a),
+                _ => None,
+            })
+        };
+        passend(true).or_else(|| passend(false))
+    }
+
+    /// Beschriftung einer Art im angegebenen Projekt (Standard: `Art::label`).
+    fn art_label(&self, projekt: &str, art: &Art) -> String {
+        self.art_anpassung(projekt, art, |a| a.label.clone())
+            .unwrap_or_else(|| art.label().to_string())
+    }
+
+    /// Eigene Farbe einer Art im angegebenen Projekt (`None` = Theme-Farbe).
+    fn art_farbe(&self, projekt: &str, art: &Art) -> Option<egui::Color32> {
+        self.art_anpassung(projekt, art, |a| a.farbe)
+    }
+
+    /// Erkennt eine eigene Beschriftung beim Einlesen – unabhängig vom Projekt,
+    /// damit auch Dateien anderer Projekte korrekt gelesen werden.
+    fn art_aus_label(&self, text: &str) -> Option<Art> {
+        let text = text.trim();
+        self.art_anpassungen
+            .iter()
+            .find(|a| a.label.as_deref().is_some_and(|l| l.eq_ignore_ascii_case(text)))
+            .map(|a| a.art.clone())
+    }
+
+    /// Erkannter Organisations-Präfix eines Kürzels (`EXT` in `EXT-AB`), sonst `None`.
+    fn kuerzel_praefix<'a>(&self, kuerzel: &'a str) -> Option<&'a str> {
+        kuerzel_praefix_finden(kuerzel, &self.kuerzel_praefixe)
+    }
+
+    /// Erzeugt das Kürzel zu `name` nach `kuerzel_schema`; ein Präfix des bisherigen
+    /// Kürzels bleibt erhalten.
+    fn kuerzel_erzeugen(&self, name: &str, bisher: &str) -> String {
+        let kern = kuerzel_nach_schema(name, &self.kuerzel_schema);
+        match self.kuerzel_praefix(bisher) {
+            Some(praefix) if !kern.is_empty() => format!("{}-{}", praefix, kern),
+            _ => kern,
+        }
+    }
+
+    /// Vorbelegung der Agenda-TOPs: die des Projekts vor der globalen (leer = keine).
+    fn top_vorbelegung(&self, projekt: &str) -> &[(Art, String)] {
+        let projekt = projekt.trim().to_lowercase();
+        self.top_vorbelegung
+            .get(&projekt)
+            .filter(|_| !projekt.is_empty())
+            .or_else(|| self.top_vorbelegung.get(""))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Betreff- und Text-Vorlage für den E-Mail-Versand: die des Projekts vor der
+    /// globalen vor dem Standard.
+    fn email_vorlage(&self, projekt: &str) -> (String, String) {
+        let projekt = projekt.trim().to_lowercase();
+        let waehlen = |vorlagen: &HashMap<String, String>, standard: &str| {
+            vorlagen
+                .get(&projekt)
+                .filter(|_| !projekt.is_empty())
+                .or_else(|| vorlagen.get(""))
+                .cloned()
+                .unwrap_or_else(|| standard.to_string())
+        };
+        (waehlen(&self.email_betreff, STANDARD_EMAIL_BETREFF), waehlen(&self.email_text, STANDARD_EMAIL_TEXT))
+    }
+
+    /// Redmine-Projektkennung für neue Tickets: die des Projekts vor der globalen
+    /// (`None` = keine eingestellt).
+    fn redmine_projekt(&self, projekt: &str) -> Option<&str> {
+        let projekt = projekt.trim().to_lowercase();
+        self.redmine_projekt
+            .get(&projekt)
+            .filter(|_| !projekt.is_empty())
+            .or_else(|| self.redmine_projekt.get(""))
+            .map(String::as_str)
+            .filter(|kennung| !kennung.is_empty())
+    }
+
+    /// Gibt den XML-Elementnamen für ein Protokollfeld zurück.
+    /// `None` bedeutet, dass das Feld im Export weggelassen wird.
+    fn xml_element(&self, feld: &str) -> Option<String> {
+        let name = match self.xml_mapping.get(feld) {
+            Some(name) => name.as_str(),
+            None => XML_STANDARD_MAPPING.iter().find(|(f, _)| *f == feld).map(|(_, n)| *n)?,
+        };
+        if name.is_empty() { None } else { Some(name.to_string()) }
+    }
+}
+
+/// Benutzerprofil aus `profil.toml` im Konfigurationsverzeichnis: die Identität der
+/// Person, die die App bedient – unabhängig vom Protokollführer des geöffneten Protokolls.
+/// Speist `erstellt_von`, den Standard-Protokollführer und das Audit-Log.
+#[derive(Clone, Default)]
+struct Benutzerprofil {
+    name: String,
+    kuerzel: String,
+    email: String,
+    /// Mehrzeilige Signatur (in der Datei mit `\n` für Zeilenumbrüche).
+    signatur: String,
+}
+
+impl Benutzerprofil {
+    /// Pfad der Profildatei (`profil.toml` im Konfigurationsverzeichnis).
+    fn pfad() -> Option<std::path::PathBuf> {
+        konfig_verzeichnis().map(|d| d.join("profil.toml"))
+    }
+
+    /// Liest das Profil ein. Fehlt die Datei, ist das Profil leer.
+    fn laden() -> Self {
+        let mut profil = Self::default();
+        let Some(content) = Self::pfad().and_then(|p| std::fs::read_to_string(p).ok()) else {
+            return profil;
+        };
+        for line in content.lines() {
+            let line = line.trim();
+            if line.starts_with('#') {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                let value = value.trim().trim_matches('"').replace("\\n", "\n");
+                match key.trim() {
+                    "name" => profil.name = value,
+                    "kuerzel" => profil.kuerzel = value,
+                    "email" => profil.email = value,
+                    "signatur" => profil.signatur = value,
+                    _ => {}
+                }
+            }
+        }
+        profil
+    }
+
+    /// Schreibt das Profil in `profil.toml`.
+    fn speichern(&self) -> std::io::Result<()> {
+        let pfad = Self::pfad().ok_or_else(|| std::io::Error::other("Kein Konfigurationsverzeichnis"))?;
+        if let Some(dir) = pfad.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let zeile = |key: &str, wert: &str| format!("{} = \"{}\"\n", key, wert.replace('\n', "\\n"));
+        let mut inhalt = String::from("# MZProtokoll – Benutzerprofil\n");
+        inhalt.push_str(&zeile("name", &self.name));
+        inhalt.push_str(&zeile("kuerzel", &self.kuerzel));
+        inhalt.push_str(&zeile("email", &self.email));
+        inhalt.push_str(&zeile("signatur", &self.signatur));
+        std::fs::write(pfad, inhalt)
+    }
+
+    /// Gibt das Profil als vorbelegten Protokollführer zurück.
+    fn als_person(&self) -> Person {
+        let mut person = Person::new();
+        person.name = self.name.clone();
+        if !self.kuerzel.is_empty() {
+            person.kuerzel = self.kuerzel.clone();
+            person.kuerzel_manuell = true;
+        }
+        person
+    }
+}
+
 fn main() -> eframe::Result {
+    // Batch-Modi: `--migrate` und `--regenerate-pdf` laufen ohne Fenster
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(pos) = args.iter().position(|a| a == "--migrate") {
+        let Some(verzeichnis) = args.get(pos + 1) else {
+            eprintln!("Aufruf: mzprotokoll --migrate <verzeichnis>");
+            std::process::exit(2);
+        };
+        std::process::exit(verzeichnis_migrieren(std::path::Path::new(verzeichnis)));
+    }
+    if let Some(pos) = args.iter().position(|a| a == "--regenerate-pdf") {
+        let Some(verzeichnis) = args.get(pos + 1) else {
+            eprintln!("Aufruf: mzprotokoll --regenerate-pdf <verzeichnis>");
+            std::process::exit(2);
+        };
+        std::process::exit(pdfs_neu_erzeugen(std::path::Path::new(verzeichnis)));
+    }
+
+    // Datei als Argument: `mzprotokoll [--neue-instanz] <datei.md>`
+    let neue_instanz = args.iter().any(|a| a == "--neue-instanz");
+    let datei = args
+        .iter()
+        .skip(1)
+        .find(|a| !a.starts_with("--"))
+        .map(|a| std::fs::canonicalize(a).unwrap_or_else(|_| std::path::PathBuf::from(a)));
+    let einzelinstanz = Einstellungen::laden().einzelinstanz;
+    if einzelinstanz && !neue_instanz {
+        let auftrag = match &datei {
+            Some(pfad) => InstanzAuftrag::Oeffnen(pfad.clone()),
+            None => InstanzAuftrag::Zeigen,
+        };
+        if an_laufende_instanz_uebergeben(&auftrag) {
+            return Ok(());
+        }
+    }
+    // Bewusst gestartete zweite Instanz: die laufende darauf hinweisen
+    let parallel = neue_instanz && an_laufende_instanz_uebergeben(&InstanzAuftrag::Parallel);
+
     let icon = eframe::icon_data::from_png_bytes(include_bytes!("../assets/icon.png"))
         .expect("Failed to load icon");
 
@@ -80,12 +1042,411 @@ fn main() -> eframe::Result {
     eframe::run_native(
         "MZProtokoll",
         options,
-        Box::new(|cc| Ok(Box::new(ProtokollApp::new(&cc.egui_ctx)))),
+        Box::new(move |cc| {
+            let mut app = ProtokollApp::new(&cc.egui_ctx);
+            if einzelinstanz && !neue_instanz {
+                app.instanz_rx = instanz_empfaenger_starten(cc.egui_ctx.clone());
+            }
+            if parallel {
+                app.toast_zeigen("Es läuft bereits eine weitere Instanz von MZProtokoll".to_string());
+            }
+            match datei {
+                Some(pfad) => match std::fs::read_to_string(&pfad) {
+                    Ok(content) => app.datei_uebernehmen(pfad, &content),
+                    Err(e) => app.toast_zeigen(format!("Datei konnte nicht gelesen werden: {}", e)),
+                },
+                // Start ohne Datei: heute bereits begonnene Protokolle anbieten
+                None => app.heute_begonnen = heute_begonnene_protokolle(&app.einstellungen),
+            }
+            Ok(Box::new(app))
+        }),
     )
 }
 
+// -- Einzelinstanz --
+
+/// Auftrag eines weiteren Programmstarts an die laufende Instanz.
+enum InstanzAuftrag {
+    /// Datei öffnen (bzw. nach vorn holen, wenn sie schon geöffnet ist).
+    Oeffnen(std::path::PathBuf),
+    /// Start ohne Datei: nur das Fenster nach vorn holen.
+    Zeigen,
+    /// Mit `--neue-instanz` wurde bewusst eine zweite Instanz gestartet.
+    Parallel,
+}
+
+impl InstanzAuftrag {
+    /// Protokollzeile, z. B. `MZPROTOKOLL OEFFNEN /pfad/datei.md`.
+    fn zeile(&self) -> String {
+        match self {
+            InstanzAuftrag::Oeffnen(pfad) => format!("MZPROTOKOLL OEFFNEN {}", pfad.display()),
+            InstanzAuftrag::Zeigen => "MZPROTOKOLL ZEIGEN".to_string(),
+            InstanzAuftrag::Parallel => "MZPROTOKOLL PARALLEL".to_string(),
+        }
+    }
+
+    /// Gegenstück zu `zeile`; fremde Verbindungen ohne Kennung werden verworfen.
+    fn parsen(zeile: &str) -> Option<InstanzAuftrag> {
+        let rest = zeile.trim_end().strip_prefix("MZPROTOKOLL ")?;
+        match rest {
+            "ZEIGEN" => Some(InstanzAuftrag::Zeigen),
+            "PARALLEL" => Some(InstanzAuftrag::Parallel),
+            _ => rest.strip_prefix("OEFFNEN ").map(|p| InstanzAuftrag::Oeffnen(std::path::PathBuf::from(p))),
+        }
+    }
+}
+
+/// Datei mit dem Port der laufenden Instanz (`instanz.port` im Basisverzeichnis,
+/// profilübergreifend).
+fn instanz_pfad() -> Option<std::path::PathBuf> {
+    konfig_basis().map(|d| d.join("instanz.port"))
+}
+
+/// Übergibt einen Auftrag an die laufende Instanz. `true`, wenn sie erreichbar war
+/// und den Auftrag bestätigt hat; eine veraltete Portdatei ergibt `false`.
+fn an_laufende_instanz_uebergeben(auftrag: &InstanzAuftrag) -> bool {
+    use std::io::{BufRead, Write};
+    let Some(port) = instanz_pfad()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|t| t.trim().parse::<u16>().ok())
+    else {
+        return false;
+    };
+    let adresse = std::net::SocketAddr::from(([127, 0, 0, 1], port));
+    let Ok(mut verbindung) = std::net::TcpStream::connect_timeout(&adresse, std::time::Duration::from_millis(300)) else {
+        return false;
+    };
+    let _ = verbindung.set_read_timeout(Some(std::time::Duration::from_secs(2)));
+    if writeln!(verbindung, "{}", auftrag.zeile()).is_err() {
+        return false;
+    }
+    let mut antwort = String::new();
+    let _ = std::io::BufReader::new(verbindung).read_line(&mut antwort);
+    antwort.trim() == "OK"
+}
+
+/// Meldet diese Instanz als laufende an: lauscht auf einem freien lokalen Port,
+/// schreibt ihn nach `instanz_pfad` und reicht eingehende Aufträge über den
+/// zurückgegebenen Kanal weiter (separater Thread, weckt die Oberfläche auf).
+fn instanz_empfaenger_starten(ctx: egui::Context) -> Option<mpsc::Receiver<InstanzAuftrag>> {
+    use std::io::{BufRead, Write};
+    let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).ok()?;
+    let port = listener.local_addr().ok()?.port();
+    let pfad = instanz_pfad()?;
+    if let Some(dir) = pfad.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    std::fs::write(&pfad, port.to_string()).ok()?;
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for mut verbindung in listener.incoming().flatten() {
+            let _ = verbindung.set_read_timeout(Some(std::time::Duration::from_secs(2)));
+            let mut zeile = String::new();
+            if std::io::BufReader::new(&verbindung).read_line(&mut zeile).is_err() {
+                continue;
+            }
+            let Some(auftrag) = InstanzAuftrag::parsen(&zeile) else {
+                continue;
+            };
+            if tx.send(auftrag).is_err() {
+                break;
+            }
+            let _ = verbindung.write_all(b"OK\n");
+            ctx.request_repaint();
+        }
+    });
+    Some(rx)
+}
+
+// -- Kommandozeile --
+
+/// Ergebnis der Migration einer einzelnen Datei.
+enum MigrationsErgebnis {
+    /// Datei wurde ins aktuelle Format umgeschrieben (Original als `.md.bak`).
+    Migriert,
+    /// Datei entsprach bereits dem aktuellen Format.
+    Unveraendert,
+    /// Keine MZProtokoll-Datei, nicht angefasst.
+    Uebersprungen,
+    /// Lesen oder Schreiben ist fehlgeschlagen.
+    Fehler(String),
+}
+
+/// Listet die Markdown-Dateien eines Verzeichnisses (nicht rekursiv, sortiert) auf.
+fn markdown_dateien(verzeichnis: &std::path::Path) -> Option<Vec<std::path::PathBuf>> {
+    let mut dateien: Vec<std::path::PathBuf> = std::fs::read_dir(verzeichnis)
+        .ok()?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e == "md"))
+        .filter(|p| p.file_name().is_some_and(|n| n != "migrationsbericht.md"))
+        .collect();
+    dateien.sort();
+    Some(dateien)
+}
+
+/// Listet die Markdown-Dateien eines Verzeichnisses samt aller Unterordner auf
+/// (etwa das nach `archiv_schema` gegliederte Archiv).
+fn markdown_dateien_rekursiv(verzeichnis: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let mut dateien = markdown_dateien(verzeichnis).unwrap_or_default();
+    let mut unterordner: Vec<std::path::PathBuf> = std::fs::read_dir(verzeichnis)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_dir())
+        .collect();
+    unterordner.sort();
+    for ordner in unterordner {
+        dateien.extend(markdown_dateien_rekursiv(&ordner));
+    }
+    dateien
+}
+
+/// `true`, wenn der Text mindestens einen bekannten `##`-Abschnitt enthält
+/// und damit als MZProtokoll-Datei gilt.
+fn ist_protokoll(inhalt: &str) -> bool {
+    inhalt
+        .lines()
+        .any(|z| z.trim().strip_prefix("## ").is_some_and(|u| alias_aufloesen(u, ABSCHNITT_ALIASE).is_some()))
+}
+
+/// Liest alle Markdown-Dateien eines Verzeichnisses (nicht rekursiv) ein, schreibt sie
+/// über `markdown_parsen`/`markdown_erstellen` im aktuellen Format zurück und legt
+/// `migrationsbericht.md` im Verzeichnis ab. Gibt den Exit-Code zurück
+/// (0 = alles in Ordnung, 1 = mindestens ein Fehler, 2 = Verzeichnis nicht lesbar).
+fn verzeichnis_migrieren(verzeichnis: &std::path::Path) -> i32 {
+    let Some(dateien) = markdown_dateien(verzeichnis) else {
+        eprintln!("Verzeichnis nicht lesbar: {}", verzeichnis.display());
+        return 2;
+    };
+
+    // Eine App-Instanz genügt: markdown_parsen setzt den Protokollzustand jedes Mal zurück
+    let mut app = ProtokollApp::new(&egui::Context::default());
+    let mut ergebnisse = Vec::new();
+    for pfad in &dateien {
+        let ergebnis = match std::fs::read_to_string(pfad) {
+            Err(e) => MigrationsErgebnis::Fehler(e.to_string()),
+            Ok(alt) => {
+                if !ist_protokoll(&alt) {
+                    MigrationsErgebnis::Uebersprungen
+                } else {
+                    app.markdown_parsen(&alt);
+                    // Reine Formatmigration: „Geändert" und „Bearbeitungszeit" bleiben wie sie waren
+                    let neu = metadaten_beibehalten(&app.markdown_erstellen(), &alt);
+                    if neu == alt {
+                        MigrationsErgebnis::Unveraendert
+                    } else if let Err(e) = std::fs::copy(pfad, pfad.with_extension("md.bak")) {
+                        MigrationsErgebnis::Fehler(format!("Sicherung fehlgeschlagen: {}", e))
+                    } else if let Err(e) = atomar_schreiben(pfad, neu) {
+                        MigrationsErgebnis::Fehler(e.to_string())
+                    } else {
+                        MigrationsErgebnis::Migriert
+                    }
+                }
+            }
+        };
+        let name = pfad.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let text = match &ergebnis {
+            MigrationsErgebnis::Migriert => "migriert".to_string(),
+            MigrationsErgebnis::Unveraendert => "bereits aktuell".to_string(),
+            MigrationsErgebnis::Uebersprungen => "übersprungen (kein Protokoll)".to_string(),
+            MigrationsErgebnis::Fehler(e) => format!("Fehler: {}", e),
+        };
+        println!("{}: {}", name, text);
+        ergebnisse.push((name, ergebnis, text));
+    }
+
+    let anzahl = |f: fn(&MigrationsErgebnis) -> bool| ergebnisse.iter().filter(|(_, e, _)| f(e)).count();
+    let migriert = anzahl(|e| matches!(e, MigrationsErgebnis::Migriert));
+    let unveraendert = anzahl(|e| matches!(e, MigrationsErgebnis::Unveraendert));
+    let uebersprungen = anzahl(|e| matches!(e, MigrationsErgebnis::Uebersprungen));
+    let fehler = anzahl(|e| matches!(e, MigrationsErgebnis::Fehler(_)));
+
+    let mut bericht = format!(
+        "# Migrationsbericht\n\n**Verzeichnis:** {}  \n**Zeitpunkt:** {}\n\n\
+         {} migriert, {} bereits aktuell, {} übersprungen, {} Fehler\n\n\
+         | Datei | Ergebnis |\n|-------|----------|\n",
+        verzeichnis.display(),
+        Local::now().format("%d.%m.%Y %H:%M"),
+        migriert,
+        unveraendert,
+        uebersprungen,
+        fehler
+    );
+    for (name, _, text) in &ergebnisse {
+        bericht.push_str(&format!("| {} | {} |\n", name.replace('|', "\\|"), text.replace('|', "\\|")));
+    }
+    let bericht_pfad = verzeichnis.join("migrationsbericht.md");
+    if std::fs::write(&bericht_pfad, bericht).is_err() {
+        eprintln!("Bericht konnte nicht geschrieben werden: {}", bericht_pfad.display());
+    }
+    println!(
+        "{} migriert, {} bereits aktuell, {} übersprungen, {} Fehler – Bericht: {}",
+        migriert,
+        unveraendert,
+        uebersprungen,
+        fehler,
+        bericht_pfad.display()
+    );
+    if fehler > 0 { 1 } else { 0 }
+}
+
+/// Ersetzt in `neu` die Metadatenzeilen `**Geändert:**` und `**Bearbeitungszeit:**`
+/// durch die aus `alt` (fehlen sie dort, entfallen sie), damit die Migration weder
+/// Änderungszeitpunkt und -person noch die erfasste Zeit anfasst.
+fn metadaten_beibehalten(neu: &str, alt: &str) -> String {
+    let ist_metadatum = |zeile: &str| zeile.starts_with("**Geändert:**") || zeile.starts_with("**Bearbeitungszeit:**");
+    let alte: Vec<&str> = alt.lines().filter(|z| ist_metadatum(z)).collect();
+    let mut ergebnis = Vec::new();
+    let mut eingefuegt = false;
+    let mut zeilen = neu.lines().peekable();
+    while let Some(zeile) = zeilen.next() {
+        if !ist_metadatum(zeile) {
+            ergebnis.push(zeile);
+            continue;
+        }
+        // Leerzeile nach dem Feld gehört dazu
+        if zeilen.peek() == Some(&"") {
+            zeilen.next();
+        }
+        if !eingefuegt {
+            for alte_zeile in &alte {
+                ergebnis.push(alte_zeile);
+                ergebnis.push("");
+            }
+            eingefuegt = true;
+        }
+    }
+    ergebnis.join("\n") + "\n"
+}
+
+/// Erzeugt für jede Protokolldatei eines Verzeichnisses das PDF neu (gleicher Name,
+/// Endung `.pdf`), z. B. nach einem Wechsel von Logo oder Kopfzeile. Die Dateien
+/// werden auf so viele Threads verteilt, wie Kerne verfügbar sind; der Fortschritt
+/// erscheint zeilenweise auf der Konsole. Exit-Codes wie bei `verzeichnis_migrieren`.
+fn pdfs_neu_erzeugen(verzeichnis: &std::path::Path) -> i32 {
+    let Some(dateien) = markdown_dateien(verzeichnis) else {
+        eprintln!("Verzeichnis nicht lesbar: {}", verzeichnis.display());
+        return 2;
+    };
+    let gesamt = dateien.len();
+    let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1).min(gesamt.max(1));
+    let naechste = std::sync::atomic::AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel::<(String, Result<bool, String>)>();
+
+    let mut erzeugt = 0;
+    let mut uebersprungen = 0;
+    let mut fehler = 0;
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            let tx = tx.clone();
+            let naechste = &naechste;
+            let dateien = &dateien;
+            scope.spawn(move || {
+                // Jeder Thread arbeitet mit eigener App-Instanz und Schrift
+                let mut app = ProtokollApp::new(&egui::Context::default());
+                let schrift = app.schrift_laden();
+                loop {
+                    let i = naechste.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    let Some(pfad) = dateien.get(i) else {
+                        break;
+                    };
+                    let ergebnis = match (std::fs::read_to_string(pfad), &schrift) {
+                        (Err(e), _) => Err(e.to_string()),
+                        (Ok(inhalt), _) if !ist_protokoll(&inhalt) => Ok(false),
+                        (Ok(_), None) => Err("keine Schriftart gefunden".to_string()),
+                        (Ok(inhalt), Some(schrift)) => {
+                            app.markdown_parsen(&inhalt);
+                            // Vorgänger (offene Punkte) werden relativ zur Datei aufgelöst
+                            app.save_path = Some(pfad.clone());
+                            app.pdf_backend(schrift.clone())
+                                .pdf_erzeugen(&app)
+                                .and_then(|pdf| std::fs::write(pfad.with_extension("pdf"), pdf).map_err(|e| e.to_string()))
+                                .map(|_| true)
+                        }
+                    };
+                    let name = pfad.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                    let _ = tx.send((name, ergebnis));
+                }
+            });
+        }
+        drop(tx);
+
+        for (nr, (name, ergebnis)) in rx.iter().enumerate() {
+            let text = match ergebnis {
+                Ok(true) => {
+                    erzeugt += 1;
+                    "PDF erzeugt".to_string()
+                }
+                Ok(false) => {
+                    uebersprungen += 1;
+                    "übersprungen (kein Protokoll)".to_string()
+                }
+                Err(e) => {
+                    fehler += 1;
+                    format!("Fehler: {}", e)
+                }
+            };
+            println!("[{}/{}] {}: {}", nr + 1, gesamt, name, text);
+        }
+    });
+
+    println!("{} PDFs erzeugt, {} übersprungen, {} Fehler", erzeugt, uebersprungen, fehler);
+    if fehler > 0 { 1 } else { 0 }
+}
+
 // -- Datenmodell --
 
+/// Bearbeitungsstatus eines Protokolls. Welche Wechsel erlaubt sind, legt
+/// `Status::nachfolger` fest; welche Zustände angeboten werden, `status_zustaende`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Status {
+    /// Protokoll wird noch bearbeitet.
+    Entwurf,
+    /// Protokoll liegt den Teilnehmern zur Prüfung vor.
+    InPruefung,
+    /// Protokoll ist abgenommen und darf versendet werden.
+    Freigegeben,
+    /// Prüfung wurde abgelehnt; das Protokoll muss überarbeitet werden.
+    Abgelehnt,
+    /// Protokoll ist abgelegt und wird nicht mehr geändert.
+    Archiviert,
+}
+
+impl Status {
+    /// Gibt den deutschen Anzeigetext des Status zurück.
+    fn label(&self) -> &'static str {
+        match self {
+            Status::Entwurf => "Entwurf",
+            Status::InPruefung => "In Prüfung",
+            Status::Freigegeben => "Freigegeben",
+            Status::Abgelehnt => "Abgelehnt",
+            Status::Archiviert => "Archiviert",
+        }
+    }
+
+    /// Gibt alle Zustände in der Reihenfolge zurück, wie sie in der UI angezeigt werden.
+    fn alle() -> &'static [Status] {
+        &[Status::Entwurf, Status::InPruefung, Status::Freigegeben, Status::Abgelehnt, Status::Archiviert]
+    }
+
+    /// Erkennt einen Status am Namen (auch englisch, siehe `STATUS_ALIASE`).
+    fn parsen(text: &str) -> Option<Status> {
+        let name = alias_aufloesen(text, STATUS_ALIASE)?;
+        Status::alle().iter().copied().find(|s| s.label() == name)
+    }
+
+    /// Zustände, in die von diesem aus gewechselt werden darf.
+    fn nachfolger(&self) -> &'static [Status] {
+        match self {
+            Status::Entwurf => &[Status::InPruefung, Status::Freigegeben],
+            Status::InPruefung => &[Status::Freigegeben, Status::Abgelehnt, Status::Entwurf],
+            Status::Abgelehnt => &[Status::Entwurf],
+            Status::Freigegeben => &[Status::Archiviert, Status::Entwurf],
+            Status::Archiviert => &[],
+        }
+    }
+}
+
 /// Klassifizierungsstufe eines Protokolls.
 /// Steuert, wer das Dokument lesen darf.
 #[derive(Clone, Debug, PartialEq)]
@@ -111,6 +1472,28 @@ impl Sicherheit {
         }
     }
 
+    /// Gibt den Rang der Stufe zurück (0 = Öffentlich … 3 = Streng vertraulich).
+    /// Wird benötigt, um Herabstufungen zu erkennen.
+    fn stufe(&self) -> u8 {
+        match self {
+            Sicherheit::Oeffentlich => 0,
+            Sicherheit::Intern => 1,
+            Sicherheit::Vertraulich => 2,
+            Sicherheit::StrengVertraulich => 3,
+        }
+    }
+
+    /// Erkennt eine Stufe an ihrem Anzeigetext (auch englisch, siehe `KLASSIFIZIERUNG_ALIASE`).
+    fn parsen(text: &str) -> Option<Sicherheit> {
+        match alias_aufloesen(text, KLASSIFIZIERUNG_ALIASE) {
+            Some("Öffentlich") => Some(Sicherheit::Oeffentlich),
+            Some("Intern") => Some(Sicherheit::Intern),
+            Some("Vertraulich") => Some(Sicherheit::Vertraulich),
+            Some("Streng vertraulich") => Some(Sicherheit::StrengVertraulich),
+            _ => None,
+        }
+    }
+
     /// Gibt alle Stufen in der Reihenfolge zurück, wie sie in der UI angezeigt werden.
     fn all() -> &'static [Sicherheit] {
         &[
@@ -136,12 +1519,17 @@ enum Art {
     Entscheidung,
     /// Erledigte Aufgabe.
     Fertig,
+    /// Offene Frage; das Kümmerer-Feld enthält, wer sie gestellt hat, das Bis-Feld
+    /// das Datum der Antwort (leer = unbeantwortet).
+    Frage,
     /// Idee oder Vorschlag.
     Idee,
     /// Allgemeine Information.
     Info,
     /// Offene Aufgabe mit Kümmerer und Fälligkeitsdatum.
     Todo,
+    /// Wortbeitrag für Wortprotokolle; das Kümmerer-Feld enthält das Kürzel des Sprechers.
+    Wortbeitrag,
 }
 
 impl Art {
@@ -153,18 +1541,21 @@ impl Art {
             Art::Agenda => "AGENDA",
             Art::Entscheidung => "ENTSCHEIDUNG",
             Art::Fertig => "FERTIG",
+            Art::Frage => "FRAGE",
             Art::Idee => "IDEE",
             Art::Info => "INFO",
             Art::Todo => "TODO",
+            Art::Wortbeitrag => "WORTBEITRAG",
         }
     }
 
     /// Gibt den Anzeigetext für das ausgewählte Element im Dropdown zurück.
     /// Bei `Leer` wird ein leerer String zurückgegeben, damit das Feld unaufdringlich wirkt.
-    fn selected_label(&self) -> &str {
+    /// `labels` sind die Beschriftungen des aktuellen Projekts (siehe `art_beschriftung`).
+    fn selected_label<'a>(&'a self, labels: &'a [String]) -> &'a str {
         match self {
             Art::Leer => "",
-            other => other.label(),
+            other => art_beschriftung(labels, other),
         }
     }
 
@@ -176,9 +1567,11 @@ impl Art {
             Art::Agenda => egui::Color32::from_rgb(155, 89, 182),
             Art::Entscheidung => egui::Color32::from_rgb(52, 152, 219),
             Art::Fertig => egui::Color32::from_rgb(46, 204, 113),
+            Art::Frage => egui::Color32::from_rgb(232, 67, 147),
             Art::Idee => egui::Color32::from_rgb(241, 196, 15),
             Art::Info => egui::Color32::from_rgb(150, 150, 150),
             Art::Todo => egui::Color32::from_rgb(230, 126, 34),
+            Art::Wortbeitrag => egui::Color32::from_rgb(26, 188, 156),
         }
     }
 
@@ -190,14 +1583,17 @@ impl Art {
             Art::Agenda,
             Art::Entscheidung,
             Art::Fertig,
+            Art::Frage,
             Art::Idee,
             Art::Info,
             Art::Todo,
+            Art::Wortbeitrag,
         ]
     }
 }
 
 /// Eine am Meeting beteiligte Person (Protokollant, Teilnehmer oder zur Kenntnis).
+#[derive(Clone)]
 struct Person {
     /// Vollständiger Name der Person.
     name: String,
@@ -236,10 +1632,26 @@ struct Eintrag {
     art: Art,
     /// Freitext-Notiz, darf Zeilenumbrüche und Markdown-Links enthalten.
     notiz: String,
-    /// Kürzel der verantwortlichen Person (nur bei Art::Todo relevant).
+    /// Kürzel der verantwortlichen Person (bei Art::Todo), des Sprechers
+    /// (bei Art::Wortbeitrag) bzw. des Fragestellers (bei Art::Frage).
     kuemmerer: String,
-    /// Fälligkeitsdatum im Format TT.MM.JJJJ (nur bei Art::Todo relevant).
+    /// Fälligkeitsdatum im Format TT.MM.JJJJ bei Art::Todo, bei Art::Frage das Datum
+    /// der Antwort („beantwortet am").
     bis: String,
+    /// Archivweit eindeutige Beschlussnummer (z. B. „B-2026-07"), wird beim
+    /// Speichern für Art::Entscheidung automatisch vergeben.
+    beschluss_nr: String,
+    /// PDF: vor diesem Eintrag eine neue Seite beginnen.
+    umbruch_davor: bool,
+    /// PDF: Eintrag nicht über Seiten umbrechen.
+    zusammenhalten: bool,
+    /// Markierung aus dem Review im Präsentationsmodus.
+    review: Review,
+    /// Klärungsvermerk zu einem strittigen Eintrag.
+    klaerung: String,
+    /// Innerhalb des Protokolls eindeutige Nummer (0 = noch nicht vergeben),
+    /// verknüpft den Eintrag mit seinen Zeilen im Audit-Log.
+    id: u32,
 }
 
 impl Eintrag {
@@ -251,2314 +1663,14219 @@ impl Eintrag {
             notiz: String::new(),
             kuemmerer: String::new(),
             bis: String::new(),
+            beschluss_nr: String::new(),
+            umbruch_davor: false,
+            zusammenhalten: false,
+            review: Review::Offen,
+            klaerung: String::new(),
+            id: 0,
         }
     }
-}
 
-/// Farbschema der Anwendungsoberfläche.
+    /// `true` bei einer Frage ohne „beantwortet am".
+    fn offene_frage(&self) -> bool {
+        self.art == Art::Frage && self.bis.trim().is_empty()
+    }
+
+    /// Inhalt der Bis-Spalte in Ausgaben: bei Fragen, ob und wann sie beantwortet
+    /// wurden; Beschriftung und Datum in `sprache`.
+    fn bis_text(&self, sprache: PdfSprache) -> String {
+        match self.art {
+            Art::Frage if self.offene_frage() => sprache.t("offen").to_string(),
+            Art::Frage => format!("{} {}", sprache.t("beantwortet"), sprache.kurzdatum_text(self.bis.trim())),
+            _ => sprache.kurzdatum_text(&self.bis),
+        }
+    }
+}
+
+/// Markierung eines Eintrags im gemeinsamen Review (Präsentationsmodus).
 #[derive(Clone, Copy, PartialEq)]
-enum Theme {
-    /// Helles egui-Standard-Theme.
-    Hell,
-    /// Dunkles Theme mit reinem Schwarz als Hintergrund.
-    Dunkel,
-    /// Passt Farben automatisch an das aktive Omarchy-Desktop-Theme an.
-    Omarchy,
+enum Review {
+    /// Noch nicht besprochen.
+    Offen,
+    /// Von den Teilnehmern bestätigt.
+    Bestaetigt,
+    /// Strittig, braucht einen Klärungsvermerk.
+    Strittig,
 }
 
-impl Theme {
-    /// Wechselt zyklisch zum nächsten Theme.
-    /// Omarchy wird nur angeboten, wenn die Konfigurationsdatei gefunden wurde.
-    fn next(self, has_omarchy: bool) -> Self {
+impl Review {
+    /// Gibt die Kennzeichnung für Markdown und PDF zurück (leer bei `Offen`).
+    fn label(&self) -> &str {
         match self {
-            Theme::Hell => Theme::Dunkel,
-            Theme::Dunkel => if has_omarchy { Theme::Omarchy } else { Theme::Hell },
-            Theme::Omarchy => Theme::Hell,
+            Review::Offen => "",
+            Review::Bestaetigt => "BESTÄTIGT",
+            Review::Strittig => "STRITTIG",
         }
     }
 
-
+    /// Gibt die Markierungsfarbe zurück (grün bzw. rot).
+    fn color(&self) -> egui::Color32 {
+        match self {
+            Review::Offen => egui::Color32::GRAY,
+            Review::Bestaetigt => egui::Color32::from_rgb(46, 204, 113),
+            Review::Strittig => egui::Color32::from_rgb(231, 76, 60),
+        }
+    }
 }
 
-/// Ergebnis eines asynchronen Datei-Dialogs (Laden, Speichern oder PDF-Export).
-enum DialogErgebnis {
-    /// Eine Markdown-Datei wurde ausgewählt und eingelesen.
-    Laden(std::path::PathBuf, String),
-    /// Ein Speicherpfad wurde gewählt (Datei wurde bereits geschrieben).
-    Speichern(std::path::PathBuf),
-    /// Ein PDF-Speicherpfad wurde gewählt.
-    PdfExport(std::path::PathBuf),
+/// Ein Eintrag im Audit-Log des Protokolls (wer hat wann was geändert).
+struct AuditEintrag {
+    /// Zeitstempel im Format TT.MM.JJJJ HH:MM.
+    zeitpunkt: String,
+    /// Name der Person, die die Änderung vorgenommen hat.
+    benutzer: String,
+    /// Beschreibung der Änderung (einzeilig).
+    aktion: String,
 }
 
-/// Zentraler Anwendungszustand von MZProtokoll.
-/// Enthält alle Daten des aktuell geöffneten Protokolls sowie UI-Steuerflags.
-struct ProtokollApp {
-    // --- Protokoll-Kopfdaten ---
-    /// Optionaler Projektname (erscheint klein über dem Titel).
-    projekt: String,
-    /// Titel / Name des Meetings (Hauptüberschrift).
-    titel: String,
-    /// Datum als freier Text, z. B. „Montag, 05.02.2026".
-    datum_text: String,
-    /// Veranstaltungsort des Meetings.
-    ort: String,
-    /// Person, die das Protokoll führt (Pflichtfeld).
-    protokollant: Person,
-    /// Liste aller Meetingteilnehmer.
-    teilnehmer: Vec<Person>,
-    /// Personen, die das Protokoll zur Kenntnis erhalten.
-    zur_kenntnis: Vec<Person>,
-    /// Freitext-Beschreibung des Meetings.
-    ueber_meeting: String,
-    /// `true` = Protokoll ist noch ein Entwurf.
-    ist_entwurf: bool,
-    /// `true` = Protokoll wurde freigegeben.
-    ist_freigegeben: bool,
-    /// Geheimhaltungsstufe des Protokolls.
-    sicherheit: Sicherheit,
-    /// Alle Tabelleneinträge des Protokolls.
-    eintraege: Vec<Eintrag>,
+/// Ein TODO aus dem Archiv, für das der Benutzer Kümmerer ist („Meine Aufgaben“).
+struct ArchivAufgabe {
+    /// Punkt und Notiz des TODOs (einzeilig).
+    aufgabe: String,
+    /// Bis-Datum wie im Protokoll eingetragen.
+    bis: String,
+    /// Titel und Meetingdatum des Protokolls.
+    protokoll: String,
+    /// Pfad der Protokolldatei.
+    pfad: std::path::PathBuf,
+}
 
-    // --- UI-Steuerflags ---
-    /// Fordert den Fokus für die zuletzt hinzugefügte Teilnehmerzeile an.
-    focus_new_teilnehmer: bool,
-    /// Fordert den Fokus für die zuletzt hinzugefügte Zur-Kenntnis-Zeile an.
-    focus_new_zur_kenntnis: bool,
-    /// Aktives Farbschema der UI.
-    theme: Theme,
-    /// Pfad der aktuell geöffneten/gespeicherten Datei (leer = noch nicht gespeichert).
-    save_path: Option<std::path::PathBuf>,
-    /// Steuert die Anzeige des Beenden-Bestätigungsdialogs.
-    show_quit_dialog: bool,
-    /// Steuert die Anzeige des Über-Dialogs.
-    show_about_dialog: bool,
-    /// Gecachte App-Icon-Textur für den Über-Dialog.
-    icon_texture: Option<egui::TextureHandle>,
-    /// Steuert die Anzeige des PDF-Fehler-Dialogs (keine Schrift gefunden).
-    show_pdf_error: bool,
-    /// Steuert die Anzeige des Pflichtfeld-Hinweisdialogs.
-    show_pflichtfeld_hinweis: bool,
-    /// Index des Notizfeldes, das beim nächsten Frame den Fokus erhalten soll.
-    focus_notiz: Option<usize>,
-    /// Speichert, welche Notizzeile zuletzt fokussiert war (Index, Cursor-Position).
-    /// Wird für die Cursor-Auf/Ab-Navigation zwischen Notizfeldern benötigt.
-    notiz_had_focus: Option<(usize, usize)>,
-    /// Textfarbe für Eingabefelder im Omarchy-Theme (aus `color2`).
-    input_text_color: Option<egui::Color32>,
-    /// Farbe für Beschriftungen/Labels im Omarchy-Theme (aus `color3`).
-    label_color: Option<egui::Color32>,
-    /// `true` wenn eine Omarchy-Theme-Konfiguration gefunden wurde.
-    has_omarchy: bool,
-    /// Empfangskanal für Ergebnisse aus Datei-Dialog-Threads.
-    dialog_rx: Option<mpsc::Receiver<DialogErgebnis>>,
-    /// Zwischengespeicherte Schriftfamilie für den PDF-Export (wird nach dem
-    /// Dialog-Thread übergeben und dann verbraucht).
-    pending_pdf_font: Option<genpdf::fonts::FontFamily<genpdf::fonts::FontData>>,
+/// Sammelt aus allen Protokollen unter `archiv` die TODOs, deren Kümmerer zum
+/// Benutzerprofil passt, sortiert nach Bis-Datum (ohne Datum zuletzt).
+fn archiv_aufgaben_laden(
+    archiv: &std::path::Path,
+    profil: &Benutzerprofil,
+    einstellungen: &Einstellungen,
+) -> Vec<ArchivAufgabe> {
+    let mut aufgaben: Vec<(Option<NaiveDate>, ArchivAufgabe)> = Vec::new();
+    for pfad in markdown_dateien_rekursiv(archiv) {
+        let Ok(content) = std::fs::read_to_string(&pfad) else {
+            continue;
+        };
+        if !ist_protokoll(&content) {
+            continue;
+        }
+        let auszug = protokoll_auszug_lesen(&content, einstellungen);
+        let protokoll = match auszug.datum {
+            Some(d) => format!("{} ({})", auszug.titel, d.format("%d.%m.%Y")),
+            None => auszug.titel.clone(),
+        };
+        for e in auszug.todos.iter().filter(|e| kuerzel_zustaendig(&e.kuemmerer, &profil.kuerzel, &profil.name)) {
+            let aufgabe = [e.punkt.trim(), e.notiz.trim()]
+                .iter()
+                .filter(|t| !t.is_empty())
+                .copied()
+                .collect::<Vec<_>>()
+                .join(": ")
+                .replace('\n', " ");
+            aufgaben.push((
+                datum_aus_text(&e.bis),
+                ArchivAufgabe { aufgabe, bis: e.bis.clone(), protokoll: protokoll.clone(), pfad: pfad.clone() },
+            ));
+        }
+    }
+    aufgaben.sort_by_key(|(d, _)| (d.is_none(), *d));
+    aufgaben.into_iter().map(|(_, a)| a).collect()
+}
 
-    // --- Metadaten zur Nachverfolgbarkeit ---
-    /// Zeitstempel der Ersterstellung (TT.MM.JJJJ HH:MM), leer wenn noch nicht gespeichert.
-    erstellt_am: String,
-    /// Name der Person, die das Protokoll erstellt hat.
-    erstellt_von: String,
+/// Filter „fällig zwischen" auf die Bis-Daten (TT.MM.JJJJ, leere Grenze = offen).
+/// Aktiv, sobald eine Grenze ein gültiges Datum ist.
+#[derive(Default, PartialEq)]
+struct FaelligFilter {
+    von: String,
+    bis: String,
 }
 
-impl ProtokollApp {
-    /// Initialisiert die App: lädt Systemschriften, ermittelt das aktuelle Datum
-    /// und setzt alle Felder auf Standardwerte.
-    fn new(ctx: &egui::Context) -> Self {
-        // Systemschriften laden: egui benötigt Regular und Bold als separate Font-Families.
-        // Liest Schriften zur Laufzeit vom System – keine Schriften werden eingebettet.
-        {
-            #[cfg(windows)]
-            let schrift_paare = [
-                ("C:\\Windows\\Fonts\\arial.ttf",    "C:\\Windows\\Fonts\\arialbd.ttf"),
-                ("C:\\Windows\\Fonts\\segoeui.ttf",  "C:\\Windows\\Fonts\\segoeuib.ttf"),
-                ("C:\\Windows\\Fonts\\calibri.ttf",  "C:\\Windows\\Fonts\\calibrib.ttf"),
-                ("C:\\Windows\\Fonts\\tahoma.ttf",   "C:\\Windows\\Fonts\\tahomabd.ttf"),
-            ];
-            #[cfg(not(windows))]
-            let schrift_paare = [
-                // Arch, Fedora, openSUSE
-                ("/usr/share/fonts/liberation/LiberationSans-Regular.ttf", "/usr/share/fonts/liberation/LiberationSans-Bold.ttf"),
-                ("/usr/share/fonts/TTF/LiberationSans-Regular.ttf",        "/usr/share/fonts/TTF/LiberationSans-Bold.ttf"),
-                ("/usr/share/fonts/noto/NotoSans-Regular.ttf",             "/usr/share/fonts/noto/NotoSans-Bold.ttf"),
-                ("/usr/share/fonts/TTF/NotoSans-Regular.ttf",              "/usr/share/fonts/TTF/NotoSans-Bold.ttf"),
-                // Debian, Ubuntu, Mint
-                ("/usr/share/fonts/truetype/liberation/LiberationSans-Regular.ttf", "/usr/share/fonts/truetype/liberation/LiberationSans-Bold.ttf"),
-                ("/usr/share/fonts/truetype/noto/NotoSans-Regular.ttf",             "/usr/share/fonts/truetype/noto/NotoSans-Bold.ttf"),
-                // DejaVu als Fallback
-                ("/usr/share/fonts/TTF/DejaVuSans.ttf",                    "/usr/share/fonts/TTF/DejaVuSans-Bold.ttf"),
-                ("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",        "/usr/share/fonts/truetype/dejavu/DejaVuSans-Bold.ttf"),
-            ];
-            for (regulaer_pfad, fett_pfad) in schrift_paare {
-                if let (Ok(regulaer_daten), Ok(fett_daten)) = (std::fs::read(regulaer_pfad), std::fs::read(fett_pfad)) {
-                    let mut schriften = egui::FontDefinitions::default();
-                    schriften.font_data.insert("regular".to_owned(), egui::FontData::from_owned(regulaer_daten).into());
-                    schriften.font_data.insert("bold".to_owned(), egui::FontData::from_owned(fett_daten).into());
-                    // Regular als Standard-Proportional-Schrift registrieren
-                    if let Some(family) = schriften.families.get_mut(&egui::FontFamily::Proportional) {
-                        family.insert(0, "regular".to_owned());
-                    }
-                    // Bold als eigene Font-Family für Eingabefelder registrieren
-                    let mut fette_schriftfamilie = vec!["bold".to_owned()];
-                    if let Some(proportional) = schriften.families.get(&egui::FontFamily::Proportional) {
-                        fette_schriftfamilie.extend(proportional.iter().cloned());
-                    }
-                    schriften.families.insert(egui::FontFamily::Name("Bold".into()), fette_schriftfamilie);
-                    ctx.set_fonts(schriften);
-                    break; // erste gefundene Schrift verwenden
-                }
-            }
+impl FaelligFilter {
+    fn aktiv(&self) -> bool {
+        datum_aus_text(&self.von).is_some() || datum_aus_text(&self.bis).is_some()
+    }
+
+    /// `true`, wenn das Bis-Datum im Bereich liegt (Grenzen eingeschlossen). Bei aktivem
+    /// Filter fallen Einträge ohne gültiges Datum heraus.
+    fn passt(&self, bis: &str) -> bool {
+        if !self.aktiv() {
+            return true;
         }
+        let Some(datum) = datum_aus_text(bis) else {
+            return false;
+        };
+        datum_aus_text(&self.von).is_none_or(|von| datum >= von) && datum_aus_text(&self.bis).is_none_or(|bis| datum <= bis)
+    }
 
+    /// Schnellauswahl: von heute bis in `tage` Tagen.
+    fn naechste_tage(&mut self, tage: i64) {
         let heute = Local::now().date_naive();
-        let wochentag = match heute.weekday() {
-            chrono::Weekday::Mon => "Montag",
-            chrono::Weekday::Tue => "Dienstag",
-            chrono::Weekday::Wed => "Mittwoch",
-            chrono::Weekday::Thu => "Donnerstag",
-            chrono::Weekday::Fri => "Freitag",
-            chrono::Weekday::Sat => "Samstag",
-            chrono::Weekday::Sun => "Sonntag",
-        };
-        Self {
-            projekt: String::new(),
-            titel: String::new(),
-            datum_text: format!(
-                "{}, {:02}.{:02}.{}",
-                wochentag,
-                heute.day(),
-                heute.month(),
-                heute.year()
-            ),
-            ort: String::new(),
-            protokollant: Person::new(),
-            teilnehmer: vec![Person::new()],
-            zur_kenntnis: vec![Person::new()],
-            ueber_meeting: String::new(),
-            ist_entwurf: true,
-            ist_freigegeben: false,
-            sicherheit: Sicherheit::Intern,
-            eintraege: vec![Eintrag::new()],
-            focus_new_teilnehmer: false,
-            focus_new_zur_kenntnis: false,
-            theme: if omarchy_farben_laden().is_some() { Theme::Omarchy } else { Theme::Dunkel },
-            save_path: None,
-            show_quit_dialog: false,
-            show_about_dialog: false,
-            icon_texture: None,
-            show_pdf_error: false,
-            show_pflichtfeld_hinweis: false,
-            focus_notiz: None,
-            notiz_had_focus: None,
-            input_text_color: None,
-            label_color: None,
-            has_omarchy: omarchy_farben_laden().is_some(),
-            dialog_rx: None,
-            pending_pdf_font: None,
-            erstellt_am: String::new(),
-            erstellt_von: String::new(),
-        }
+        self.von = heute.format("%d.%m.%Y").to_string();
+        self.bis = (heute + chrono::Duration::days(tage)).format("%d.%m.%Y").to_string();
     }
 
-    /// Generiert einen vorgeschlagenen Dateinamen für die Markdown-Datei.
-    /// Format: `MZProtokoll_<Titel>__<JJJJ-MM-TT>.md`
-    fn dateinamen_erstellen(&self) -> String {
-        let name_part: String = self.titel.chars().filter(|c| c.is_alphabetic()).collect();
-        let datum = Local::now().format("%Y-%m-%d").to_string();
-        format!("MZProtokoll_{}__{}.md", name_part, datum)
+    /// Von heute bis Sonntag der laufenden Woche.
+    fn diese_woche() -> Self {
+        let heute = Local::now().date_naive();
+        let sonntag = heute + chrono::Duration::days(6 - heute.weekday().num_days_from_monday() as i64);
+        FaelligFilter { von: heute.format("%d.%m.%Y").to_string(), bis: sonntag.format("%d.%m.%Y").to_string() }
     }
 
-    /// Serialisiert den aktuellen Protokollzustand als Markdown-String.
-    /// Das Format ist spezifisch für MZProtokoll und wird von `markdown_parsen` wieder eingelesen.
-    fn markdown_erstellen(&self) -> String {
-        let mut md = String::new();
+    /// Alles bis gestern, also überfällig.
+    fn ueberfaellig() -> Self {
+        let gestern = Local::now().date_naive() - chrono::Duration::days(1);
+        FaelligFilter { von: String::new(), bis: gestern.format("%d.%m.%Y").to_string() }
+    }
+}
 
-        if !self.projekt.is_empty() {
-            md.push_str(&format!("**Projekt:** {}\n\n", self.projekt));
-        }
+/// Eine Zeile im archivweiten Beschlussregister.
+struct BeschlussEintrag {
+    /// Beschlussnummer im Format `B-<Jahr>-<laufende Nummer>`.
+    nr: String,
+    /// Meetingdatum des Protokolls.
+    datum: String,
+    /// Titel des Protokolls.
+    titel: String,
+    /// Punkt und Notiz des Entscheidungs-Eintrags (einzeilig).
+    beschluss: String,
+}
 
-        md.push_str(&format!("# {}\n\n", self.titel));
+/// Gibt den Pfad des Beschlussregisters zurück (Markdown-Tabelle im Konfigurationsverzeichnis).
+fn beschlussregister_pfad() -> Option<std::path::PathBuf> {
+    konfig_verzeichnis().map(|d| d.join("beschlussregister.md"))
+}
 
-        let mut meta = Vec::new();
-        if !self.datum_text.is_empty() {
-            meta.push(format!("**Datum:** {}", self.datum_text));
-        }
-        if !self.ort.is_empty() {
-            meta.push(format!("**Ort:** {}", self.ort));
-        }
-        if !meta.is_empty() {
-            md.push_str(&meta.join(" | "));
-            md.push_str("\n\n");
-        }
+/// Liest das Beschlussregister ein. Fehlt die Datei, ist das Register leer.
+fn beschlussregister_laden() -> Vec<BeschlussEintrag> {
+    let Some(content) = beschlussregister_pfad().and_then(|p| std::fs::read_to_string(p).ok()) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter(|l| l.trim().starts_with("| B-"))
+        .map(tabellenzeile_aufteilen)
+        .filter(|cells| cells.len() >= 4)
+        .map(|cells| BeschlussEintrag {
+            nr: cells[0].clone(),
+            datum: cells[1].clone(),
+            titel: cells[2].clone(),
+            beschluss: cells[3].clone(),
+        })
+        .collect()
+}
 
-        md.push_str("---\n\n");
+/// Schreibt das Beschlussregister vollständig neu.
+fn beschlussregister_speichern(register: &[BeschlussEintrag]) {
+    let Some(pfad) = beschlussregister_pfad() else {
+        return;
+    };
+    if let Some(dir) = pfad.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let mut md = String::from("# Beschlussregister\n\n| Nr. | Datum | Protokoll | Beschluss |\n|-----|-------|-----------|-----------|\n");
+    for b in register {
+        md.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            b.nr,
+            b.datum.replace('|', "\\|"),
+            b.titel.replace('|', "\\|"),
+            b.beschluss.replace('|', "\\|")
+        ));
+    }
+    let _ = std::fs::write(pfad, md);
+}
 
-        if !self.protokollant.name.is_empty() {
-            md.push_str("## Protokollführer\n\n");
-            md.push_str(&self.protokollant.name);
-            if !self.protokollant.kuerzel.is_empty() {
-                md.push_str(&format!(" [{}]", self.protokollant.kuerzel));
+/// Die für die Offene-Punkte-Liste benötigten Angaben eines (Vorgänger-)Protokolls.
+struct ProtokollAuszug {
+    titel: String,
+    datum: Option<NaiveDate>,
+    projekt: String,
+    /// Angekreuzter Status (`None` = keiner angekreuzt bzw. unbekannt).
+    status: Option<Status>,
+    /// Verknüpftes Vorgängerprotokoll (Pfad wie in der Datei angegeben).
+    vorgaenger: String,
+    /// Alle TODO-Einträge des Protokolls.
+    todos: Vec<Eintrag>,
+    /// Alle Einträge des Protokolls (für den Vergleich mit dem letzten Versandstand).
+    eintraege: Vec<Eintrag>,
+}
+
+/// Liest Titel, Datum, Projekt, Status, Vorgänger-Verknüpfung und TODOs aus einer
+/// Protokolldatei, ohne den App-Zustand anzutasten (vereinfachte Variante von `markdown_parsen`).
+fn protokoll_auszug_lesen(content: &str, einstellungen: &Einstellungen) -> ProtokollAuszug {
+    let mut auszug = ProtokollAuszug {
+        titel: String::new(),
+        datum: None,
+        projekt: String::new(),
+        status: None,
+        vorgaenger: String::new(),
+        todos: Vec::new(),
+        eintraege: Vec::new(),
+    };
+    let mut in_eintraegen = false;
+    let mut in_status = false;
+    let mut table_rows_seen = 0u32;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if let Some(ueberschrift) = trimmed.strip_prefix("## ") {
+            let abschnitt = alias_aufloesen(ueberschrift, ABSCHNITT_ALIASE);
+            in_eintraegen = abschnitt == Some("Einträge");
+            in_status = abschnitt == Some("Status");
+            table_rows_seen = 0;
+            continue;
+        }
+        if in_status {
+            if let Some(status) = trimmed.strip_prefix("- [x] ").and_then(Status::parsen) {
+                auszug.status = Some(status);
             }
-            md.push_str("\n\n");
+            continue;
         }
-
-        let tn: Vec<_> = self.teilnehmer.iter().filter(|t| !t.name.is_empty()).collect();
-        if !tn.is_empty() {
-            md.push_str("## Teilnehmer\n\n");
-            for t in &tn {
-                md.push_str(&format!("- {}", t.name));
-                if !t.kuerzel.is_empty() {
-                    md.push_str(&format!(" [{}]", t.kuerzel));
+        if auszug.titel.is_empty() && trimmed.starts_with("# ") {
+            auszug.titel = trimmed[2..].to_string();
+        } else if trimmed.starts_with("**") {
+            for part in trimmed.split(" | ") {
+                let Some((name, wert)) = fett_feld(part) else {
+                    continue;
+                };
+                match alias_aufloesen(name, FELD_ALIASE) {
+                    Some("Vorgänger") => auszug.vorgaenger = wert.to_string(),
+                    Some("Projekt") => auszug.projekt = wert.to_string(),
+                    // Nur das Kopfdatum zählt, nicht der Termin des Folgemeetings
+                    Some("Datum") if auszug.datum.is_none() => auszug.datum = datum_aus_text(wert),
+                    _ => {}
                 }
-                md.push('\n');
             }
-            md.push('\n');
-        }
-
-        let zk: Vec<_> = self.zur_kenntnis.iter().filter(|z| !z.name.is_empty()).collect();
-        if !zk.is_empty() {
-            md.push_str("## Zur Kenntnis\n\n");
-            for z in &zk {
-                md.push_str(&format!("- {}", z.name));
-                if !z.kuerzel.is_empty() {
-                    md.push_str(&format!(" [{}]", z.kuerzel));
+        } else if in_eintraegen && trimmed.starts_with('|') {
+            table_rows_seen += 1;
+            if table_rows_seen >= 3 {
+                let cells = tabellenzeile_aufteilen(trimmed);
+                if cells.len() >= 5 {
+                    if art_parsen(&cells[1]) == Art::Todo {
+                        let mut e = Eintrag::new();
+                        e.art = Art::Todo;
+                        e.notiz = cells[2].replace(" <br> ", "\n");
+                        e.kuemmerer = cells[3].clone();
+                        e.bis = cells[4].clone();
+                        auszug.todos.push(e);
+                    }
+                    auszug.eintraege.push(eintrag_aus_zellen(&cells, einstellungen));
                 }
-                md.push('\n');
             }
-            md.push('\n');
         }
+    }
+    auszug
+}
 
-        md.push_str("## Über dieses Meeting\n\n");
-        if !self.ueber_meeting.is_empty() {
-            md.push_str(&self.ueber_meeting);
-            md.push_str("\n\n");
+/// Löst einen in einer Protokolldatei angegebenen Vorgängerpfad auf.
+/// Relative Pfade beziehen sich auf das Verzeichnis der verweisenden Datei.
+fn vorgaenger_pfad_aufloesen(vorgaenger: &str, basis: Option<&std::path::Path>) -> std::path::PathBuf {
+    let pfad = std::path::PathBuf::from(vorgaenger);
+    match basis.and_then(|b| b.parent()) {
+        Some(dir) if pfad.is_relative() => dir.join(pfad),
+        _ => pfad,
+    }
+}
+
+/// Art einer Änderung gegenüber dem zuletzt exportierten Stand.
+#[derive(Clone, Copy, PartialEq)]
+enum AenderungsArt {
+    Neu,
+    Geaendert,
+    Entfernt,
+}
+
+impl AenderungsArt {
+    /// Gibt die Kennzeichnung für Dialog und PDF zurück.
+    fn label(&self) -> &str {
+        match self {
+            AenderungsArt::Neu => "NEU",
+            AenderungsArt::Geaendert => "GEÄNDERT",
+            AenderungsArt::Entfernt => "ENTFERNT",
         }
+    }
 
-        md.push_str("## Status\n\n");
-        if self.ist_entwurf {
-            md.push_str("- [x] Entwurf\n");
-            md.push_str("- [ ] Freigegeben\n");
-        } else if self.ist_freigegeben {
-            md.push_str("- [ ] Entwurf\n");
-            md.push_str("- [x] Freigegeben\n");
+    /// Gibt die Hervorhebungsfarbe im Exportdialog zurück.
+    fn color(&self) -> egui::Color32 {
+        match self {
+            AenderungsArt::Neu => egui::Color32::from_rgb(46, 204, 113),
+            AenderungsArt::Geaendert => egui::Color32::from_rgb(230, 126, 34),
+            AenderungsArt::Entfernt => egui::Color32::from_rgb(231, 76, 60),
+        }
+    }
+}
+
+/// Ein neuer, geänderter oder entfernter Eintrag seit dem letzten Export.
+struct EintragsAenderung {
+    art: AenderungsArt,
+    /// Eintragsart und Punkt, z. B. „TODO" oder „INFO – Budget".
+    titel: String,
+    /// Notiz im aktuellen Stand (bei entfernten Einträgen im alten Stand).
+    notiz: String,
+}
+
+/// Vergleich des aktuellen Protokolls mit dem zuletzt exportierten Stand.
+struct VersandDiff {
+    /// Nummer des Vergleichsstands (`v<n>.md`).
+    version: u32,
+    aenderungen: Vec<EintragsAenderung>,
+}
+
+/// Schlüssel, über den ein Eintrag im alten Stand wiedergefunden wird:
+/// der Punkt, bei Einträgen ohne Punkt die erste Notizzeile.
+fn eintrag_schluessel(e: &Eintrag) -> String {
+    if e.punkt.trim().is_empty() {
+        e.notiz.lines().next().unwrap_or("").trim().to_string()
+    } else {
+        e.punkt.trim().to_string()
+    }
+}
+
+/// Vergleicht zwei Eintragslisten. Unveränderte Einträge werden über ihre
+/// Markdown-Zeile erkannt, geänderte über `eintrag_schluessel`; was im alten
+/// Stand übrig bleibt, gilt als entfernt. Leere Einträge werden ignoriert.
+fn eintraege_vergleichen(alt: &[Eintrag], neu: &[Eintrag]) -> Vec<EintragsAenderung> {
+    let leer = |e: &Eintrag| e.art == Art::Leer && e.punkt.trim().is_empty() && e.notiz.trim().is_empty();
+    let titel = |e: &Eintrag| {
+        if e.punkt.trim().is_empty() {
+            e.art.label().to_string()
         } else {
-            md.push_str("- [ ] Entwurf\n");
-            md.push_str("- [ ] Freigegeben\n");
+            format!("{} – {}", e.art.label(), e.punkt.trim())
         }
-        md.push('\n');
+    };
+    let alt_zeilen: Vec<String> = alt.iter().map(|e| eintrag_markdown_zeile(e, e.art.label(), "")).collect();
+    let mut alt_benutzt: Vec<bool> = alt.iter().map(leer).collect();
+
+    // 1. Unveränderte Einträge aussortieren
+    let mut offen = Vec::new();
+    for e in neu.iter().filter(|e| !leer(e)) {
+        let zeile = eintrag_markdown_zeile(e, e.art.label(), "");
+        match (0..alt.len()).find(|&j| !alt_benutzt[j] && alt_zeilen[j] == zeile) {
+            Some(j) => alt_benutzt[j] = true,
+            None => offen.push(e),
+        }
+    }
 
-        md.push_str("## Klassifizierung\n\n");
-        for s in Sicherheit::all() {
-            if *s == self.sicherheit {
-                md.push_str(&format!("- [x] {}\n", s.label()));
-            } else {
-                md.push_str(&format!("- [ ] {}\n", s.label()));
+    // 2. Übrige Einträge: gleicher Schlüssel → geändert, sonst neu
+    let mut aenderungen = Vec::new();
+    for e in offen {
+        let schluessel = eintrag_schluessel(e);
+        let art = match (0..alt.len()).find(|&j| !alt_benutzt[j] && eintrag_schluessel(&alt[j]) == schluessel) {
+            Some(j) => {
+                alt_benutzt[j] = true;
+                AenderungsArt::Geaendert
             }
+            None => AenderungsArt::Neu,
+        };
+        aenderungen.push(EintragsAenderung { art, titel: titel(e), notiz: e.notiz.clone() });
+    }
+
+    // 3. Nicht zugeordnete alte Einträge wurden entfernt
+    for (j, e) in alt.iter().enumerate() {
+        if !alt_benutzt[j] {
+            aenderungen.push(EintragsAenderung {
+                art: AenderungsArt::Entfernt,
+                titel: titel(e),
+                notiz: e.notiz.clone(),
+            });
         }
-        md.push('\n');
+    }
+    aenderungen
+}
 
-        let entries: Vec<_> = self
-            .eintraege
-            .iter()
-            .filter(|e| !e.punkt.is_empty() || e.art != Art::Leer || !e.notiz.is_empty())
-            .collect();
+/// Ein Kopffeld oder Abschnitt, der in der Datei außerhalb der App geändert wurde.
+struct ExternerBereich {
+    /// Kopffeld („Titel", „Ort" …) oder Abschnitt („Teilnehmer", „Einträge" …).
+    name: String,
+    /// `true` = Kopffeld, wird einzeln gesetzt statt als Abschnitt ersetzt.
+    kopffeld: bool,
+    /// Inhalt in der App bzw. in der Datei (normalisiert, leer = nicht vorhanden).
+    lokal: String,
+    datei: String,
+    /// Der Bereich wurde seit dem letzten Speichern auch in der App geändert.
+    konflikt: bool,
+    /// Auswahl im Dialog: Stand aus der Datei übernehmen.
+    uebernehmen: bool,
+}
 
-        if !entries.is_empty() {
-            md.push_str("---\n\n");
-            md.push_str("## Einträge\n\n");
-            md.push_str("| Punkt | Art | Notiz | Kümmerer | Bis |\n");
-            md.push_str("|-------|-----|-------|----------|-----|\n");
-            for e in &entries {
-                let art_str = if e.art == Art::Leer {
-                    ""
-                } else {
-                    e.art.label()
-                };
-                let notiz = e.notiz.replace('\n', " <br> ").replace('|', "\\|");
-                let punkt = e.punkt.replace('|', "\\|");
-                let kuemmerer = e.kuemmerer.replace('|', "\\|");
-                md.push_str(&format!(
-                    "| {} | {} | {} | {} | {} |\n",
-                    punkt, art_str, notiz, kuemmerer, e.bis
-                ));
+/// Externe Änderung der geöffneten Datei, bis der Benutzer sie übernimmt oder verwirft.
+struct ExterneAenderung {
+    /// Dateiinhalt zum Zeitpunkt der Erkennung.
+    inhalt: String,
+    /// Änderungszeit der Datei, wird nach der Entscheidung als bekannt vermerkt.
+    zeit: Option<std::time::SystemTime>,
+    bereiche: Vec<ExternerBereich>,
+    /// Änderungen an den einzelnen Einträgen (nur zur Anzeige).
+    eintraege: Vec<EintragsAenderung>,
+}
+
+/// Name, unter dem der Kopfbereich in `markdown_abschnitte` geführt wird.
+const KOPF_ABSCHNITT: &str = "Kopf";
+
+/// Zeilen, die beim Vergleich mit der Datei keine Rolle spielen: Trennlinien und die
+/// Fußzeilen (Erstellt, Geändert, Bearbeitungszeit, Eintragsnummer, Hinweis auf MZProtokoll).
+fn fusszeile(zeile: &str) -> bool {
+    let zeile = zeile.trim();
+    zeile == "---"
+        || zeile.starts_with("**Erstellt:**")
+        || zeile.starts_with("**Geändert:**")
+        || zeile.starts_with("**Bearbeitungszeit:**")
+        || zeile.starts_with("**Nächste Eintragsnummer:**")
+        || zeile.starts_with("*Erstellt mit MZProtokoll")
+}
+
+/// Zerlegt Protokoll-Markdown in den Kopfbereich und die `## `-Abschnitte, jeweils
+/// als (kanonischer Name, Zeilen ohne Fußzeilen). Die Überschrift gehört zum Abschnitt.
+fn markdown_abschnitte(md: &str) -> Vec<(String, Vec<String>)> {
+    let mut abschnitte: Vec<(String, Vec<String>)> = vec![(KOPF_ABSCHNITT.to_string(), Vec::new())];
+    for zeile in md.lines().filter(|l| !fusszeile(l)) {
+        if let Some(ueberschrift) = zeile.trim().strip_prefix("## ") {
+            let name = alias_aufloesen(ueberschrift, ABSCHNITT_ALIASE).unwrap_or(ueberschrift.trim());
+            abschnitte.push((name.to_string(), Vec::new()));
+        }
+        if let Some((_, zeilen)) = abschnitte.last_mut() {
+            zeilen.push(zeile.to_string());
+        }
+    }
+    abschnitte
+}
+
+/// Kopffelder aus den Zeilen des Kopfbereichs: Titel und die fett gesetzten Felder
+/// (kanonischer Name laut `FELD_ALIASE`).
+fn kopf_felder(zeilen: &[String]) -> Vec<(String, String)> {
+    let mut felder = Vec::new();
+    for zeile in zeilen {
+        let zeile = zeile.trim();
+        if let Some(titel) = zeile.strip_prefix("# ") {
+            felder.push(("Titel".to_string(), titel.trim().to_string()));
+        } else {
+            for teil in zeile.split(" | ") {
+                if let Some((name, wert)) = fett_feld(teil) {
+                    let name = alias_aufloesen(name, FELD_ALIASE).unwrap_or(name);
+                    felder.push((name.to_string(), wert.to_string()));
+                }
             }
         }
+    }
+    felder
+}
 
-        md.push_str("\n---\n\n");
-        if !self.erstellt_am.is_empty() {
-            md.push_str(&format!("**Erstellt:** {} von {}\n\n", self.erstellt_am, self.erstellt_von));
+/// Vergleicht den Stand der App (`lokal`) mit der Datei feldgenau: Kopffelder einzeln,
+/// übrige Abschnitte als Ganzes (ohne Audit-Log). `basis` ist der zuletzt gespeicherte
+/// Stand; was seitdem auch in der App geändert wurde, gilt als Konflikt und ist nicht
+/// zur Übernahme vorausgewählt.
+fn externe_bereiche_vergleichen(lokal: &str, datei: &str, basis: Option<&str>) -> Vec<ExternerBereich> {
+    let lokal = markdown_abschnitte(lokal);
+    let datei = markdown_abschnitte(datei);
+    let basis = basis.map(markdown_abschnitte);
+    let abschnitt = |liste: &[(String, Vec<String>)], name: &str| -> Vec<String> {
+        liste.iter().find(|(n, _)| n == name).map(|(_, z)| z.clone()).unwrap_or_default()
+    };
+    let mut bereiche = Vec::new();
+    let mut bereich = |name: &str, kopffeld: bool, l: String, d: String, b: Option<String>| {
+        if l != d {
+            let konflikt = b.is_some_and(|b| b != l);
+            bereiche.push(ExternerBereich { name: name.to_string(), kopffeld, lokal: l, datei: d, konflikt, uebernehmen: !konflikt });
         }
-        let geaendert_am = Local::now().format("%d.%m.%Y %H:%M").to_string();
-        md.push_str(&format!("**Geändert:** {} von {}\n\n", geaendert_am, self.protokollant.name));
-        md.push_str("*Erstellt mit MZProtokoll von Marcel Zimmer — [www.marcelzimmer.de](https://www.marcelzimmer.de) | [X @marcelzimmer](https://x.com/marcelzimmer) | [GitHub @marcelzimmer](https://github.com/marcelzimmer)*\n");
+    };
 
-        md
+    // Kopffelder einzeln
+    let feld = |felder: &[(String, String)], name: &str| -> String {
+        felder.iter().find(|(n, _)| n == name).map(|(_, w)| w.clone()).unwrap_or_default()
+    };
+    let lokal_kopf = kopf_felder(&abschnitt(&lokal, KOPF_ABSCHNITT));
+    let datei_kopf = kopf_felder(&abschnitt(&datei, KOPF_ABSCHNITT));
+    let basis_kopf = basis.as_ref().map(|b| kopf_felder(&abschnitt(b, KOPF_ABSCHNITT)));
+    let mut namen: Vec<&String> = Vec::new();
+    for (name, _) in lokal_kopf.iter().chain(&datei_kopf) {
+        if !namen.contains(&name) {
+            namen.push(name);
+        }
+    }
+    for name in namen {
+        let b = basis_kopf.as_ref().map(|k| feld(k, name));
+        bereich(name, true, feld(&lokal_kopf, name), feld(&datei_kopf, name), b);
     }
 
-    /// Sortiert Teilnehmer und Zur-Kenntnis-Personen alphabetisch.
-    /// Leere Einträge werden ans Ende verschoben.
-    fn sort_personen(&mut self) {
-        let sort_fn = |a: &Person, b: &Person| {
-            let a_empty = a.name.trim().is_empty();
-            let b_empty = b.name.trim().is_empty();
-            match (a_empty, b_empty) {
-                (true, false) => std::cmp::Ordering::Greater,
-                (false, true) => std::cmp::Ordering::Less,
-                _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-            }
+    // Abschnitte als Ganzes, Überschrift und Leerzeilen ausgenommen
+    let mut gesehen: Vec<&String> = Vec::new();
+    for (name, _) in lokal.iter().chain(&datei) {
+        if name == KOPF_ABSCHNITT || name == "Audit-Log" || gesehen.contains(&name) {
+            continue;
+        }
+        gesehen.push(name);
+        let text = |liste: &[(String, Vec<String>)]| {
+            abschnitt(liste, name)
+                .iter()
+                .skip(1)
+                .map(|z| z.trim())
+                .filter(|z| !z.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n")
         };
-        self.teilnehmer.sort_by(sort_fn);
-        self.zur_kenntnis.sort_by(sort_fn);
+        let b = basis.as_ref().map(|b| text(b));
+        bereich(name, false, text(&lokal), text(&datei), b);
     }
+    bereiche
+}
 
-    /// Speichert das Protokoll als Markdown-Datei.
-    /// Ist bereits ein Pfad bekannt (`save_path`), wird direkt überschrieben.
-    /// Andernfalls öffnet sich ein Datei-Speichern-Dialog in einem separaten Thread.
-    fn speichern(&mut self) {
-        self.sort_personen();
-        if self.protokollant.name.trim().is_empty() {
-            self.show_pflichtfeld_hinweis = true;
-            return;
-        }
-        if self.erstellt_am.is_empty() {
-            self.erstellt_am = Local::now().format("%d.%m.%Y %H:%M").to_string();
-            self.erstellt_von = self.protokollant.name.clone();
+/// Vereinfacht einen Text für den Ähnlichkeitsvergleich: Kleinschreibung,
+/// nur Buchstaben und Ziffern, einfache Leerzeichen.
+fn text_normalisieren(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Ähnlichkeit zweier Texte (0–1) als Dice-Koeffizient über Zeichen-Bigramme
+/// der normalisierten Texte. Identische Texte ergeben 1,0.
+fn text_aehnlichkeit(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = text_normalisieren(a).chars().collect();
+    let b: Vec<char> = text_normalisieren(b).chars().collect();
+    if a == b {
+        return 1.0;
+    }
+    if a.len() < 2 || b.len() < 2 {
+        return 0.0;
+    }
+    let mut bigramme_b: Vec<(char, char)> = b.windows(2).map(|w| (w[0], w[1])).collect();
+    let anzahl_a = a.len() - 1;
+    let anzahl_b = bigramme_b.len();
+    let mut gemeinsam = 0;
+    for w in a.windows(2) {
+        if let Some(pos) = bigramme_b.iter().position(|&bg| bg == (w[0], w[1])) {
+            bigramme_b.swap_remove(pos);
+            gemeinsam += 1;
         }
-        let content = self.markdown_erstellen();
+    }
+    2.0 * gemeinsam as f32 / (anzahl_a + anzahl_b) as f32
+}
 
-        if let Some(ref path) = self.save_path {
-            let _ = std::fs::write(path, content);
-        } else {
-            let filename = self.dateinamen_erstellen();
-            let (tx, rx) = mpsc::channel();
-            self.dialog_rx = Some(rx);
-            std::thread::spawn(move || {
-                if let Some(path) = rfd::FileDialog::new()
-                    .set_file_name(&filename)
-                    .add_filter("Markdown", &["md"])
-                    .save_file()
-                {
-                    let _ = std::fs::write(&path, &content);
-                    let _ = tx.send(DialogErgebnis::Speichern(path));
-                }
-            });
+/// Ein Eintrag der konsolidierten Offene-Punkte-Liste.
+struct OffenerPunkt {
+    notiz: String,
+    kuemmerer: String,
+    bis: String,
+    /// Titel des ältesten Protokolls, in dem der Punkt vorkommt.
+    protokoll: String,
+    /// Datum des ältesten Protokolls, in dem der Punkt vorkommt.
+    seit: Option<NaiveDate>,
+}
+
+/// Liest einen Termin der Form „TT.MM.JJJJ" oder „TT.MM.JJJJ HH:MM" (auch mit
+/// vorangestelltem Wochentag). Gibt `None` zurück, wenn kein Datum erkennbar ist.
+fn termin_parsen(text: &str) -> Option<(NaiveDate, Option<chrono::NaiveTime>)> {
+    let mut datum = None;
+    let mut uhrzeit = None;
+    for token in text.split(|c: char| c.is_whitespace() || c == ',') {
+        let token = token.trim().trim_end_matches("Uhr");
+        if datum.is_none() {
+            datum = NaiveDate::parse_from_str(token, "%d.%m.%Y").ok();
+        } else if uhrzeit.is_none() {
+            uhrzeit = chrono::NaiveTime::parse_from_str(token, "%H:%M").ok();
         }
     }
+    datum.map(|d| (d, uhrzeit))
+}
 
-    /// Öffnet einen Datei-Öffnen-Dialog (separater Thread) und lädt
-    /// die gewählte Markdown-Datei via `markdown_parsen` in den App-Zustand.
-    fn laden(&mut self) {
-        let (tx, rx) = mpsc::channel();
-        self.dialog_rx = Some(rx);
-        std::thread::spawn(move || {
-            if let Some(path) = rfd::FileDialog::new()
-                .add_filter("Markdown", &["md"])
-                .pick_file()
-            {
-                if let Ok(content) = std::fs::read_to_string(&path) {
-                    let _ = tx.send(DialogErgebnis::Laden(path, content));
-                }
+/// Lädt ein Logo für das PDF-Deckblatt und skaliert es auf die angegebene Höhe.
+/// Das Bild wird vorab nach RGB gewandelt, da genpdf keine Alphakanäle unterstützt.
+fn pdf_logo_laden(pfad: &str, hoehe_mm: f64) -> Option<genpdf::elements::Image> {
+    let bild = image::open(pfad).ok()?;
+    let hoehe_px = f64::from(bild.height().max(1));
+    let rgb = image::DynamicImage::ImageRgb8(bild.to_rgb8());
+    let mut puffer = std::io::Cursor::new(Vec::new());
+    rgb.write_to(&mut puffer, image::ImageFormat::Png).ok()?;
+    puffer.set_position(0);
+    let logo = genpdf::elements::Image::from_reader(puffer).ok()?;
+    // Auflösung so wählen, dass das Bild genau `hoehe_mm` hoch wird
+    Some(logo.with_alignment(genpdf::Alignment::Center).with_dpi(hoehe_px * 25.4 / hoehe_mm))
+}
+
+/// Maskiert Sonderzeichen für Textwerte in iCalendar-Dateien (RFC 5545).
+/// Zeilenumbrüche (auch `\r\n` und einzelnes `\r`) werden zu `\n`.
+fn ics_escapen(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Faltet die Inhaltszeilen einer iCalendar-Datei nach RFC 5545: Zeilen über
+/// 75 Oktette werden an Zeichengrenzen umbrochen, Folgezeilen beginnen mit einem
+/// Leerzeichen.
+fn ics_falten(ics: &str) -> String {
+    let mut ergebnis = String::with_capacity(ics.len() + ics.len() / 64);
+    for zeile in ics.split_terminator("\r\n") {
+        let mut laenge = 0;
+        for ch in zeile.chars() {
+            if laenge + ch.len_utf8() > 75 {
+                ergebnis.push_str("\r\n ");
+                laenge = 1;
             }
-        });
+            ergebnis.push(ch);
+            laenge += ch.len_utf8();
+        }
+        ergebnis.push_str("\r\n");
     }
+    ergebnis
+}
 
-    /// Liest einen MZProtokoll-Markdown-String ein und befüllt alle Felder
-    /// der App. Vorhandene Daten werden dabei vollständig überschrieben.
-    /// Der Parser ist zeilenbasiert und arbeitet mit einem Sektions-Zustandsautomaten.
-    fn markdown_parsen(&mut self, content: &str) {
-        self.projekt = String::new();
-        self.titel = String::new();
-        self.datum_text = String::new();
-        self.ort = String::new();
-        self.protokollant = Person::new();
-        self.teilnehmer.clear();
-        self.zur_kenntnis.clear();
-        self.ueber_meeting = String::new();
-        self.ist_entwurf = true;
-        self.ist_freigegeben = false;
-        self.sicherheit = Sicherheit::Intern;
-        self.eintraege.clear();
-        self.erstellt_am = String::new();
-        self.erstellt_von = String::new();
+/// Löst einen Verzeichnispfad aus den Einstellungen auf (`~/` = Home-Verzeichnis).
+/// Gibt `None` zurück, wenn nichts eingestellt ist.
+fn einstellungspfad(einstellung: &str) -> Option<std::path::PathBuf> {
+    let pfad = einstellung.trim();
+    if pfad.is_empty() {
+        return None;
+    }
+    match pfad.strip_prefix("~/") {
+        Some(rest) => Some(std::path::PathBuf::from(std::env::var("HOME").ok()?).join(rest)),
+        None => Some(std::path::PathBuf::from(pfad)),
+    }
+}
 
-        #[derive(PartialEq)]
-        enum Section {
-            Header,
-            Protokollfuehrer,
-            Teilnehmer,
-            ZurKenntnis,
-            UeberMeeting,
-            Status,
-            Sicherheit,
-            Eintraege,
+/// Verschiebt eine Datei; liegt das Ziel auf einem anderen Dateisystem,
+/// wird kopiert und das Original anschließend entfernt.
+fn datei_verschieben(von: &std::path::Path, nach: &std::path::Path) -> std::io::Result<()> {
+    if std::fs::rename(von, nach).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(von, nach)?;
+    std::fs::remove_file(von)
+}
+
+/// Schreibt `inhalt` zunächst in eine temporäre Datei im Zielverzeichnis, synchronisiert
+/// sie (fsync) und benennt sie dann atomar in `pfad` um. Ein Absturz oder ein volles
+/// Dateisystem hinterlässt so nie eine halb geschriebene Datei; schlägt ein Schritt
+/// fehl, bleibt die bisherige Datei unverändert und die temporäre wird entfernt.
+fn atomar_schreiben(pfad: &std::path::Path, inhalt: impl AsRef<[u8]>) -> std::io::Result<()> {
+    use std::io::Write;
+    let verzeichnis = match pfad.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => std::path::Path::new("."),
+    };
+    let name = pfad.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let temp = verzeichnis.join(format!(".{}.{}.tmp", name, std::process::id()));
+    let ergebnis = (|| {
+        let mut datei = std::fs::File::create(&temp)?;
+        datei.write_all(inhalt.as_ref())?;
+        datei.sync_all()?;
+        drop(datei);
+        // Rechte der bisherigen Datei übernehmen
+        if let Ok(meta) = std::fs::metadata(pfad) {
+            let _ = std::fs::set_permissions(&temp, meta.permissions());
         }
+        std::fs::rename(&temp, pfad)
+    })();
+    if ergebnis.is_err() {
+        let _ = std::fs::remove_file(&temp);
+        return ergebnis;
+    }
+    // Verzeichniseintrag sichern, damit die Umbenennung einen Absturz übersteht
+    #[cfg(unix)]
+    if let Ok(dir) = std::fs::File::open(verzeichnis) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
 
-        let mut section = Section::Header;
-        let mut table_rows_seen = 0u32;
-        let mut ueber_lines: Vec<&str> = Vec::new();
+/// `true`, wenn die Datei existiert und schreibgeschützt ist (z. B. nach dem Archivieren).
+fn schreibgeschuetzt(pfad: &std::path::Path) -> bool {
+    std::fs::metadata(pfad).map(|m| m.permissions().readonly()).unwrap_or(false)
+}
 
-        for line in content.lines() {
-            let trimmed = line.trim();
+/// Setzt eine Datei schreibgeschützt; Fehler werden ignoriert.
+fn schreibschutz_setzen(pfad: &std::path::Path) {
+    if let Ok(meta) = std::fs::metadata(pfad) {
+        let mut rechte = meta.permissions();
+        rechte.set_readonly(true);
+        let _ = std::fs::set_permissions(pfad, rechte);
+    }
+}
 
-            // Erstellt-Metadaten parsen (stehen am Ende der Datei)
-            if trimmed.starts_with("**Erstellt:**") {
-                let rest = trimmed.trim_start_matches("**Erstellt:**").trim();
-                if let Some((datum, von)) = rest.split_once(" von ") {
-                    self.erstellt_am = datum.trim().to_string();
-                    self.erstellt_von = von.trim().to_string();
-                }
-                continue;
-            }
+/// Anzeigename eines vdir-Kalenders: die von vdirsyncer abgelegte Datei `displayname`,
+/// ersatzweise der Verzeichnisname.
+fn kalender_name(kalender: &std::path::Path) -> String {
+    std::fs::read_to_string(kalender.join("displayname"))
+        .ok()
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| kalender.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default())
+}
 
-            // Sektionswechsel bei ## Überschriften
-            if trimmed.starts_with("## ") {
-                if section == Section::UeberMeeting {
-                    self.ueber_meeting = ueber_lines.join("\n").trim().to_string();
-                    ueber_lines.clear();
-                }
+/// Gibt den deutschen Namen des Wochentags zurück.
+fn wochentag_name(datum: NaiveDate) -> &'static str {
+    match datum.weekday() {
+        chrono::Weekday::Mon => "Montag",
+        chrono::Weekday::Tue => "Dienstag",
+        chrono::Weekday::Wed => "Mittwoch",
+        chrono::Weekday::Thu => "Donnerstag",
+        chrono::Weekday::Fri => "Freitag",
+        chrono::Weekday::Sat => "Samstag",
+        chrono::Weekday::Sun => "Sonntag",
+    }
+}
 
-                if trimmed.starts_with("## Protokollführer") {
-                    section = Section::Protokollfuehrer;
-                    continue;
-                } else if trimmed.starts_with("## Teilnehmer") {
-                    section = Section::Teilnehmer;
-                    continue;
-                } else if trimmed.starts_with("## Zur Kenntnis") {
-                    section = Section::ZurKenntnis;
-                    continue;
-                } else if trimmed.starts_with("## Über dieses Meeting") {
-                    section = Section::UeberMeeting;
-                    continue;
-                } else if trimmed.starts_with("## Status") {
-                    section = Section::Status;
-                    continue;
-                } else if trimmed.starts_with("## Klassifizierung") {
-                    section = Section::Sicherheit;
-                    continue;
-                } else if trimmed.starts_with("## Einträge") {
-                    section = Section::Eintraege;
-                    table_rows_seen = 0;
-                    continue;
-                }
-            }
+/// Gibt den Pfad der Wiederherstellungsdatei zurück (`wiederherstellung.md` im
+/// Konfigurationsverzeichnis). Sie enthält das zuletzt durch Laden ersetzte Protokoll.
+fn wiederherstellungs_pfad() -> Option<std::path::PathBuf> {
+    konfig_verzeichnis().map(|d| d.join("wiederherstellung.md"))
+}
 
-            match section {
-                Section::Header => {
-                    if trimmed.starts_with("**Projekt:**") {
-                        self.projekt =
-                            trimmed.trim_start_matches("**Projekt:**").trim().to_string();
-                    } else if trimmed.starts_with("# ") {
-                        self.titel = trimmed[2..].to_string();
-                    } else if trimmed.contains("**Datum:**") || trimmed.contains("**Ort:**") {
-                        for part in trimmed.split(" | ") {
-                            let part = part.trim();
-                            if part.starts_with("**Datum:**") {
-                                self.datum_text =
-                                    part.trim_start_matches("**Datum:**").trim().to_string();
-                            } else if part.starts_with("**Ort:**") {
-                                self.ort = part.trim_start_matches("**Ort:**").trim().to_string();
-                            }
-                        }
-                    }
-                }
-                Section::Protokollfuehrer => {
-                    if !trimmed.is_empty() && trimmed != "---" {
-                        let (name, kuerzel) = name_kuerzel_parsen(trimmed);
-                        self.protokollant.name = name;
-                        if !kuerzel.is_empty() {
-                            self.protokollant.kuerzel = kuerzel;
-                            self.protokollant.kuerzel_manuell = true;
-                        }
-                    }
-                }
-                Section::Teilnehmer => {
-                    if trimmed.starts_with("- ") {
-                        let (name, kuerzel) = name_kuerzel_parsen(&trimmed[2..]);
-                        let mut p = Person::new();
-                        p.name = name;
-                        if !kuerzel.is_empty() {
-                            p.kuerzel = kuerzel;
-                            p.kuerzel_manuell = true;
-                        }
-                        self.teilnehmer.push(p);
-                    }
-                }
-                Section::ZurKenntnis => {
-                    if trimmed.starts_with("- ") {
-                        let (name, kuerzel) = name_kuerzel_parsen(&trimmed[2..]);
-                        let mut p = Person::new();
-                        p.name = name;
-                        if !kuerzel.is_empty() {
-                            p.kuerzel = kuerzel;
-                            p.kuerzel_manuell = true;
-                        }
-                        self.zur_kenntnis.push(p);
-                    }
-                }
-                Section::UeberMeeting => {
-                    if trimmed != "---" {
-                        ueber_lines.push(line);
-                    }
-                }
-                Section::Status => {
-                    if trimmed.starts_with("- [x] Entwurf") {
-                        self.ist_entwurf = true;
-                    } else if trimmed.starts_with("- [x] Freigegeben") {
-                        self.ist_freigegeben = true;
-                    }
-                }
-                Section::Sicherheit => {
-                    if trimmed.starts_with("- [x] Öffentlich") {
-                        self.sicherheit = Sicherheit::Oeffentlich;
-                    } else if trimmed.starts_with("- [x] Intern") {
-                        self.sicherheit = Sicherheit::Intern;
-                    } else if trimmed.starts_with("- [x] Vertraulich") {
-                        self.sicherheit = Sicherheit::Vertraulich;
-                    } else if trimmed.starts_with("- [x] Streng vertraulich") {
-                        self.sicherheit = Sicherheit::StrengVertraulich;
-                    }
-                }
-                Section::Eintraege => {
-                    if trimmed.starts_with('|') {
-                        table_rows_seen += 1;
-                        // Zeile 1 = Kopfzeile, Zeile 2 = Trennlinie, ab Zeile 3 = Daten
-                        if table_rows_seen >= 3 {
-                            let cells = tabellenzeile_aufteilen(trimmed);
-                            if cells.len() >= 5 {
-                                let mut e = Eintrag::new();
-                                e.punkt = cells[0].clone();
-                                e.art = art_parsen(&cells[1]);
-                                e.notiz = cells[2].replace(" <br> ", "\n");
-                                e.kuemmerer = cells[3].clone();
-                                e.bis = cells[4].clone();
-                                if e.art == Art::Todo {
-                                    e.punkt.clear();
-                                }
-                                self.eintraege.push(e);
-                            }
-                        }
-                    }
-                }
-            }
-        }
+/// Gibt das Verzeichnis der Zwischenentwürfe zurück (`entwuerfe/` im Konfigurationsverzeichnis).
+fn entwurf_verzeichnis() -> Option<std::path::PathBuf> {
+    konfig_verzeichnis().map(|d| d.join("entwuerfe"))
+}
 
-        // Restlichen "Über dieses Meeting"-Text flushen
-        if section == Section::UeberMeeting {
-            self.ueber_meeting = ueber_lines.join("\n").trim().to_string();
+/// Erste Zeile einer mit „Als Entwurf zwischenspeichern" geschriebenen Datei.
+const ENTWURF_KENNUNG: &str = "<!-- mzprotokoll:entwurf -->";
+
+/// Gibt das Vorlagenverzeichnis zurück (`vorlagen/` im Konfigurationsverzeichnis).
+fn vorlagen_verzeichnis() -> Option<std::path::PathBuf> {
+    konfig_verzeichnis().map(|d| d.join("vorlagen"))
+}
+
+/// Listet alle Vorlagen (`*.md`) im Vorlagenverzeichnis alphabetisch auf.
+fn vorlagen_auflisten() -> Vec<std::path::PathBuf> {
+    let mut vorlagen: Vec<_> = vorlagen_verzeichnis()
+        .and_then(|d| std::fs::read_dir(d).ok())
+        .map(|eintraege| {
+            eintraege
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|ext| ext == "md"))
+                .collect()
+        })
+        .unwrap_or_default();
+    vorlagen.sort();
+    vorlagen
+}
+
+/// Findet alle Platzhalter der Form `{{name}}` in einem Text (ohne Duplikate,
+/// in der Reihenfolge des ersten Auftretens).
+fn platzhalter_finden(text: &str) -> Vec<String> {
+    let mut namen: Vec<String> = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let nach_start = &rest[start + 2..];
+        let Some(ende) = nach_start.find("}}") else {
+            break;
+        };
+        let name = nach_start[..ende].trim().to_string();
+        if !name.is_empty() && !namen.contains(&name) {
+            namen.push(name);
         }
+        rest = &nach_start[ende + 2..];
+    }
+    namen
+}
 
-        // Mindestens je einen leeren Eintrag sicherstellen
-        if self.teilnehmer.is_empty() {
-            self.teilnehmer.push(Person::new());
+/// Ersetzt den Platzhalter `{{name}}` durch `wert`. Steht er in einer Tabellenzeile,
+/// werden `|` und Zeilenumbrüche im Wert maskiert, damit die Tabelle intakt bleibt.
+fn platzhalter_einsetzen(inhalt: &str, name: &str, wert: &str) -> String {
+    let muster = format!("{{{{{}}}}}", name);
+    if !inhalt.contains(&muster) {
+        return inhalt.to_string();
+    }
+    let in_tabelle = wert.replace('\n', " <br> ").replace('|', "\\|");
+    inhalt
+        .split('\n')
+        .map(|zeile| {
+            if zeile.trim_start().starts_with('|') {
+                zeile.replace(&muster, &in_tabelle)
+            } else {
+                zeile.replace(&muster, wert)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Löst die eingebauten, automatisch berechenbaren Platzhalter auf:
+/// `datum`, `datum+N`/`datum-N` (Tage, TT.MM.JJJJ), `wochentag`, `jahr` und `kw`.
+/// Gibt `None` zurück, wenn der Platzhalter nicht automatisch auflösbar ist.
+fn platzhalter_automatisch(name: &str, heute: NaiveDate) -> Option<String> {
+    match name {
+        "datum" => Some(heute.format("%d.%m.%Y").to_string()),
+        "wochentag" => Some(wochentag_name(heute).to_string()),
+        "jahr" => Some(heute.year().to_string()),
+        "kw" => Some(heute.iso_week().week().to_string()),
+        _ => {
+            let rest = name.strip_prefix("datum")?;
+            let tage: i64 = rest.strip_prefix('+').unwrap_or(rest).parse().ok()?;
+            let datum = heute.checked_add_signed(chrono::Duration::days(tage))?;
+            Some(datum.format("%d.%m.%Y").to_string())
         }
-        if self.zur_kenntnis.is_empty() {
-            self.zur_kenntnis.push(Person::new());
+    }
+}
+
+/// Liest das Datum (TT.MM.JJJJ) aus einem Datumstext wie „Montag, 05.02.2026".
+fn datum_aus_text(text: &str) -> Option<NaiveDate> {
+    let teil = text.rsplit([',', ' ']).find(|t| !t.is_empty())?;
+    NaiveDate::parse_from_str(teil.trim(), "%d.%m.%Y").ok()
+}
+
+/// Ostersonntag im gregorianischen Kalender (anonymer gregorianischer Algorithmus).
+fn ostersonntag(jahr: i32) -> NaiveDate {
+    let a = jahr % 19;
+    let b = jahr / 100;
+    let c = jahr % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let monat = (h + l - 7 * m + 114) / 31;
+    let tag = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(jahr, monat as u32, tag as u32).unwrap_or_default()
+}
+
+/// Gesetzliche Feiertage (bundesweit und je Bundesland) plus eigene freie Tage.
+/// Wochenenden zählen immer als arbeitsfrei.
+#[derive(Default)]
+struct Feiertagskalender {
+    /// Kürzel des Bundeslands (`BW`, `BY`, `BE`, … `TH`); leer = nur bundesweite
+    /// Feiertage, `KEINE` = gar keine.
+    bundesland: String,
+    /// Zusätzliche freie Tage als `(Tag, Monat, Jahr)`; ohne Jahr jedes Jahr
+    /// (z. B. Heiligabend oder Betriebsferien).
+    zusaetzlich: Vec<(u32, u32, Option<i32>)>,
+}
+
+impl Feiertagskalender {
+    /// Name des Feiertags an `datum` (`None` = kein Feiertag). Regionale Feiertage, die
+    /// nur in Teilen eines Landes gelten (etwa Mariä Himmelfahrt in Bayern), fehlen.
+    fn feiertag(&self, datum: NaiveDate) -> Option<&'static str> {
+        if self.zusaetzlich.iter().any(|&(tag, monat, jahr)| {
+            datum.day() == tag && datum.month() == monat && jahr.is_none_or(|j| j == datum.year())
+        }) {
+            return Some("Freier Tag");
         }
-        if self.eintraege.is_empty() {
-            self.eintraege.push(Eintrag::new());
+        let land = self.bundesland.as_str();
+        if land == "KEINE" {
+            return None;
         }
+        let in_land = |laender: &[&str]| laender.contains(&land);
+        let ostern = (datum - ostersonntag(datum.year())).num_days();
+        let name = match (datum.month(), datum.day(), ostern) {
+            (1, 1, _) => "Neujahr",
+            (1, 6, _) if in_land(&["BW", "BY", "ST"]) => "Heilige Drei Könige",
+            (3, 8, _) if in_land(&["BE", "MV"]) => "Internationaler Frauentag",
+            (_, _, -2) => "Karfreitag",
+            (_, _, 1) => "Ostermontag",
+            (5, 1, _) => "Tag der Arbeit",
+            (_, _, 39) => "Christi Himmelfahrt",
+            (_, _, 50) => "Pfingstmontag",
+            (_, _, 60) if in_land(&["BW", "BY", "HE", "NW", "RP", "SL"]) => "Fronleichnam",
+            (8, 15, _) if in_land(&["SL"]) => "Mariä Himmelfahrt",
+            (9, 20, _) if in_land(&["TH"]) => "Weltkindertag",
+            (10, 3, _) => "Tag der Deutschen Einheit",
+            (10, 31, _) if in_land(&["BB", "HB", "HH", "MV", "NI", "SN", "ST", "SH", "TH"]) => "Reformationstag",
+            (11, 1, _) if in_land(&["BW", "BY", "NW", "RP", "SL"]) => "Allerheiligen",
+            // Buß- und Bettag: Mittwoch vor dem 23. November
+            (11, 16..=22, _) if in_land(&["SN"]) && datum.weekday() == chrono::Weekday::Wed => "Buß- und Bettag",
+            (12, 25, _) => "1. Weihnachtstag",
+            (12, 26, _) => "2. Weihnachtstag",
+            _ => return None,
+        };
+        Some(name)
     }
 
-    /// Generiert einen vorgeschlagenen Dateinamen für den PDF-Export.
-    /// Format: `MZProtokoll_<Titel>__<JJJJ-MM-TT>.pdf`
-    fn pdf_dateinamen_erstellen(&self) -> String {
-        let name_part: String = self.titel.chars().filter(|c| c.is_alphabetic()).collect();
-        let datum = Local::now().format("%Y-%m-%d").to_string();
-        format!("MZProtokoll_{}__{}.pdf", name_part, datum)
+    /// Grund, warum `datum` kein Arbeitstag ist (Feiertag vor Wochentag), sonst `None`.
+    fn arbeitsfrei(&self, datum: NaiveDate) -> Option<&'static str> {
+        self.feiertag(datum).or(match datum.weekday() {
+            chrono::Weekday::Sat | chrono::Weekday::Sun => Some(wochentag_name(datum)),
+            _ => None,
+        })
     }
 
-    /// Sucht auf dem System nach einer passenden Schriftfamilie für den PDF-Export.
-    /// Probiert nacheinander Liberation Sans, Noto Sans und DejaVu Sans.
-    /// Gibt `None` zurück, wenn keine Schrift gefunden wird.
-    fn schrift_laden(&self) -> Option<genpdf::fonts::FontFamily<genpdf::fonts::FontData>> {
-        // Liest Schriften zur Laufzeit vom System – keine Schriften werden eingebettet.
-
-        // 1. Linux: Schriftfamilien mit Standard-Benennung (Name-Regular.ttf, Name-Bold.ttf, ...)
-        #[cfg(not(windows))]
-        {
-            let schrift_familien = [
-                ("/usr/share/fonts/liberation",          "LiberationSans"),
-                ("/usr/share/fonts/noto",                "NotoSans"),
-                ("/usr/share/fonts/TTF",                 "LiberationSans"),
-                ("/usr/share/fonts/TTF",                 "NotoSans"),
-                ("/usr/share/fonts/truetype/liberation", "LiberationSans"),
-                ("/usr/share/fonts/truetype/noto",       "NotoSans"),
-            ];
-            for (pfad, familie) in schrift_familien {
-                if let Ok(schrift) = genpdf::fonts::from_files(pfad, familie, None) {
-                    return Some(schrift);
-                }
+    /// Das Datum `tage` Arbeitstage nach `start` (Wochenenden und Feiertage übersprungen).
+    fn plus_arbeitstage(&self, start: NaiveDate, tage: u32) -> NaiveDate {
+        let mut datum = start;
+        let mut uebrig = tage;
+        while uebrig > 0 {
+            datum = datum.succ_opt().unwrap_or(datum);
+            if self.arbeitsfrei(datum).is_none() {
+                uebrig -= 1;
             }
         }
+        datum
+    }
 
-        // 2. Einzelne .ttf-Dateien (Windows-Systemschriften + Linux DejaVu als Fallback)
-        #[cfg(windows)]
-        let einzel_schriften = [
-            ("C:\\Windows\\Fonts\\arial.ttf",   "C:\\Windows\\Fonts\\arialbd.ttf"),
-            ("C:\\Windows\\Fonts\\verdana.ttf", "C:\\Windows\\Fonts\\verdanab.ttf"),
-            ("C:\\Windows\\Fonts\\calibri.ttf", "C:\\Windows\\Fonts\\calibrib.ttf"),
-            ("C:\\Windows\\Fonts\\segoeui.ttf", "C:\\Windows\\Fonts\\segoeuib.ttf"),
-        ];
-        #[cfg(not(windows))]
-        let einzel_schriften = [
-            ("/usr/share/fonts/TTF/DejaVuSans.ttf",                    "/usr/share/fonts/TTF/DejaVuSans-Bold.ttf"),
-            ("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",        "/usr/share/fonts/truetype/dejavu/DejaVuSans-Bold.ttf"),
-            ("/usr/share/fonts/TTF/DejaVuSans.ttf",                    "/usr/share/fonts/TTF/DejaVuSans-Bold.ttf"),
-            ("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",        "/usr/share/fonts/truetype/dejavu/DejaVuSans-Bold.ttf"),
-        ];
-        for (regular_path, bold_path) in einzel_schriften {
-            if let Ok(regular_data) = std::fs::read(regular_path) {
-                let bold_data = std::fs::read(bold_path).unwrap_or_else(|_| regular_data.clone());
-                if let (Ok(regular), Ok(bold), Ok(italic), Ok(bold_italic)) = (
-                    genpdf::fonts::FontData::new(regular_data.clone(), None),
-                    genpdf::fonts::FontData::new(bold_data.clone(), None),
-                    genpdf::fonts::FontData::new(regular_data, None),
-                    genpdf::fonts::FontData::new(bold_data, None),
-                ) {
-                    return Some(genpdf::fonts::FontFamily { regular, bold, italic, bold_italic });
-                }
-            }
+    /// Hinweis für ein Bis-Datum, das auf einen arbeitsfreien Tag fällt (`None` = gültig
+    /// und Arbeitstag oder kein Datum).
+    fn bis_hinweis(&self, bis: &str) -> Option<String> {
+        let datum = NaiveDate::parse_from_str(bis.trim(), "%d.%m.%Y").ok()?;
+        let grund = self.arbeitsfrei(datum)?;
+        Some(format!("Fällig an einem arbeitsfreien Tag ({}); nächster Arbeitstag: {}", grund, self.plus_arbeitstage(datum, 1).format("%d.%m.%Y")))
+    }
+}
+
+/// So viele Monate nach dem Meeting gilt ein Bis-Datum noch als plausibel.
+const BIS_HORIZONT_MONATE: u32 = 24;
+
+/// Hinweis für ein gültiges, aber unplausibles Bis-Datum: vor dem Meeting-Datum oder mehr
+/// als `BIS_HORIZONT_MONATE` danach – meist ein vertipptes Jahr. Ohne Meeting-Datum zählt
+/// nur der Horizont ab heute.
+fn bis_plausibilitaet(bis: &str, meeting: Option<NaiveDate>) -> Option<String> {
+    let datum = NaiveDate::parse_from_str(bis.trim(), "%d.%m.%Y").ok()?;
+    if let Some(meeting) = meeting.filter(|m| datum < *m) {
+        return Some(format!("Liegt vor dem Meeting-Datum ({}) – Jahr vertippt?", meeting.format("%d.%m.%Y")));
+    }
+    let basis = meeting.unwrap_or_else(|| Local::now().date_naive());
+    let grenze = basis.checked_add_months(chrono::Months::new(BIS_HORIZONT_MONATE))?;
+    (datum > grenze).then(|| format!("Liegt mehr als {} Jahre nach dem Meeting – Jahr vertippt?", BIS_HORIZONT_MONATE / 12))
+}
+
+/// Schnellvorschläge für ein Bis-Datum: N Arbeitstage nach `basis`, ohne Wochenenden
+/// und Feiertage. Ein gewählter Vorschlag ersetzt den Inhalt von `bis`.
+fn bis_vorschlaege_anzeigen(ui: &mut egui::Ui, bis: &mut String, basis: NaiveDate, kalender: &Feiertagskalender) {
+    ui.label(RichText::new(format!("Ab {}", basis.format("%d.%m.%Y"))).weak());
+    for tage in [1, 3, 5, 10, 20] {
+        let datum = kalender.plus_arbeitstage(basis, tage);
+        let text = format!(
+            "+{} {} ({}, {})",
+            tage,
+            if tage == 1 { "Arbeitstag" } else { "Arbeitstage" },
+            wochentag_name(datum),
+            datum.format("%d.%m.%Y")
+        );
+        if ui.button(text).clicked() {
+            *bis = datum.format("%d.%m.%Y").to_string();
+            ui.close_menu();
         }
-        None
     }
+}
 
-    /// Fügt den gesamten Protokollinhalt (Kopfdaten, Eintrags-Tabelle, Links)
-    /// in das übergebene genpdf-Dokument ein.
-    /// Wird zweimal aufgerufen: einmal für den Vorberechnungsdurchlauf
-    /// (Seitenanzahl ermitteln) und einmal für den eigentlichen Export.
-    fn pdf_inhalt_hinzufuegen(&self, doc: &mut genpdf::Document) {
-        let small = genpdf::style::Style::new().with_font_size(9);
-        let small_bold = genpdf::style::Style::new().bold().with_font_size(9);
-        let heading_style = genpdf::style::Style::new().bold().with_font_size(20);
+/// Farbschema der Anwendungsoberfläche.
+#[derive(Clone, Copy, PartialEq)]
+enum Theme {
+    /// Helles egui-Standard-Theme.
+    Hell,
+    /// Dunkles Theme mit reinem Schwarz als Hintergrund.
+    Dunkel,
+    /// Passt Farben automatisch an das aktive Omarchy-Desktop-Theme an.
+    Omarchy,
+}
 
-        // Projekt
-        if !self.projekt.is_empty() {
-            doc.push(
-                genpdf::elements::Paragraph::new(&self.projekt)
-                    .styled(small),
-            );
+impl Theme {
+    /// Wechselt zyklisch zum nächsten Theme.
+    /// Omarchy wird nur angeboten, wenn die Konfigurationsdatei gefunden wurde.
+    fn next(self, has_omarchy: bool) -> Self {
+        match self {
+            Theme::Hell => Theme::Dunkel,
+            Theme::Dunkel => if has_omarchy { Theme::Omarchy } else { Theme::Hell },
+            Theme::Omarchy => Theme::Hell,
         }
+    }
 
-        // Titel
-        doc.push(
-            genpdf::elements::Paragraph::new(&self.titel)
-                .styled(heading_style),
-        );
-        doc.push(genpdf::elements::Break::new(0.5));
 
-        // Datum | Ort
-        let mut meta_parts = Vec::new();
-        if !self.datum_text.is_empty() {
-            meta_parts.push(format!("Datum: {}", self.datum_text));
+}
+
+/// Eingabefelder des Kopfbereichs in Tab-Reihenfolge.
+#[derive(Clone, Copy, PartialEq)]
+enum KopfFeld {
+    Projekt,
+    Titel,
+    Datum,
+    Ort,
+    ProtokollDatum,
+    Protokollfuehrer,
+}
+
+impl KopfFeld {
+    /// Erkennt den Wert von `erst_fokus` (`keiner` bzw. Unbekanntes = kein Erst-Fokus).
+    fn parsen(text: &str) -> Option<KopfFeld> {
+        match text.trim().to_lowercase().as_str() {
+            "projekt" => Some(KopfFeld::Projekt),
+            "titel" => Some(KopfFeld::Titel),
+            "datum" => Some(KopfFeld::Datum),
+            "ort" => Some(KopfFeld::Ort),
+            "protokoll_datum" => Some(KopfFeld::ProtokollDatum),
+            "protokollfuehrer" | "protokollführer" => Some(KopfFeld::Protokollfuehrer),
+            _ => None,
         }
-        if !self.ort.is_empty() {
-            meta_parts.push(format!("Ort: {}", self.ort));
+    }
+
+    /// Nächstes Feld bei Tab (Projekt → Titel → Datum → Ort → Protokoll erstellt am →
+    /// Protokollführer).
+    fn naechstes(&self) -> Option<KopfFeld> {
+        match self {
+            KopfFeld::Projekt => Some(KopfFeld::Titel),
+            KopfFeld::Titel => Some(KopfFeld::Datum),
+            KopfFeld::Datum => Some(KopfFeld::Ort),
+            KopfFeld::Ort => Some(KopfFeld::ProtokollDatum),
+            KopfFeld::ProtokollDatum => Some(KopfFeld::Protokollfuehrer),
+            KopfFeld::Protokollfuehrer => None,
         }
-        if !meta_parts.is_empty() {
-            doc.push(genpdf::elements::Paragraph::new(meta_parts.join("  |  ")).styled(small));
-            doc.push(genpdf::elements::Break::new(0.5));
+    }
+}
+
+/// Nummerierung der Agenda-Einträge: `aus`, nur die TOPs („TOP 1", „TOP 2") oder
+/// gegliedert, dann erhalten die folgenden Einträge zusätzlich „1.1", „1.2" usw.
+#[derive(Clone, Copy, PartialEq)]
+enum TopNummerierung {
+    Aus,
+    Tops,
+    Gegliedert,
+}
+
+impl TopNummerierung {
+    /// Erkennt den Wert von `top_nummerierung` (Unbekanntes = nur TOPs).
+    fn parsen(text: &str) -> TopNummerierung {
+        match text.trim().to_lowercase().as_str() {
+            "aus" | "false" => TopNummerierung::Aus,
+            "gegliedert" => TopNummerierung::Gegliedert,
+            _ => TopNummerierung::Tops,
         }
+    }
+}
 
-        // Trennlinie
-        doc.push(
-            genpdf::elements::Paragraph::new("_".repeat(250))
-                .styled(genpdf::style::Style::new().with_font_size(6).with_color(
-                    genpdf::style::Color::Greyscale(180),
-                )),
-        );
-        doc.push(genpdf::elements::Break::new(0.5));
+/// Weg, auf dem an das Finalisieren eines Entwurfs erinnert wird (`finalisierung_erinnerung`).
+#[derive(Clone, Copy, PartialEq)]
+enum Erinnerungsweg {
+    /// Desktop-Benachrichtigung.
+    Benachrichtigung,
+    /// Ganztägiger Termin am Fristtag im vdir-Kalender (`kalender_vdir`).
+    Kalender,
+    /// E-Mail an die eigene Adresse aus dem Benutzerprofil (über `mailto:`).
+    Email,
+}
 
-        // Protokollführer, Teilnehmer, Zur Kenntnis, Über dieses Meeting
-        // als zweispaltige Tabelle, damit die Werte bündig starten
-        {
-            let mut info_table = genpdf::elements::TableLayout::new(vec![3, 11]);
+impl Erinnerungsweg {
+    /// Erkennt einen Eintrag der Liste `finalisierung_erinnerung` (Unbekanntes = `None`).
+    fn parsen(text: &str) -> Option<Erinnerungsweg> {
+        match text.trim().to_lowercase().as_str() {
+            "benachrichtigung" => Some(Erinnerungsweg::Benachrichtigung),
+            "kalender" | "ics" => Some(Erinnerungsweg::Kalender),
+            "email" | "e-mail" => Some(Erinnerungsweg::Email),
+            _ => None,
+        }
+    }
+}
 
-            // Protokollführer
-            if !self.protokollant.name.is_empty() {
-                let mut name = self.protokollant.name.clone();
-                if !self.protokollant.kuerzel.is_empty() {
-                    name.push_str(&format!(" [{}]", self.protokollant.kuerzel));
-                }
-                let _ = info_table.row()
-                    .element(genpdf::elements::Paragraph::new("Protokollführer").styled(small_bold).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
-                    .element(genpdf::elements::Paragraph::new(name).styled(small).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
-                    .push();
+/// Anfang des Audit-Eintrags, mit dem eine Finalisierungs-Erinnerung vermerkt wird.
+const FINALISIERUNG_VERMERK: &str = "Erinnerung: Protokoll finalisieren bis ";
+
+/// Berechnet die Nummern aller Einträge aus ihrer Reihenfolge: je Eintrag die Nummer
+/// des zugehörigen TOPs (0 = vor dem ersten TOP) und die angezeigte Nummer („TOP 2",
+/// „2.1" oder leer). Nummern werden nicht gespeichert, sondern bei jeder Ausgabe neu
+/// vergeben und folgen so jeder Umsortierung.
+fn top_nummern(eintraege: &[Eintrag], modus: TopNummerierung) -> Vec<(u32, String)> {
+    let mut top = 0;
+    let mut unter = 0;
+    eintraege
+        .iter()
+        .map(|e| {
+            if modus == TopNummerierung::Aus
+                || (e.art == Art::Leer && e.punkt.trim().is_empty() && e.notiz.trim().is_empty())
+            {
+                return (top, String::new());
             }
-
-            // Teilnehmer
-            let tn: Vec<_> = self.teilnehmer.iter().filter(|t| !t.name.is_empty()).collect();
-            if !tn.is_empty() {
-                let namen: Vec<String> = tn.iter().map(|t| {
-                    let mut text = t.name.clone();
-                    if !t.kuerzel.is_empty() {
-                        text.push_str(&format!(" [{}]", t.kuerzel));
-                    }
-                    text
-                }).collect();
-                let _ = info_table.row()
-                    .element(genpdf::elements::Paragraph::new("Teilnehmer").styled(small_bold).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
-                    .element(genpdf::elements::Paragraph::new(namen.join(", ")).styled(small).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
-                    .push();
+            if e.art == Art::Agenda {
+                top += 1;
+                unter = 0;
+                (top, format!("TOP {}", top))
+            } else if modus == TopNummerierung::Gegliedert && top > 0 {
+                unter += 1;
+                (top, format!("{}.{}", top, unter))
+            } else {
+                (top, String::new())
             }
+        })
+        .collect()
+}
 
-            // Zur Kenntnis
-            let zk: Vec<_> = self.zur_kenntnis.iter().filter(|z| !z.name.is_empty()).collect();
-            if !zk.is_empty() {
-                let namen: Vec<String> = zk.iter().map(|z| {
-                    let mut text = z.name.clone();
-                    if !z.kuerzel.is_empty() {
-                        text.push_str(&format!(" [{}]", z.kuerzel));
-                    }
-                    text
-                }).collect();
-                let _ = info_table.row()
-                    .element(genpdf::elements::Paragraph::new("Zur Kenntnis").styled(small_bold).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
-                    .element(genpdf::elements::Paragraph::new(namen.join(", ")).styled(small).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
-                    .push();
-            }
+/// Stellt die Nummer dem Punkt voran („TOP 1: Budget", „1.2 Budget").
+fn nummer_voranstellen(nummer: &str, punkt: &str) -> String {
+    if nummer.is_empty() {
+        punkt.to_string()
+    } else if punkt.is_empty() {
+        nummer.to_string()
+    } else if nummer.starts_with("TOP") {
+        format!("{}: {}", nummer, punkt)
+    } else {
+        format!("{} {}", nummer, punkt)
+    }
+}
 
-            // Über dieses Meeting
-            if !self.ueber_meeting.is_empty() {
-                let _ = info_table.row()
-                    .element(genpdf::elements::Paragraph::new("Über dieses Meeting").styled(small_bold).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
-                    .element(genpdf::elements::Paragraph::new(&self.ueber_meeting).styled(small).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
-                    .push();
+/// Entfernt eine von `nummer_voranstellen` geschriebene Nummer beim Einlesen wieder;
+/// „1.2"-Nummern nur bei gegliederter Nummerierung, damit eigene Punkte wie
+/// „3.5 Release" sonst erhalten bleiben.
+fn top_nummer_entfernen(punkt: &str, modus: TopNummerierung) -> &str {
+    if modus == TopNummerierung::Aus {
+        return punkt;
+    }
+    let ziffern = |s: &str| s.len() - s.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if let Some(rest) = punkt.strip_prefix("TOP ") {
+        let n = ziffern(rest);
+        if n > 0 {
+            let rest = &rest[n..];
+            if rest.is_empty() {
+                return rest;
             }
-
-            // Status (Entwurf / Freigegeben)
-            {
-                let entwurf = if self.ist_entwurf { "[x] Entwurf" } else { "[  ] Entwurf" };
-                let freigegeben = if self.ist_freigegeben { "[x] Freigegeben" } else { "[  ] Freigegeben" };
-                let mut cb_table = genpdf::elements::TableLayout::new(vec![1, 1, 1, 1]);
-                let _ = cb_table.row()
-                    .element(genpdf::elements::Paragraph::new(entwurf).styled(small))
-                    .element(genpdf::elements::Paragraph::new(freigegeben).styled(small))
-                    .element(genpdf::elements::Paragraph::new(""))
-                    .element(genpdf::elements::Paragraph::new(""))
-                    .push();
-                let _ = info_table.row()
-                    .element(genpdf::elements::Paragraph::new("Status").styled(small_bold).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
-                    .element(cb_table.padded(genpdf::Margins::trbl(1, 0, 1, 0)))
-                    .push();
+            if let Some(rest) = rest.strip_prefix(':') {
+                return rest.trim_start();
             }
-
-            // Klassifizierung
-            {
-                let entries: Vec<String> = Sicherheit::all()
-                    .iter()
-                    .map(|s| {
-                        if *s == self.sicherheit {
-                            format!("[x] {}", s.label())
-                        } else {
-                            format!("[  ] {}", s.label())
-                        }
-                    })
-                    .collect();
-                let mut cb_table = genpdf::elements::TableLayout::new(vec![1, 1, 1, 1]);
-                let _ = cb_table.row()
-                    .element(genpdf::elements::Paragraph::new(entries[0].clone()).styled(small))
-                    .element(genpdf::elements::Paragraph::new(entries[1].clone()).styled(small))
-                    .element(genpdf::elements::Paragraph::new(entries[2].clone()).styled(small))
-                    .element(genpdf::elements::Paragraph::new(entries[3].clone()).styled(small))
-                    .push();
-                let _ = info_table.row()
-                    .element(genpdf::elements::Paragraph::new("Klassifizierung").styled(small_bold).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
-                    .element(cb_table.padded(genpdf::Margins::trbl(1, 0, 1, 0)))
-                    .push();
+        }
+    }
+    if modus == TopNummerierung::Gegliedert {
+        let n = ziffern(punkt);
+        if let Some(rest) = punkt[n..].strip_prefix('.').filter(|_| n > 0) {
+            let m = ziffern(rest);
+            let rest = &rest[m..];
+            if m > 0 && (rest.is_empty() || rest.starts_with(' ')) {
+                return rest.trim_start();
             }
-
-            doc.push(info_table);
-            doc.push(genpdf::elements::Break::new(0.5));
         }
+    }
+    punkt
+}
 
-        // Trennlinie
-        doc.push(
-            genpdf::elements::Paragraph::new("_".repeat(250))
-                .styled(genpdf::style::Style::new().with_font_size(6).with_color(
-                    genpdf::style::Color::Greyscale(180),
-                )),
-        );
-        doc.push(genpdf::elements::Break::new(0.5));
-
-        // Einträge als Tabelle
-        let entries: Vec<_> = self
-            .eintraege
-            .iter()
-            .filter(|e| !e.punkt.is_empty() || e.art != Art::Leer || !e.notiz.is_empty())
-            .collect();
+/// Querverweis auf den TOP eines Eintrags („TOP 2" bzw. gegliedert „TOP 2.1"),
+/// `None` vor dem ersten TOP oder ohne Nummerierung.
+fn top_verweis(top: u32, nummer: &str) -> Option<String> {
+    if top == 0 {
+        return None;
+    }
+    if nummer.is_empty() || nummer.starts_with("TOP") {
+        Some(format!("TOP {}", top))
+    } else {
+        Some(format!("TOP {}", nummer))
+    }
+}
 
-        if !entries.is_empty() {
-            let mut all_links: Vec<(usize, String, String)> = Vec::new();
-            let mut table = genpdf::elements::TableLayout::new(vec![3, 5, 13, 4, 4]);
+/// Empfängerkreis eines PDF-Exports. Der Anhang „Nur intern" erscheint nur in
+/// der internen Fassung.
+#[derive(Clone, Copy, PartialEq)]
+enum Zielgruppe {
+    Intern,
+    Extern,
+}
 
-            // Kopfzeile
-            let _ = table
-                .row()
-                .element(
-                    genpdf::elements::Paragraph::new("")
-                        .styled(small_bold)
-                        .padded(genpdf::Margins::trbl(1, 2, 1, 0)),
-                )
-                .element(
-                    genpdf::elements::Paragraph::new("Art")
-                        .styled(small_bold)
-                        .padded(genpdf::Margins::trbl(1, 2, 1, 2)),
-                )
-                .element(
-                    genpdf::elements::Paragraph::new("Notiz")
-                        .styled(small_bold)
-                        .padded(genpdf::Margins::trbl(1, 2, 1, 2)),
-                )
-                .element(
-                    genpdf::elements::Paragraph::new("Kümmerer")
-                        .styled(small_bold)
-                        .padded(genpdf::Margins::trbl(1, 2, 1, 2)),
-                )
-                .element(
-                    genpdf::elements::Paragraph::new("Bis")
-                        .styled(small_bold)
-                        .padded(genpdf::Margins::trbl(1, 2, 1, 2)),
-                )
-                .push();
+impl Zielgruppe {
+    /// Gibt den Anzeigetext der Zielgruppe zurück.
+    fn label(&self) -> &str {
+        match self {
+            Zielgruppe::Intern => "Intern",
+            Zielgruppe::Extern => "Extern (ohne Anhang „Nur intern“)",
+        }
+    }
+}
 
-            for e in &entries {
-                let art_str = if e.art == Art::Leer {
-                    ""
-                } else {
-                    e.art.label()
-                };
-                let is_todo = e.art == Art::Todo;
-                let row_style = if is_todo { small_bold } else { small };
+/// Schriftgrößen- und Dichte-Profil für den PDF-Export.
+#[derive(Clone, Copy, PartialEq)]
+enum PdfProfil {
+    /// Kleinere Schrift, engere Zeilen und Ränder – mehr Einträge pro Seite.
+    Kompakt,
+    /// Bisheriges Layout mit 9-pt-Schrift.
+    Standard,
+    /// Größere Schrift und mehr Weißraum.
+    Lesefreundlich,
+}
 
-                let notiz_cell = {
-                    let mut layout = genpdf::elements::LinearLayout::vertical();
-                    for line in e.notiz.split('\n') {
-                        let (replaced, new_links) =
-                            markdown_links_extrahieren(line, all_links.len() + 1);
-                        all_links.extend(new_links);
-                        layout.push(
-                            genpdf::elements::Paragraph::new(replaced)
-                                .styled(row_style),
-                        );
-                    }
-                    layout.padded(genpdf::Margins::trbl(1, 2, 1, 2))
-                };
+impl PdfProfil {
+    /// Gibt den Anzeigetext des Profils zurück.
+    fn label(&self) -> &str {
+        match self {
+            PdfProfil::Kompakt => "Kompakt",
+            PdfProfil::Standard => "Standard",
+            PdfProfil::Lesefreundlich => "Lesefreundlich",
+        }
+    }
 
-                if is_todo {
-                    // Großzügiger max_height — nächste Zeile mit weißem Hintergrund deckt Überlauf ab
-                    let notiz_lines = e.notiz.split('\n').count().max(1) as f64;
-                    let row_h = notiz_lines * 8.0 + 10.0;
+    /// Gibt alle Profile in Anzeigereihenfolge zurück.
+    fn all() -> &'static [PdfProfil] {
+        &[PdfProfil::Kompakt, PdfProfil::Standard, PdfProfil::Lesefreundlich]
+    }
 
-                    let _ = table
-                        .row()
-                        .element(ZellenHintergrund::grau(
-                            genpdf::elements::Paragraph::new(&e.punkt)
-                                .styled(row_style)
-                                .padded(genpdf::Margins::trbl(1.5, 2, 2.5, 0)),
-                            row_h,
-                        ))
-                        .element(ZellenHintergrund::grau(
-                            genpdf::elements::Paragraph::new(art_str)
-                                .styled(row_style)
-                                .padded(genpdf::Margins::trbl(1.5, 2, 2.5, 2)),
-                            row_h,
-                        ))
-                        .element(ZellenHintergrund::grau(
-                            notiz_cell.padded(genpdf::Margins::trbl(0.5, 0, 1.5, 0)),
-                            row_h,
-                        ))
-                        .element(ZellenHintergrund::grau(
-                            genpdf::elements::Paragraph::new(&e.kuemmerer)
-                                .styled(row_style)
-                                .padded(genpdf::Margins::trbl(1.5, 2, 2.5, 2)),
-                            row_h,
-                        ))
-                        .element(ZellenHintergrund::grau(
-                            genpdf::elements::Paragraph::new(&e.bis)
-                                .styled(row_style)
-                                .padded(genpdf::Margins::trbl(1.5, 2, 2.5, 2)),
-                            row_h,
-                        ))
-                        .push();
-                } else {
-                    // Weißer Hintergrund deckt etwaigen Grau-Überlauf der Zeile darüber ab
-                    let white_h = 40.0;
-                    let _ = table
-                        .row()
-                        .element(ZellenHintergrund::weiss(
-                            genpdf::elements::Paragraph::new(&e.punkt)
-                                .styled(row_style)
-                                .padded(genpdf::Margins::trbl(1.75, 2, 2.25, 0)),
-                            white_h,
-                        ))
-                        .element(ZellenHintergrund::weiss(
-                            genpdf::elements::Paragraph::new(art_str)
-                                .styled(row_style)
-                                .padded(genpdf::Margins::trbl(1.75, 2, 2.25, 2)),
-                            white_h,
-                        ))
-                        .element(ZellenHintergrund::weiss(
-                            notiz_cell.padded(genpdf::Margins::trbl(0.75, 0, 1.25, 0)),
-                            white_h,
-                        ))
-                        .element(ZellenHintergrund::weiss(
-                            genpdf::elements::Paragraph::new(&e.kuemmerer)
-                                .styled(row_style)
-                                .padded(genpdf::Margins::trbl(1.75, 2, 2.25, 2)),
-                            white_h,
-                        ))
-                        .element(ZellenHintergrund::weiss(
-                            genpdf::elements::Paragraph::new(&e.bis)
-                                .styled(row_style)
-                                .padded(genpdf::Margins::trbl(1.75, 2, 2.25, 2)),
-                            white_h,
-                        ))
-                        .push();
-                }
-            }
+    /// Schriftgröße für Fließtext und Tabellen (in Punkt).
+    fn schriftgroesse(&self) -> u8 {
+        match self {
+            PdfProfil::Kompakt => 8,
+            PdfProfil::Standard => 9,
+            PdfProfil::Lesefreundlich => 11,
+        }
+    }
 
-            doc.push(table);
+    /// Schriftgröße des Titels (in Punkt).
+    fn titelgroesse(&self) -> u8 {
+        match self {
+            PdfProfil::Kompakt => 16,
+            PdfProfil::Standard => 20,
+            PdfProfil::Lesefreundlich => 24,
+        }
+    }
 
-            if !all_links.is_empty() {
-                let tiny = genpdf::style::Style::new().with_font_size(7);
-                let tiny_bold = genpdf::style::Style::new().bold().with_font_size(9);
-                doc.push(genpdf::elements::Break::new(1.0));
-                doc.push(
-                    genpdf::elements::Paragraph::new("Links")
-                        .styled(tiny_bold),
-                );
-                doc.push(genpdf::elements::Break::new(0.3));
-                for (num, label, url) in &all_links {
-                    let mut layout = genpdf::elements::LinearLayout::vertical();
-                    layout.push(
-                        genpdf::elements::Paragraph::new(
-                            format!("[{}] {}:", num, label),
-                        )
-                        .styled(tiny),
-                    );
-                    // URL an '/' aufteilen, damit genpdf umbrechen kann
-                    let mut url_lines: Vec<String> = Vec::new();
-                    let mut current = String::new();
-                    for ch in url.chars() {
-                        current.push(ch);
-                        if ch == '/' && current.len() > 100 {
-                            url_lines.push(current);
-                            current = String::new();
-                        }
-                    }
-                    if !current.is_empty() {
-                        url_lines.push(current);
-                    }
-                    for chunk in &url_lines {
-                        layout.push(
-                            genpdf::elements::Paragraph::new(chunk.as_str())
-                                .styled(tiny)
-                                .padded(genpdf::Margins::trbl(0, 0, 0, 3.5)),
-                        );
-                    }
-                    doc.push(layout);
-                }
-            }
+    /// Zeilenabstand als Vielfaches der Schriftgröße.
+    fn zeilenabstand(&self) -> f64 {
+        match self {
+            PdfProfil::Kompakt => 0.9,
+            PdfProfil::Standard => 1.0,
+            PdfProfil::Lesefreundlich => 1.25,
         }
     }
 
-    /// Startet den PDF-Export-Prozess:
-    /// 1. Personen sortieren und Pflichtfelder prüfen.
-    /// 2. Markdown automatisch speichern (falls Pfad bekannt).
-    /// 3. Schriftart laden (Fehler → Fehlerdialog).
-    /// 4. Datei-Speichern-Dialog in separatem Thread öffnen.
-    /// 5. Bei Bestätigung: `pdf_generieren` aufrufen.
-    fn pdf_exportieren(&mut self) {
-        self.sort_personen();
-        if self.protokollant.name.trim().is_empty() {
-            self.show_pflichtfeld_hinweis = true;
-            return;
+    /// Faktor für vertikale Abstände zwischen Abschnitten.
+    fn abstand(&self) -> f64 {
+        match self {
+            PdfProfil::Kompakt => 0.5,
+            PdfProfil::Standard => 1.0,
+            PdfProfil::Lesefreundlich => 1.6,
         }
-        // Vor PDF-Erzeugung automatisch speichern
-        if let Some(ref path) = self.save_path {
-            if self.erstellt_am.is_empty() {
-                self.erstellt_am = Local::now().format("%d.%m.%Y %H:%M").to_string();
-                self.erstellt_von = self.protokollant.name.clone();
-            }
-            let content = self.markdown_erstellen();
-            let _ = std::fs::write(path, content);
+    }
+
+    /// Seitenränder als `[oben, rechts, unten, links]` in mm.
+    fn raender_mm(&self) -> [f64; 4] {
+        match self {
+            PdfProfil::Kompakt => [15.0, 12.0, 15.0, 12.0],
+            PdfProfil::Standard => [20.0, 15.0, 20.0, 15.0],
+            PdfProfil::Lesefreundlich => [25.0, 20.0, 25.0, 20.0],
         }
-        let font_family = match self.schrift_laden() {
-            Some(f) => f,
-            None => {
-                self.show_pdf_error = true;
-                return;
-            }
-        };
+    }
 
-        self.pending_pdf_font = Some(font_family);
-        let pdf_filename = self.pdf_dateinamen_erstellen();
-        let (tx, rx) = mpsc::channel();
-        self.dialog_rx = Some(rx);
-        std::thread::spawn(move || {
-            if let Some(path) = rfd::FileDialog::new()
-                .set_file_name(&pdf_filename)
-                .add_filter("PDF", &["pdf"])
-                .save_file()
-            {
-                let _ = tx.send(DialogErgebnis::PdfExport(path));
-            }
-        });
+    /// Seitenränder für genpdf (aus `raender_mm`).
+    fn raender(&self) -> genpdf::Margins {
+        let [oben, rechts, unten, links] = self.raender_mm();
+        genpdf::Margins::trbl(oben, rechts, unten, links)
     }
+}
 
-    /// Rendert das Protokoll als PDF-Datei in zwei Durchläufen:
-    /// - **Durchlauf 1**: Inhalt in einen In-Memory-Puffer rendern, um die Gesamtseitenzahl
-    ///   zu ermitteln (genpdf kennt diese erst nach dem Rendern).
-    /// - **Durchlauf 2**: Inhalt erneut rendern, diesmal mit `FusszeileDekorator`, der
-    ///   die korrekte Gesamtseitenzahl in die Fußzeile schreibt.
-    fn pdf_generieren(&self, path: &std::path::Path, schriftfamilie: genpdf::fonts::FontFamily<genpdf::fonts::FontData>) {
-        // Durchlauf 1: Gesamtseitenzahl durch In-Memory-Rendering ermitteln
-        let gesamtseiten = {
-            let seitenanzahl = std::rc::Rc::new(std::cell::Cell::new(0usize));
-            let zaehler = seitenanzahl.clone();
+/// Sprache der Beschriftungen und Datumsformate im PDF, unabhängig von der Oberfläche.
+/// Inhalte (Notizen, Namen, eigene Art-Beschriftungen) bleiben unverändert.
+#[derive(Clone, Copy, PartialEq)]
+enum PdfSprache {
+    Deutsch,
+    Englisch,
+}
 
-            let mut vorberechnungs_dok = genpdf::Document::new(schriftfamilie.clone());
-            let mut dekorator = genpdf::SimplePageDecorator::new();
-            dekorator.set_margins(20);
-            // Callback wird pro Seite aufgerufen – speichert die letzte Seitennummer
-            dekorator.set_header(move |seite| {
-                zaehler.set(seite);
-                genpdf::elements::Break::new(0.0)
-            });
-            vorberechnungs_dok.set_page_decorator(dekorator);
-            self.pdf_inhalt_hinzufuegen(&mut vorberechnungs_dok);
-            let mut puffer = Vec::new();
-            let _ = vorberechnungs_dok.render(&mut puffer);
-            seitenanzahl.get()
-        };
+/// Übersetzungen der festen PDF-Beschriftungen als `(Deutsch, Englisch)`.
+const PDF_BESCHRIFTUNGEN: &[(&str, &str)] = &[
+    ("Protokollführer", "Minutes taker"),
+    ("Teilnehmer", "Participants"),
+    ("Zur Kenntnis", "For information"),
+    ("Über dieses Meeting", "About this meeting"),
+    ("Klassifizierung", "Classification"),
+    ("Nächstes Meeting", "Next meeting"),
+    ("Datum", "Date"),
+    ("Ort", "Location"),
+    ("Protokoll erstellt am", "Minutes written on"),
+    ("Punkt", "Item"),
+    ("Art", "Type"),
+    ("Notiz", "Note"),
+    ("Kümmerer", "Owner"),
+    ("Bis", "Due"),
+    ("Notiz (Fortsetzung)", "Note (continued)"),
+    ("Klärung", "Clarification"),
+    ("Änderungen seit Version", "Changes since version"),
+    ("Anlage: Offene-Punkte-Liste", "Appendix: Open items"),
+    ("offene Punkte, Stand", "open items as of"),
+    ("Aus Protokoll", "From minutes"),
+    ("Alter", "Age"),
+    ("T.", "d"),
+    ("Anhang: Nur intern", "Appendix: Internal only"),
+    ("Quellen & Referenzen", "Sources & references"),
+    ("Allgemein", "General"),
+    ("Seite", "Page"),
+    ("von", "of"),
+    ("offen", "open"),
+    ("beantwortet", "answered"),
+    ("Entwurf", "Draft"),
+    ("In Prüfung", "In review"),
+    ("Freigegeben", "Approved"),
+    ("Abgelehnt", "Rejected"),
+    ("Archiviert", "Archived"),
+    ("Öffentlich", "Public"),
+    ("Intern", "Internal"),
+    ("Vertraulich", "Confidential"),
+    ("Streng vertraulich", "Strictly confidential"),
+    ("NEU", "NEW"),
+    ("GEÄNDERT", "CHANGED"),
+    ("ENTFERNT", "REMOVED"),
+    ("BESTÄTIGT", "CONFIRMED"),
+    ("STRITTIG", "DISPUTED"),
+    ("ABGEBROCHEN", "CANCELLED"),
+    ("ENTSCHEIDUNG", "DECISION"),
+    ("FERTIG", "DONE"),
+    ("FRAGE", "QUESTION"),
+    ("IDEE", "IDEA"),
+    ("WORTBEITRAG", "STATEMENT"),
+];
+
+impl PdfSprache {
+    /// Gibt den Anzeigetext der Sprache zurück.
+    fn label(&self) -> &str {
+        match self {
+            PdfSprache::Deutsch => "Deutsch",
+            PdfSprache::Englisch => "English",
+        }
+    }
 
-        // Durchlauf 2: Echtes PDF mit Fußzeile und korrekter Gesamtseitenzahl erstellen
-        let mut dok = genpdf::Document::new(schriftfamilie);
-        let pdf_titel = if self.titel.is_empty() {
-            "MZProtokoll".to_string()
-        } else {
-            format!("{} — MZProtokoll von Marcel Zimmer (www.marcelzimmer.de)", self.titel)
-        };
-        dok.set_title(&pdf_titel);
-        dok.set_page_decorator(FusszeileDekorator::new(gesamtseiten));
-        self.pdf_inhalt_hinzufuegen(&mut dok);
-        let _ = dok.render_to_file(path);
+    /// Gibt alle Sprachen in Anzeigereihenfolge zurück.
+    fn all() -> &'static [PdfSprache] {
+        &[PdfSprache::Deutsch, PdfSprache::Englisch]
     }
 
-    /// Gibt alle bekannten Kürzel (Protokollant + Teilnehmer + Zur-Kenntnis)
-    /// sortiert und dedupliziert zurück. Wird für das Kümmerer-Dropdown in TODO-Zeilen verwendet.
-    fn alle_kuerzel(&self) -> Vec<String> {
-        let mut k = Vec::new();
-        if !self.protokollant.kuerzel.is_empty() {
-            k.push(self.protokollant.kuerzel.clone());
+    /// Liest `de` oder `en` (auch ausgeschrieben) aus der Einstellungsdatei.
+    fn parsen(text: &str) -> Option<PdfSprache> {
+        match text.trim().to_lowercase().as_str() {
+            "de" | "deutsch" => Some(PdfSprache::Deutsch),
+            "en" | "englisch" | "english" => Some(PdfSprache::Englisch),
+            _ => None,
         }
-        for t in &self.teilnehmer {
-            if !t.kuerzel.is_empty() {
-                k.push(t.kuerzel.clone());
-            }
+    }
+
+    /// Sprachcode für HTML und Typst.
+    fn code(&self) -> &str {
+        match self {
+            PdfSprache::Deutsch => "de",
+            PdfSprache::Englisch => "en",
         }
-        for z in &self.zur_kenntnis {
-            if !z.kuerzel.is_empty() {
-                k.push(z.kuerzel.clone());
-            }
+    }
+
+    /// Übersetzt eine feste deutsche Beschriftung; unbekannte Texte (etwa eigene
+    /// Art-Beschriftungen) bleiben unverändert.
+    fn t<'a>(&self, text: &'a str) -> &'a str {
+        match self {
+            PdfSprache::Deutsch => text,
+            PdfSprache::Englisch => PDF_BESCHRIFTUNGEN
+                .iter()
+                .find(|(de, _)| *de == text)
+                .map_or(text, |(_, en)| en),
         }
-        k.sort();
-        k.dedup();
-        k
     }
-}
 
-// -- Parse-Helfer --
+    /// Kurzes Datum für Tabellen, z. B. „05.02.2026" bzw. „5 Feb 2026".
+    fn datum(&self, datum: NaiveDate) -> String {
+        match self {
+            PdfSprache::Deutsch => datum.format("%d.%m.%Y").to_string(),
+            PdfSprache::Englisch => datum.format("%-d %b %Y").to_string(),
+        }
+    }
 
-/// Trennt einen Personeneintrag der Form `"Name [Kürzel]"` in Name und Kürzel auf.
-/// Wenn kein Kürzel in eckigen Klammern vorhanden ist, wird ein leerer Kürzel-String zurückgegeben.
-fn name_kuerzel_parsen(s: &str) -> (String, String) {
-    let trimmed = s.trim();
-    if let Some(bracket_start) = trimmed.rfind('[') {
-        if let Some(bracket_end) = trimmed.rfind(']') {
-            if bracket_end > bracket_start {
-                let name = trimmed[..bracket_start].trim().to_string();
-                let kuerzel = trimmed[bracket_start + 1..bracket_end].trim().to_string();
-                return (name, kuerzel);
-            }
+    /// Wie `datum_text`, aber im kurzen Tabellenformat und ohne Wochentag.
+    fn kurzdatum_text(&self, text: &str) -> String {
+        match datum_aus_text(text).filter(|_| *self != PdfSprache::Deutsch) {
+            Some(datum) => self.datum(datum),
+            None => text.to_string(),
         }
     }
-    (trimmed.to_string(), String::new())
-}
 
-/// Wandelt den Text einer Markdown-Tabellenzelle in die zugehörige `Art`-Variante um.
-/// Unbekannte Strings werden als `Art::Leer` interpretiert.
-fn art_parsen(s: &str) -> Art {
-    match s.trim() {
-        "ABGEBROCHEN" => Art::Abgebrochen,
-        "AGENDA" => Art::Agenda,
-        "ENTSCHEIDUNG" => Art::Entscheidung,
-        "FERTIG" => Art::Fertig,
-        "IDEE" => Art::Idee,
-        "INFO" => Art::Info,
-        "TODO" => Art::Todo,
-        _ => Art::Leer,
+    /// Formatiert einen Datumstext wie „Montag, 05.02.2026" oder „05.02.2026" in der
+    /// Sprache um („Monday, 5 February 2026"); andere Texte bleiben unverändert.
+    fn datum_text(&self, text: &str) -> String {
+        let Some(datum) = datum_aus_text(text).filter(|_| *self != PdfSprache::Deutsch) else {
+            return text.to_string();
+        };
+        if text.contains(',') {
+            datum.format("%A, %-d %B %Y").to_string()
+        } else {
+            datum.format("%-d %B %Y").to_string()
+        }
     }
 }
 
-/// Teilt eine Markdown-Tabellenzeile (`| A | B | C |`) in einzelne Zellen auf.
-/// Berücksichtigt escaped Pipe-Zeichen (`\|`), die innerhalb von Zellen vorkommen dürfen.
-fn tabellenzeile_aufteilen(row: &str) -> Vec<String> {
-    let trimmed = row.trim().trim_start_matches('|').trim_end_matches('|');
-    let mut cells = Vec::new();
-    let mut current = String::new();
-    let mut chars = trimmed.chars().peekable();
+/// Benanntes Exportprofil (Schlüssel `exportprofil.<Name>.<feld>`): bündelt Zielgruppe,
+/// Dichte, Branding, Anonymisierung, Art-Auswahl, PDF/A und Passwortschutz, damit eine
+/// Fassung wie die Kundenfassung mit einem Klick eingestellt ist. Nicht gesetzte
+/// Felder lassen die Auswahl im Exportdialog bzw. die Einstellungen unverändert.
+#[derive(Clone)]
+struct ExportProfil {
+    /// Anzeigename im Exportdialog.
+    name: String,
+    /// Vorgewählte Zielgruppe (`zielgruppe = intern|extern`).
+    zielgruppe: Option<Zielgruppe>,
+    /// Vorgewähltes Dichte-Profil (`dichte = kompakt|standard|lesefreundlich`).
+    dichte: Option<PdfProfil>,
+    /// Vorgewählte Sprache der PDF-Beschriftungen (`sprache = de|en`).
+    sprache: Option<PdfSprache>,
+    /// Logo des Deckblatts anstelle von `pdf_logo`.
+    logo: Option<String>,
+    /// Kopfzeile der Folgeseiten anstelle von `pdf_kopfzeile`.
+    kopfzeile: Option<String>,
+    /// Personen nur mit Kürzel bzw. Initialen ausgeben (siehe `pdf_person`).
+    anonymisieren: bool,
+    /// Nur Einträge dieser Arten exportieren (kommagetrennt); leer = alle.
+    arten: Vec<Art>,
+    /// Ergebnis über `pdfa_kommando` nach PDF/A wandeln.
+    pdfa: bool,
+    /// Ergebnis mit einem im Exportdialog eingegebenen Passwort verschlüsseln.
+    passwortschutz: bool,
+}
 
-    while let Some(c) = chars.next() {
-        if c == '\\' {
-            if let Some(&next) = chars.peek() {
-                if next == '|' {
-                    current.push('|');
-                    chars.next();
-                    continue;
+impl ExportProfil {
+    /// Leeres Profil, das nichts vorgibt.
+    fn neu(name: &str) -> ExportProfil {
+        ExportProfil {
+            name: name.to_string(),
+            zielgruppe: None,
+            dichte: None,
+            sprache: None,
+            logo: None,
+            kopfzeile: None,
+            anonymisieren: false,
+            arten: Vec::new(),
+            pdfa: false,
+            passwortschutz: false,
+        }
+    }
+
+    /// Eingebautes Profil „Kundenfassung": extern, anonymisiert, nur Entscheidungen
+    /// und Informationen, PDF/A und Passwortschutz. Lässt sich über
+    /// `exportprofil.Kundenfassung.<feld>` anpassen.
+    fn kundenfassung() -> ExportProfil {
+        ExportProfil {
+            zielgruppe: Some(Zielgruppe::Extern),
+            anonymisieren: true,
+            arten: vec![Art::Entscheidung, Art::Info],
+            pdfa: true,
+            passwortschutz: true,
+            ..ExportProfil::neu("Kundenfassung")
+        }
+    }
+
+    /// Übernimmt ein Feld aus der Einstellungsdatei; unbekannte Felder und Werte
+    /// werden ignoriert.
+    fn feld_setzen(&mut self, feld: &str, wert: &str) {
+        match feld {
+            "zielgruppe" => {
+                self.zielgruppe = match wert {
+                    "intern" => Some(Zielgruppe::Intern),
+                    "extern" => Some(Zielgruppe::Extern),
+                    _ => None,
                 }
             }
-            current.push(c);
-        } else if c == '|' {
-            cells.push(current.trim().to_string());
-            current = String::new();
-        } else {
-            current.push(c);
+            "dichte" => {
+                self.dichte = match wert {
+                    "kompakt" => Some(PdfProfil::Kompakt),
+                    "standard" => Some(PdfProfil::Standard),
+                    "lesefreundlich" => Some(PdfProfil::Lesefreundlich),
+                    _ => None,
+                }
+            }
+            "sprache" => self.sprache = PdfSprache::parsen(wert),
+            "logo" => self.logo = Some(wert.to_string()),
+            "kopfzeile" => self.kopfzeile = Some(wert.to_string()),
+            "anonymisieren" => self.anonymisieren = wert == "true",
+            "arten" => {
+                self.arten = wert
+                    .split(',')
+                    .filter_map(|teil| Art::all().iter().find(|a| a.label().eq_ignore_ascii_case(teil.trim())).cloned())
+                    .collect()
+            }
+            "pdfa" => self.pdfa = wert == "true",
+            "passwortschutz" => self.passwortschutz = wert == "true",
+            _ => {}
         }
     }
-    cells.push(current.trim().to_string());
-    cells
+
+    /// Kurzbeschreibung der Vorgaben für den Exportdialog.
+    fn beschreibung(&self) -> String {
+        let mut teile = Vec::new();
+        if let Some(zielgruppe) = self.zielgruppe {
+            teile.push(match zielgruppe {
+                Zielgruppe::Intern => "intern".to_string(),
+                Zielgruppe::Extern => "extern".to_string(),
+            });
+        }
+        if let Some(dichte) = self.dichte {
+            teile.push(dichte.label().to_string());
+        }
+        if let Some(sprache) = self.sprache {
+            teile.push(sprache.label().to_string());
+        }
+        if self.logo.is_some() || self.kopfzeile.is_some() {
+            teile.push("eigenes Branding".to_string());
+        }
+        if self.anonymisieren {
+            teile.push("anonymisiert".to_string());
+        }
+        if !self.arten.is_empty() {
+            let arten: Vec<&str> = self.arten.iter().map(Art::label).collect();
+            teile.push(format!("nur {}", arten.join(", ")));
+        }
+        if self.pdfa {
+            teile.push("PDF/A".to_string());
+        }
+        if self.passwortschutz {
+            teile.push("Passwortschutz".to_string());
+        }
+        teile.join(" · ")
+    }
 }
 
-/// Ersetzt Markdown-Links der Form `[Text](URL)` durch `Text [N]` und
-/// gibt eine Liste der gefundenen Links als Tupel `(Nummer, Text, URL)` zurück.
-/// `start_num` gibt die erste Fußnotennummer an (1-basiert).
-/// Wird für den PDF-Export verwendet, da genpdf keine Hyperlinks unterstützt.
-fn markdown_links_extrahieren(text: &str, start_num: usize) -> (String, Vec<(usize, String, String)>) {
-    let mut result = String::new();
-    let mut links: Vec<(usize, String, String)> = Vec::new();
-    let mut num = start_num;
-    let mut pos = 0;
+/// Leere Zeilen am Ende der Anwesenheitsliste für nicht angemeldete Gäste.
+const ANWESENHEIT_LEERZEILEN: usize = 5;
 
-    while pos < text.len() {
-        if text.as_bytes()[pos] == b'[' {
-            let after_bracket = pos + 1;
-            if after_bracket < text.len() {
-                if let Some(rel_close) = text[after_bracket..].find(']') {
-                    let close_bracket = after_bracket + rel_close;
-                    let label = &text[after_bracket..close_bracket];
-                    let after_close = close_bracket + 1;
-                    if after_close < text.len() && text.as_bytes()[after_close] == b'(' {
-                        let after_paren = after_close + 1;
-                        if after_paren < text.len() {
-                            if let Some(rel_end) = text[after_paren..].find(')') {
-                                let close_paren = after_paren + rel_end;
-                                let url = &text[after_paren..close_paren];
-                                if !label.is_empty() && !url.is_empty() {
-                                    result.push_str(&format!("{} [{}]", label, num));
-                                    links.push((num, label.to_string(), url.to_string()));
-                                    num += 1;
-                                    pos = close_paren + 1;
-                                    continue;
-                                }
-                            }
-                        }
+/// Standard-Gewichte der PDF-Tabellenspalten Punkt, Art, Notiz, Kümmerer und Bis.
+const PDF_SPALTENGEWICHTE: [usize; 5] = [3, 5, 13, 4, 4];
+
+/// Gewichte der PDF-Tabellenspalten Punkt, Art, Notiz, Kümmerer und Bis; 0 lässt die
+/// Spalte weg (außer bei Punkt mit der TOP-Nummerierung und Notiz). Als `3/5/13/0/4` in `pdf_spalten` bzw. im Protokoll (`**PDF-Spalten:**`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct PdfSpalten([usize; 5]);
+
+impl PdfSpalten {
+    /// Spaltennamen in Tabellenreihenfolge.
+    const NAMEN: [&'static str; 5] = ["Punkt", "Art", "Notiz", "Kümmerer", "Bis"];
+
+    /// Spalten, die sich nicht weglassen lassen: Punkt (trägt die TOP-Nummern) und Notiz.
+    const PFLICHT: [usize; 2] = [0, 2];
+
+    /// Liest fünf durch `/` getrennte Gewichte; Punkt- und Notiz-Spalte dürfen nicht fehlen.
+    fn parsen(text: &str) -> Option<PdfSpalten> {
+        let zahlen: Vec<usize> = text.split('/').map(|z| z.trim().parse().ok()).collect::<Option<_>>()?;
+        let gewichte: [usize; 5] = zahlen.try_into().ok()?;
+        PdfSpalten::PFLICHT.iter().all(|i| gewichte[*i] > 0).then_some(PdfSpalten(gewichte))
+    }
+
+    /// Schreibweise wie in `parsen`, z. B. `3/5/13/4/4`.
+    fn text(&self) -> String {
+        self.0.iter().map(|g| g.to_string()).collect::<Vec<_>>().join("/")
+    }
+
+    /// Neue, leere Tabelle mit den sichtbaren Spalten.
+    fn tabelle(&self) -> genpdf::elements::TableLayout {
+        genpdf::elements::TableLayout::new(self.0.iter().copied().filter(|g| *g > 0).collect())
+    }
+
+    /// Hängt eine Zeile aus den Zellen Punkt, Art, Notiz, Kümmerer und Bis an;
+    /// Zellen weggelassener Spalten entfallen.
+    fn zeile(&self, tabelle: &mut genpdf::elements::TableLayout, zellen: [Box<dyn genpdf::Element>; 5]) {
+        let zeile = self.0.iter().zip(zellen).filter(|(gewicht, _)| **gewicht > 0).map(|(_, zelle)| zelle).collect();
+        let _ = tabelle.push_row(zeile);
+    }
+}
+
+/// Höchstzahl geschätzter Textzeilen einer Notiz je PDF-Tabellenzeile (knapp eine
+/// Seite). Längere Notizen, etwa eingefügte Log-Auszüge, werden auf Fortsetzungszeilen
+/// verteilt (siehe `notiz_abschnitte`).
+const PDF_NOTIZ_ABSCHNITT_ZEILEN: usize = 45;
+
+/// Ergebnis eines asynchronen Datei-Dialogs (Laden, Speichern oder PDF-Export).
+enum DialogErgebnis {
+    /// Eine Markdown-Datei wurde ausgewählt und eingelesen.
+    Laden(std::path::PathBuf, String),
+    /// Ein Speicherpfad wurde gewählt (Datei wurde bereits geschrieben).
+    Speichern(std::path::PathBuf),
+    /// Die Datei konnte am gewählten Speicherpfad nicht geschrieben werden.
+    SpeichernFehlgeschlagen(String),
+    /// Ein PDF-Speicherpfad wurde gewählt.
+    PdfExport(std::path::PathBuf),
+    /// Ein Speicherpfad für den Tabellen-Export (XLSX) wurde gewählt.
+    XlsxExport(std::path::PathBuf),
+    /// Ein Speicherpfad für den Typst-Export (`.typ`) wurde gewählt.
+    TypstExport(std::path::PathBuf),
+    /// Eine E-Mail-Datei (EML oder Text) wurde gewählt und eingelesen.
+    EmailImport(String),
+    /// Das Protokoll wurde als Vorlage gespeichert.
+    VorlageGespeichert,
+    /// Ein Vorgängerprotokoll zur Verknüpfung wurde gewählt.
+    Vorgaenger(std::path::PathBuf),
+    /// Die ICS-Einladung für das nächste Meeting wurde geschrieben.
+    IcsExport,
+    /// Ein Speicherpfad für die Anwesenheitsliste (PDF) wurde gewählt.
+    Anwesenheitsliste(std::path::PathBuf),
+    /// Ein Speicherpfad für den Nachtrag (Korrektur-PDF) wurde gewählt.
+    Nachtrag(std::path::PathBuf),
+    /// Ein Speicherpfad für den Bericht wurde gewählt (`.pdf` = PDF, sonst Markdown).
+    Bericht(std::path::PathBuf),
+}
+
+/// Mitgelieferte Typst-Vorlage für Typst-Export und -Backend (`typst_erstellen`).
+const TYPST_VORLAGE: &str = include_str!("../assets/typst/protokoll.typ");
+
+/// Auswahlmöglichkeiten für den Duplexdruck (IPP-Schlüsselwort, Anzeigetext).
+const IPP_SEITEN: &[(&str, &str)] = &[
+    ("one-sided", "Einseitig"),
+    ("two-sided-long-edge", "Beidseitig (lange Kante)"),
+    ("two-sided-short-edge", "Beidseitig (kurze Kante)"),
+];
+
+/// Auswahlmöglichkeiten für das Papierfach (IPP-Schlüsselwort, Anzeigetext).
+const IPP_FAECHER: &[(&str, &str)] = &[
+    ("auto", "Automatisch"),
+    ("main", "Hauptfach"),
+    ("manual", "Manuelle Zufuhr"),
+    ("tray-1", "Fach 1"),
+    ("tray-2", "Fach 2"),
+];
+
+/// Zustand des Dialogs „An Gerät senden" (Direktdruck über IPP).
+struct DruckDialog {
+    /// Per mDNS gefundene Drucker als `(Name, IPP-URI)`.
+    gefundene_drucker: Vec<(String, String)>,
+    /// Empfangskanal des mDNS-Suchthreads (`None` = Suche beendet).
+    suche_rx: Option<mpsc::Receiver<(String, String)>>,
+    /// Ziel-URI, z. B. `ipp://drucker.local:631/ipp/print`.
+    uri: String,
+    /// Index in `IPP_SEITEN`.
+    seiten: usize,
+    /// Index in `IPP_FAECHER`.
+    fach: usize,
+    /// Anzahl der Exemplare.
+    kopien: u32,
+    /// Statusmeldung des letzten Druckauftrags.
+    status: Option<String>,
+    /// Empfangskanal des Sende-Threads.
+    auftrag_rx: Option<mpsc::Receiver<Result<(), String>>>,
+}
+
+/// Seiten der Offline-Hilfe als `(Titel, Markdown)`, eingebettet ins Binary.
+const HILFE_SEITEN: &[(&str, &str)] = &[
+    ("Erste Schritte", include_str!("../assets/hilfe/erste_schritte.md")),
+    ("Eintragsarten", include_str!("../assets/hilfe/eintragsarten.md")),
+    ("PDF-Export", include_str!("../assets/hilfe/pdf_export.md")),
+    ("Einstellungen", include_str!("../assets/hilfe/einstellungen.md")),
+    ("Tastenkombinationen", include_str!("../assets/hilfe/tastenkombinationen.md")),
+];
+
+/// Zustand des Hilfe-Fensters.
+struct HilfeFenster {
+    /// Index der angezeigten Seite in `HILFE_SEITEN`.
+    seite: usize,
+    /// Suchbegriff der Volltextsuche (leer = Seitenliste anzeigen).
+    suche: String,
+}
+
+/// Eine Protokolldatei im eigenen Öffnen-Dialog mit den Angaben aus dem Schnell-Parse.
+struct OeffnenEintrag {
+    pfad: std::path::PathBuf,
+    titel: String,
+    datum: Option<NaiveDate>,
+    projekt: String,
+    status: Option<Status>,
+    /// Anzahl der TODO-Einträge (offene Aufgaben).
+    offene_todos: usize,
+}
+
+/// Zustand des eigenen Öffnen-Dialogs.
+struct OeffnenDialog {
+    /// Durchsuchtes Verzeichnis (Archiv bzw. Ordner des aktuellen Protokolls).
+    verzeichnis: std::path::PathBuf,
+    /// Gefundene Protokolle, neueste zuerst.
+    dateien: Vec<OeffnenEintrag>,
+    /// Filter über Titel, Projekt und Dateiname.
+    suche: String,
+}
+
+/// Liest alle MZProtokoll-Dateien unter `verzeichnis` (samt Unterordnern) für den
+/// Öffnen-Dialog ein, sortiert nach Meetingdatum (neueste zuerst, ohne Datum zuletzt).
+fn oeffnen_eintraege_laden(verzeichnis: &std::path::Path, einstellungen: &Einstellungen) -> Vec<OeffnenEintrag> {
+    oeffnen_eintraege(markdown_dateien_rekursiv(verzeichnis), einstellungen)
+}
+
+/// Liest die Kopfangaben der MZProtokoll-Dateien unter `pfade` (Schnell-Parse),
+/// neueste zuerst; andere Markdown-Dateien werden übergangen.
+fn oeffnen_eintraege(pfade: Vec<std::path::PathBuf>, einstellungen: &Einstellungen) -> Vec<OeffnenEintrag> {
+    let mut dateien: Vec<OeffnenEintrag> = pfade
+        .into_iter()
+        .filter_map(|pfad| {
+            let content = std::fs::read_to_string(&pfad).ok()?;
+            if !ist_protokoll(&content) {
+                return None;
+            }
+            let auszug = protokoll_auszug_lesen(&content, einstellungen);
+            Some(OeffnenEintrag {
+                titel: auszug.titel,
+                datum: auszug.datum,
+                projekt: auszug.projekt,
+                status: auszug.status,
+                offene_todos: auszug.todos.len(),
+                pfad,
+            })
+        })
+        .collect();
+    dateien.sort_by(|a, b| b.datum.is_some().cmp(&a.datum.is_some()).then(b.datum.cmp(&a.datum)));
+    dateien
+}
+
+/// Protokolle mit dem heutigen Meetingdatum im Arbeitsverzeichnis (`arbeitsverzeichnis`,
+/// sonst das aktuelle Verzeichnis; nicht rekursiv) – Grundlage des Starthinweises
+/// „Heute bereits begonnen", der Doppelprotokolle vermeiden soll.
+fn heute_begonnene_protokolle(einstellungen: &Einstellungen) -> Vec<OeffnenEintrag> {
+    let Some(verzeichnis) = einstellungspfad(&einstellungen.arbeitsverzeichnis).or_else(|| std::env::current_dir().ok()) else {
+        return Vec::new();
+    };
+    let heute = Local::now().date_naive();
+    oeffnen_eintraege(markdown_dateien(&verzeichnis).unwrap_or_default(), einstellungen)
+        .into_iter()
+        .filter(|d| d.datum == Some(heute))
+        .collect()
+}
+
+/// Zeitraum des Management-Berichts.
+#[derive(Clone, Copy, PartialEq)]
+enum BerichtZeitraum {
+    /// Aktuelle Kalenderwoche (Montag bis Sonntag).
+    Woche,
+    /// Aktueller Kalendermonat.
+    Monat,
+    /// Frei eingegebener Zeitraum.
+    Frei,
+}
+
+/// Zustand des Dialogs „Bericht erstellen".
+struct BerichtDialog {
+    /// Im Archiv gefundene Projekte (für die Auswahl).
+    projekte: Vec<String>,
+    projekt: String,
+    zeitraum: BerichtZeitraum,
+    /// Beginn des Zeitraums (TT.MM.JJJJ).
+    von: String,
+    /// Ende des Zeitraums (TT.MM.JJJJ, einschließlich).
+    bis: String,
+}
+
+/// Eine Zeile im Bericht, z. B. eine Entscheidung oder ein TODO.
+struct BerichtPunkt {
+    text: String,
+    /// Ergänzende Angaben (Kümmerer und Bis-Datum bei TODOs).
+    zusatz: String,
+    /// Titel und Datum des Protokolls, aus dem der Punkt stammt.
+    protokoll: String,
+}
+
+/// Zusammenfassung aller Protokolle eines Projekts in einem Zeitraum.
+struct Bericht {
+    projekt: String,
+    von: NaiveDate,
+    bis: NaiveDate,
+    /// Meetings im Zeitraum als Protokollbezeichnung („Titel (TT.MM.JJJJ)").
+    meetings: Vec<String>,
+    entscheidungen: Vec<BerichtPunkt>,
+    /// TODOs, die im Zeitraum zum ersten Mal auftauchen.
+    neue_todos: Vec<BerichtPunkt>,
+    /// TODOs, die im Zeitraum als FERTIG markiert wurden.
+    erledigte_todos: Vec<BerichtPunkt>,
+    /// Offene TODOs laut dem letzten Protokoll im Zeitraum.
+    offene_todos: usize,
+}
+
+/// Sammelt die Protokolle eines Projekts unter `verzeichnis` und fasst den Zeitraum
+/// `von`–`bis` zusammen. Ob ein TODO neu bzw. erst jetzt erledigt ist, entscheidet
+/// der Vergleich der Notiz mit allen älteren Protokollen des Projekts.
+fn bericht_erstellen(
+    verzeichnis: &std::path::Path,
+    projekt: &str,
+    von: NaiveDate,
+    bis: NaiveDate,
+    einstellungen: &Einstellungen,
+) -> Bericht {
+    let mut protokolle: Vec<(NaiveDate, ProtokollAuszug)> = markdown_dateien_rekursiv(verzeichnis)
+        .into_iter()
+        .filter_map(|pfad| std::fs::read_to_string(pfad).ok())
+        .filter(|content| ist_protokoll(content))
+        .map(|content| protokoll_auszug_lesen(&content, einstellungen))
+        .filter(|a| a.projekt.trim().eq_ignore_ascii_case(projekt.trim()))
+        .filter_map(|a| Some((a.datum?, a)))
+        .collect();
+    protokolle.sort_by_key(|(datum, _)| *datum);
+
+    let mut bericht = Bericht {
+        projekt: projekt.trim().to_string(),
+        von,
+        bis,
+        meetings: Vec::new(),
+        entscheidungen: Vec::new(),
+        neue_todos: Vec::new(),
+        erledigte_todos: Vec::new(),
+        offene_todos: 0,
+    };
+    let mut bekannt: std::collections::HashSet<(String, String)> = std::collections::HashSet::new();
+    for (datum, auszug) in protokolle.iter().take_while(|(datum, _)| *datum <= bis) {
+        let im_zeitraum = *datum >= von;
+        let bezeichnung = format!("{} ({})", auszug.titel, datum.format("%d.%m.%Y"));
+        for e in &auszug.eintraege {
+            if !matches!(e.art, Art::Todo | Art::Fertig | Art::Entscheidung) {
+                continue;
+            }
+            let notiz = e.notiz.trim().replace('\n', " ");
+            let schluessel = if e.art == Art::Entscheidung && !e.beschluss_nr.is_empty() {
+                e.beschluss_nr.clone()
+            } else {
+                notiz.to_lowercase()
+            };
+            // Nur das erste Auftreten zählt; übernommene Punkte sind nicht neu
+            if schluessel.is_empty() || !bekannt.insert((e.art.label().to_string(), schluessel)) || !im_zeitraum {
+                continue;
+            }
+            let mut punkt = BerichtPunkt { text: notiz, zusatz: String::new(), protokoll: bezeichnung.clone() };
+            match e.art {
+                Art::Entscheidung => {
+                    if !e.punkt.trim().is_empty() {
+                        punkt.text = format!("{}: {}", e.punkt.trim(), punkt.text);
                     }
+                    if !e.beschluss_nr.is_empty() {
+                        punkt.text = format!("{} – {}", e.beschluss_nr, punkt.text);
+                    }
+                    bericht.entscheidungen.push(punkt);
+                }
+                Art::Todo => {
+                    punkt.zusatz = [e.kuemmerer.trim(), e.bis.trim()]
+                        .iter()
+                        .filter(|t| !t.is_empty())
+                        .copied()
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    bericht.neue_todos.push(punkt);
                 }
+                _ => bericht.erledigte_todos.push(punkt),
             }
-            result.push('[');
-            pos += 1;
+        }
+        if im_zeitraum {
+            bericht.meetings.push(bezeichnung);
+            bericht.offene_todos = auszug.todos.len();
+        }
+    }
+    bericht
+}
+
+impl Bericht {
+    /// Überschrift und Zeitraum des Berichts.
+    fn titel(&self) -> String {
+        let projekt = if self.projekt.is_empty() { "Ohne Projekt" } else { self.projekt.as_str() };
+        format!("Bericht {} – {} bis {}", projekt, self.von.format("%d.%m.%Y"), self.bis.format("%d.%m.%Y"))
+    }
+
+    /// Abschnitte des Berichts als (Überschrift, Punkte) in Ausgabereihenfolge.
+    fn abschnitte(&self) -> [(&'static str, &[BerichtPunkt]); 3] {
+        [
+            ("Entscheidungen", &self.entscheidungen),
+            ("Neue TODOs", &self.neue_todos),
+            ("Erledigte TODOs", &self.erledigte_todos),
+        ]
+    }
+
+    /// Kennzahlen für den Überblick.
+    fn ueberblick(&self) -> Vec<String> {
+        vec![
+            format!("Meetings: {}", self.meetings.len()),
+            format!("Entscheidungen: {}", self.entscheidungen.len()),
+            format!("Neue TODOs: {}", self.neue_todos.len()),
+            format!("Erledigte TODOs: {}", self.erledigte_todos.len()),
+            format!("Offene TODOs (Stand letztes Meeting): {}", self.offene_todos),
+        ]
+    }
+
+    /// Eine Zeile eines Punkts: Text, Zusatzangaben und Herkunft.
+    fn punkt_zeile(p: &BerichtPunkt) -> String {
+        if p.zusatz.is_empty() {
+            format!("{} ({})", p.text, p.protokoll)
         } else {
-            let ch = text[pos..].chars().next().unwrap();
-            result.push(ch);
-            pos += ch.len_utf8();
+            format!("{} – {} ({})", p.text, p.zusatz, p.protokoll)
         }
     }
 
-    (result, links)
+    /// Serialisiert den Bericht als Markdown.
+    fn markdown(&self) -> String {
+        let mut md = format!("# {}\n\n", self.titel());
+        md.push_str(&format!("**Erstellt:** {}\n\n## Überblick\n\n", Local::now().format("%d.%m.%Y")));
+        for zeile in self.ueberblick() {
+            md.push_str(&format!("- {}\n", zeile));
+        }
+        md.push_str("\n## Meetings\n\n");
+        if self.meetings.is_empty() {
+            md.push_str("- keine\n");
+        }
+        for m in &self.meetings {
+            md.push_str(&format!("- {}\n", m));
+        }
+        for (ueberschrift, punkte) in self.abschnitte() {
+            md.push_str(&format!("\n## {}\n\n", ueberschrift));
+            if punkte.is_empty() {
+                md.push_str("- keine\n");
+            }
+            for p in punkte {
+                md.push_str(&format!("- {}\n", Bericht::punkt_zeile(p)));
+            }
+        }
+        md
+    }
 }
 
-// -- PDF-Helfer --
+/// Belegt Von/Bis des Berichtsdialogs passend zur gewählten Woche bzw. zum Monat
+/// (jeweils der aktuelle); bei freiem Zeitraum bleiben die Eingaben unverändert.
+fn bericht_zeitraum_setzen(dialog: &mut BerichtDialog) {
+    let heute = Local::now().date_naive();
+    let (von, bis) = match dialog.zeitraum {
+        BerichtZeitraum::Woche => {
+            let montag = heute - chrono::Duration::days(i64::from(heute.weekday().num_days_from_monday()));
+            (montag, montag + chrono::Duration::days(6))
+        }
+        BerichtZeitraum::Monat => {
+            let erster = heute.with_day(1).unwrap_or(heute);
+            let naechster = if heute.month() == 12 {
+                NaiveDate::from_ymd_opt(heute.year() + 1, 1, 1)
+            } else {
+                NaiveDate::from_ymd_opt(heute.year(), heute.month() + 1, 1)
+            };
+            (erster, naechster.map(|n| n - chrono::Duration::days(1)).unwrap_or(heute))
+        }
+        BerichtZeitraum::Frei => return,
+    };
+    dialog.von = von.format("%d.%m.%Y").to_string();
+    dialog.bis = bis.format("%d.%m.%Y").to_string();
+}
 
-/// Seitendekorierer für den PDF-Export: fügt jeder Seite eine Fußzeile
-/// mit der aktuellen Seitenzahl und der Gesamtseitenanzahl hinzu.
-struct FusszeileDekorator {
-    /// Seitenränder für den Inhaltsbereich (oben, rechts, unten, links in mm).
-    raender: genpdf::Margins,
-    /// Laufende Seitennummer (wird beim Rendern pro Seite hochgezählt).
-    aktuelle_seite: usize,
-    /// Gesamtanzahl der Seiten (aus dem ersten Render-Durchlauf).
-    gesamtseiten: usize,
+/// Zustand des Dialogs „Profil wechseln".
+struct ProfilWechsel {
+    /// Angelegte Profile (ohne das Standardprofil).
+    profile: Vec<String>,
+    /// Eingabe für ein neues Profil.
+    neu: String,
 }
 
-impl FusszeileDekorator {
-    /// Erstellt einen neuen Fußzeile-Dekorierer mit der bekannten Gesamtseitenzahl.
-    fn new(gesamtseiten: usize) -> Self {
-        Self {
-            raender: genpdf::Margins::trbl(20, 15, 20, 15),
-            aktuelle_seite: 0,
-            gesamtseiten,
+/// Zustand des Dialogs „Zugangsdaten" (je Eintrag in `ZUGANGSDATEN_KONTEN`).
+struct ZugangsdatenDialog {
+    /// Neu eingegebene Geheimnisse (leer = unverändert).
+    eingaben: Vec<String>,
+    /// `true`, wenn im Schlüsselbund bereits ein Geheimnis hinterlegt ist
+    /// (`None` = wird noch geprüft).
+    hinterlegt: Vec<Option<bool>>,
+    /// Ergebnisse der Prüfung im Hintergrund als `(Index, hinterlegt)`.
+    pruefung_rx: Option<mpsc::Receiver<(usize, bool)>>,
+    /// Rückmeldung der letzten Aktion.
+    status: Option<String>,
+}
+
+/// Zustand des Abschluss-Assistenten „Protokoll versandfertig".
+struct VersandCheckliste {
+    /// Checklistenpunkte als `(Text, abgehakt)`.
+    punkte: Vec<(String, bool)>,
+    /// Ergebnisse der automatischen Prüfung (leer = keine Auffälligkeiten).
+    hinweise: Vec<String>,
+}
+
+/// Ein Schritt der Freigabe-Pipeline (`freigabe_pipeline`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PipelineSchritt {
+    /// Automatische Prüfung wie in „Versandfertig prüfen".
+    Pruefen,
+    /// Speichern, PDF neu erzeugen und alles ins Archiv verschieben.
+    Archiv,
+    /// Zusammenfassung in den Matrix-Raum posten.
+    Matrix,
+    /// E-Mail-Entwurf im Mailprogramm öffnen.
+    Email,
+    /// Freigabe als JSON an `freigabe_webhook` senden.
+    Webhook,
+    /// Markdown, PDF und XML schreibgeschützt setzen.
+    Schreibschutz,
+}
+
+impl PipelineSchritt {
+    /// Erkennt einen Schritt an seinem Namen in `freigabe_pipeline`.
+    fn parsen(text: &str) -> Option<PipelineSchritt> {
+        match text.trim().to_lowercase().as_str() {
+            "pruefen" | "prüfen" | "validierung" => Some(PipelineSchritt::Pruefen),
+            "archiv" | "archivieren" | "pdf" => Some(PipelineSchritt::Archiv),
+            "matrix" => Some(PipelineSchritt::Matrix),
+            "email" | "e-mail" | "mail" => Some(PipelineSchritt::Email),
+            "webhook" => Some(PipelineSchritt::Webhook),
+            "schreibschutz" => Some(PipelineSchritt::Schreibschutz),
+            _ => None,
+        }
+    }
+
+    /// Gibt den Anzeigetext des Schritts zurück.
+    fn label(&self) -> &'static str {
+        match self {
+            PipelineSchritt::Pruefen => "Validierung",
+            PipelineSchritt::Archiv => "PDF-Export ins Archiv",
+            PipelineSchritt::Matrix => "Zusammenfassung in Matrix",
+            PipelineSchritt::Email => "E-Mail-Versand",
+            PipelineSchritt::Webhook => "Webhook",
+            PipelineSchritt::Schreibschutz => "Schreibschutz",
         }
     }
 }
 
-impl genpdf::PageDecorator for FusszeileDekorator {
-    fn decorate_page<'a>(
-        &mut self,
-        context: &genpdf::Context,
-        area: genpdf::render::Area<'a>,
-        _style: genpdf::style::Style,
-    ) -> Result<genpdf::render::Area<'a>, genpdf::error::Error> {
-        self.aktuelle_seite += 1;
+/// Zustand der laufenden Freigabe-Pipeline. Der aktuelle Schritt ist der erste ohne
+/// Eintrag in `erledigt`; pro Frame startet höchstens ein Schritt.
+struct FreigabePipeline {
+    /// Alle Schritte in der eingestellten Reihenfolge.
+    schritte: Vec<PipelineSchritt>,
+    /// Meldung je abgeschlossenem Schritt (`Err` = nach Fehler übersprungen).
+    erledigt: Vec<Result<String, String>>,
+    /// Fehler des aktuellen Schritts; die Pipeline wartet auf „Überspringen" oder „Abbrechen".
+    fehler: Option<String>,
+    /// Empfangskanal für das Ergebnis des aktuellen Schritts (`None` = noch nicht gestartet).
+    laeuft: Option<mpsc::Receiver<Result<String, String>>>,
+}
 
-        let mut area = area;
+/// Ab dieser Ähnlichkeit (0–1) gilt ein übernommener Eintrag als mögliche Dublette.
+const DUPLIKAT_SCHWELLE: f32 = 0.8;
 
-        // Fußzeile auf der Rohseite platzieren, bevor die Seitenränder gesetzt werden
-        let rohseiten_groesse = area.size();
-        let rohseite_hoehe: f64 = rohseiten_groesse.height.into();
-        let rohseite_breite: f64 = rohseiten_groesse.width.into();
+/// Entscheidung für einen übernommenen Eintrag, der einem vorhandenen ähnelt.
+#[derive(Clone, Copy, PartialEq)]
+enum DuplikatAktion {
+    /// Notiz, Kümmerer und Bis-Datum in den vorhandenen Eintrag übernehmen.
+    Zusammenfuehren,
+    /// Den neuen Eintrag zusätzlich anlegen.
+    BeideBehalten,
+    /// Den neuen Eintrag nicht übernehmen.
+    Verwerfen,
+}
 
-        let fusszeilen_text = format!(
-            "Seite {} von {}",
-            self.aktuelle_seite, self.gesamtseiten
-        );
-        let fusszeilen_stil = genpdf::style::Style::new().with_font_size(9);
-        // Textbreite bei 9pt: ca. 2.0 mm pro Zeichen (Näherungswert)
-        let text_breite = fusszeilen_text.len() as f64 * 2.0;
-        // Text bündig mit dem rechten Inhaltsrand ausrichten
-        let rechter_rand = 8.0;
+/// Ein übernommener Eintrag mit dem ähnlichsten vorhandenen Eintrag.
+struct DuplikatPaar {
+    neu: Eintrag,
+    /// Nummer (`Eintrag::id`) des ähnlichen Eintrags; bleibt gültig, auch wenn die
+    /// Tabelle bei offenem Dialog bearbeitet oder umsortiert wird.
+    vorhanden: u32,
+    /// Ähnlichkeit von Punkt und Notiz (0–1).
+    aehnlichkeit: f32,
+    aktion: DuplikatAktion,
+}
 
-        let _ = area.print_str(
-            &context.font_cache,
-            genpdf::Position::new(rohseite_breite - rechter_rand - text_breite, rohseite_hoehe - 15.0),
-            fusszeilen_stil,
-            &fusszeilen_text,
-        );
+/// Textfarbe für Spaltenwarnungen: Orange bei Umbruch, Rot bei abgeschnittenem Text.
+fn spaltenwarnung_farbe(abgeschnitten: bool) -> egui::Color32 {
+    if abgeschnitten {
+        egui::Color32::from_rgb(231, 76, 60)
+    } else {
+        egui::Color32::from_rgb(230, 126, 34)
+    }
+}
 
-        // Seitenränder für den eigentlichen Inhaltsbereich anwenden
-        area.add_margins(self.raender);
+/// Indizes der TODO-Einträge, denen noch ein Kümmerer oder ein Bis-Datum fehlt.
+fn todos_ohne_zuweisung(eintraege: &[Eintrag]) -> Vec<usize> {
+    eintraege
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.art == Art::Todo && (e.kuemmerer.trim().is_empty() || e.bis.trim().is_empty()))
+        .map(|(i, _)| i)
+        .collect()
+}
 
-        Ok(area)
-    }
+/// Kürzel im Kümmerer-Feld, die zu keiner Person des Protokolls gehören. Mehrere
+/// Kümmerer dürfen mit `,`, `/`, `+` oder `&` getrennt sein; der Vergleich ignoriert
+/// Groß-/Kleinschreibung und akzeptiert auch den vollen Namen.
+fn unbekannte_kuerzel(kuemmerer: &str, personen: &[&Person]) -> Vec<String> {
+    kuemmerer
+        .split([',', '/', '+', '&'])
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .filter(|k| {
+            !personen
+                .iter()
+                .any(|p| p.kuerzel.trim().eq_ignore_ascii_case(k) || p.name.trim().eq_ignore_ascii_case(k))
+        })
+        .map(str::to_string)
+        .collect()
 }
 
-/// Wrapper-Element für genpdf: zeichnet zuerst einen farbigen Hintergrund (durch
-/// dichte horizontale Linien simuliert), danach wird der eigentliche Inhalt darüber gerendert.
-struct ZellenHintergrund<E: genpdf::Element> {
-    /// Das eingebettete genpdf-Element, das nach dem Hintergrund gerendert wird.
-    inhalt: E,
-    /// Hintergrundfarbe (Graustufe oder RGB).
-    farbe: genpdf::style::Color,
-    /// Zusätzlicher Überhang nach links in mm (aktuell immer 0).
-    erweiterung_links: f64,
-    /// Maximale Hintergrundhöhe in mm — verhindert, dass grauer Überlauf
-    /// auf die nächste weiße Zeile reicht.
-    max_hoehe: f64,
+/// `true`, wenn das Kürzel (oder der Name) unter den Kümmerern eines Eintrags steht.
+/// Trennzeichen und Vergleich wie bei `unbekannte_kuerzel`.
+fn kuerzel_zustaendig(kuemmerer: &str, kuerzel: &str, name: &str) -> bool {
+    kuemmerer.split([',', '/', '+', '&']).map(str::trim).filter(|k| !k.is_empty()).any(|k| {
+        (!kuerzel.trim().is_empty() && k.eq_ignore_ascii_case(kuerzel.trim()))
+            || (!name.trim().is_empty() && k.eq_ignore_ascii_case(name.trim()))
+    })
 }
 
-impl<E: genpdf::Element> ZellenHintergrund<E> {
-    /// Erstellt eine graue Hintergrundzeile (Graustufe 220).
-    fn grau(inhalt: E, max_hoehe: f64) -> Self {
-        Self {
-            inhalt,
-            farbe: genpdf::style::Color::Greyscale(220),
-            erweiterung_links: 0.0,
-            max_hoehe,
+/// Organisations-Präfix eines Kürzels wie `EXT-AB`, sofern er in `praefixe` steht.
+fn kuerzel_praefix_finden<'a>(kuerzel: &'a str, praefixe: &[(String, String)]) -> Option<&'a str> {
+    kuerzel
+        .trim()
+        .split_once('-')
+        .filter(|(praefix, rest)| !rest.is_empty() && praefixe.iter().any(|(p, _)| p.eq_ignore_ascii_case(praefix)))
+        .map(|(praefix, _)| praefix)
+}
+
+/// Zerlegt einen Kümmerer-Text (Trennzeichen wie bei `unbekannte_kuerzel`) für die
+/// Darstellung in Stücke; `true` markiert einen erkannten Präfix samt Bindestrich.
+fn kuerzel_segmente<'a>(text: &'a str, praefixe: &[(String, String)]) -> Vec<(&'a str, bool)> {
+    let trenner = [',', '/', '+', '&', ' '];
+    let mut segmente = Vec::new();
+    for teil in text.split_inclusive(trenner) {
+        match kuerzel_praefix_finden(teil.trim_end_matches(trenner), praefixe) {
+            Some(praefix) if teil.starts_with(praefix) => {
+                segmente.push((&teil[..praefix.len() + 1], true));
+                segmente.push((&teil[praefix.len() + 1..], false));
+            }
+            _ => segmente.push((teil, false)),
         }
     }
-    /// Erstellt eine weiße Hintergrundzeile (deckt grauen Überlauf der Vorgängerzeile ab).
-    fn weiss(inhalt: E, max_hoehe: f64) -> Self {
-        Self {
-            inhalt,
-            farbe: genpdf::style::Color::Greyscale(255),
-            erweiterung_links: 0.0,
-            max_hoehe,
-        }
+    segmente
+}
+
+/// Kürzel nach einem Schema wie `{vorname:1}{nachname:2}`: `{initialen}` sind die
+/// Anfangsbuchstaben aller Namensteile, `{vorname}`/`{nachname}` der erste bzw. letzte
+/// Namensteil, jeweils auf die angegebene Zeichenzahl gekürzt. Ergebnis in Großbuchstaben.
+fn kuerzel_nach_schema(name: &str, schema: &str) -> String {
+    let teile: Vec<&str> = name.split_whitespace().collect();
+    let mut kuerzel = String::new();
+    let mut rest = schema;
+    while let Some(start) = rest.find('{') {
+        kuerzel.push_str(&rest[..start]);
+        let Some(ende) = rest[start..].find('}') else {
+            kuerzel.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+        let platzhalter = &rest[start + 1..start + ende];
+        let (feld, laenge) = match platzhalter.split_once(':') {
+            Some((feld, laenge)) => (feld.trim(), laenge.trim().parse().unwrap_or(1)),
+            None => (platzhalter.trim(), usize::MAX),
+        };
+        let quelle = match feld {
+            "initialen" => Person::auto_kuerzel(name),
+            "vorname" => teile.first().map(|t| t.to_string()).unwrap_or_default(),
+            "nachname" if teile.len() > 1 => teile.last().map(|t| t.to_string()).unwrap_or_default(),
+            _ => String::new(),
+        };
+        kuerzel.extend(quelle.chars().take(laenge));
+        rest = &rest[start + ende + 1..];
     }
+    kuerzel.push_str(rest);
+    kuerzel.to_uppercase()
 }
 
-impl<E: genpdf::Element> genpdf::Element for ZellenHintergrund<E> {
-    fn render(
-        &mut self,
-        context: &genpdf::Context,
-        area: genpdf::render::Area<'_>,
-        stil: genpdf::style::Style,
-    ) -> Result<genpdf::RenderResult, genpdf::error::Error> {
-        let zellen_groesse = area.size();
-        let hintergrund_stil = genpdf::style::Style::new().with_color(self.farbe);
-        let breite: f64 = zellen_groesse.width.into();
-        let volle_hoehe: f64 = zellen_groesse.height.into();
-        // Hintergrund nur bis zur maximalen Höhe zeichnen
-        let hoehe: f64 = volle_hoehe.min(self.max_hoehe);
-        let x_start = -self.erweiterung_links;
-        let mut y = 0.0;
-        // Hintergrund durch sehr dichte horizontale Linien (0,15 mm Abstand) simulieren
-        while y <= hoehe - 0.5 {
-            area.draw_line(
-                vec![
-                    genpdf::Position::new(x_start, y),
-                    genpdf::Position::new(breite, y),
-                ],
-                hintergrund_stil,
-            );
-            y += 0.15;
+/// Editierabstand (Levenshtein) zweier Texte ohne Groß-/Kleinschreibung, für
+/// Korrekturvorschläge bei vertippten Kürzeln.
+fn editierabstand(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut zeile: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut links_oben = zeile[0];
+        zeile[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let oben = zeile[j + 1];
+            zeile[j + 1] = (links_oben + usize::from(ca != cb)).min(oben + 1).min(zeile[j] + 1);
+            links_oben = oben;
         }
-        // Inhalt über dem Hintergrund rendern
-        self.inhalt.render(context, area, stil)
     }
+    zeile[b.len()]
 }
 
-// -- UI-Helfer --
+/// Zustand des Dialogs „Kümmerer prüfen": je unbekanntem Kürzel der Name für
+/// „Person anlegen" und das Zielkürzel für „Kürzel korrigieren".
+struct KuerzelPruefung {
+    namen: HashMap<String, String>,
+    korrekturen: HashMap<String, String>,
+}
 
-/// Rendert eine einzelne Personenzeile (Name + Kürzel in eckigen Klammern + optionaler Lösch-Button).
-/// Gibt `(wurde_gelöscht, Enter_gedrückt)` zurück, damit der Aufrufer reagieren kann.
-fn personen_zeile(
-    ui: &mut egui::Ui,
-    person: &mut Person,
-    show_delete: bool,
-    request_focus: bool,
-    text_color: Option<egui::Color32>,
-) -> (bool, bool) {
-    let mut deleted = false;
-    let mut enter_pressed = false;
-    ui.horizontal(|ui| {
-        let available = ui.available_width();
-        let kuerzel_w = 45.0;
-        let bracket_space = 50.0; // [ ] und Spacing
-        let delete_space = 28.0; // immer Platz reservieren
-        let name_w = (available - kuerzel_w - bracket_space - delete_space).max(100.0);
+/// Zustand des Dialogs „In neues Protokoll überführen": je Element, ob es in das
+/// Folgeprotokoll übernommen wird.
+struct Ueberfuehrung {
+    titel: bool,
+    /// Teilnehmer und Verteiler „Zur Kenntnis".
+    personen: bool,
+    /// Tagesordnungspunkte aus der vorläufigen Agenda und den AGENDA-Einträgen.
+    tops: Vec<(Eintrag, bool)>,
+    /// Offene TODOs und unbeantwortete Fragen des Protokolls.
+    offene: Vec<(Eintrag, bool)>,
+}
 
-        let mut name_edit = egui::TextEdit::singleline(&mut person.name)
-            .hint_text(RichText::new("Name").font(egui::FontId::proportional(14.0)))
-            .desired_width(name_w)
-            .font(fette_schrift(14.0));
-        if let Some(c) = text_color {
-            name_edit = name_edit.text_color(c);
+/// Vorgang, der das aktuelle Protokoll ersetzt und bei ungespeicherten Änderungen
+/// erst nachfragt (`ProtokollApp::ersetzen_anfragen`).
+enum Ersetzen {
+    Neu,
+    Ueberfuehren(Ueberfuehrung),
+}
+
+/// Anzahl der Einträge, die die Eintrags-Zwischenablage höchstens behält.
+const EINTRAG_ABLAGE_GROESSE: usize = 10;
+
+/// Erste Zeile der Kopfdaten in der System-Zwischenablage; nur Text mit dieser
+/// Kennung übernimmt „Kopfdaten einfügen".
+const KOPFDATEN_KENNUNG: &str = "<!-- mzprotokoll:kopfdaten -->";
+
+/// Kopfdaten eines Protokolls für „Kopfdaten kopieren/einfügen" zwischen zwei
+/// geöffneten Protokollen. In der Zwischenablage stehen sie im Markdown-Format der
+/// Protokolldatei hinter `KOPFDATEN_KENNUNG`.
+struct Kopfdaten {
+    projekt: String,
+    titel: String,
+    ort: String,
+    teilnehmer: Vec<Person>,
+    zur_kenntnis: Vec<Person>,
+    sicherheit: Option<Sicherheit>,
+}
+
+impl Kopfdaten {
+    /// Text für die Zwischenablage.
+    fn als_text(&self) -> String {
+        let mut text = format!("{}\n", KOPFDATEN_KENNUNG);
+        for (name, wert) in [("Projekt", &self.projekt), ("Titel", &self.titel), ("Ort", &self.ort)] {
+            if !wert.is_empty() {
+                text.push_str(&format!("**{}:** {}\n", name, wert));
+            }
         }
-        let name_r = ui.add(name_edit);
-        if request_focus {
-            name_r.request_focus();
+        if let Some(ref sicherheit) = self.sicherheit {
+            text.push_str(&format!("**Klassifizierung:** {}\n", sicherheit.label()));
         }
-        if name_r.changed() {
-            if person.name == "mz" {
-                person.name = "Marcel Zimmer".to_string();
-                // Cursor ans Ende setzen
-                if let Some(mut state) = egui::TextEdit::load_state(ui.ctx(), name_r.id) {
-                    let end = egui::text::CCursor::new(person.name.len());
-                    state.cursor.set_char_range(Some(egui::text::CCursorRange::one(end)));
-                    state.store(ui.ctx(), name_r.id);
-                }
+        for (abschnitt, personen) in [("Teilnehmer", &self.teilnehmer), ("Zur Kenntnis", &self.zur_kenntnis)] {
+            if personen.is_empty() {
+                continue;
             }
-            if !person.kuerzel_manuell {
-                person.kuerzel = Person::auto_kuerzel(&person.name);
+            text.push_str(&format!("\n## {}\n\n", abschnitt));
+            for p in personen {
+                text.push_str(&format!("- {}", p.name));
+                if !p.kuerzel.is_empty() {
+                    text.push_str(&format!(" [{}]", p.kuerzel));
+                }
+                text.push('\n');
             }
         }
+        text
+    }
 
-        ui.label("[");
-        let mut k_edit = egui::TextEdit::singleline(&mut person.kuerzel)
-            .desired_width(kuerzel_w)
-            .hint_text(RichText::new("Kürzel").font(egui::FontId::proportional(14.0)))
-            .horizontal_align(egui::Align::Center)
-            .font(fette_schrift(14.0));
-        if let Some(c) = text_color {
-            k_edit = k_edit.text_color(c);
-        }
-        let k_r = ui.add(k_edit);
-        if k_r.changed() {
-            person.kuerzel_manuell = !person.kuerzel.is_empty();
+    /// Liest Kopfdaten aus der Zwischenablage (`None` ohne `KOPFDATEN_KENNUNG`).
+    fn parsen(text: &str) -> Option<Kopfdaten> {
+        let mut zeilen = text.lines().map(str::trim);
+        if zeilen.next()? != KOPFDATEN_KENNUNG {
+            return None;
         }
-        ui.label("]");
-
-        if show_delete {
-            if ui
-                .add(
-                    egui::Button::new(
-                        RichText::new("×").color(egui::Color32::from_rgb(231, 76, 60)),
-                    )
-                    .small(),
-                )
-                .clicked()
-            {
-                deleted = true;
+        let mut kopf = Kopfdaten {
+            projekt: String::new(),
+            titel: String::new(),
+            ort: String::new(),
+            teilnehmer: Vec::new(),
+            zur_kenntnis: Vec::new(),
+            sicherheit: None,
+        };
+        let mut abschnitt = "";
+        for zeile in zeilen {
+            if let Some(titel) = zeile.strip_prefix("## ") {
+                abschnitt = if titel.trim() == "Zur Kenntnis" { "zk" } else { "tn" };
+            } else if let Some(rest) = zeile.strip_prefix("- ") {
+                let (name, kuerzel) = name_kuerzel_parsen(rest);
+                let person = Person { kuerzel_manuell: !kuerzel.is_empty(), name, kuerzel };
+                match abschnitt {
+                    "zk" => kopf.zur_kenntnis.push(person),
+                    _ => kopf.teilnehmer.push(person),
+                }
+            } else if let Some((name, wert)) = fett_feld(zeile) {
+                match name {
+                    "Projekt" => kopf.projekt = wert.to_string(),
+                    "Titel" => kopf.titel = wert.to_string(),
+                    "Ort" => kopf.ort = wert.to_string(),
+                    "Klassifizierung" => kopf.sicherheit = Sicherheit::parsen(wert),
+                    _ => {}
+                }
             }
-        } else {
-            ui.allocate_space(egui::vec2(20.0, 0.0));
         }
-
-        enter_pressed = (name_r.lost_focus() || k_r.lost_focus())
-            && ui.input(|i| i.key_pressed(egui::Key::Enter));
-    });
-    (deleted, enter_pressed)
+        Some(kopf)
+    }
 }
 
-/// Rendert eine linksbündige, fette Abschnittsüberschrift mit fixer Mindestbreite.
-/// Optionale `farbe` überschreibt die Theme-Standardfarbe (für Omarchy-Theme).
-fn abschnitts_beschriftung(ui: &mut egui::Ui, text: &str, label_w: f32, color: Option<egui::Color32>) {
-    ui.allocate_ui_with_layout(
-        egui::vec2(label_w, ui.spacing().interact_size.y),
-        egui::Layout::left_to_right(egui::Align::Center),
-        |ui| {
-            ui.set_min_width(label_w);
-            let mut rt = RichText::new(text).font(fette_schrift(14.0));
-            if let Some(c) = color { rt = rt.color(c); }
-            ui.label(rt);
-        },
-    );
+/// Pausen ohne Eingabe, die länger dauern, zählen nicht zur Bearbeitungszeit.
+const LEERLAUF_GRENZE: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// Formatiert eine Dauer als „H:MM h".
+fn dauer_formatieren(dauer: std::time::Duration) -> String {
+    let minuten = dauer.as_secs() / 60;
+    format!("{}:{:02} h", minuten / 60, minuten % 60)
 }
 
-/// Wie `abschnitts_beschriftung`, zeigt aber zusätzlich einen kleinen „+"-Button an.
-/// Gibt `true` zurück, wenn der Button geklickt wurde (zum Hinzufügen einer weiteren Zeile).
-fn abschnitts_beschriftung_mit_plus(ui: &mut egui::Ui, text: &str, label_w: f32, color: Option<egui::Color32>) -> bool {
-    let mut clicked = false;
-    ui.allocate_ui_with_layout(
-        egui::vec2(label_w, ui.spacing().interact_size.y),
-        egui::Layout::left_to_right(egui::Align::Center),
-        |ui| {
-            ui.set_min_width(label_w);
-            let mut rt = RichText::new(text).font(fette_schrift(14.0));
-            if let Some(c) = color { rt = rt.color(c); }
-            ui.label(rt);
-            if ui.small_button("+").clicked() {
-                clicked = true;
-            }
-        },
-    );
-    clicked
+/// Liest eine Dauer im Format „H:MM" (optional mit „h") ein.
+fn dauer_parsen(text: &str) -> Option<std::time::Duration> {
+    let (stunden, minuten) = text.trim().trim_end_matches('h').trim().split_once(':')?;
+    let minuten = stunden.trim().parse::<u64>().ok()? * 60 + minuten.trim().parse::<u64>().ok()?;
+    Some(std::time::Duration::from_secs(minuten * 60))
 }
 
-// -- UI --
+/// Zustand des Dialogs zum Ausfüllen interaktiver Vorlagen-Platzhalter.
+struct PlatzhalterDialog {
+    /// Vorlageninhalt, in dem die automatischen Platzhalter bereits ersetzt sind.
+    inhalt: String,
+    /// Noch offene Platzhalter als `(Name, eingegebener Wert)`.
+    werte: Vec<(String, String)>,
+}
 
-impl eframe::App for ProtokollApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Event-Loop periodisch wecken für Wayland-Pings
-        // (vsync: false in NativeOptions verhindert das Blockieren von eglSwapBuffers)
-        ctx.request_repaint_after(std::time::Duration::from_secs(1));
+/// Meetingtypen des Erste-Schritte-Assistenten als `(Titel, Text für „Über dieses Meeting")`.
+const MEETINGTYPEN: &[(&str, &str)] = &[
+    ("Jour fixe", "Regelmäßige Abstimmung zum aktuellen Stand."),
+    ("Projektbesprechung", "Abstimmung zu Fortschritt, Risiken und nächsten Schritten im Projekt."),
+    ("Workshop", "Gemeinsame Erarbeitung von Ergebnissen zu einem Schwerpunktthema."),
+    ("Review", "Vorstellung und Abnahme der erreichten Ergebnisse."),
+    ("Retrospektive", "Rückblick auf die Zusammenarbeit und Vereinbarung von Verbesserungen."),
+    ("Sonstiges", ""),
+];
+
+/// Schritte des Erste-Schritte-Assistenten in Anzeigereihenfolge.
+const ASSISTENT_SCHRITTE: &[&str] = &["Projekt", "Meetingtyp", "Teilnehmer", "Agenda"];
+
+/// Zustand des Erste-Schritte-Assistenten, der ein neues Protokoll Schritt für Schritt anlegt.
+struct ErsteSchritteAssistent {
+    /// Index des aktuellen Schritts in `ASSISTENT_SCHRITTE`.
+    schritt: usize,
+    projekt: String,
+    titel: String,
+    ort: String,
+    /// Index des gewählten Typs in `MEETINGTYPEN`.
+    meetingtyp: usize,
+    /// Teilnehmernamen, einer pro Zeile.
+    teilnehmer: String,
+    /// Agendapunkte, einer pro Zeile.
+    agenda: String,
+}
 
-        // Tastenkombinationen
-        if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::N)) {
-            let theme = self.theme;
-            let has_omarchy = self.has_omarchy;
-            let icon_texture = self.icon_texture.take();
-            *self = ProtokollApp::new(ctx);
-            self.theme = theme;
-            self.has_omarchy = has_omarchy;
-            self.icon_texture = icon_texture;
-        }
-        if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::O)) {
-            self.laden();
-        }
-        if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::S)) {
-            self.speichern();
-        }
-        if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::P)) {
-            self.pdf_exportieren();
-        }
-        if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::W)) {
-            self.show_quit_dialog = true;
-        }
-        if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::T)) {
-            self.theme = self.theme.next(self.has_omarchy);
-        }
-        if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::H)) {
-            url_oeffnen("https://www.marcelzimmer.de");
-        }
-        if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::I)) {
-            self.show_about_dialog = true;
+impl ErsteSchritteAssistent {
+    fn new() -> Self {
+        Self {
+            schritt: 0,
+            projekt: String::new(),
+            titel: String::new(),
+            ort: String::new(),
+            meetingtyp: 0,
+            teilnehmer: String::new(),
+            agenda: String::new(),
         }
+    }
+}
 
-        // Ergebnisse von Datei-Dialogen verarbeiten
-        if let Some(ref rx) = self.dialog_rx {
-            if let Ok(result) = rx.try_recv() {
-                match result {
-                    DialogErgebnis::Laden(path, content) => {
-                        self.markdown_parsen(&content);
-                        self.sort_personen();
-                        self.save_path = Some(path);
-                    }
-                    DialogErgebnis::Speichern(path) => {
-                        self.save_path = Some(path);
-                    }
-                    DialogErgebnis::PdfExport(path) => {
-                        if let Some(font) = self.pending_pdf_font.take() {
-                            self.pdf_generieren(&path, font);
-                        }
-                    }
-                }
-                self.dialog_rx = None;
-            }
-        }
+/// Sicherung des vorherigen Protokolls, bevor ein Laden den App-Zustand überschreibt.
+struct Sicherung {
+    /// Markdown-Inhalt des überschriebenen Protokolls.
+    inhalt: String,
+    /// Speicherpfad des überschriebenen Protokolls (falls bereits gespeichert).
+    pfad: Option<std::path::PathBuf>,
+}
 
-        ctx.input_mut(|i| i.smooth_scroll_delta.y *= 10.0);
+/// Zentraler Anwendungszustand von MZProtokoll.
+/// Enthält alle Daten des aktuell geöffneten Protokolls sowie UI-Steuerflags.
+struct ProtokollApp {
+    // --- Protokoll-Kopfdaten ---
+    /// Optionaler Projektname (erscheint klein über dem Titel).
+    projekt: String,
+    /// Titel / Name des Meetings (Hauptüberschrift).
+    titel: String,
+    /// Datum als freier Text, z. B. „Montag, 05.02.2026".
+    datum_text: String,
+    /// Veranstaltungsort des Meetings.
+    ort: String,
+    /// Person, die das Protokoll führt (Pflichtfeld).
+    protokollant: Person,
+    /// Liste aller Meetingteilnehmer.
+    teilnehmer: Vec<Person>,
+    /// Personen, die das Protokoll zur Kenntnis erhalten.
+    zur_kenntnis: Vec<Person>,
+    /// Freitext-Beschreibung des Meetings.
+    ueber_meeting: String,
+    /// Bearbeitungsstatus (Entwurf, In Prüfung, Freigegeben …).
+    status: Status,
+    /// Geheimhaltungsstufe des Protokolls.
+    sicherheit: Sicherheit,
+    /// Alle Tabelleneinträge des Protokolls.
+    eintraege: Vec<Eintrag>,
+    /// Pfad des verknüpften Vorgängerprotokolls (leer = keine Verknüpfung).
+    vorgaenger: String,
+    /// Eigene Spaltengewichte der PDF-Tabelle, etwa aus der Vorlage (`None` = `pdf_spalten`
+    /// der Einstellungen).
+    pdf_spalten: Option<PdfSpalten>,
+    /// Termin des Folgemeetings als Text, z. B. „12.02.2026 10:00".
+    naechstes_datum: String,
+    /// Ort des Folgemeetings.
+    naechstes_ort: String,
+    /// Vorläufige Agenda des Folgemeetings (ein Punkt pro Zeile).
+    naechste_agenda: String,
+    /// Anhang „Nur intern", der im PDF für externe Empfänger entfällt.
+    interner_anhang: String,
+    /// Nachvollziehbare Änderungen am Protokoll (z. B. Herabstufungen).
+    audit_log: Vec<AuditEintrag>,
 
-        self.input_text_color = None;
-        self.label_color = None;
-        match self.theme {
-            Theme::Hell => ctx.set_visuals(egui::Visuals::light()),
-            Theme::Dunkel => {
-                let mut visuals = egui::Visuals::dark();
-                visuals.panel_fill = egui::Color32::BLACK;
-                visuals.window_fill = egui::Color32::BLACK;
-                visuals.extreme_bg_color = egui::Color32::BLACK;
-                let white_stroke = egui::Stroke::new(1.0, egui::Color32::WHITE);
-                visuals.widgets.noninteractive.fg_stroke = white_stroke;
-                visuals.widgets.inactive.fg_stroke = white_stroke;
-                visuals.widgets.hovered.fg_stroke = white_stroke;
-                visuals.widgets.active.fg_stroke = white_stroke;
-                ctx.set_visuals(visuals);
-            }
-            Theme::Omarchy => {
-                let mut visuals = egui::Visuals::dark();
-                if let Some(colors) = omarchy_farben_laden() {
-                    // Hintergrund voll deckend (wie Terminal)
-                    if let Some(bg) = colors.get("background") {
-                        visuals.panel_fill = *bg;
-                        visuals.window_fill = *bg;
-                        visuals.extreme_bg_color = *bg;
-                    }
-                    // Hints → cursor (über noninteractive, wird automatisch abgedunkelt)
-                    if let Some(cursor) = colors.get("cursor") {
-                        visuals.widgets.noninteractive.fg_stroke = egui::Stroke::new(1.0, *cursor);
-                    }
-                    // Buttons → accent
-                    if let Some(accent) = colors.get("accent") {
-                        let stroke = egui::Stroke::new(1.0, *accent);
-                        visuals.widgets.inactive.fg_stroke = stroke;
-                        visuals.widgets.hovered.fg_stroke = stroke;
+    // --- UI-Steuerflags ---
+    /// Fordert den Fokus für die zuletzt hinzugefügte Teilnehmerzeile an.
+    focus_new_teilnehmer: bool,
+    /// Fordert den Fokus für die zuletzt hinzugefügte Zur-Kenntnis-Zeile an.
+    focus_new_zur_kenntnis: bool,
+    /// Aktives Farbschema der UI.
+    theme: Theme,
+    /// Beim Start geladene Benutzereinstellungen.
+    einstellungen: Einstellungen,
+    /// Pfad der aktuell geöffneten/gespeicherten Datei (leer = noch nicht gespeichert).
+    save_path: Option<std::path::PathBuf>,
+    /// Zwischenentwurf im Entwurfsverzeichnis, der ohne Pflichtfeldprüfung geschrieben
+    /// wurde; wird nach dem regulären Speichern entfernt.
+    entwurf_pfad: Option<std::path::PathBuf>,
+    /// Steuert die Anzeige des Beenden-Bestätigungsdialogs.
+    show_quit_dialog: bool,
+    /// Markdown-Stand beim letzten Speichern bzw. Laden ohne Bearbeitungszeit
+    /// (`None` = noch nie gespeichert), Grundlage für `ungespeichert`.
+    gespeicherter_stand: Option<String>,
+    /// „Speichern und beenden" bzw. „PDF exportieren und beenden" wartet auf den
+    /// Dateidialog; nach erfolgreichem Schreiben wird die App geschlossen.
+    beenden_nach_dialog: bool,
+    /// Beenden wurde bestätigt, die Schließen-Anforderung des Fensters wird nicht
+    /// mehr abgefangen.
+    beenden_bestaetigt: bool,
+    /// Änderungszeit der Datei beim letzten Speichern, Laden bzw. Abgleich.
+    datei_zeit: Option<std::time::SystemTime>,
+    /// Zeitpunkt der letzten Prüfung auf externe Änderungen.
+    datei_geprueft: std::time::Instant,
+    /// Autospeicherung: zuletzt beobachteter ungespeicherter Stand und seit wann er
+    /// unverändert ist (`None` = nichts zu speichern).
+    auto_speichern_stand: Option<(String, std::time::Instant)>,
+    /// Zeitpunkt der letzten Prüfung der Autospeicherung.
+    auto_speichern_geprueft: std::time::Instant,
+    /// Dialog „Datei wurde extern geändert" (`None` = geschlossen).
+    externe_aenderung: Option<ExterneAenderung>,
+    /// Steuert die Anzeige des Über-Dialogs.
+    show_about_dialog: bool,
+    /// Gecachte App-Icon-Textur für den Über-Dialog.
+    icon_texture: Option<egui::TextureHandle>,
+    /// Steuert die Anzeige des PDF-Fehler-Dialogs (keine Schrift gefunden).
+    show_pdf_error: bool,
+    /// Steuert die Anzeige des PDF-Exportdialogs (Profilauswahl).
+    show_pdf_export_dialog: bool,
+    /// Gewähltes Schriftgrößen- und Dichte-Profil für den PDF-Export.
+    pdf_profil: PdfProfil,
+    /// Gewählte Sprache der PDF-Beschriftungen (Exportdialog, gilt für alle Backends).
+    pdf_sprache: PdfSprache,
+    /// `true` = Links auf der Seite „Quellen & Referenzen" am Ende statt unter der Tabelle.
+    pdf_quellenseite: bool,
+    /// Schriftmetriken der PDF-Schrift für die Spaltenwarnungen (beim ersten Bedarf
+    /// geladen; innen `None`, wenn keine Schrift gefunden wurde).
+    pdf_schriftmetrik: std::cell::OnceCell<Option<genpdf::fonts::FontCache>>,
+    /// Steuert die Anzeige des Pflichtfeld-Hinweisdialogs.
+    show_pflichtfeld_hinweis: bool,
+    /// Verfügbare Vorlagen, solange der Vorlagen-Auswahldialog offen ist.
+    vorlagen_auswahl: Option<Vec<std::path::PathBuf>>,
+    /// Mögliche Dubletten beim Übernehmen von Einträgen (`None` = Dialog geschlossen).
+    duplikat_dialog: Option<Vec<DuplikatPaar>>,
+    /// Dialog für interaktiv aufzulösende Platzhalter (`None` = geschlossen).
+    platzhalter_dialog: Option<PlatzhalterDialog>,
+    /// Abschluss-Assistent vor dem Versand (`None` = geschlossen).
+    versand_checkliste: Option<VersandCheckliste>,
+    /// Benutzerprofil der Person, die die App bedient.
+    profil: Benutzerprofil,
+    /// Bearbeitungskopie des Profils, solange der Profildialog offen ist.
+    profil_dialog: Option<Benutzerprofil>,
+    /// Name des aktiven Profils (leer = Standardprofil), siehe `konfig_verzeichnis`.
+    konfig_profil: String,
+    /// Dialog „Profil wechseln" (`None` = geschlossen).
+    profil_wechsel: Option<ProfilWechsel>,
+    /// Eigener Öffnen-Dialog mit Protokollvorschau (`None` = geschlossen).
+    oeffnen_dialog: Option<OeffnenDialog>,
+    /// Dialog „Bericht erstellen" (`None` = geschlossen).
+    bericht_dialog: Option<BerichtDialog>,
+    /// Beim Start gefundene Protokolle von heute (leer = kein Hinweis).
+    heute_begonnen: Vec<OeffnenEintrag>,
+    /// Empfangskanal für Aufträge weiterer Programmstarts (`None` = keine Einzelinstanz).
+    instanz_rx: Option<mpsc::Receiver<InstanzAuftrag>>,
+    /// Erstellter Bericht, bis der Speicherdialog einen Pfad liefert.
+    pending_bericht: Option<Bericht>,
+    /// Aktive Bearbeitungszeit dieses Protokolls (ohne Leerlauf, siehe `LEERLAUF_GRENZE`).
+    bearbeitungszeit: std::time::Duration,
+    /// `**Bearbeitungszeit:**` wie aus der Datei gelesen; bleibt beim Speichern
+    /// erhalten, wenn `zeiterfassung_speichern` aus ist.
+    bearbeitungszeit_datei: Option<std::time::Duration>,
+    /// Nächste freie Eintragsnummer (`**Nächste Eintragsnummer:**`); steigt nur, damit
+    /// die Nummer eines entfernten Eintrags nie erneut vergeben wird.
+    naechste_eintrag_id: u32,
+    /// Zeitpunkt der letzten Benutzereingabe (für die Leerlauferkennung).
+    letzte_aktivitaet: Option<std::time::Instant>,
+    /// Steuert die Anzeige des Statistik-Panels.
+    show_statistik: bool,
+    /// Dialog „Kümmerer prüfen" (`None` = geschlossen).
+    kuerzel_pruefung: Option<KuerzelPruefung>,
+    /// Auswahl im Dialog „In neues Protokoll überführen" (`None` = geschlossen).
+    ueberfuehrung: Option<Ueberfuehrung>,
+    /// Rückfrage „Ungespeicherte Änderungen" vor Neu bzw. Überführen (`None` = geschlossen).
+    ersetzen_rueckfrage: Option<Ersetzen>,
+    /// Vorgang, der nach dem Speichern-Dialog ausgeführt wird (`None` = keiner).
+    ersetzen_nach_speichern: Option<Ersetzen>,
+    /// Vorschlag im Dialog „Aus E-Mail importieren" (`None` = geschlossen).
+    email_import: Option<EmailImport>,
+    /// `true` = Touch-Modus: größere Bedienelemente und Einträge als einspaltige Karten.
+    touch_modus: bool,
+    /// Kopffeld, das im nächsten Durchlauf den Fokus erhält (Erst-Fokus bzw. Tab-Kette).
+    kopf_fokus: Option<KopfFeld>,
+    /// Vor dem letzten Laden gesicherter Zustand; solange gesetzt, wird „Rückgängig" angeboten.
+    sicherung: Option<Sicherung>,
+    /// Steuert die Anzeige des Beschlussregisters.
+    show_beschlussregister: bool,
+    /// Suchbegriff im Beschlussregister.
+    beschluss_suche: String,
+    /// Beim Öffnen des Registers eingelesene Beschlüsse.
+    beschlussregister: Vec<BeschlussEintrag>,
+    /// Schnellfilter „Meine Aufgaben“: zeigt nur TODOs mit dem eigenen Kürzel.
+    nur_meine_aufgaben: bool,
+    /// Filter „fällig zwischen" in der Filterleiste und in „Meine Aufgaben im Archiv“.
+    faellig_filter: FaelligFilter,
+    /// Meine Aufgaben aus dem Archiv (`None` = Fenster geschlossen).
+    archiv_aufgaben: Option<Vec<ArchivAufgabe>>,
+    /// Dialog für den Direktdruck über IPP (`None` = geschlossen).
+    druck_dialog: Option<DruckDialog>,
+    /// Zuletzt protokollierter Stand je Eintragsnummer (siehe `eintraege_protokollieren`).
+    eintrag_stand: HashMap<u32, Vec<(&'static str, String)>>,
+    /// Beschriftungen der Arten im aktuellen Projekt (Reihenfolge `Art::all()`).
+    art_labels: Vec<String>,
+    /// `true`, wenn zuletzt mit einem Mausrad (statt Touchpad) gescrollt wurde.
+    scroll_mausrad: bool,
+    /// Aus dem aktuellen Theme abgeleitete Farben der Eintragstabelle.
+    tabellen_farben: TabellenFarben,
+    /// Präsentationsmodus: Position in der Liste der belegten Einträge (`None` = aus).
+    praesentation: Option<usize>,
+    /// Kurzmeldung unten rechts mit Anzeigebeginn (z. B. nach „Schnell exportieren").
+    toast: Option<(String, std::time::Instant)>,
+    /// Erste-Schritte-Assistent (`None` = geschlossen).
+    assistent: Option<ErsteSchritteAssistent>,
+    /// Offline-Hilfe (`None` = geschlossen).
+    hilfe: Option<HilfeFenster>,
+    /// Verwaltung der Zugangsdaten im Schlüsselbund (`None` = geschlossen).
+    zugangsdaten_dialog: Option<ZugangsdatenDialog>,
+    /// Angefragte niedrigere Klassifizierung, die noch bestätigt werden muss.
+    /// Solange `Some`, wird der Herabstufungs-Dialog angezeigt.
+    herabstufung: Option<Sicherheit>,
+    /// Begründung, die im Herabstufungs-Dialog eingegeben wird.
+    herabstufung_begruendung: String,
+    /// Index des Notizfeldes, das beim nächsten Frame den Fokus erhalten soll.
+    focus_notiz: Option<usize>,
+    /// Mit Strg+X ausgeschnittene Einträge (neuester zuletzt, höchstens
+    /// `EINTRAG_ABLAGE_GROESSE`); bleibt beim Öffnen anderer Protokolle erhalten.
+    eintrag_ablage: Vec<Eintrag>,
+    /// Zeitpunkt von „Kopfdaten einfügen": Bis die angeforderte Zwischenablage als
+    /// Einfüge-Ereignis eintrifft, werden Kopfdaten darin übernommen.
+    kopfdaten_anfrage: Option<std::time::Instant>,
+    /// Speichert, welche Notizzeile zuletzt fokussiert war (Index, Cursor-Position).
+    /// Wird für die Cursor-Auf/Ab-Navigation zwischen Notizfeldern benötigt.
+    notiz_had_focus: Option<(usize, usize)>,
+    /// Textfarbe für Eingabefelder im Omarchy-Theme (aus `color2`).
+    input_text_color: Option<egui::Color32>,
+    /// Farbe für Beschriftungen/Labels im Omarchy-Theme (aus `color3`).
+    label_color: Option<egui::Color32>,
+    /// `true` wenn eine Omarchy-Theme-Konfiguration gefunden wurde.
+    has_omarchy: bool,
+    /// Empfangskanal für Ergebnisse aus Datei-Dialog-Threads.
+    dialog_rx: Option<mpsc::Receiver<DialogErgebnis>>,
+    /// Empfangskanal für das Ergebnis der laufenden Aufgaben-Synchronisation.
+    aufgaben_rx: Option<mpsc::Receiver<Result<AufgabenErgebnis, String>>>,
+    /// Nachfrage nach der Freigabe: Zusammenfassung in den Matrix-Raum posten?
+    matrix_nachfrage: bool,
+    /// Empfangskanal für das Ergebnis des Matrix-Versands.
+    matrix_rx: Option<mpsc::Receiver<Result<(), String>>>,
+    /// Laufende Freigabe-Pipeline (`None` = keine).
+    freigabe_pipeline: Option<FreigabePipeline>,
+    /// Zuletzt abgefragter Status der in Notizen erwähnten Redmine-Tickets.
+    redmine_status: HashMap<u32, RedmineStatus>,
+    /// Empfangskanal für die laufende Redmine-Anfrage.
+    redmine_rx: Option<mpsc::Receiver<RedmineErgebnis>>,
+    /// Vorschlag für „Über dieses Meeting" zur Prüfung (`None` = kein Dialog).
+    zusammenfassung_vorschlag: Option<String>,
+    /// Empfangskanal für die Antwort des Sprachmodells.
+    zusammenfassung_rx: Option<mpsc::Receiver<Result<String, String>>>,
+    /// Änderungen seit dem letzten Export (beim Öffnen des Exportdialogs ermittelt).
+    pdf_aenderungen: Option<VersandDiff>,
+    /// `true` = Kapitel „Änderungen seit Version n" ins PDF aufnehmen.
+    pdf_aenderungen_aufnehmen: bool,
+    /// Empfängerkreis der PDF-Exporte (Exportdialog und „An Gerät senden").
+    pdf_zielgruppe: Zielgruppe,
+    /// Im Exportdialog gewähltes Exportprofil (Index in `export_profile`).
+    pdf_exportprofil: Option<usize>,
+    /// Passwort für Exportprofile mit Passwortschutz; wird nach dem Export geleert.
+    pdf_passwort: String,
+    /// Lädt das unter „Zugangsdaten" hinterlegte Exportpasswort im Hintergrund.
+    pdf_passwort_rx: Option<mpsc::Receiver<Option<String>>>,
+    /// Kanal für im Hintergrund erzeugte PDFs (`pdf_im_hintergrund`, `pdf_fertig`).
+    pdf_tx: mpsc::Sender<PdfFertig>,
+    pdf_rx: mpsc::Receiver<PdfFertig>,
+    /// Anzahl der noch laufenden PDF-Threads.
+    pdf_laufend: usize,
+    /// Exportprofil des laufenden PDF-Exports (bis der Speichern-Dialog zurückkehrt).
+    pending_exportprofil: Option<ExportProfil>,
+    /// Exportprofil, das nur während `pdf_generieren` gilt; außerhalb `None`, damit
+    /// Vorschau und andere Exporte unverändert bleiben.
+    exportprofil_aktiv: Option<ExportProfil>,
+    /// Zwischengespeicherte Schriftfamilie für den PDF-Export (wird nach dem
+    /// Dialog-Thread übergeben und dann verbraucht).
+    pending_pdf_font: Option<genpdf::fonts::FontFamily<genpdf::fonts::FontData>>,
+
+    /// Datum der Protokollerstellung (TT.MM.JJJJ), wenn die Nachbereitung nicht am
+    /// Meetingtag erfolgt. Fälligkeiten beziehen sich weiter auf `datum_text`.
+    protokoll_datum: String,
+
+    // --- Metadaten zur Nachverfolgbarkeit ---
+    /// Zeitstempel der Ersterstellung (TT.MM.JJJJ HH:MM), leer wenn noch nicht gespeichert.
+    erstellt_am: String,
+    /// Name der Person, die das Protokoll erstellt hat.
+    erstellt_von: String,
+}
+
+impl ProtokollApp {
+    /// Initialisiert die App: lädt Systemschriften, ermittelt das aktuelle Datum
+    /// und setzt alle Felder auf Standardwerte.
+    fn new(ctx: &egui::Context) -> Self {
+        // Systemschriften laden: egui benötigt Regular und Bold als separate Font-Families.
+        // Liest Schriften zur Laufzeit vom System – keine Schriften werden eingebettet.
+        {
+            #[cfg(windows)]
+            let schrift_paare = [
+                ("C:\\Windows\\Fonts\\arial.ttf",    "C:\\Windows\\Fonts\\arialbd.ttf"),
+                ("C:\\Windows\\Fonts\\segoeui.ttf",  "C:\\Windows\\Fonts\\segoeuib.ttf"),
+                ("C:\\Windows\\Fonts\\calibri.ttf",  "C:\\Windows\\Fonts\\calibrib.ttf"),
+                ("C:\\Windows\\Fonts\\tahoma.ttf",   "C:\\Windows\\Fonts\\tahomabd.ttf"),
+            ];
+            #[cfg(not(windows))]
+            let schrift_paare = [
+                // Arch, Fedora, openSUSE
+                ("/usr/share/fonts/liberation/LiberationSans-Regular.ttf", "/usr/share/fonts/liberation/LiberationSans-Bold.ttf"),
+                ("/usr/share/fonts/TTF/LiberationSans-Regular.ttf",        "/usr/share/fonts/TTF/LiberationSans-Bold.ttf"),
+                ("/usr/share/fonts/noto/NotoSans-Regular.ttf",             "/usr/share/fonts/noto/NotoSans-Bold.ttf"),
+                ("/usr/share/fonts/TTF/NotoSans-Regular.ttf",              "/usr/share/fonts/TTF/NotoSans-Bold.ttf"),
+                // Debian, Ubuntu, Mint
+                ("/usr/share/fonts/truetype/liberation/LiberationSans-Regular.ttf", "/usr/share/fonts/truetype/liberation/LiberationSans-Bold.ttf"),
+                ("/usr/share/fonts/truetype/noto/NotoSans-Regular.ttf",             "/usr/share/fonts/truetype/noto/NotoSans-Bold.ttf"),
+                // DejaVu als Fallback
+                ("/usr/share/fonts/TTF/DejaVuSans.ttf",                    "/usr/share/fonts/TTF/DejaVuSans-Bold.ttf"),
+                ("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",        "/usr/share/fonts/truetype/dejavu/DejaVuSans-Bold.ttf"),
+            ];
+            for (regulaer_pfad, fett_pfad) in schrift_paare {
+                if let (Ok(regulaer_daten), Ok(fett_daten)) = (std::fs::read(regulaer_pfad), std::fs::read(fett_pfad)) {
+                    let mut schriften = egui::FontDefinitions::default();
+                    schriften.font_data.insert("regular".to_owned(), egui::FontData::from_owned(regulaer_daten).into());
+                    schriften.font_data.insert("bold".to_owned(), egui::FontData::from_owned(fett_daten).into());
+                    // Regular als Standard-Proportional-Schrift registrieren
+                    if let Some(family) = schriften.families.get_mut(&egui::FontFamily::Proportional) {
+                        family.insert(0, "regular".to_owned());
+                    }
+                    // Bold als eigene Font-Family für Eingabefelder registrieren
+                    let mut fette_schriftfamilie = vec!["bold".to_owned()];
+                    if let Some(proportional) = schriften.families.get(&egui::FontFamily::Proportional) {
+                        fette_schriftfamilie.extend(proportional.iter().cloned());
+                    }
+                    schriften.families.insert(egui::FontFamily::Name("Bold".into()), fette_schriftfamilie);
+                    ctx.set_fonts(schriften);
+                    break; // erste gefundene Schrift verwenden
+                }
+            }
+        }
+
+        let heute = Local::now().date_naive();
+        let wochentag = wochentag_name(heute);
+        let einstellungen = Einstellungen::laden();
+        let profil = Benutzerprofil::laden();
+        let pdf_profil = einstellungen.pdf_profil;
+        let pdf_sprache = einstellungen.pdf_sprache;
+        let pdf_quellenseite = einstellungen.pdf_quellenseite;
+        let touch_modus = einstellungen.touch_modus;
+        let kopf_fokus = einstellungen.erst_fokus;
+        touch_stil_anwenden(ctx, touch_modus);
+        let zeilenhoehe = einstellungen.mausrad_zeilenhoehe;
+        ctx.options_mut(|o| o.line_scroll_speed = zeilenhoehe);
+        let (pdf_tx, pdf_rx) = mpsc::channel();
+        Self {
+            projekt: String::new(),
+            titel: String::new(),
+            datum_text: format!(
+                "{}, {:02}.{:02}.{}",
+                wochentag,
+                heute.day(),
+                heute.month(),
+                heute.year()
+            ),
+            ort: String::new(),
+            protokollant: profil.als_person(),
+            teilnehmer: vec![Person::new()],
+            zur_kenntnis: vec![Person::new()],
+            ueber_meeting: String::new(),
+            status: Status::Entwurf,
+            sicherheit: Sicherheit::Intern,
+            eintraege: vec![Eintrag::new()],
+            vorgaenger: String::new(),
+            pdf_spalten: None,
+            naechstes_datum: String::new(),
+            naechstes_ort: String::new(),
+            naechste_agenda: String::new(),
+            interner_anhang: String::new(),
+            audit_log: Vec::new(),
+            focus_new_teilnehmer: false,
+            focus_new_zur_kenntnis: false,
+            theme: if omarchy_farben_laden().is_some() { Theme::Omarchy } else { Theme::Dunkel },
+            einstellungen,
+            save_path: None,
+            entwurf_pfad: None,
+            show_quit_dialog: false,
+            gespeicherter_stand: None,
+            beenden_nach_dialog: false,
+            beenden_bestaetigt: false,
+            datei_zeit: None,
+            datei_geprueft: std::time::Instant::now(),
+            auto_speichern_stand: None,
+            auto_speichern_geprueft: std::time::Instant::now(),
+            externe_aenderung: None,
+            show_about_dialog: false,
+            icon_texture: None,
+            show_pdf_error: false,
+            show_pdf_export_dialog: false,
+            pdf_profil,
+            pdf_sprache,
+            pdf_quellenseite,
+            pdf_schriftmetrik: std::cell::OnceCell::new(),
+            show_pflichtfeld_hinweis: false,
+            vorlagen_auswahl: None,
+            platzhalter_dialog: None,
+            duplikat_dialog: None,
+            versand_checkliste: None,
+            touch_modus,
+            kopf_fokus,
+            profil,
+            profil_dialog: None,
+            konfig_profil: aktives_profil(),
+            profil_wechsel: None,
+            oeffnen_dialog: None,
+            bericht_dialog: None,
+            heute_begonnen: Vec::new(),
+            instanz_rx: None,
+            pending_bericht: None,
+            bearbeitungszeit: std::time::Duration::ZERO,
+            bearbeitungszeit_datei: None,
+            naechste_eintrag_id: 0,
+            letzte_aktivitaet: None,
+            show_statistik: false,
+            kuerzel_pruefung: None,
+            ueberfuehrung: None,
+            ersetzen_rueckfrage: None,
+            ersetzen_nach_speichern: None,
+            email_import: None,
+            sicherung: None,
+            show_beschlussregister: false,
+            beschluss_suche: String::new(),
+            beschlussregister: Vec::new(),
+            nur_meine_aufgaben: false,
+            faellig_filter: FaelligFilter::default(),
+            archiv_aufgaben: None,
+            druck_dialog: None,
+            eintrag_stand: HashMap::new(),
+            art_labels: Vec::new(),
+            scroll_mausrad: true,
+            tabellen_farben: TabellenFarben::default(),
+            praesentation: None,
+            toast: None,
+            assistent: None,
+            hilfe: None,
+            zugangsdaten_dialog: None,
+            herabstufung: None,
+            herabstufung_begruendung: String::new(),
+            focus_notiz: None,
+            eintrag_ablage: Vec::new(),
+            kopfdaten_anfrage: None,
+            notiz_had_focus: None,
+            input_text_color: None,
+            label_color: None,
+            has_omarchy: omarchy_farben_laden().is_some(),
+            dialog_rx: None,
+            aufgaben_rx: None,
+            matrix_nachfrage: false,
+            matrix_rx: None,
+            freigabe_pipeline: None,
+            redmine_status: HashMap::new(),
+            redmine_rx: None,
+            zusammenfassung_vorschlag: None,
+            zusammenfassung_rx: None,
+            pdf_aenderungen: None,
+            pdf_aenderungen_aufnehmen: false,
+            pdf_zielgruppe: Zielgruppe::Intern,
+            pdf_exportprofil: None,
+            pdf_passwort: String::new(),
+            pdf_passwort_rx: None,
+            pdf_tx,
+            pdf_rx,
+            pdf_laufend: 0,
+            pending_exportprofil: None,
+            exportprofil_aktiv: None,
+            pending_pdf_font: None,
+            protokoll_datum: String::new(),
+            erstellt_am: String::new(),
+            erstellt_von: String::new(),
+        }
+    }
+
+    /// Generiert einen vorgeschlagenen Dateinamen für die Markdown-Datei.
+    /// Format: `MZProtokoll_<Titel>__<JJJJ-MM-TT>.md`
+    fn dateinamen_erstellen(&self) -> String {
+        let name_part: String = self.titel.chars().filter(|c| c.is_alphabetic()).collect();
+        let datum = Local::now().format("%Y-%m-%d").to_string();
+        format!("MZProtokoll_{}__{}.md", name_part, datum)
+    }
+
+    /// Serialisiert den aktuellen Protokollzustand als Markdown-String.
+    /// Das Format ist spezifisch für MZProtokoll und wird von `markdown_parsen` wieder eingelesen.
+    fn markdown_erstellen(&self) -> String {
+        let mut md = String::new();
+
+        if !self.projekt.is_empty() {
+            md.push_str(&format!("**Projekt:** {}\n\n", self.projekt));
+        }
+
+        md.push_str(&format!("# {}\n\n", self.titel));
+
+        let mut meta = Vec::new();
+        if !self.datum_text.is_empty() {
+            meta.push(format!("**Datum:** {}", self.datum_text));
+        }
+        if !self.ort.is_empty() {
+            meta.push(format!("**Ort:** {}", self.ort));
+        }
+        if !self.protokoll_datum.is_empty() {
+            meta.push(format!("**Protokoll erstellt am:** {}", self.protokoll_datum));
+        }
+        if !meta.is_empty() {
+            md.push_str(&meta.join(" | "));
+            md.push_str("\n\n");
+        }
+        if !self.vorgaenger.is_empty() {
+            md.push_str(&format!("**Vorgänger:** {}\n\n", self.vorgaenger));
+        }
+        if let Some(spalten) = self.pdf_spalten {
+            md.push_str(&format!("**PDF-Spalten:** {}\n\n", spalten.text()));
+        }
+
+        md.push_str("---\n\n");
+
+        if !self.protokollant.name.is_empty() {
+            md.push_str("## Protokollführer\n\n");
+            md.push_str(&self.protokollant.name);
+            if !self.protokollant.kuerzel.is_empty() {
+                md.push_str(&format!(" [{}]", self.protokollant.kuerzel));
+            }
+            md.push_str("\n\n");
+        }
+
+        let tn: Vec<_> = self.teilnehmer.iter().filter(|t| !t.name.is_empty()).collect();
+        if !tn.is_empty() {
+            md.push_str("## Teilnehmer\n\n");
+            for t in &tn {
+                md.push_str(&format!("- {}", t.name));
+                if !t.kuerzel.is_empty() {
+                    md.push_str(&format!(" [{}]", t.kuerzel));
+                }
+                md.push('\n');
+            }
+            md.push('\n');
+        }
+
+        let zk: Vec<_> = self.zur_kenntnis.iter().filter(|z| !z.name.is_empty()).collect();
+        if !zk.is_empty() {
+            md.push_str("## Zur Kenntnis\n\n");
+            for z in &zk {
+                md.push_str(&format!("- {}", z.name));
+                if !z.kuerzel.is_empty() {
+                    md.push_str(&format!(" [{}]", z.kuerzel));
+                }
+                md.push('\n');
+            }
+            md.push('\n');
+        }
+
+        md.push_str("## Über dieses Meeting\n\n");
+        if !self.ueber_meeting.is_empty() {
+            md.push_str(&self.ueber_meeting);
+            md.push_str("\n\n");
+        }
+
+        md.push_str("## Status\n\n");
+        for s in self.status_auswahl() {
+            let marke = if s == self.status { "x" } else { " " };
+            md.push_str(&format!("- [{}] {}\n", marke, s.label()));
+        }
+        md.push('\n');
+
+        md.push_str("## Klassifizierung\n\n");
+        for s in Sicherheit::all() {
+            if *s == self.sicherheit {
+                md.push_str(&format!("- [x] {}\n", s.label()));
+            } else {
+                md.push_str(&format!("- [ ] {}\n", s.label()));
+            }
+        }
+        md.push('\n');
+
+        let nummern = top_nummern(&self.eintraege, self.einstellungen.top_nummerierung);
+        let entries: Vec<_> = self
+            .eintraege
+            .iter()
+            .zip(&nummern)
+            .filter(|(e, _)| !e.punkt.is_empty() || e.art != Art::Leer || !e.notiz.is_empty())
+            .collect();
+
+        if !entries.is_empty() {
+            md.push_str("---\n\n");
+            md.push_str("## Einträge\n\n");
+            md.push_str("| Punkt | Art | Notiz | Kümmerer | Bis | Beschluss | Umbruch | Review | ID |\n");
+            md.push_str("|-------|-----|-------|----------|-----|-----------|---------|--------|----|\n");
+            for (e, (_, nummer)) in &entries {
+                md.push_str(&eintrag_markdown_zeile(e, &self.art_label(&e.art), nummer));
+            }
+        }
+
+        if !self.naechstes_datum.is_empty() || !self.naechstes_ort.is_empty() || !self.naechste_agenda.trim().is_empty() {
+            md.push_str("\n## Nächstes Meeting\n\n");
+            let mut meta = Vec::new();
+            if !self.naechstes_datum.is_empty() {
+                meta.push(format!("**Datum:** {}", self.naechstes_datum));
+            }
+            if !self.naechstes_ort.is_empty() {
+                meta.push(format!("**Ort:** {}", self.naechstes_ort));
+            }
+            if !meta.is_empty() {
+                md.push_str(&meta.join(" | "));
+                md.push_str("\n\n");
+            }
+            for punkt in self.naechste_agenda.lines().filter(|l| !l.trim().is_empty()) {
+                md.push_str(&format!("- {}\n", punkt.trim()));
+            }
+        }
+
+        if !self.interner_anhang.trim().is_empty() {
+            md.push_str("\n## Nur intern\n\n");
+            md.push_str(self.interner_anhang.trim());
+            md.push('\n');
+        }
+
+        if !self.audit_log.is_empty() {
+            md.push_str("\n## Audit-Log\n\n");
+            for a in &self.audit_log {
+                md.push_str(&format!("- {} | {} | {}\n", a.zeitpunkt, a.benutzer, a.aktion));
+            }
+        }
+
+        md.push_str("\n---\n\n");
+        if !self.erstellt_am.is_empty() {
+            md.push_str(&format!("**Erstellt:** {} von {}\n\n", self.erstellt_am, self.erstellt_von));
+        }
+        if self.naechste_eintrag_id > 0 {
+            md.push_str(&format!("**Nächste Eintragsnummer:** {}\n\n", self.naechste_eintrag_id));
+        }
+        let geaendert_am = Local::now().format("%d.%m.%Y %H:%M").to_string();
+        md.push_str(&format!("**Geändert:** {} von {}\n\n", geaendert_am, self.benutzer_name()));
+        // Ohne `zeiterfassung_speichern` wird neue Zeit nicht vermerkt, ein
+        // vorhandener Wert aber unverändert weitergeschrieben
+        let bearbeitungszeit = if self.einstellungen.zeiterfassung_speichern {
+            Some(self.bearbeitungszeit)
+        } else {
+            self.bearbeitungszeit_datei
+        };
+        if let Some(dauer) = bearbeitungszeit {
+            md.push_str(&format!("**Bearbeitungszeit:** {}\n\n", dauer_formatieren(dauer)));
+        }
+        md.push_str("*Erstellt mit MZProtokoll von Marcel Zimmer — [www.marcelzimmer.de](https://www.marcelzimmer.de) | [X @marcelzimmer](https://x.com/marcelzimmer) | [GitHub @marcelzimmer](https://github.com/marcelzimmer)*\n");
+
+        md
+    }
+
+    /// Baut das Protokoll als eigenständiges HTML-Dokument für das HTML-Backend auf:
+    /// Kopf, Personen, Status und Klassifizierung, Eintragstabelle (Wortbeiträge als
+    /// Absätze, Links anklickbar) und nächstes Meeting. Deckblatt, Anlagen und
+    /// Quellenseite bleiben dem genpdf-Backend vorbehalten.
+    fn html_erstellen(&self) -> String {
+        let farbe = |c: egui::Color32| format!("#{:02x}{:02x}{:02x}", c.r(), c.g(), c.b());
+        let sprache = self.pdf_sprache;
+        let personen = |liste: &[Person]| {
+            liste
+                .iter()
+                .filter(|p| !p.name.is_empty())
+                .map(|p| xml_escapen(&self.pdf_person(p)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut html = format!("<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n", sprache.code());
+        html.push_str(&format!("<title>{}</title>\n", xml_escapen(&self.titel)));
+        html.push_str(&format!(
+            "<style>\n@page {{ size: A4; margin: 15mm; @bottom-right {{ content: \"{} \" counter(page) \" {} \" counter(pages); font-size: 8pt; }} }}\n",
+            sprache.t("Seite"),
+            sprache.t("von")
+        ));
+        html.push_str(
+            "body { font-family: sans-serif; font-size: 9pt; }\n\
+             h1 { font-size: 16pt; margin: 0 0 4pt; }\n\
+             table { border-collapse: collapse; width: 100%; }\n\
+             th, td { text-align: left; vertical-align: top; padding: 2pt 4pt; border-bottom: 0.3pt solid #b4b4b4; }\n\
+             table.info th { width: 22%; border: none; }\n\
+             table.info td { border: none; }\n\
+             td.art { font-weight: bold; white-space: nowrap; }\n\
+             tr.todo td { font-weight: bold; }\n\
+             p.wortbeitrag { margin: 4pt 0; }\n\
+             </style>\n</head>\n<body>\n",
+        );
+        if !self.projekt.is_empty() {
+            html.push_str(&format!("<p>{}</p>\n", xml_escapen(&self.projekt)));
+        }
+        html.push_str(&format!("<h1>{}</h1>\n", xml_escapen(&self.titel)));
+        let mut meta = Vec::new();
+        if !self.datum_text.is_empty() {
+            meta.push(format!("{}: {}", sprache.t("Datum"), sprache.datum_text(&self.datum_text)));
+        }
+        if !self.ort.is_empty() {
+            meta.push(format!("{}: {}", sprache.t("Ort"), self.ort));
+        }
+        if self.protokoll_abweichend_erstellt() {
+            meta.push(format!("{}: {}", sprache.t("Protokoll erstellt am"), sprache.datum_text(&self.protokoll_datum)));
+        }
+        if !meta.is_empty() {
+            html.push_str(&format!("<p>{}</p>\n", xml_escapen(&meta.join("  |  "))));
+        }
+        html.push_str("<hr>\n<table class=\"info\">\n");
+        let mut info = |name: &str, wert: String| {
+            if !wert.is_empty() {
+                html.push_str(&format!("<tr><th>{}</th><td>{}</td></tr>\n", name, wert));
+            }
+        };
+        info(sprache.t("Protokollführer"), personen(std::slice::from_ref(&self.protokollant)));
+        info(sprache.t("Teilnehmer"), personen(&self.teilnehmer));
+        info(sprache.t("Zur Kenntnis"), personen(&self.zur_kenntnis));
+        info(sprache.t("Über dieses Meeting"), html_text(&self.pdf_text(&self.ueber_meeting)));
+        info(sprache.t("Status"), xml_escapen(sprache.t(self.status.label())));
+        info(sprache.t("Klassifizierung"), xml_escapen(sprache.t(self.sicherheit.label())));
+        html.push_str("</table>\n<hr>\n");
+
+        let nummern = top_nummern(&self.eintraege, self.einstellungen.top_nummerierung);
+        let spalten = self.pdf_spalten();
+        let gesamt: usize = spalten.0.iter().sum();
+        let zeile = |zellen: [String; 5]| -> String {
+            spalten.0.iter().zip(zellen).filter(|(gewicht, _)| **gewicht > 0).map(|(_, zelle)| zelle).collect()
+        };
+        let mut tabelle_offen = false;
+        for (e, (_, nummer)) in self.eintraege.iter().zip(&nummern) {
+            if (e.punkt.is_empty() && e.art == Art::Leer && e.notiz.is_empty()) || !self.pdf_art_exportieren(&e.art) {
+                continue;
+            }
+            if e.art == Art::Wortbeitrag {
+                if tabelle_offen {
+                    html.push_str("</table>\n");
+                    tabelle_offen = false;
+                }
+                html.push_str(&format!(
+                    "<p class=\"wortbeitrag\"><b>{}:</b> {}</p>\n",
+                    xml_escapen(&self.pdf_sprecher(&e.kuemmerer)),
+                    html_text(&self.pdf_text(&e.notiz))
+                ));
+                continue;
+            }
+            if !tabelle_offen {
+                html.push_str("<table>\n<colgroup>");
+                for gewicht in spalten.0.iter().filter(|g| **g > 0) {
+                    html.push_str(&format!("<col style=\"width: {}%\">", gewicht * 100 / gesamt));
+                }
+                html.push_str("</colgroup>\n<tr>");
+                html.push_str(&zeile(PdfSpalten::NAMEN.map(|name| if name == "Punkt" { "<th></th>".to_string() } else { format!("<th>{}</th>", sprache.t(name)) })));
+                html.push_str("</tr>\n");
+                tabelle_offen = true;
+            }
+            let mut art = xml_escapen(&self.pdf_art_label(&e.art));
+            if !e.beschluss_nr.is_empty() {
+                art.push_str(&format!("<br>{}", xml_escapen(&e.beschluss_nr)));
+            }
+            html.push_str(&format!(
+                "<tr{}>{}</tr>\n",
+                if e.art == Art::Todo { " class=\"todo\"" } else { "" },
+                zeile([
+                    format!("<td>{}</td>", xml_escapen(&nummer_voranstellen(nummer, &e.punkt))),
+                    format!("<td class=\"art\" style=\"color: {}\">{}</td>", farbe(self.tabellen_farben.art(&e.art)), art),
+                    format!("<td>{}</td>", html_text(&self.pdf_text(&e.notiz))),
+                    format!("<td>{}</td>", xml_escapen(&e.kuemmerer)),
+                    format!("<td>{}</td>", xml_escapen(&e.bis_text(self.pdf_sprache))),
+                ])
+            ));
+        }
+        if tabelle_offen {
+            html.push_str("</table>\n");
+        }
+
+        if !self.naechstes_datum.is_empty() || !self.naechstes_ort.is_empty() || !self.naechste_agenda.trim().is_empty() {
+            html.push_str(&format!("<h2>{}</h2>\n", sprache.t("Nächstes Meeting")));
+            let mut termin = Vec::new();
+            if !self.naechstes_datum.is_empty() {
+                termin.push(format!("{}: {}", sprache.t("Datum"), sprache.datum_text(&self.naechstes_datum)));
+            }
+            if !self.naechstes_ort.is_empty() {
+                termin.push(format!("{}: {}", sprache.t("Ort"), self.naechstes_ort));
+            }
+            if !termin.is_empty() {
+                html.push_str(&format!("<p>{}</p>\n", xml_escapen(&termin.join("  |  "))));
+            }
+            let agenda: Vec<String> = self
+                .naechste_agenda
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+                .map(|l| format!("<li>{}</li>", html_text(l.trim())))
+                .collect();
+            if !agenda.is_empty() {
+                html.push_str(&format!("<ul>\n{}\n</ul>\n", agenda.join("\n")));
+            }
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    /// Baut den Typst-Quelltext: die Vorlage (`typst_vorlage` oder die mitgelieferte
+    /// `assets/typst/protokoll.typ`) gefolgt vom Aufruf `#protokoll(daten)` mit
+    /// Kopf, Personen, Einträgen und nächstem Meeting. Fehlt eine eingestellte
+    /// Vorlagendatei, wird die mitgelieferte dort als Ausgangspunkt angelegt.
+    fn typst_erstellen(&self) -> String {
+        let mut vorlage = TYPST_VORLAGE.to_string();
+        if let Some(pfad) = einstellungspfad(&self.einstellungen.typst_vorlage) {
+            match std::fs::read_to_string(&pfad) {
+                Ok(inhalt) => vorlage = inhalt,
+                Err(_) => {
+                    if let Some(ordner) = pfad.parent() {
+                        let _ = std::fs::create_dir_all(ordner);
+                    }
+                    let _ = std::fs::write(&pfad, TYPST_VORLAGE);
+                }
+            }
+        }
+        let farbe = |c: egui::Color32| format!("rgb(\"#{:02x}{:02x}{:02x}\")", c.r(), c.g(), c.b());
+        let sprache = self.pdf_sprache;
+        let personen = |liste: &[Person]| {
+            liste
+                .iter()
+                .filter(|p| !p.name.is_empty())
+                .map(|p| self.pdf_person(p))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut meta = Vec::new();
+        if !self.datum_text.is_empty() {
+            meta.push(format!("{}: {}", sprache.t("Datum"), sprache.datum_text(&self.datum_text)));
+        }
+        if !self.ort.is_empty() {
+            meta.push(format!("{}: {}", sprache.t("Ort"), self.ort));
+        }
+        if self.protokoll_abweichend_erstellt() {
+            meta.push(format!("{}: {}", sprache.t("Protokoll erstellt am"), sprache.datum_text(&self.protokoll_datum)));
+        }
+        let info: Vec<String> = [
+            (sprache.t("Protokollführer"), typst_str(&personen(std::slice::from_ref(&self.protokollant)))),
+            (sprache.t("Teilnehmer"), typst_str(&personen(&self.teilnehmer))),
+            (sprache.t("Zur Kenntnis"), typst_str(&personen(&self.zur_kenntnis))),
+            (sprache.t("Über dieses Meeting"), if self.ueber_meeting.trim().is_empty() { String::new() } else { typst_text(&self.pdf_text(&self.ueber_meeting)) }),
+            (sprache.t("Status"), typst_str(sprache.t(self.status.label()))),
+            (sprache.t("Klassifizierung"), typst_str(sprache.t(self.sicherheit.label()))),
+        ]
+        .into_iter()
+        .filter(|(_, wert)| !wert.is_empty() && wert != "\"\"")
+        .map(|(name, wert)| format!("    ({}, {}),", typst_str(name), wert))
+        .collect();
+
+        let nummern = top_nummern(&self.eintraege, self.einstellungen.top_nummerierung);
+        let mut eintraege = Vec::new();
+        for (e, (_, nummer)) in self.eintraege.iter().zip(&nummern) {
+            if (e.punkt.is_empty() && e.art == Art::Leer && e.notiz.is_empty()) || !self.pdf_art_exportieren(&e.art) {
+                continue;
+            }
+            let mut art = self.pdf_art_label(&e.art);
+            if !e.beschluss_nr.is_empty() {
+                art = format!("{}\n{}", art, e.beschluss_nr);
+            }
+            let kuemmerer = if e.art == Art::Wortbeitrag { self.pdf_sprecher(&e.kuemmerer) } else { e.kuemmerer.clone() };
+            eintraege.push(format!(
+                "    (punkt: {}, art: {}, farbe: {}, notiz: {}, kuemmerer: {}, bis: {}, todo: {}, wortbeitrag: {}),",
+                typst_str(&nummer_voranstellen(nummer, &e.punkt)),
+                typst_str(&art),
+                farbe(self.tabellen_farben.art(&e.art)),
+                typst_text(&self.pdf_text(&e.notiz)),
+                typst_str(&kuemmerer),
+                typst_str(&e.bis_text(self.pdf_sprache)),
+                e.art == Art::Todo,
+                e.art == Art::Wortbeitrag
+            ));
+        }
+
+        let naechstes = if self.naechstes_datum.is_empty() && self.naechstes_ort.is_empty() && self.naechste_agenda.trim().is_empty() {
+            "none".to_string()
+        } else {
+            let mut termin = Vec::new();
+            if !self.naechstes_datum.is_empty() {
+                termin.push(format!("{}: {}", sprache.t("Datum"), sprache.datum_text(&self.naechstes_datum)));
+            }
+            if !self.naechstes_ort.is_empty() {
+                termin.push(format!("{}: {}", sprache.t("Ort"), self.naechstes_ort));
+            }
+            let agenda: Vec<String> = self
+                .naechste_agenda
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+                .map(|l| format!("{}, ", typst_text(l.trim())))
+                .collect();
+            format!("(termin: {}, agenda: ({}))", typst_str(&termin.join("  |  ")), agenda.concat())
+        };
+
+        let beschriftung: Vec<String> = [
+            ("seite", "Seite"),
+            ("von", "von"),
+            ("art", "Art"),
+            ("notiz", "Notiz"),
+            ("kuemmerer", "Kümmerer"),
+            ("bis", "Bis"),
+            ("naechstes", "Nächstes Meeting"),
+        ]
+        .iter()
+        .map(|(feld, text)| format!("{}: {}", feld, typst_str(sprache.t(text))))
+        .collect();
+
+        format!(
+            "{}\n#protokoll((\n  sprache: {},\n  beschriftung: ({}),\n  projekt: {},\n  titel: {},\n  meta: {},\n  info: (\n{}\n  ),\n  eintraege: (\n{}\n  ),\n  naechstes: {},\n))\n",
+            vorlage.trim_end(),
+            typst_str(sprache.code()),
+            beschriftung.join(", "),
+            typst_str(&self.projekt),
+            typst_str(&self.titel),
+            typst_str(&meta.join("  |  ")),
+            info.join("\n"),
+            eintraege.join("\n"),
+            naechstes
+        )
+    }
+
+    /// Serialisiert die Protokoll-Metadaten und Einträge als XML-Begleitdatei
+    /// für Dokumentenmanagementsysteme. Elementnamen stammen aus dem
+    /// konfigurierbaren Mapping (`Einstellungen::xml_element`).
+    fn xml_erstellen(&self, pdf_datei: &str) -> String {
+        let e = &self.einstellungen;
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let wurzel = e.xml_element("wurzel").unwrap_or_else(|| "protokoll".to_string());
+        xml.push_str(&format!("<{}>\n", wurzel));
+
+        let feld = |xml: &mut String, name: &str, wert: &str, einzug: &str| {
+            if let Some(el) = e.xml_element(name) {
+                xml.push_str(&format!("{}<{}>{}</{}>\n", einzug, el, xml_escapen(wert), el));
+            }
+        };
+        let person = |xml: &mut String, el: &str, p: &Person, einzug: &str| {
+            xml.push_str(&format!(
+                "{}<{} kuerzel=\"{}\">{}</{}>\n",
+                einzug,
+                el,
+                xml_escapen(&p.kuerzel),
+                xml_escapen(&p.name),
+                el
+            ));
+        };
+
+        feld(&mut xml, "projekt", &self.projekt, "  ");
+        feld(&mut xml, "titel", &self.titel, "  ");
+        feld(&mut xml, "datum", &self.datum_text, "  ");
+        feld(&mut xml, "protokoll_datum", &self.protokoll_datum, "  ");
+        feld(&mut xml, "ort", &self.ort, "  ");
+        if let Some(el) = e.xml_element("protokollfuehrer") {
+            person(&mut xml, &el, &self.protokollant, "  ");
+        }
+        let person_el = e.xml_element("person").unwrap_or_else(|| "person".to_string());
+        for (name, liste) in [("teilnehmer", &self.teilnehmer), ("zur_kenntnis", &self.zur_kenntnis)] {
+            if let Some(el) = e.xml_element(name) {
+                xml.push_str(&format!("  <{}>\n", el));
+                for p in liste.iter().filter(|p| !p.name.is_empty()) {
+                    person(&mut xml, &person_el, p, "    ");
+                }
+                xml.push_str(&format!("  </{}>\n", el));
+            }
+        }
+        feld(&mut xml, "ueber_meeting", &self.ueber_meeting, "  ");
+        feld(&mut xml, "status", self.status.label(), "  ");
+        feld(&mut xml, "klassifizierung", self.sicherheit.label(), "  ");
+        feld(&mut xml, "erstellt_am", &self.erstellt_am, "  ");
+        feld(&mut xml, "erstellt_von", &self.erstellt_von, "  ");
+        feld(&mut xml, "pdf_datei", pdf_datei, "  ");
+
+        if let Some(liste_el) = e.xml_element("eintraege") {
+            let eintrag_el = e.xml_element("eintrag").unwrap_or_else(|| "eintrag".to_string());
+            xml.push_str(&format!("  <{}>\n", liste_el));
+            for eintrag in self
+                .eintraege
+                .iter()
+                .filter(|e| !e.punkt.is_empty() || e.art != Art::Leer || !e.notiz.is_empty())
+            {
+                let art_str = if eintrag.art == Art::Leer { "" } else { eintrag.art.label() };
+                xml.push_str(&format!("    <{}>\n", eintrag_el));
+                feld(&mut xml, "punkt", &eintrag.punkt, "      ");
+                feld(&mut xml, "art", art_str, "      ");
+                feld(&mut xml, "notiz", &eintrag.notiz, "      ");
+                feld(&mut xml, "kuemmerer", &eintrag.kuemmerer, "      ");
+                feld(&mut xml, "bis", &eintrag.bis, "      ");
+                feld(&mut xml, "beschluss_nr", &eintrag.beschluss_nr, "      ");
+                xml.push_str(&format!("    </{}>\n", eintrag_el));
+            }
+            xml.push_str(&format!("  </{}>\n", liste_el));
+        }
+
+        xml.push_str(&format!("</{}>\n", wurzel));
+        xml
+    }
+
+    /// Sortiert Teilnehmer und Zur-Kenntnis-Personen alphabetisch.
+    /// Leere Einträge werden ans Ende verschoben.
+    fn sort_personen(&mut self) {
+        let sort_fn = |a: &Person, b: &Person| {
+            let a_empty = a.name.trim().is_empty();
+            let b_empty = b.name.trim().is_empty();
+            match (a_empty, b_empty) {
+                (true, false) => std::cmp::Ordering::Greater,
+                (false, true) => std::cmp::Ordering::Less,
+                _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            }
+        };
+        self.teilnehmer.sort_by(sort_fn);
+        self.zur_kenntnis.sort_by(sort_fn);
+    }
+
+    /// Speichert das Protokoll als Markdown-Datei.
+    /// Ist bereits ein Pfad bekannt (`save_path`), wird direkt überschrieben.
+    /// Andernfalls öffnet sich ein Datei-Speichern-Dialog in einem separaten Thread.
+    fn speichern(&mut self) {
+        self.sort_personen();
+        if self.protokollant.name.trim().is_empty() {
+            self.show_pflichtfeld_hinweis = true;
+            return;
+        }
+        if self.save_path.as_deref().is_some_and(schreibgeschuetzt) {
+            self.toast_zeigen("Die Datei ist archiviert und schreibgeschützt".to_string());
+            return;
+        }
+        self.erstellung_vermerken();
+        self.beschluesse_nummerieren();
+        self.eintraege_protokollieren();
+        self.finalisierung_erinnern();
+        let content = self.markdown_erstellen();
+
+        if let Some(path) = self.save_path.clone() {
+            match atomar_schreiben(&path, content) {
+                Ok(()) => {
+                    self.stand_merken();
+                    self.entwurf_entfernen();
+                }
+                Err(e) => self.toast_zeigen(format!("Speichern fehlgeschlagen: {}", e)),
+            }
+        } else {
+            let filename = self.dateinamen_erstellen();
+            let (tx, rx) = mpsc::channel();
+            self.dialog_rx = Some(rx);
+            std::thread::spawn(move || {
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_file_name(&filename)
+                    .add_filter("Markdown", &["md"])
+                    .save_file()
+                {
+                    let _ = match atomar_schreiben(&path, &content) {
+                        Ok(()) => tx.send(DialogErgebnis::Speichern(path)),
+                        Err(e) => tx.send(DialogErgebnis::SpeichernFehlgeschlagen(e.to_string())),
+                    };
+                }
+            });
+        }
+    }
+
+    /// „Als Entwurf zwischenspeichern": schreibt das Protokoll ohne Pflichtfeldprüfung
+    /// mit `ENTWURF_KENNUNG` in das Entwurfsverzeichnis, damit spontane Meetings sofort
+    /// mitgeschrieben werden können. Derselbe Entwurf wird bei jedem Aufruf überschrieben;
+    /// der Speicherpfad bleibt unberührt.
+    fn entwurf_zwischenspeichern(&mut self) {
+        if self.ist_leer() {
+            self.toast_zeigen("Das Protokoll ist noch leer".to_string());
+            return;
+        }
+        let pfad = match self.entwurf_pfad.clone() {
+            Some(pfad) => pfad,
+            None => {
+                let Some(verzeichnis) = entwurf_verzeichnis() else {
+                    self.toast_zeigen("Kein Konfigurationsverzeichnis für Entwürfe".to_string());
+                    return;
+                };
+                let _ = std::fs::create_dir_all(&verzeichnis);
+                verzeichnis.join(format!("Entwurf_{}.md", Local::now().format("%Y-%m-%d_%H%M%S")))
+            }
+        };
+        let inhalt = format!("{}
+
+{}", ENTWURF_KENNUNG, self.markdown_erstellen());
+        match atomar_schreiben(&pfad, inhalt) {
+            Ok(()) => {
+                self.toast_zeigen(format!("Entwurf zwischengespeichert: {}", pfad.display()));
+                self.entwurf_pfad = Some(pfad);
+            }
+            Err(e) => self.toast_zeigen(format!("Entwurf konnte nicht gespeichert werden: {}", e)),
+        }
+    }
+
+    /// Entfernt den Zwischenentwurf, nachdem das Protokoll regulär gespeichert wurde.
+    fn entwurf_entfernen(&mut self) {
+        if let Some(pfad) = self.entwurf_pfad.take() {
+            let _ = std::fs::remove_file(pfad);
+        }
+    }
+
+    /// Listet die Zwischenentwürfe im Öffnen-Dialog auf.
+    fn entwuerfe_oeffnen(&mut self) {
+        let Some(verzeichnis) = entwurf_verzeichnis().filter(|p| p.is_dir()) else {
+            self.toast_zeigen("Es gibt keine zwischengespeicherten Entwürfe".to_string());
+            return;
+        };
+        let dateien = oeffnen_eintraege_laden(&verzeichnis, &self.einstellungen);
+        self.oeffnen_dialog = Some(OeffnenDialog { verzeichnis, dateien, suche: String::new() });
+    }
+
+    /// Öffnet einen Datei-Öffnen-Dialog (separater Thread) und lädt
+    /// die gewählte Markdown-Datei via `markdown_parsen` in den App-Zustand.
+    fn laden(&mut self) {
+        let (tx, rx) = mpsc::channel();
+        self.dialog_rx = Some(rx);
+        std::thread::spawn(move || {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("Markdown", &["md"])
+                .pick_file()
+            {
+                if let Ok(content) = std::fs::read_to_string(&path) {
+                    let _ = tx.send(DialogErgebnis::Laden(path, content));
+                }
+            }
+        });
+    }
+
+    /// Öffnet den eigenen Öffnen-Dialog für das Archiv (`archiv_verzeichnis`), ersatzweise
+    /// für den Ordner des aktuellen Protokolls. Ist keins von beiden bekannt, kommt
+    /// direkt der Dateidialog des Systems (`laden`).
+    fn oeffnen_dialog_oeffnen(&mut self) {
+        let Some(verzeichnis) = self.protokoll_verzeichnis() else {
+            self.laden();
+            return;
+        };
+        let dateien = oeffnen_eintraege_laden(&verzeichnis, &self.einstellungen);
+        self.oeffnen_dialog = Some(OeffnenDialog { verzeichnis, dateien, suche: String::new() });
+    }
+
+    /// Verzeichnis mit den gesammelten Protokollen: das Archiv (`archiv_verzeichnis`),
+    /// ersatzweise der Ordner des aktuellen Protokolls.
+    fn protokoll_verzeichnis(&self) -> Option<std::path::PathBuf> {
+        einstellungspfad(&self.einstellungen.archiv_verzeichnis)
+            .filter(|p| p.is_dir())
+            .or_else(|| self.save_path.as_ref().and_then(|p| p.parent()).map(|p| p.to_path_buf()))
+    }
+
+    /// Öffnet den Dialog „Bericht erstellen" mit den Projekten aus dem Archiv und
+    /// der aktuellen Woche als Zeitraum.
+    fn bericht_dialog_oeffnen(&mut self) {
+        let Some(verzeichnis) = self.protokoll_verzeichnis() else {
+            self.toast_zeigen("Kein Archiv eingestellt (archiv_verzeichnis in einstellungen.toml)".to_string());
+            return;
+        };
+        let mut projekte: Vec<String> = oeffnen_eintraege_laden(&verzeichnis, &self.einstellungen)
+            .into_iter()
+            .map(|d| d.projekt.trim().to_string())
+            .collect();
+        projekte.sort();
+        projekte.dedup();
+        let projekt = if projekte.contains(&self.projekt.trim().to_string()) {
+            self.projekt.trim().to_string()
+        } else {
+            projekte.first().cloned().unwrap_or_default()
+        };
+        let mut dialog = BerichtDialog { projekte, projekt, zeitraum: BerichtZeitraum::Woche, von: String::new(), bis: String::new() };
+        bericht_zeitraum_setzen(&mut dialog);
+        self.bericht_dialog = Some(dialog);
+    }
+
+    /// Erstellt den Bericht aus dem Dialog und öffnet einen Datei-Speichern-Dialog
+    /// (separater Thread); `pdf` wählt das Format.
+    fn bericht_exportieren(&mut self, dialog: &BerichtDialog, pdf: bool) {
+        let (Some(von), Some(bis)) = (datum_aus_text(&dialog.von), datum_aus_text(&dialog.bis)) else {
+            self.toast_zeigen("Bitte Von und Bis als TT.MM.JJJJ angeben".to_string());
+            return;
+        };
+        let Some(verzeichnis) = self.protokoll_verzeichnis() else {
+            return;
+        };
+        if pdf {
+            let Some(schrift) = self.schrift_laden() else {
+                self.show_pdf_error = true;
+                return;
+            };
+            self.pending_pdf_font = Some(schrift);
+        }
+        let name_part: String = dialog.projekt.chars().filter(|c| c.is_alphanumeric()).collect();
+        let filename = format!(
+            "MZProtokoll_{}__Bericht_{}_{}.{}",
+            name_part,
+            von.format("%Y-%m-%d"),
+            bis.format("%Y-%m-%d"),
+            if pdf { "pdf" } else { "md" }
+        );
+        self.pending_bericht = Some(bericht_erstellen(&verzeichnis, &dialog.projekt, von, bis, &self.einstellungen));
+        let (tx, rx) = mpsc::channel();
+        self.dialog_rx = Some(rx);
+        std::thread::spawn(move || {
+            let (filter, endung) = if pdf { ("PDF", "pdf") } else { ("Markdown", "md") };
+            if let Some(path) = rfd::FileDialog::new()
+                .set_file_name(&filename)
+                .add_filter(filter, &[endung])
+                .save_file()
+            {
+                let _ = tx.send(DialogErgebnis::Bericht(path));
+            }
+        });
+    }
+
+    /// Rendert den Bericht als PDF: Titel, Kennzahlen, Meetings und je ein Abschnitt
+    /// für Entscheidungen, neue und erledigte TODOs.
+    fn bericht_pdf_generieren(
+        &self,
+        bericht: &Bericht,
+        path: &std::path::Path,
+        schriftfamilie: genpdf::fonts::FontFamily<genpdf::fonts::FontData>,
+    ) -> bool {
+        let profil = self.pdf_profil;
+        let small = genpdf::style::Style::new().with_font_size(profil.schriftgroesse());
+        let small_bold = genpdf::style::Style::new().bold().with_font_size(profil.schriftgroesse());
+        let heading_style = genpdf::style::Style::new().bold().with_font_size(profil.titelgroesse());
+        let abstand = profil.abstand();
+
+        let mut dok = genpdf::Document::new(schriftfamilie);
+        dok.set_title(bericht.titel());
+        let mut dekorator = genpdf::SimplePageDecorator::new();
+        dekorator.set_margins(profil.raender());
+        dok.set_page_decorator(dekorator);
+        dok.set_line_spacing(profil.zeilenabstand());
+
+        dok.push(genpdf::elements::Paragraph::new(bericht.titel()).styled(heading_style));
+        dok.push(
+            genpdf::elements::Paragraph::new(format!("Erstellt am {}", Local::now().format("%d.%m.%Y"))).styled(small),
+        );
+        dok.push(genpdf::elements::Break::new(abstand));
+
+        let mut liste = genpdf::elements::UnorderedList::new();
+        for zeile in bericht.ueberblick() {
+            liste.push(genpdf::elements::Paragraph::new(zeile).styled(small));
+        }
+        dok.push(liste);
+
+        let abschnitt = |dok: &mut genpdf::Document, ueberschrift: &str, zeilen: Vec<String>| {
+            dok.push(genpdf::elements::Break::new(abstand));
+            dok.push(genpdf::elements::Paragraph::new(ueberschrift).styled(small_bold));
+            dok.push(genpdf::elements::Break::new(0.3 * abstand));
+            if zeilen.is_empty() {
+                dok.push(genpdf::elements::Paragraph::new("keine").styled(small));
+                return;
+            }
+            let mut liste = genpdf::elements::UnorderedList::new();
+            for zeile in zeilen {
+                liste.push(genpdf::elements::Paragraph::new(zeile).styled(small));
+            }
+            dok.push(liste);
+        };
+        abschnitt(&mut dok, "Meetings", bericht.meetings.clone());
+        for (ueberschrift, punkte) in bericht.abschnitte() {
+            abschnitt(&mut dok, ueberschrift, punkte.iter().map(Bericht::punkt_zeile).collect());
+        }
+        dok.render_to_file(path).is_ok()
+    }
+
+    /// Übernimmt eine geladene Protokolldatei (aus dem Dateidialog oder dem Öffnen-Dialog).
+    fn datei_uebernehmen(&mut self, path: std::path::PathBuf, content: &str) {
+        self.sicherung_anlegen();
+        // Offene Dubletten beziehen sich auf Eintragsnummern des bisherigen Protokolls
+        self.duplikat_dialog = None;
+        // Ausgeschnittene Einträge stammen nun aus einem anderen Protokoll (siehe `leeres_protokoll`)
+        for e in &mut self.eintrag_ablage {
+            e.beschluss_nr.clear();
+        }
+        // Zwischenentwürfe bleiben ohne Speicherpfad, bis regulär gespeichert wird
+        let entwurf = content.trim_start().strip_prefix(ENTWURF_KENNUNG);
+        self.markdown_parsen(entwurf.unwrap_or(content));
+        self.sort_personen();
+        if entwurf.is_some() {
+            self.save_path = None;
+            self.entwurf_pfad = Some(path);
+        } else {
+            self.save_path = Some(path);
+            self.entwurf_pfad = None;
+        }
+        self.stand_merken();
+        self.aufgaben_synchronisieren(false);
+        self.redmine_status_aktualisieren(false);
+    }
+
+    /// Führt den Auftrag eines weiteren Programmstarts aus und holt das Fenster nach
+    /// vorn. Eine bereits geöffnete Datei wird nicht erneut geladen; beim Öffnen einer
+    /// anderen bleibt der bisherige Stand über „Rückgängig" erreichbar.
+    fn instanz_auftrag_ausfuehren(&mut self, ctx: &egui::Context, auftrag: InstanzAuftrag) {
+        ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        match auftrag {
+            InstanzAuftrag::Zeigen => {}
+            InstanzAuftrag::Parallel => {
+                self.toast_zeigen("Eine zweite Instanz von MZProtokoll wurde gestartet".to_string());
+            }
+            InstanzAuftrag::Oeffnen(pfad) => {
+                let gleich = self
+                    .save_path
+                    .as_ref()
+                    .is_some_and(|p| std::fs::canonicalize(p).unwrap_or_else(|_| p.clone()) == pfad);
+                if gleich {
+                    self.toast_zeigen("Die Datei ist hier bereits geöffnet".to_string());
+                    return;
+                }
+                match std::fs::read_to_string(&pfad) {
+                    Ok(content) => {
+                        let ungespeichert = self.ungespeichert();
+                        self.datei_uebernehmen(pfad, &content);
+                        if ungespeichert {
+                            self.toast_zeigen("Ungespeicherte Änderungen lassen sich mit „Rückgängig“ wiederherstellen".to_string());
+                        }
+                    }
+                    Err(e) => self.toast_zeigen(format!("Datei konnte nicht gelesen werden: {}", e)),
+                }
+            }
+        }
+    }
+
+    /// Schließt die App ohne weitere Nachfrage.
+    fn beenden(&mut self, ctx: &egui::Context) {
+        self.beenden_bestaetigt = true;
+        self.beenden_nach_dialog = false;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+    }
+
+    /// „Speichern und beenden": mit bekanntem Pfad sofort, sonst nach dem
+    /// Speichern-Dialog. Fehlt der Protokollführer, bleibt die App offen.
+    fn speichern_und_beenden(&mut self, ctx: &egui::Context) {
+        self.speichern();
+        if self.save_path.is_some() && !self.ungespeichert() {
+            self.beenden(ctx);
+        } else if self.save_path.is_none() && !self.show_pflichtfeld_hinweis {
+            self.beenden_nach_dialog = true;
+        }
+    }
+
+    /// Merkt sich den aktuellen Stand und die Änderungszeit der Datei als gespeichert.
+    fn stand_merken(&mut self) {
+        self.gespeicherter_stand = Some(self.vergleichsstand());
+        self.datei_zeit = self.datei_aenderungszeit();
+    }
+
+    /// Änderungszeit der geöffneten Datei (`None` = ungespeichert oder nicht lesbar).
+    fn datei_aenderungszeit(&self) -> Option<std::time::SystemTime> {
+        self.save_path.as_ref().and_then(|p| std::fs::metadata(p).ok()).and_then(|m| m.modified().ok())
+    }
+
+    /// Prüft höchstens alle zwei Sekunden, ob die geöffnete Datei außerhalb der App
+    /// geändert wurde (etwa in einem Texteditor), und zeigt dann den Abgleich.
+    fn externe_aenderung_pruefen(&mut self) {
+        if self.externe_aenderung.is_some() || self.datei_geprueft.elapsed() < std::time::Duration::from_secs(2) {
+            return;
+        }
+        self.datei_geprueft = std::time::Instant::now();
+        let zeit = self.datei_aenderungszeit();
+        if zeit.is_some() && zeit != self.datei_zeit {
+            self.datei_abgleichen(false);
+        }
+    }
+
+    /// Autospeicherung (`auto_speichern`): prüft jede Sekunde, ob sich der Stand seit dem
+    /// letzten Speichern geändert hat, und schreibt ihn, sobald er `auto_speichern_sekunden`
+    /// lang unverändert geblieben ist, still in die Datei. Ohne Speicherpfad, bei
+    /// schreibgeschützter Datei, fehlendem Protokollführer oder offenem Abgleich einer
+    /// externen Änderung wird nichts geschrieben.
+    fn auto_speichern_pruefen(&mut self) {
+        if !self.einstellungen.auto_speichern || self.auto_speichern_geprueft.elapsed() < std::time::Duration::from_secs(1) {
+            return;
+        }
+        self.auto_speichern_geprueft = std::time::Instant::now();
+        let Some(pfad) = self.save_path.clone() else {
+            self.auto_speichern_stand = None;
+            return;
+        };
+        if self.externe_aenderung.is_some()
+            || self.datei_aenderungszeit() != self.datei_zeit
+            || self.protokollant.name.trim().is_empty()
+            || schreibgeschuetzt(&pfad)
+        {
+            return;
+        }
+        let stand = self.vergleichsstand();
+        if self.gespeicherter_stand.as_ref() == Some(&stand) {
+            self.auto_speichern_stand = None;
+            return;
+        }
+        match &self.auto_speichern_stand {
+            Some((beobachtet, seit)) if *beobachtet == stand => {
+                if seit.elapsed() < std::time::Duration::from_secs(self.einstellungen.auto_speichern_sekunden) {
+                    return;
+                }
+            }
+            _ => {
+                self.auto_speichern_stand = Some((stand, std::time::Instant::now()));
+                return;
+            }
+        }
+        self.auto_speichern_stand = None;
+        self.erstellung_vermerken();
+        self.beschluesse_nummerieren();
+        // Die Eintragshistorie entsteht nur beim ausdrücklichen Speichern und Exportieren;
+        // bis dahin sammeln sich die Änderungen zu je einer Zeile pro Eintrag
+        self.eintrag_ids_vergeben();
+        match atomar_schreiben(&pfad, self.markdown_erstellen()) {
+            Ok(()) => self.stand_merken(),
+            Err(e) => self.toast_zeigen(format!("Automatisches Speichern fehlgeschlagen: {}", e)),
+        }
+    }
+
+    /// „Neu laden": vergleicht die Datei feldgenau mit dem Stand der App und öffnet
+    /// bei Unterschieden den Abgleich-Dialog. `manuell` = aus dem Menü aufgerufen,
+    /// dann gibt es auch ohne Unterschiede eine Rückmeldung.
+    fn datei_abgleichen(&mut self, manuell: bool) {
+        let Some(pfad) = self.save_path.clone() else {
+            if manuell {
+                self.toast_zeigen("Das Protokoll wurde noch nicht gespeichert".to_string());
+            }
+            return;
+        };
+        let zeit = self.datei_aenderungszeit();
+        let inhalt = match std::fs::read_to_string(&pfad) {
+            Ok(inhalt) => inhalt,
+            Err(e) => {
+                self.datei_zeit = zeit;
+                self.toast_zeigen(format!("Datei konnte nicht gelesen werden: {}", e));
+                return;
+            }
+        };
+        let bereiche = externe_bereiche_vergleichen(&self.markdown_erstellen(), &inhalt, self.gespeicherter_stand.as_deref());
+        if bereiche.is_empty() {
+            self.datei_zeit = zeit;
+            if manuell {
+                self.toast_zeigen("Die Datei entspricht dem Stand in der App".to_string());
+            }
+            return;
+        }
+        let datei_eintraege = protokoll_auszug_lesen(&inhalt, &self.einstellungen).eintraege;
+        let eintraege = eintraege_vergleichen(&self.eintraege, &datei_eintraege);
+        self.externe_aenderung = Some(ExterneAenderung { inhalt, zeit, bereiche, eintraege });
+    }
+
+    /// Übernimmt die im Abgleich gewählten Bereiche aus der Datei: Abschnitte werden im
+    /// Markdown der App ersetzt und neu eingelesen, Kopffelder einzeln gesetzt. Der
+    /// vorherige Stand bleibt über „Rückgängig" erreichbar.
+    fn externe_aenderung_uebernehmen(&mut self, aenderung: ExterneAenderung) {
+        self.datei_zeit = aenderung.zeit;
+        let gewaehlt: Vec<&ExternerBereich> = aenderung.bereiche.iter().filter(|b| b.uebernehmen).collect();
+        if gewaehlt.is_empty() {
+            return;
+        }
+        let lokal = markdown_abschnitte(&self.markdown_erstellen());
+        let datei = markdown_abschnitte(&aenderung.inhalt);
+        let mut md = String::new();
+        let mut gesehen: Vec<&String> = Vec::new();
+        for (name, _) in lokal.iter().chain(&datei) {
+            if gesehen.contains(&name) {
+                continue;
+            }
+            gesehen.push(name);
+            let aus_datei = gewaehlt.iter().any(|b| !b.kopffeld && b.name == *name);
+            let quelle = if aus_datei { &datei } else { &lokal };
+            if let Some((_, zeilen)) = quelle.iter().find(|(n, _)| n == name) {
+                for zeile in zeilen {
+                    md.push_str(zeile);
+                    md.push('\n');
+                }
+                md.push('\n');
+            }
+        }
+
+        // Fußzeilenwerte stehen nicht im zusammengesetzten Markdown
+        let erstellt_am = std::mem::take(&mut self.erstellt_am);
+        let erstellt_von = std::mem::take(&mut self.erstellt_von);
+        let bearbeitungszeit = self.bearbeitungszeit;
+        let bearbeitungszeit_datei = self.bearbeitungszeit_datei;
+        // Die höhere Eintragsnummer gewinnt, damit keine Nummer doppelt vergeben wird
+        let datei_naechste = aenderung
+            .inhalt
+            .lines()
+            .find_map(|l| l.trim().strip_prefix("**Nächste Eintragsnummer:**"))
+            .and_then(|rest| rest.trim().parse::<u32>().ok())
+            .unwrap_or(0);
+        md.push_str(&format!("**Nächste Eintragsnummer:** {}\n", self.naechste_eintrag_id.max(datei_naechste)));
+        self.sicherung_anlegen();
+        self.markdown_parsen(&md);
+        self.erstellt_am = erstellt_am;
+        self.erstellt_von = erstellt_von;
+        self.bearbeitungszeit = bearbeitungszeit;
+        self.bearbeitungszeit_datei = bearbeitungszeit_datei;
+        for b in gewaehlt.iter().filter(|b| b.kopffeld) {
+            let wert = b.datei.clone();
+            match b.name.as_str() {
+                "Titel" => self.titel = wert,
+                "Projekt" => self.projekt = wert,
+                "Datum" => self.datum_text = wert,
+                "Ort" => self.ort = wert,
+                "Protokoll erstellt am" => self.protokoll_datum = wert,
+                "Vorgänger" => self.vorgaenger = wert,
+                _ => {}
+            }
+        }
+        self.sort_personen();
+        if gewaehlt.len() == aenderung.bereiche.len() {
+            self.stand_merken();
+        }
+        let namen: Vec<&str> = gewaehlt.iter().map(|b| b.name.as_str()).collect();
+        self.audit_protokollieren(&format!("Externe Änderungen übernommen: {}", namen.join(", ")));
+    }
+
+    /// Markdown des aktuellen Stands ohne die Zeilen, die sich von selbst ändern
+    /// (Änderungszeitpunkt und Bearbeitungszeit).
+    fn vergleichsstand(&self) -> String {
+        self.markdown_erstellen()
+            .lines()
+            .filter(|l| !l.starts_with("**Geändert:**") && !l.starts_with("**Bearbeitungszeit:**"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// `true`, wenn das Protokoll seit dem letzten Speichern bzw. Laden geändert wurde;
+    /// ein nie gespeichertes Protokoll gilt als geändert, sobald es Inhalt hat.
+    fn ungespeichert(&self) -> bool {
+        match &self.gespeicherter_stand {
+            Some(stand) => *stand != self.vergleichsstand(),
+            None => !self.ist_leer(),
+        }
+    }
+
+    /// `true`, wenn weder Projekt, Titel, Ort, Personen noch Einträge erfasst sind.
+    /// Die leeren Platzhalterzeilen eines neuen Protokolls zählen nicht als Inhalt.
+    fn ist_leer(&self) -> bool {
+        let ohne_namen = |liste: &[Person]| liste.iter().all(|p| p.name.trim().is_empty());
+        self.projekt.trim().is_empty()
+            && self.titel.trim().is_empty()
+            && self.ort.trim().is_empty()
+            && self.ueber_meeting.trim().is_empty()
+            && ohne_namen(&self.teilnehmer)
+            && ohne_namen(&self.zur_kenntnis)
+            && self.eintraege.iter().all(|e| e.punkt.trim().is_empty() && e.notiz.trim().is_empty())
+    }
+
+    /// Sichert den aktuellen Zustand, bevor er durch Laden überschrieben wird:
+    /// in `self.sicherung` (für „Rückgängig") und in die Wiederherstellungsdatei
+    /// (übersteht auch einen Neustart). Ein leeres Protokoll (`ist_leer`, auch mit
+    /// leeren Platzhalterzeilen) wird nicht gesichert, damit eine ältere Sicherung
+    /// erhalten bleibt.
+    fn sicherung_anlegen(&mut self) {
+        if self.ist_leer() {
+            return;
+        }
+        let inhalt = self.markdown_erstellen();
+        if let Some(pfad) = wiederherstellungs_pfad() {
+            if let Some(dir) = pfad.parent() {
+                let _ = std::fs::create_dir_all(dir);
+            }
+            let _ = atomar_schreiben(&pfad, &inhalt);
+        }
+        self.sicherung = Some(Sicherung { inhalt, pfad: self.save_path.clone() });
+    }
+
+    /// Stellt das vor dem letzten Laden gesicherte Protokoll wieder her.
+    /// Ohne Sicherung aus dieser Sitzung wird die Wiederherstellungsdatei gelesen;
+    /// der Speicherpfad ist dann unbekannt und wird beim nächsten Speichern erfragt.
+    fn sicherung_wiederherstellen(&mut self) {
+        let sicherung = self.sicherung.take().or_else(|| {
+            let inhalt = std::fs::read_to_string(wiederherstellungs_pfad()?).ok()?;
+            Some(Sicherung { inhalt, pfad: None })
+        });
+        if let Some(sicherung) = sicherung {
+            self.markdown_parsen(&sicherung.inhalt);
+            self.sort_personen();
+            self.save_path = sicherung.pfad;
+            self.entwurf_pfad = None;
+        }
+    }
+
+    /// Liest einen MZProtokoll-Markdown-String ein und befüllt alle Felder
+    /// der App. Vorhandene Daten werden dabei vollständig überschrieben.
+    /// Der Parser ist zeilenbasiert und arbeitet mit einem Sektions-Zustandsautomaten.
+    fn markdown_parsen(&mut self, content: &str) {
+        self.projekt = String::new();
+        self.titel = String::new();
+        self.datum_text = String::new();
+        self.protokoll_datum = String::new();
+        self.ort = String::new();
+        self.protokollant = Person::new();
+        self.teilnehmer.clear();
+        self.zur_kenntnis.clear();
+        self.ueber_meeting = String::new();
+        self.status = Status::Entwurf;
+        self.sicherheit = Sicherheit::Intern;
+        self.eintraege.clear();
+        self.vorgaenger = String::new();
+        self.pdf_spalten = None;
+        self.naechstes_datum = String::new();
+        self.naechstes_ort = String::new();
+        self.naechste_agenda = String::new();
+        self.interner_anhang = String::new();
+        self.audit_log.clear();
+        self.erstellt_am = String::new();
+        self.erstellt_von = String::new();
+        self.bearbeitungszeit = std::time::Duration::ZERO;
+        self.bearbeitungszeit_datei = None;
+        self.naechste_eintrag_id = 0;
+
+        #[derive(PartialEq)]
+        enum Section {
+            Header,
+            Protokollfuehrer,
+            Teilnehmer,
+            ZurKenntnis,
+            UeberMeeting,
+            Status,
+            Sicherheit,
+            Eintraege,
+            NaechstesMeeting,
+            NurIntern,
+            AuditLog,
+        }
+
+        let mut section = Section::Header;
+        let mut table_rows_seen = 0u32;
+        let mut ueber_lines: Vec<&str> = Vec::new();
+        let mut intern_lines: Vec<&str> = Vec::new();
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+
+            // Erstellt-Metadaten parsen (stehen am Ende der Datei)
+            if trimmed.starts_with("**Erstellt:**") {
+                let rest = trimmed.trim_start_matches("**Erstellt:**").trim();
+                if let Some((datum, von)) = rest.split_once(" von ") {
+                    self.erstellt_am = datum.trim().to_string();
+                    self.erstellt_von = von.trim().to_string();
+                }
+                continue;
+            }
+            if let Some(rest) = trimmed.strip_prefix("**Bearbeitungszeit:**") {
+                self.bearbeitungszeit = dauer_parsen(rest).unwrap_or_default();
+                self.bearbeitungszeit_datei = dauer_parsen(rest);
+                continue;
+            }
+            if let Some(rest) = trimmed.strip_prefix("**Nächste Eintragsnummer:**") {
+                self.naechste_eintrag_id = rest.trim().parse().unwrap_or(0);
+                continue;
+            }
+
+            // Sektionswechsel bei ## Überschriften
+            if let Some(ueberschrift) = trimmed.strip_prefix("## ") {
+                if section == Section::UeberMeeting {
+                    self.ueber_meeting = ueber_lines.join("\n").trim().to_string();
+                    ueber_lines.clear();
+                }
+                if section == Section::NurIntern {
+                    self.interner_anhang = intern_lines.join("\n").trim().to_string();
+                    intern_lines.clear();
+                }
+
+                // Deutsche und übersetzte Überschriften (siehe ABSCHNITT_ALIASE)
+                let neue_section = match alias_aufloesen(ueberschrift, ABSCHNITT_ALIASE) {
+                    Some("Protokollführer") => Some(Section::Protokollfuehrer),
+                    Some("Teilnehmer") => Some(Section::Teilnehmer),
+                    Some("Zur Kenntnis") => Some(Section::ZurKenntnis),
+                    Some("Über dieses Meeting") => Some(Section::UeberMeeting),
+                    Some("Status") => Some(Section::Status),
+                    Some("Klassifizierung") => Some(Section::Sicherheit),
+                    Some("Einträge") => Some(Section::Eintraege),
+                    Some("Nächstes Meeting") => Some(Section::NaechstesMeeting),
+                    Some("Nur intern") => Some(Section::NurIntern),
+                    Some("Audit-Log") => Some(Section::AuditLog),
+                    _ => None,
+                };
+                if let Some(neue_section) = neue_section {
+                    if neue_section == Section::Eintraege {
+                        table_rows_seen = 0;
+                    }
+                    section = neue_section;
+                    continue;
+                }
+            }
+
+            match section {
+                Section::Header => {
+                    if trimmed.starts_with("# ") {
+                        self.titel = trimmed[2..].to_string();
+                    } else {
+                        // Kopffelder, ggf. mehrere pro Zeile („**Datum:** … | **Ort:** …")
+                        for part in trimmed.split(" | ") {
+                            let Some((name, wert)) = fett_feld(part) else {
+                                continue;
+                            };
+                            match alias_aufloesen(name, FELD_ALIASE) {
+                                Some("Projekt") => self.projekt = wert.to_string(),
+                                Some("Datum") => self.datum_text = wert.to_string(),
+                                Some("Protokoll erstellt am") => self.protokoll_datum = wert.to_string(),
+                                Some("Ort") => self.ort = wert.to_string(),
+                                Some("Vorgänger") => self.vorgaenger = wert.to_string(),
+                                Some("PDF-Spalten") => self.pdf_spalten = PdfSpalten::parsen(wert),
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                Section::Protokollfuehrer => {
+                    if !trimmed.is_empty() && trimmed != "---" {
+                        let (name, kuerzel) = name_kuerzel_parsen(trimmed);
+                        self.protokollant.name = name;
+                        if !kuerzel.is_empty() {
+                            self.protokollant.kuerzel = kuerzel;
+                            self.protokollant.kuerzel_manuell = true;
+                        }
+                    }
+                }
+                Section::Teilnehmer => {
+                    if trimmed.starts_with("- ") {
+                        let (name, kuerzel) = name_kuerzel_parsen(&trimmed[2..]);
+                        let mut p = Person::new();
+                        p.name = name;
+                        if !kuerzel.is_empty() {
+                            p.kuerzel = kuerzel;
+                            p.kuerzel_manuell = true;
+                        }
+                        self.teilnehmer.push(p);
+                    }
+                }
+                Section::ZurKenntnis => {
+                    if trimmed.starts_with("- ") {
+                        let (name, kuerzel) = name_kuerzel_parsen(&trimmed[2..]);
+                        let mut p = Person::new();
+                        p.name = name;
+                        if !kuerzel.is_empty() {
+                            p.kuerzel = kuerzel;
+                            p.kuerzel_manuell = true;
+                        }
+                        self.zur_kenntnis.push(p);
+                    }
+                }
+                Section::UeberMeeting => {
+                    if trimmed != "---" {
+                        ueber_lines.push(line);
+                    }
+                }
+                Section::Status => {
+                    if let Some(status) = trimmed.strip_prefix("- [x] ").and_then(Status::parsen) {
+                        self.status = status;
+                    }
+                }
+                Section::Sicherheit => {
+                    if let Some(sicherheit) = trimmed.strip_prefix("- [x] ").and_then(Sicherheit::parsen) {
+                        self.sicherheit = sicherheit;
+                    }
+                }
+                Section::Eintraege => {
+                    if trimmed.starts_with('|') {
+                        table_rows_seen += 1;
+                        // Zeile 1 = Kopfzeile, Zeile 2 = Trennlinie, ab Zeile 3 = Daten
+                        if table_rows_seen >= 3 {
+                            let cells = tabellenzeile_aufteilen(trimmed);
+                            if cells.len() >= 5 {
+                                self.eintraege.push(eintrag_aus_zellen(&cells, &self.einstellungen));
+                            }
+                        }
+                    }
+                }
+                Section::NaechstesMeeting => {
+                    if trimmed.starts_with("**") {
+                        for part in trimmed.split(" | ") {
+                            let Some((name, wert)) = fett_feld(part) else {
+                                continue;
+                            };
+                            match alias_aufloesen(name, FELD_ALIASE) {
+                                Some("Datum") => self.naechstes_datum = wert.to_string(),
+                                Some("Ort") => self.naechstes_ort = wert.to_string(),
+                                _ => {}
+                            }
+                        }
+                    } else if let Some(punkt) = trimmed.strip_prefix("- ") {
+                        if !self.naechste_agenda.is_empty() {
+                            self.naechste_agenda.push('\n');
+                        }
+                        self.naechste_agenda.push_str(punkt);
+                    }
+                }
+                Section::NurIntern => {
+                    if trimmed != "---" {
+                        intern_lines.push(line);
+                    }
+                }
+                Section::AuditLog => {
+                    if let Some(rest) = trimmed.strip_prefix("- ") {
+                        let teile: Vec<&str> = rest.splitn(3, " | ").collect();
+                        if teile.len() == 3 {
+                            self.audit_log.push(AuditEintrag {
+                                zeitpunkt: teile[0].trim().to_string(),
+                                benutzer: teile[1].trim().to_string(),
+                                aktion: teile[2].trim().to_string(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        // Restlichen "Über dieses Meeting"-Text flushen
+        if section == Section::UeberMeeting {
+            self.ueber_meeting = ueber_lines.join("\n").trim().to_string();
+        }
+        if section == Section::NurIntern {
+            self.interner_anhang = intern_lines.join("\n").trim().to_string();
+        }
+
+        // Mindestens je einen leeren Eintrag sicherstellen
+        if self.teilnehmer.is_empty() {
+            self.teilnehmer.push(Person::new());
+        }
+        if self.zur_kenntnis.is_empty() {
+            self.zur_kenntnis.push(Person::new());
+        }
+        if self.eintraege.is_empty() {
+            self.eintraege.push(Eintrag::new());
+        }
+        // Geladener Stand ist die Grundlage für die Eintragshistorie
+        self.eintrag_ids_vergeben();
+        self.eintrag_stand_merken();
+    }
+
+    /// Text, den ein ausgeschnittener Eintrag in der System-Zwischenablage hinterlässt
+    /// (seine Zeile der Markdown-Tabelle). Ein Strg+V mit genau diesem Text fügt den
+    /// Eintrag selbst statt des Textes ein.
+    fn eintrag_ablage_text(&self, e: &Eintrag) -> String {
+        eintrag_markdown_zeile(e, &self.art_label(&e.art), "")
+    }
+
+    /// Schneidet Eintrag `i` aus und legt ihn oben auf die Eintrags-Zwischenablage.
+    /// Der letzte verbleibende Eintrag wird durch eine leere Zeile ersetzt.
+    fn eintrag_ausschneiden(&mut self, ctx: &egui::Context, i: usize) {
+        if i >= self.eintraege.len() {
+            return;
+        }
+        let eintrag = self.eintraege.remove(i);
+        if self.eintraege.is_empty() {
+            self.eintraege.push(Eintrag::new());
+        }
+        ctx.copy_text(self.eintrag_ablage_text(&eintrag));
+        self.eintrag_ablage.push(eintrag);
+        if self.eintrag_ablage.len() > EINTRAG_ABLAGE_GROESSE {
+            self.eintrag_ablage.remove(0);
+        }
+        self.toast_zeigen(format!("Eintrag ausgeschnitten ({} in der Zwischenablage)", self.eintrag_ablage.len()));
+    }
+
+    /// Fügt den Eintrag `ablage_index` der Zwischenablage hinter Eintrag `nach` ein
+    /// (`None` = am Ende) und entfernt ihn aus der Ablage. Danach liegt der nächste
+    /// Eintrag in der System-Zwischenablage, sodass Strg+V mehrere nacheinander einfügt.
+    fn eintrag_einfuegen(&mut self, ctx: &egui::Context, nach: Option<usize>, ablage_index: usize) {
+        if ablage_index >= self.eintrag_ablage.len() {
+            return;
+        }
+        let mut eintrag = self.eintrag_ablage.remove(ablage_index);
+        // Aus einem anderen Protokoll: vorhandene Nummer nicht doppelt vergeben
+        if self.eintraege.iter().any(|e| e.id == eintrag.id) {
+            eintrag.id = 0;
+        }
+        let pos = nach.map_or(self.eintraege.len(), |i| i + 1).min(self.eintraege.len());
+        self.eintraege.insert(pos, eintrag);
+        self.focus_notiz = Some(pos);
+        if let Some(oben) = self.eintrag_ablage.last() {
+            ctx.copy_text(self.eintrag_ablage_text(oben));
+        }
+    }
+
+    /// Strg+X/Strg+V für ganze Einträge: Strg+X im Notizfeld ohne Textauswahl schneidet
+    /// den Eintrag aus, Strg+V fügt den zuletzt ausgeschnittenen dahinter ein (ohne Fokus
+    /// am Ende). Die Ereignisse werden verbraucht, bevor das Notizfeld sie sieht; Text
+    /// ausschneiden und einfügen funktioniert sonst wie gewohnt.
+    fn eintrag_ablage_tasten(&mut self, ctx: &egui::Context) {
+        let fokus = ctx.memory(|m| m.focused());
+        let zeile = fokus.and_then(|id| (0..self.eintraege.len()).find(|&i| egui::Id::new(("notiz", i)) == id));
+        let ohne_auswahl = fokus
+            .and_then(|id| egui::TextEdit::load_state(ctx, id))
+            .and_then(|state| state.cursor.char_range())
+            .is_none_or(|bereich| bereich.primary == bereich.secondary);
+        if let Some(i) = zeile.filter(|_| ohne_auswahl) {
+            if ctx.input(|inp| inp.events.iter().any(|e| matches!(e, egui::Event::Cut))) {
+                ctx.input_mut(|inp| inp.events.retain(|e| !matches!(e, egui::Event::Cut)));
+                self.eintrag_ausschneiden(ctx, i);
+                return;
+            }
+        }
+        if fokus.is_some() && zeile.is_none() {
+            return;
+        }
+        let Some(erwartet) = self.eintrag_ablage.last().map(|e| self.eintrag_ablage_text(e)) else {
+            return;
+        };
+        let ist_eintrag = |e: &egui::Event| matches!(e, egui::Event::Paste(text) if *text == erwartet);
+        if ctx.input(|inp| inp.events.iter().any(ist_eintrag)) {
+            ctx.input_mut(|inp| inp.events.retain(|e| !ist_eintrag(e)));
+            self.eintrag_einfuegen(ctx, zeile, self.eintrag_ablage.len() - 1);
+        }
+    }
+
+    /// „Kopfdaten kopieren": legt Projekt, Titel, Ort, Personen und Klassifizierung in
+    /// die System-Zwischenablage.
+    fn kopfdaten_kopieren(&mut self, ctx: &egui::Context) {
+        let personen = |liste: &[Person]| liste.iter().filter(|p| !p.name.trim().is_empty()).cloned().collect();
+        let kopf = Kopfdaten {
+            projekt: self.projekt.clone(),
+            titel: self.titel.clone(),
+            ort: self.ort.clone(),
+            teilnehmer: personen(&self.teilnehmer),
+            zur_kenntnis: personen(&self.zur_kenntnis),
+            sicherheit: Some(self.sicherheit.clone()),
+        };
+        ctx.copy_text(kopf.als_text());
+        self.toast_zeigen("Kopfdaten in die Zwischenablage kopiert".to_string());
+    }
+
+    /// „Kopfdaten einfügen": fordert den Inhalt der Zwischenablage an; übernommen wird
+    /// er in `kopfdaten_tasten`, sobald das Einfüge-Ereignis eintrifft.
+    fn kopfdaten_einfuegen(&mut self, ctx: &egui::Context) {
+        self.kopfdaten_anfrage = Some(std::time::Instant::now());
+        ctx.send_viewport_cmd(egui::ViewportCommand::RequestPaste);
+        ctx.request_repaint();
+    }
+
+    /// Übernimmt Kopfdaten aus einem Einfüge-Ereignis (angefordert oder per Strg+V).
+    /// Projekt, Titel, Ort und Klassifizierung werden überschrieben, sofern angegeben;
+    /// Personen kommen hinzu, wenn ihr Name noch fehlt.
+    fn kopfdaten_tasten(&mut self, ctx: &egui::Context) {
+        let ist_kopf = |e: &egui::Event| matches!(e, egui::Event::Paste(text) if text.trim_start().starts_with(KOPFDATEN_KENNUNG));
+        let eingefuegt = ctx.input(|inp| {
+            inp.events.iter().find_map(|e| match e {
+                egui::Event::Paste(text) => Some(text.clone()),
+                _ => None,
+            })
+        });
+        let Some(kopf) = eingefuegt.as_deref().map(str::trim_start).and_then(Kopfdaten::parsen) else {
+            // Ohne passendes Ereignis gilt die Anfrage nach einer Sekunde als gescheitert
+            let abgelaufen = self.kopfdaten_anfrage.is_some_and(|t| t.elapsed() > std::time::Duration::from_secs(1));
+            if eingefuegt.is_some() && self.kopfdaten_anfrage.is_some() || abgelaufen {
+                self.kopfdaten_anfrage = None;
+                self.toast_zeigen("Die Zwischenablage enthält keine Kopfdaten".to_string());
+            } else if self.kopfdaten_anfrage.is_some() {
+                ctx.request_repaint();
+            }
+            return;
+        };
+        ctx.input_mut(|inp| inp.events.retain(|e| !ist_kopf(e)));
+        self.kopfdaten_anfrage = None;
+        for (ziel, wert) in [(&mut self.projekt, kopf.projekt), (&mut self.titel, kopf.titel), (&mut self.ort, kopf.ort)] {
+            if !wert.is_empty() {
+                *ziel = wert;
+            }
+        }
+        if let Some(sicherheit) = kopf.sicherheit {
+            self.sicherheit_setzen(sicherheit);
+        }
+        let mut neu = 0;
+        for (liste, personen) in [(&mut self.teilnehmer, kopf.teilnehmer), (&mut self.zur_kenntnis, kopf.zur_kenntnis)] {
+            for p in personen {
+                if !liste.iter().any(|v| v.name.trim().eq_ignore_ascii_case(p.name.trim())) {
+                    liste.retain(|v| !v.name.trim().is_empty());
+                    liste.push(p);
+                    neu += 1;
+                }
+            }
+            if liste.is_empty() || liste.last().is_some_and(|p| !p.name.is_empty()) {
+                liste.push(Person::new());
+            }
+        }
+        self.sort_personen();
+        self.toast_zeigen(format!("Kopfdaten übernommen ({} Personen neu)", neu));
+    }
+
+    /// Vergibt fehlende bzw. doppelte Eintragsnummern aus `naechste_eintrag_id`, mindestens
+    /// oberhalb der höchsten vorhandenen; einmal vergebene Nummern werden nie wiederverwendet.
+    fn eintrag_ids_vergeben(&mut self) {
+        let hoechste = self.eintraege.iter().map(|e| e.id).max().unwrap_or(0);
+        let mut naechste = self.naechste_eintrag_id.max(hoechste + 1);
+        let mut vergeben = std::collections::HashSet::new();
+        for e in &mut self.eintraege {
+            if e.id == 0 || !vergeben.insert(e.id) {
+                e.id = naechste;
+                vergeben.insert(naechste);
+                naechste += 1;
+            }
+        }
+        self.naechste_eintrag_id = naechste;
+    }
+
+    /// Merkt sich den aktuellen Stand aller Einträge als Vergleichsbasis für das Audit-Log.
+    fn eintrag_stand_merken(&mut self) {
+        self.eintrag_stand = self.eintraege.iter().map(|e| (e.id, eintrag_felder(e))).collect();
+    }
+
+    /// Schreibt seit dem letzten Speichern angelegte, geänderte und entfernte Einträge
+    /// ins Audit-Log (je Eintrag eine Zeile mit Präfix `Eintrag #<id>`).
+    fn eintraege_protokollieren(&mut self) {
+        self.eintrag_ids_vergeben();
+        let mut aktionen = Vec::new();
+        for e in &self.eintraege {
+            let felder = eintrag_felder(e);
+            if felder.iter().all(|(_, wert)| wert.is_empty()) {
+                continue;
+            }
+            let titel = eintrag_titel(e);
+            match self.eintrag_stand.get(&e.id) {
+                None => aktionen.push(format!("Eintrag #{} angelegt: {}", e.id, titel)),
+                Some(alt) => {
+                    let geaendert: Vec<&str> = felder
+                        .iter()
+                        .zip(alt)
+                        .filter(|((_, neu), (_, alt))| neu != alt)
+                        .map(|((name, _), _)| *name)
+                        .collect();
+                    if !geaendert.is_empty() {
+                        aktionen.push(format!("Eintrag #{} geändert ({}): {}", e.id, geaendert.join(", "), titel));
+                    }
+                }
+            }
+        }
+        for (id, alt) in &self.eintrag_stand {
+            let leer = alt.iter().all(|(_, wert)| wert.is_empty());
+            if !leer && !self.eintraege.iter().any(|e| e.id == *id) {
+                aktionen.push(format!("Eintrag #{} entfernt", id));
+            }
+        }
+        for aktion in aktionen {
+            self.audit_protokollieren(&aktion);
+        }
+        self.eintrag_stand_merken();
+    }
+
+    /// Verzeichnis mit den exportierten Ständen dieses Protokolls
+    /// (`versand/<Dateiname>/` im Konfigurationsverzeichnis). Ungespeicherte
+    /// Protokolle verwenden den vorgeschlagenen Dateinamen.
+    fn versand_verzeichnis(&self) -> Option<std::path::PathBuf> {
+        let name = match &self.save_path {
+            Some(pfad) => pfad.file_stem()?.to_string_lossy().to_string(),
+            None => self.dateinamen_erstellen().trim_end_matches(".md").to_string(),
+        };
+        konfig_verzeichnis().map(|d| d.join("versand").join(name))
+    }
+
+    /// Gibt die Nummer des zuletzt exportierten Stands zurück (0 = noch kein Export).
+    fn letzte_versand_version(&self) -> u32 {
+        self.versand_verzeichnis()
+            .and_then(|d| std::fs::read_dir(d).ok())
+            .map(|eintraege| {
+                eintraege
+                    .filter_map(|e| e.ok())
+                    .filter_map(|e| {
+                        let name = e.file_name().to_string_lossy().to_string();
+                        name.strip_prefix('v')?.strip_suffix(".md")?.parse::<u32>().ok()
+                    })
+                    .max()
+                    .unwrap_or(0)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Vergleicht die Einträge mit dem zuletzt exportierten Stand.
+    /// Gibt `None` zurück, wenn das Protokoll noch nie exportiert wurde.
+    fn versand_diff(&self) -> Option<VersandDiff> {
+        let version = self.letzte_versand_version();
+        if version == 0 {
+            return None;
+        }
+        let pfad = self.versand_verzeichnis()?.join(format!("v{}.md", version));
+        let alt = protokoll_auszug_lesen(&std::fs::read_to_string(pfad).ok()?, &self.einstellungen);
+        Some(VersandDiff { version, aenderungen: eintraege_vergleichen(&alt.eintraege, &self.eintraege) })
+    }
+
+    /// Pfad der nächsten Version (`v<n+1>.md`) und der aktuelle Stand als deren Inhalt.
+    fn versand_stand(&self) -> Option<(std::path::PathBuf, String)> {
+        let dir = self.versand_verzeichnis()?;
+        let version = self.letzte_versand_version() + 1;
+        Some((dir.join(format!("v{}.md", version)), self.markdown_erstellen()))
+    }
+
+    /// Legt den aktuellen Stand als nächste Version (`v<n+1>.md`) ab.
+    /// Wird nach jedem PDF-Export aufgerufen und dient dem nächsten Vergleich.
+    fn versand_stand_sichern(&self) {
+        if let Some((pfad, inhalt)) = self.versand_stand() {
+            versand_stand_schreiben(&pfad, &inhalt);
+        }
+    }
+
+    /// Sammelt alle TODOs dieses Protokolls und der verknüpften Vorgängerprotokolle.
+    /// Gleichlautende Punkte werden zusammengefasst; maßgeblich für das Alter ist
+    /// das älteste Protokoll, in dem der Punkt vorkommt. Ein TODO, das ein neueres
+    /// Protokoll erledigt oder abgebrochen hat, entfällt (gleiche Eintragsnummer,
+    /// ersatzweise gleicher Text).
+    fn offene_punkte_sammeln(&self) -> Vec<OffenerPunkt> {
+        let mut punkte: Vec<OffenerPunkt> = Vec::new();
+        let mut geschlossen: Vec<(u32, String)> = Vec::new();
+        let mut aufnehmen = |eintraege: &[Eintrag], titel: &str, datum: Option<NaiveDate>| {
+            for e in eintraege.iter().filter(|e| e.art == Art::Todo && !e.notiz.trim().is_empty()) {
+                let schluessel = e.notiz.trim().to_lowercase();
+                if geschlossen.iter().any(|(id, text)| (e.id != 0 && *id == e.id) || *text == schluessel) {
+                    continue;
+                }
+                if let Some(p) = punkte.iter_mut().find(|p| p.notiz.trim().to_lowercase() == schluessel) {
+                    // Älteres Vorkommen gefunden: Herkunft und Alter übernehmen
+                    p.protokoll = titel.to_string();
+                    p.seit = datum.or(p.seit);
+                } else {
+                    punkte.push(OffenerPunkt {
+                        notiz: e.notiz.clone(),
+                        kuemmerer: e.kuemmerer.clone(),
+                        bis: e.bis.clone(),
+                        protokoll: titel.to_string(),
+                        seit: datum,
+                    });
+                }
+            }
+            geschlossen.extend(
+                eintraege
+                    .iter()
+                    .filter(|e| matches!(e.art, Art::Fertig | Art::Abgebrochen))
+                    .map(|e| (e.id, e.notiz.trim().to_lowercase())),
+            );
+        };
+        aufnehmen(&self.eintraege, &self.titel, datum_aus_text(&self.datum_text));
+
+        // Vorgängerkette verfolgen (mit Schutz vor zyklischen Verknüpfungen)
+        let mut besucht: Vec<std::path::PathBuf> = self.save_path.iter().cloned().collect();
+        let mut naechster = (!self.vorgaenger.is_empty())
+            .then(|| vorgaenger_pfad_aufloesen(&self.vorgaenger, self.save_path.as_deref()));
+        while let Some(pfad) = naechster.take() {
+            if besucht.contains(&pfad) {
+                break;
+            }
+            let Ok(content) = std::fs::read_to_string(&pfad) else {
+                break;
+            };
+            let auszug = protokoll_auszug_lesen(&content, &self.einstellungen);
+            aufnehmen(&auszug.eintraege, &auszug.titel, auszug.datum);
+            if !auszug.vorgaenger.is_empty() {
+                naechster = Some(vorgaenger_pfad_aufloesen(&auszug.vorgaenger, Some(&pfad)));
+            }
+            besucht.push(pfad);
+        }
+        punkte
+    }
+
+    /// Fügt übernommene Einträge an. Einträge, die einem vorhandenen Eintrag
+    /// ähneln (Punkt + Notiz, siehe `DUPLIKAT_SCHWELLE`), landen im
+    /// Zusammenführen-Dialog statt als Dublette in der Tabelle.
+    fn eintraege_einfuegen(&mut self, neue: Vec<Eintrag>) {
+        // Einzelnen leeren Platzhalter-Eintrag eines neuen Protokolls ersetzen
+        if self.eintraege.len() == 1
+            && self.eintraege[0].art == Art::Leer
+            && self.eintraege[0].punkt.trim().is_empty()
+            && self.eintraege[0].notiz.trim().is_empty()
+        {
+            self.eintraege.clear();
+        }
+        // Vorhandene Einträge werden im Dialog über ihre Nummer angesprochen
+        self.eintrag_ids_vergeben();
+        let vergleichstext = |e: &Eintrag| format!("{} {}", e.punkt, e.notiz);
+        let mut paare = Vec::new();
+        for mut neu in neue {
+            // Nummern stammen aus einem anderen Protokoll und werden neu vergeben
+            neu.id = 0;
+            neu.beschluss_nr.clear();
+            let text = vergleichstext(&neu);
+            let bester = self
+                .eintraege
+                .iter()
+                .map(|e| (e.id, text_aehnlichkeit(&text, &vergleichstext(e))))
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            match bester {
+                Some((vorhanden, aehnlichkeit)) if aehnlichkeit >= DUPLIKAT_SCHWELLE => {
+                    // Identische Einträge standardmäßig verwerfen, ähnliche zusammenführen
+                    let aktion = if aehnlichkeit >= 0.999 {
+                        DuplikatAktion::Verwerfen
+                    } else {
+                        DuplikatAktion::Zusammenfuehren
+                    };
+                    paare.push(DuplikatPaar { neu, vorhanden, aehnlichkeit, aktion });
+                }
+                _ => {
+                    neu.id = self.naechste_eintrag_id;
+                    self.naechste_eintrag_id += 1;
+                    self.eintraege.push(neu);
+                }
+            }
+        }
+        if !paare.is_empty() {
+            self.duplikat_dialog = Some(paare);
+        }
+    }
+
+    /// Setzt die im Zusammenführen-Dialog getroffenen Entscheidungen um.
+    /// Beim Zusammenführen werden neue Notizzeilen angehängt sowie leere
+    /// Kümmerer- und Bis-Felder aus dem übernommenen Eintrag ergänzt.
+    fn duplikate_anwenden(&mut self, paare: Vec<DuplikatPaar>) {
+        for paar in paare {
+            match paar.aktion {
+                DuplikatAktion::Verwerfen => {}
+                DuplikatAktion::BeideBehalten => self.eintraege.push(paar.neu),
+                DuplikatAktion::Zusammenfuehren => {
+                    let Some(ziel) = self.eintraege.iter_mut().find(|e| e.id == paar.vorhanden) else {
+                        self.eintraege.push(paar.neu);
+                        continue;
+                    };
+                    for zeile in paar.neu.notiz.lines().filter(|z| !z.trim().is_empty()) {
+                        if !ziel.notiz.lines().any(|z| text_normalisieren(z) == text_normalisieren(zeile)) {
+                            if !ziel.notiz.is_empty() {
+                                ziel.notiz.push('\n');
+                            }
+                            ziel.notiz.push_str(zeile);
+                        }
+                    }
+                    if ziel.kuemmerer.is_empty() {
+                        ziel.kuemmerer = paar.neu.kuemmerer;
+                    }
+                    if ziel.bis.is_empty() {
+                        ziel.bis = paar.neu.bis;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Übernimmt die offenen TODOs und unbeantworteten Fragen des verknüpften
+    /// Vorgängerprotokolls (mit Dublettenprüfung über `eintraege_einfuegen`).
+    fn offene_todos_uebernehmen(&mut self) {
+        if self.vorgaenger.is_empty() {
+            return;
+        }
+        let pfad = vorgaenger_pfad_aufloesen(&self.vorgaenger, self.save_path.as_deref());
+        let Ok(content) = std::fs::read_to_string(pfad) else {
+            return;
+        };
+        let auszug = protokoll_auszug_lesen(&content, &self.einstellungen);
+        let fragen = auszug.eintraege.into_iter().filter(Eintrag::offene_frage);
+        let offene = auszug
+            .todos
+            .into_iter()
+            .chain(fragen)
+            .filter(|e| !e.notiz.trim().is_empty())
+            .collect();
+        self.eintraege_einfuegen(offene);
+    }
+
+    /// Legt das Folgeprotokoll an: Kopfdaten und Personen werden übernommen, Datum und
+    /// Ort stammen aus dem Abschnitt „Nächstes Meeting", die vorläufige Agenda wird zu
+    /// AGENDA-Einträgen, unbeantwortete Fragen werden mitgenommen. Das bisherige
+    /// Protokoll wird als Vorgänger verknüpft.
+    fn folgeprotokoll_anlegen(&mut self, ctx: &egui::Context) {
+        let mut auswahl = self.ueberfuehrung_vorschlagen();
+        auswahl.tops.retain(|(_, gewaehlt)| *gewaehlt);
+        // TODOs holt „Offene TODOs übernehmen“ bei Bedarf; unbeantwortete Fragen wandern immer mit
+        auswahl.offene.retain(|(e, _)| e.art == Art::Frage);
+        self.ersetzen_anfragen(ctx, Ersetzen::Ueberfuehren(auswahl));
+    }
+
+    /// Führt einen Vorgang aus, der das aktuelle Protokoll ersetzt. Bei ungespeicherten
+    /// Änderungen kommt erst die Rückfrage „Speichern / Verwerfen / Abbrechen".
+    fn ersetzen_anfragen(&mut self, ctx: &egui::Context, aktion: Ersetzen) {
+        if self.ungespeichert() {
+            self.ersetzen_rueckfrage = Some(aktion);
+        } else {
+            self.ersetzen_ausfuehren(ctx, aktion);
+        }
+    }
+
+    /// Ersetzt das aktuelle Protokoll ohne weitere Nachfrage; der bisherige Stand
+    /// bleibt über „Rückgängig" erreichbar.
+    fn ersetzen_ausfuehren(&mut self, ctx: &egui::Context, aktion: Ersetzen) {
+        self.sicherung_anlegen();
+        let sicherung = self.sicherung.take();
+        match aktion {
+            Ersetzen::Neu => self.neu_anlegen(ctx),
+            Ersetzen::Ueberfuehren(auswahl) => self.ueberfuehren(ctx, auswahl),
+        }
+        self.sicherung = sicherung;
+    }
+
+    /// Vorauswahl für „In neues Protokoll überführen": die vorläufige Agenda, alle
+    /// offenen TODOs und unbeantworteten Fragen sind angehakt, die AGENDA-Einträge des laufenden Protokolls
+    /// (ohne Dubletten zur Agenda) stehen zusätzlich zur Wahl.
+    fn ueberfuehrung_vorschlagen(&self) -> Ueberfuehrung {
+        let mut tops: Vec<(Eintrag, bool)> = self
+            .naechste_agenda
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| {
+                let mut e = Eintrag::new();
+                e.art = Art::Agenda;
+                e.notiz = l.trim().to_string();
+                (e, true)
+            })
+            .collect();
+        for e in self.eintraege.iter().filter(|e| e.art == Art::Agenda) {
+            let text = if e.notiz.trim().is_empty() { &e.punkt } else { &e.notiz };
+            let schluessel = text_normalisieren(text);
+            if schluessel.is_empty() || tops.iter().any(|(t, _)| text_normalisieren(&t.notiz) == schluessel) {
+                continue;
+            }
+            let mut top = Eintrag::new();
+            top.art = Art::Agenda;
+            top.punkt = e.punkt.clone();
+            top.notiz = e.notiz.clone();
+            tops.push((top, false));
+        }
+        let offene = self
+            .eintraege
+            .iter()
+            .filter(|e| (e.art == Art::Todo || e.offene_frage()) && !e.notiz.trim().is_empty())
+            .map(|e| {
+                let mut todo = Eintrag::new();
+                todo.art = e.art.clone();
+                todo.punkt = e.punkt.clone();
+                todo.notiz = e.notiz.clone();
+                todo.kuemmerer = e.kuemmerer.clone();
+                todo.bis = e.bis.clone();
+                // Gleiche Nummer wie im Vorgänger, damit das Erledigen dort zugeordnet wird
+                todo.id = e.id;
+                (todo, true)
+            })
+            .collect();
+        Ueberfuehrung { titel: true, personen: true, tops, offene }
+    }
+
+    /// „Aus E-Mail importieren": wählt eine EML- oder Textdatei (separater Thread); der
+    /// Vorschlag erscheint anschließend im Dialog `email_import`.
+    fn email_importieren(&mut self) {
+        let (tx, rx) = mpsc::channel();
+        self.dialog_rx = Some(rx);
+        std::thread::spawn(move || {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("E-Mail", &["eml", "txt"])
+                .pick_file()
+            {
+                if let Ok(bytes) = std::fs::read(&path) {
+                    let _ = tx.send(DialogErgebnis::EmailImport(String::from_utf8_lossy(&bytes).to_string()));
+                }
+            }
+        });
+    }
+
+    /// Legt aus der Auswahl des E-Mail-Imports ein neues Protokoll an (Ort „E-Mail").
+    /// Das bisherige Protokoll bleibt über „Rückgängig" erreichbar.
+    fn email_import_uebernehmen(&mut self, ctx: &egui::Context, import: EmailImport) {
+        self.sicherung_anlegen();
+        let sicherung = self.sicherung.take();
+        self.neu_anlegen(ctx);
+        self.sicherung = sicherung;
+        self.titel = import.titel.trim().to_string();
+        self.ort = "E-Mail".to_string();
+        if let Some(datum) = import.datum {
+            self.datum_text = format!("{}, {}", wochentag_name(datum), datum.format("%d.%m.%Y"));
+        }
+        self.teilnehmer = import.teilnehmer.into_iter().filter(|(_, gewaehlt)| *gewaehlt).map(|(p, _)| p).collect();
+        self.eintraege = import.eintraege.into_iter().filter(|(_, gewaehlt)| *gewaehlt).map(|(e, _)| e).collect();
+        self.eintraege.push(Eintrag::new());
+        self.sort_personen();
+    }
+
+    /// Erzeugt das Folgeprotokoll aus der Auswahl des Überführen-Dialogs. Projekt,
+    /// Protokollführer und Klassifizierung werden immer übernommen.
+    fn ueberfuehren(&mut self, ctx: &egui::Context, auswahl: Ueberfuehrung) {
+        let mut neu = self.leeres_protokoll(ctx);
+        neu.projekt = self.projekt.clone();
+        if auswahl.titel {
+            neu.titel = self.titel.clone();
+        }
+        neu.ort = if self.naechstes_ort.is_empty() { self.ort.clone() } else { self.naechstes_ort.clone() };
+        if let Some((datum, _)) = termin_parsen(&self.naechstes_datum) {
+            neu.datum_text = format!("{}, {}", wochentag_name(datum), datum.format("%d.%m.%Y"));
+        }
+        neu.protokollant = std::mem::replace(&mut self.protokollant, Person::new());
+        if auswahl.personen {
+            neu.teilnehmer = std::mem::take(&mut self.teilnehmer);
+            neu.zur_kenntnis = std::mem::take(&mut self.zur_kenntnis);
+        }
+        neu.sicherheit = self.sicherheit.clone();
+        // Neue Einträge erhalten Nummern oberhalb aller Nummern des Vorgängers
+        self.eintrag_ids_vergeben();
+        neu.naechste_eintrag_id = self.naechste_eintrag_id;
+        let eintraege: Vec<Eintrag> = auswahl
+            .tops
+            .into_iter()
+            .chain(auswahl.offene)
+            .filter(|(_, gewaehlt)| *gewaehlt)
+            .map(|(e, _)| e)
+            .collect();
+        if !eintraege.is_empty() {
+            neu.eintraege = eintraege;
+        }
+        if let Some(ref path) = self.save_path {
+            neu.vorgaenger = path.to_string_lossy().to_string();
+        }
+        if neu.teilnehmer.is_empty() {
+            neu.teilnehmer.push(Person::new());
+        }
+        if neu.zur_kenntnis.is_empty() {
+            neu.zur_kenntnis.push(Person::new());
+        }
+        *self = neu;
+    }
+
+    /// Erzeugt eine iCalendar-Einladung (ICS) für das nächste Meeting.
+    /// Gibt `None` zurück, wenn kein gültiger Termin eingetragen ist.
+    fn ics_erstellen(&self) -> Option<String> {
+        let uid = format!("{}@mzprotokoll", Local::now().format("%Y%m%dT%H%M%S%f"));
+        let ort = if self.naechstes_ort.is_empty() { &self.ort } else { &self.naechstes_ort };
+        let agenda: Vec<String> = self
+            .naechste_agenda
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| format!("- {}", l.trim()))
+            .collect();
+        let beschreibung = if agenda.is_empty() {
+            String::new()
+        } else {
+            format!("Vorläufige Agenda:\n{}", agenda.join("\n"))
+        };
+        let titel = if self.titel.is_empty() { "Meeting" } else { self.titel.as_str() };
+        self.ics_termin(&uid, titel, &self.naechstes_datum, ort, &beschreibung, "")
+    }
+
+    /// Baut einen iCalendar-Termin. Mit Uhrzeit entsteht ein einstündiger, ohne Uhrzeit
+    /// ein ganztägiger Termin; `link` (leer = keiner) landet in der Eigenschaft `URL`.
+    fn ics_termin(&self, uid: &str, zusammenfassung: &str, termin: &str, ort: &str, beschreibung: &str, link: &str) -> Option<String> {
+        let (datum, uhrzeit) = termin_parsen(termin)?;
+        let jetzt = Local::now();
+        let mut ics = String::from("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//MZProtokoll//DE\r\nBEGIN:VEVENT\r\n");
+        ics.push_str(&format!("UID:{}\r\n", uid));
+        ics.push_str(&format!("DTSTAMP:{}\r\n", jetzt.naive_utc().format("%Y%m%dT%H%M%SZ")));
+        match uhrzeit {
+            Some(zeit) => {
+                let beginn = datum.and_time(zeit);
+                let ende = beginn + chrono::Duration::hours(1);
+                ics.push_str(&format!("DTSTART:{}\r\n", beginn.format("%Y%m%dT%H%M%S")));
+                ics.push_str(&format!("DTEND:{}\r\n", ende.format("%Y%m%dT%H%M%S")));
+            }
+            None => {
+                let folgetag = datum.succ_opt().unwrap_or(datum);
+                ics.push_str(&format!("DTSTART;VALUE=DATE:{}\r\n", datum.format("%Y%m%d")));
+                ics.push_str(&format!("DTEND;VALUE=DATE:{}\r\n", folgetag.format("%Y%m%d")));
+            }
+        }
+        ics.push_str(&format!("SUMMARY:{}\r\n", ics_escapen(zusammenfassung)));
+        if !ort.is_empty() {
+            ics.push_str(&format!("LOCATION:{}\r\n", ics_escapen(ort)));
+        }
+        if !beschreibung.is_empty() {
+            ics.push_str(&format!("DESCRIPTION:{}\r\n", ics_escapen(beschreibung)));
+        }
+        if !link.is_empty() {
+            ics.push_str(&format!("URL:{}\r\n", link));
+        }
+        ics.push_str("END:VEVENT\r\nEND:VCALENDAR\r\n");
+        Some(ics_falten(&ics))
+    }
+
+    /// `true`, wenn Homeserver und Raum für Matrix-Zusammenfassungen eingestellt sind.
+    fn matrix_eingerichtet(&self) -> bool {
+        !self.einstellungen.matrix_homeserver.trim().is_empty() && !self.einstellungen.matrix_raum.trim().is_empty()
+    }
+
+    /// Baut die Zusammenfassung für Matrix: Titel, Entscheidungen und offene TODOs
+    /// mit Kümmerern. Gibt Klartext und HTML (`formatted_body`) zurück.
+    fn matrix_zusammenfassung(&self) -> (String, String) {
+        let titel = if self.titel.is_empty() { "Meeting" } else { self.titel.as_str() };
+        let kopf = if self.projekt.trim().is_empty() {
+            format!("{} ({})", titel, self.datum_text)
+        } else {
+            format!("{} – {} ({})", self.projekt.trim(), titel, self.datum_text)
+        };
+        let mut text = format!("Protokoll freigegeben: {}\n", kopf);
+        let mut html = format!("<p><strong>Protokoll freigegeben:</strong> {}</p>", xml_escapen(&kopf));
+        let erste_zeile = |e: &Eintrag| e.notiz.lines().find(|l| !l.trim().is_empty()).unwrap_or("").trim().to_string();
+        // Querverweis auf den TOP, unter dem der Eintrag steht
+        let nummern = top_nummern(&self.eintraege, self.einstellungen.top_nummerierung);
+        let verweis = |i: usize| match top_verweis(nummern[i].0, &nummern[i].1) {
+            Some(top) => format!(" [{}]", top),
+            None => String::new(),
+        };
+        let entscheidungen: Vec<String> = self
+            .eintraege
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.art == Art::Entscheidung)
+            .map(|(i, e)| {
+                let zeile = if e.beschluss_nr.is_empty() { erste_zeile(e) } else { format!("{}: {}", e.beschluss_nr, erste_zeile(e)) };
+                zeile + &verweis(i)
+            })
+            .collect();
+        let todos: Vec<String> = self
+            .eintraege
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.art == Art::Todo)
+            .map(|(i, e)| {
+                let mut zeile = erste_zeile(e);
+                if !e.kuemmerer.trim().is_empty() {
+                    zeile.push_str(&format!(" → {}", e.kuemmerer.trim()));
+                }
+                if !e.bis.trim().is_empty() {
+                    zeile.push_str(&format!(" (bis {})", e.bis.trim()));
+                }
+                zeile + &verweis(i)
+            })
+            .collect();
+        for (ueberschrift, punkte) in [
+            (format!("{} ({})", self.art_label(&Art::Entscheidung), entscheidungen.len()), &entscheidungen),
+            (format!("{} offen ({})", self.art_label(&Art::Todo), todos.len()), &todos),
+        ] {
+            if punkte.is_empty() {
+                continue;
+            }
+            text.push_str(&format!("\n{}\n", ueberschrift));
+            html.push_str(&format!("<p><strong>{}</strong></p><ul>", xml_escapen(&ueberschrift)));
+            for punkt in punkte {
+                text.push_str(&format!("- {}\n", punkt));
+                html.push_str(&format!("<li>{}</li>", xml_escapen(punkt)));
+            }
+            html.push_str("</ul>");
+        }
+        (text, html)
+    }
+
+    /// Postet die Zusammenfassung in den eingestellten Matrix-Raum (separater Thread).
+    fn matrix_posten(&mut self) {
+        let (text, html) = self.matrix_zusammenfassung();
+        let homeserver = self.einstellungen.matrix_homeserver.trim().to_string();
+        let raum = self.einstellungen.matrix_raum.trim().to_string();
+        let (tx, rx) = mpsc::channel();
+        self.matrix_rx = Some(rx);
+        std::thread::spawn(move || {
+            let ergebnis = match zugangsdaten_laden("matrix") {
+                None => Err("Kein Matrix-Zugangstoken hinterlegt (Menü „Zugangsdaten“)".to_string()),
+                Some(token) => matrix_nachricht_senden(&homeserver, &raum, &token, &text, &html),
+            };
+            let _ = tx.send(ergebnis);
+        });
+    }
+
+    /// Ersetzt die Platzhalter einer E-Mail-Vorlage: `{titel}`, `{projekt}`, `{datum}`,
+    /// `{ort}`, `{protokollfuehrer}`, `{offene_todos}` (eine Zeile je TODO) und `{signatur}`.
+    fn email_platzhalter_ersetzen(&self, vorlage: &str) -> String {
+        let offene_todos: Vec<String> = self
+            .eintraege
+            .iter()
+            .filter(|e| e.art == Art::Todo)
+            .map(|e| {
+                let mut zeile = format!("- {}", e.notiz.lines().find(|l| !l.trim().is_empty()).unwrap_or("").trim());
+                let details: Vec<String> = [
+                    (!e.kuemmerer.trim().is_empty()).then(|| e.kuemmerer.trim().to_string()),
+                    (!e.bis.trim().is_empty()).then(|| format!("bis {}", e.bis.trim())),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+                if !details.is_empty() {
+                    zeile.push_str(&format!(" ({})", details.join(", ")));
+                }
+                zeile
+            })
+            .collect();
+        let offene_todos = if offene_todos.is_empty() { "- keine".to_string() } else { offene_todos.join("\n") };
+        vorlage
+            .replace("{titel}", &self.titel)
+            .replace("{projekt}", &self.projekt)
+            .replace("{datum}", &self.datum_text)
+            .replace("{ort}", &self.ort)
+            .replace("{protokollfuehrer}", &self.protokollant.name)
+            .replace("{offene_todos}", &offene_todos)
+            .replace("{signatur}", &self.profil.signatur)
+            .trim_end()
+            .to_string()
+    }
+
+    /// Öffnet eine neue E-Mail im Standard-Mailprogramm (`mailto:`) mit Betreff und
+    /// Text aus der Vorlage des Projekts.
+    fn per_email_versenden(&mut self) {
+        let (betreff, text) = self.einstellungen.email_vorlage(&self.projekt);
+        let betreff = self.email_platzhalter_ersetzen(&betreff).replace('\n', " ");
+        let text = self.email_platzhalter_ersetzen(&text).replace('\n', "\r\n");
+        url_oeffnen(&format!("mailto:?subject={}&body={}", url_kodieren(&betreff), url_kodieren(&text)));
+    }
+
+    /// Regelbasierte Zusammenfassung für „Über dieses Meeting": wichtigste Agenda-Punkte,
+    /// Anzahl der Entscheidungen und TODOs sowie die nächste Fälligkeit.
+    fn zusammenfassung_regelbasiert(&self) -> String {
+        let anzahl = |art: Art| self.eintraege.iter().filter(|e| e.art == art).count();
+        let mehrzahl = |n: usize, einzahl: &str, plural: &str| format!("{} {}", n, if n == 1 { einzahl } else { plural });
+        let mut saetze = Vec::new();
+        let agenda: Vec<String> = self
+            .eintraege
+            .iter()
+            .filter(|e| e.art == Art::Agenda)
+            .map(eintrag_titel)
+            .filter(|t| !t.is_empty())
+            .take(3)
+            .collect();
+        match agenda.len() {
+            0 => {}
+            1 => saetze.push(format!("Besprochen wurde {}.", agenda[0])),
+            n => saetze.push(format!("Besprochen wurden {} und {}.", agenda[..n - 1].join(", "), agenda[n - 1])),
+        }
+        let entscheidungen = anzahl(Art::Entscheidung);
+        let todos = anzahl(Art::Todo);
+        let fertig = anzahl(Art::Fertig);
+        if entscheidungen + todos > 0 {
+            let mut teile = Vec::new();
+            if entscheidungen > 0 {
+                teile.push(format!("{} getroffen", mehrzahl(entscheidungen, "Entscheidung wurde", "Entscheidungen wurden")));
+            }
+            if todos > 0 {
+                teile.push(format!("{} vereinbart", mehrzahl(todos, "offene Aufgabe", "offene Aufgaben")));
+            }
+            saetze.push(format!("{}.", teile.join(", ")));
+        }
+        if fertig > 0 {
+            saetze.push(format!("{} erledigt.", mehrzahl(fertig, "Aufgabe ist", "Aufgaben sind")));
+        }
+        let naechste = self
+            .eintraege
+            .iter()
+            .filter(|e| e.art == Art::Todo)
+            .filter_map(|e| NaiveDate::parse_from_str(e.bis.trim(), "%d.%m.%Y").ok())
+            .min();
+        if let Some(datum) = naechste {
+            saetze.push(format!("Die nächste Aufgabe ist am {} fällig.", datum.format("%d.%m.%Y")));
+        }
+        if saetze.is_empty() {
+            saetze.push("Es wurden keine Entscheidungen oder Aufgaben festgehalten.".to_string());
+        }
+        saetze.join(" ")
+    }
+
+    /// Erzeugt einen Vorschlag für „Über dieses Meeting". Mit `llm_endpunkt` fragt ein
+    /// separater Thread das lokale Sprachmodell, sonst entsteht er sofort regelbasiert.
+    fn zusammenfassung_erzeugen(&mut self) {
+        let endpunkt = self.einstellungen.llm_endpunkt.trim().to_string();
+        if endpunkt.is_empty() {
+            self.zusammenfassung_vorschlag = Some(self.zusammenfassung_regelbasiert());
+            return;
+        }
+        if self.zusammenfassung_rx.is_some() {
+            return;
+        }
+        let mut protokoll = format!("Titel: {}\nDatum: {}\n", self.titel, self.datum_text);
+        for e in self.eintraege.iter().filter(|e| e.art != Art::Leer) {
+            protokoll.push_str(&format!("- {}: {}", self.art_label(&e.art), eintrag_titel(e)));
+            if !e.punkt.trim().is_empty() && !e.notiz.trim().is_empty() {
+                protokoll.push_str(&format!(" – {}", e.notiz.trim().replace('\n', " ")));
+            }
+            if !e.kuemmerer.trim().is_empty() {
+                protokoll.push_str(&format!(" (Kümmerer {}", e.kuemmerer.trim()));
+                protokoll.push_str(&if e.bis.trim().is_empty() { ")".to_string() } else { format!(", bis {})", e.bis.trim()) });
+            }
+            protokoll.push('\n');
+        }
+        let modell = self.einstellungen.llm_modell.trim().to_string();
+        let (tx, rx) = mpsc::channel();
+        self.zusammenfassung_rx = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send(llm_zusammenfassung(&endpunkt, &modell, &protokoll));
+        });
+    }
+
+    /// Stabiler Schlüssel eines Termins aus Titel und Datum (`mzprotokoll-<titel>-<JJJJMMTT>`),
+    /// Grundlage der UIDs von Kalenderterminen und Aufgaben.
+    fn termin_schluessel(&self, datum: NaiveDate) -> String {
+        let titel_teil: String = self.titel.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+        format!("mzprotokoll-{}-{}", titel_teil.to_lowercase(), datum.format("%Y%m%d"))
+    }
+
+    /// Gleicht die TODOs des Protokolls mit der CalDAV-Aufgabenliste ab (separater Thread).
+    /// `hochladen = false` fragt nur den Rückkanal ab (beim Öffnen einer Datei):
+    /// auf dem Server erledigte Aufgaben werden lokal auf FERTIG gesetzt.
+    /// Mit `hochladen = true` werden zusätzlich offene TODOs als VTODO geschrieben
+    /// und bereits übertragene, lokal erledigte Aufgaben abgeschlossen.
+    fn aufgaben_synchronisieren(&mut self, hochladen: bool) {
+        let url = self.einstellungen.caldav_aufgaben_url.trim().to_string();
+        if url.is_empty() {
+            if hochladen {
+                self.toast_zeigen("Keine Aufgabenliste eingestellt (caldav_aufgaben_url in einstellungen.toml)".to_string());
+            }
+            return;
+        }
+        if self.aufgaben_rx.is_some() {
+            return;
+        }
+        let Some(datum) = datum_aus_text(&self.datum_text) else {
+            if hochladen {
+                self.toast_zeigen("Ohne Meeting-Datum lassen sich keine Aufgaben zuordnen".to_string());
+            }
+            return;
+        };
+        self.eintrag_ids_vergeben();
+        let schluessel = self.termin_schluessel(datum);
+        let aufgaben: Vec<CaldavAufgabe> = self
+            .eintraege
+            .iter()
+            .filter(|e| matches!(e.art, Art::Todo | Art::Fertig | Art::Abgebrochen) && e.id != 0)
+            .map(|e| self.caldav_aufgabe(e, &schluessel))
+            .collect();
+        if aufgaben.is_empty() {
+            if hochladen {
+                self.toast_zeigen("Das Protokoll enthält keine TODOs".to_string());
+            }
+            return;
+        }
+        let zugang = CaldavZugang {
+            url: if url.ends_with('/') { url } else { format!("{}/", url) },
+            benutzer: self.einstellungen.caldav_benutzer.trim().to_string(),
+            passwort: zugangsdaten_laden("caldav"),
+        };
+        let (tx, rx) = mpsc::channel();
+        self.aufgaben_rx = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send(aufgaben_abgleichen(&zugang, &aufgaben, hochladen));
+        });
+    }
+
+    /// Baut die CalDAV-Aufgabe zu einem TODO: erste Notizzeile als Titel, Herkunft,
+    /// Kümmerer und Notiz als Beschreibung, Link auf die Protokolldatei.
+    fn caldav_aufgabe(&self, e: &Eintrag, schluessel: &str) -> CaldavAufgabe {
+        let titel = if self.titel.is_empty() { "Meeting" } else { self.titel.as_str() };
+        let mut beschreibung = format!("Aus dem Protokoll „{}“ vom {}", titel, self.datum_text);
+        let nummern = top_nummern(&self.eintraege, self.einstellungen.top_nummerierung);
+        if let Some((top, nummer)) = self.eintraege.iter().position(|x| std::ptr::eq(x, e)).map(|i| &nummern[i]) {
+            if let Some(verweis) = top_verweis(*top, nummer) {
+                beschreibung.push_str(&format!(", {}", verweis));
+            }
+        }
+        if !e.kuemmerer.trim().is_empty() {
+            beschreibung.push_str(&format!("\nKümmerer: {}", e.kuemmerer.trim()));
+        }
+        beschreibung.push_str(&format!("\n\n{}", e.notiz.trim()));
+        CaldavAufgabe {
+            id: e.id,
+            uid: format!("{}-todo{}", schluessel, e.id),
+            offen: e.art == Art::Todo,
+            zusammenfassung: e.notiz.lines().find(|l| !l.trim().is_empty()).unwrap_or("TODO").trim().to_string(),
+            beschreibung,
+            kategorie: self.projekt.trim().to_string(),
+            faellig: datum_aus_text(&e.bis),
+            link: self.protokoll_link(),
+        }
+    }
+
+    /// `file://`-Link auf die gespeicherte Protokolldatei (leer = noch nicht gespeichert).
+    fn protokoll_link(&self) -> String {
+        let Some(pfad) = self.save_path.as_ref() else {
+            return String::new();
+        };
+        let pfad = std::fs::canonicalize(pfad).unwrap_or_else(|_| pfad.clone());
+        let pfad = pfad.to_string_lossy().replace('\\', "/");
+        let kodiert: Vec<String> = pfad.trim_start_matches('/').split('/').map(url_kodieren).collect();
+        format!("file:///{}", kodiert.join("/"))
+    }
+
+    /// „Fälligkeit in Kalender eintragen" für einen TODO-Eintrag: legt am Bis-Datum einen
+    /// ganztägigen Termin im vdir-Kalender an (`kalender_vdir`) bzw. – ohne vdir-Kalender –
+    /// eine Aufgabe in der CalDAV-Aufgabenliste, jeweils mit Link zum Protokoll. Die UID
+    /// bleibt je Eintrag gleich, erneutes Eintragen aktualisiert den Termin.
+    fn frist_in_kalender_eintragen(&mut self, i: usize) {
+        let Some(faellig) = self
+            .eintraege
+            .get(i)
+            .filter(|e| e.art == Art::Todo)
+            .and_then(|e| datum_aus_text(&e.bis))
+        else {
+            self.toast_zeigen("Nur TODOs mit gültigem Bis-Datum lassen sich eintragen".to_string());
+            return;
+        };
+        self.eintrag_ids_vergeben();
+        let schluessel = self.termin_schluessel(datum_aus_text(&self.datum_text).unwrap_or(faellig));
+        let aufgabe = self.caldav_aufgabe(&self.eintraege[i], &schluessel);
+        if let Some(kalender) = einstellungspfad(&self.einstellungen.kalender_vdir) {
+            if !kalender.is_dir() {
+                self.toast_zeigen(format!("Kalender nicht gefunden: {}", kalender.display()));
+                return;
+            }
+            let name = format!("{}-frist{}", schluessel, aufgabe.id);
+            let uid = format!("{}@mzprotokoll", name);
+            let zusammenfassung = format!("Frist: {}", aufgabe.zusammenfassung);
+            let termin = faellig.format("%d.%m.%Y").to_string();
+            let Some(ics) = self.ics_termin(&uid, &zusammenfassung, &termin, "", &aufgabe.beschreibung, &aufgabe.link) else {
+                return;
+            };
+            let anzeigename = kalender_name(&kalender);
+            let meldung = if std::fs::write(kalender.join(format!("{}.ics", name)), ics).is_ok() {
+                format!("Frist am {} in „{}“ eingetragen", termin, anzeigename)
+            } else {
+                format!("Kalender „{}“ ist nicht beschreibbar", anzeigename)
+            };
+            self.toast_zeigen(meldung);
+            return;
+        }
+        let url = self.einstellungen.caldav_aufgaben_url.trim().to_string();
+        if url.is_empty() {
+            self.toast_zeigen("Kein Kalender eingestellt (kalender_vdir oder caldav_aufgaben_url in einstellungen.toml)".to_string());
+            return;
+        }
+        if self.aufgaben_rx.is_some() {
+            self.toast_zeigen("Die Aufgaben-Synchronisation läuft noch".to_string());
+            return;
+        }
+        let zugang = CaldavZugang {
+            url: if url.ends_with('/') { url } else { format!("{}/", url) },
+            benutzer: self.einstellungen.caldav_benutzer.trim().to_string(),
+            passwort: zugangsdaten_laden("caldav"),
+        };
+        let (tx, rx) = mpsc::channel();
+        self.aufgaben_rx = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send(aufgaben_abgleichen(&zugang, &[aufgabe], true));
+        });
+    }
+
+    /// `true`, wenn eine Redmine-URL eingestellt ist.
+    fn redmine_eingerichtet(&self) -> bool {
+        !self.einstellungen.redmine_url.trim().is_empty()
+    }
+
+    /// Fragt den Status aller in Notizen erwähnten Redmine-Tickets ab (separater Thread).
+    /// Ohne `manuell` (beim Öffnen einer Datei) bleiben fehlende Einstellungen und ein
+    /// Protokoll ohne Tickets stumm.
+    fn redmine_status_aktualisieren(&mut self, manuell: bool) {
+        if !self.redmine_eingerichtet() {
+            if manuell {
+                self.toast_zeigen("Kein Redmine-Server eingestellt (redmine_url in einstellungen.toml)".to_string());
+            }
+            return;
+        }
+        if self.redmine_rx.is_some() {
+            if manuell {
+                self.toast_zeigen("Die Redmine-Abfrage läuft noch".to_string());
+            }
+            return;
+        }
+        let url = self.einstellungen.redmine_url.trim().to_string();
+        let mut tickets: Vec<u32> = Vec::new();
+        for e in &self.eintraege {
+            for ticket in redmine_tickets(&e.notiz, &url) {
+                if !tickets.contains(&ticket) {
+                    tickets.push(ticket);
+                }
+            }
+        }
+        if tickets.is_empty() {
+            if manuell {
+                self.toast_zeigen("Das Protokoll erwähnt keine Redmine-Tickets".to_string());
+            }
+            return;
+        }
+        let schluessel = zugangsdaten_laden("redmine");
+        let (tx, rx) = mpsc::channel();
+        self.redmine_rx = Some(rx);
+        std::thread::spawn(move || {
+            let ergebnis = match redmine_status_laden(&url, schluessel.as_deref(), &tickets) {
+                Ok(status) => RedmineErgebnis::Status { status, manuell },
+                Err(fehler) => RedmineErgebnis::Fehler(fehler),
+            };
+            let _ = tx.send(ergebnis);
+        });
+    }
+
+    /// „Als Redmine-Ticket anlegen" für einen TODO-Eintrag: erste Notizzeile als Betreff,
+    /// Herkunft, Kümmerer und Notiz als Beschreibung, Bis-Datum als Fälligkeit. Die
+    /// Ticketnummer wird danach als `#123` an die Notiz angehängt.
+    fn redmine_ticket_anlegen(&mut self, i: usize) {
+        if !self.redmine_eingerichtet() {
+            self.toast_zeigen("Kein Redmine-Server eingestellt (redmine_url in einstellungen.toml)".to_string());
+            return;
+        }
+        let Some(projekt) = self.einstellungen.redmine_projekt(&self.projekt).map(str::to_string) else {
+            self.toast_zeigen("Kein Redmine-Projekt eingestellt (redmine_projekt in einstellungen.toml)".to_string());
+            return;
+        };
+        let Some(schluessel) = zugangsdaten_laden("redmine") else {
+            self.toast_zeigen("Kein Redmine-API-Key hinterlegt (Menü „Zugangsdaten“)".to_string());
+            return;
+        };
+        if self.redmine_rx.is_some() {
+            self.toast_zeigen("Die Redmine-Abfrage läuft noch".to_string());
+            return;
+        }
+        if self.eintraege.get(i).is_none_or(|e| e.art != Art::Todo || e.notiz.trim().is_empty()) {
+            self.toast_zeigen("Nur TODOs mit Notiz lassen sich als Ticket anlegen".to_string());
+            return;
+        }
+        self.eintrag_ids_vergeben();
+        let aufgabe = self.caldav_aufgabe(&self.eintraege[i], "");
+        let url = self.einstellungen.redmine_url.trim().to_string();
+        let (tx, rx) = mpsc::channel();
+        self.redmine_rx = Some(rx);
+        std::thread::spawn(move || {
+            let ergebnis = redmine_ticket_erstellen(&url, &schluessel, &projekt, &aufgabe.zusammenfassung, &aufgabe.beschreibung, aufgabe.faellig)
+                .map(|ticket| {
+                    // Status des neuen Tickets gleich mitnehmen; fehlt er, bleibt das Badge leer
+                    let status = redmine_status_laden(&url, Some(&schluessel), &[ticket]).unwrap_or_default();
+                    RedmineErgebnis::Angelegt { eintrag: aufgabe.id, ticket, status }
+                });
+            let _ = tx.send(ergebnis.unwrap_or_else(RedmineErgebnis::Fehler));
+        });
+    }
+
+    /// Erinnert nach dem Meeting (Ende der Präsentation oder Speichern als Entwurf) an das
+    /// Finalisieren: Frist sind `finalisierung_frist` Arbeitstage nach dem Meeting, frühestens
+    /// ab heute. Die Erinnerung wird im Audit-Log vermerkt und so je Protokoll nur einmal erzeugt.
+    fn finalisierung_erinnern(&mut self) {
+        let wege = self.einstellungen.finalisierung_erinnerung.clone();
+        if wege.is_empty()
+            || self.status != Status::Entwurf
+            || self.eintraege.iter().all(|e| e.notiz.trim().is_empty())
+            || self.audit_log.iter().any(|a| a.aktion.starts_with(FINALISIERUNG_VERMERK))
+        {
+            return;
+        }
+        let heute = Local::now().date_naive();
+        let meeting = datum_aus_text(&self.datum_text);
+        let basis = meeting.map_or(heute, |d| d.max(heute));
+        let frist = self.einstellungen.feiertage.plus_arbeitstage(basis, self.einstellungen.finalisierung_frist);
+        let frist_text = frist.format("%d.%m.%Y").to_string();
+        let titel = if self.titel.is_empty() { "Meeting" } else { self.titel.as_str() };
+        let betreff = format!("Protokoll finalisieren bis {}", frist_text);
+        let text = format!("Das Protokoll „{}“ vom {} ist noch ein Entwurf.", titel, self.datum_text);
+        let mut erfolgt = Vec::new();
+        let mut fehler = Vec::new();
+        for weg in wege {
+            match weg {
+                Erinnerungsweg::Benachrichtigung => {
+                    // Ohne Desktop-Benachrichtigung bleibt die Meldung in der App
+                    erfolgt.push(if desktop_benachrichtigung(&betreff, &text) { "Benachrichtigung" } else { "Hinweis in der App" }.to_string());
+                }
+                Erinnerungsweg::Kalender => {
+                    let Some(kalender) = einstellungspfad(&self.einstellungen.kalender_vdir).filter(|k| k.is_dir()) else {
+                        fehler.push("kein Kalender (kalender_vdir)".to_string());
+                        continue;
+                    };
+                    let name = format!("{}-finalisieren", self.termin_schluessel(meeting.unwrap_or(heute)));
+                    let uid = format!("{}@mzprotokoll", name);
+                    let Some(ics) = self.ics_termin(&uid, &betreff, &frist_text, "", &text, &self.protokoll_link()) else {
+                        continue;
+                    };
+                    if std::fs::write(kalender.join(format!("{}.ics", name)), ics).is_ok() {
+                        erfolgt.push(format!("Kalender „{}“", kalender_name(&kalender)));
+                    } else {
+                        fehler.push(format!("Kalender „{}“ nicht beschreibbar", kalender_name(&kalender)));
+                    }
+                }
+                Erinnerungsweg::Email => {
+                    url_oeffnen(&format!(
+                        "mailto:{}?subject={}&body={}",
+                        self.profil.email.trim(),
+                        url_kodieren(&betreff),
+                        url_kodieren(&text)
+                    ));
+                    erfolgt.push("E-Mail".to_string());
+                }
+            }
+        }
+        let mut meldung = betreff.clone();
+        if !erfolgt.is_empty() {
+            meldung.push_str(&format!(" – Erinnerung: {}", erfolgt.join(", ")));
+            self.audit_protokollieren(&format!("{}{}", FINALISIERUNG_VERMERK, frist_text));
+        }
+        if !fehler.is_empty() {
+            meldung.push_str(&format!(" ({})", fehler.join(", ")));
+        }
+        self.toast_zeigen(meldung);
+    }
+
+    /// Trägt Meeting und Folgetermin direkt in den eingestellten vdir-Kalender
+    /// (`kalender_vdir`, z. B. von vdirsyncer synchronisiert und von khal gelesen) ein.
+    /// Die UID ergibt sich aus Titel und Datum: erneutes Eintragen aktualisiert den
+    /// Termin, und der Folgetermin wird vom Meeting des Folgeprotokolls überschrieben.
+    fn kalender_eintragen(&mut self) {
+        let Some(kalender) = einstellungspfad(&self.einstellungen.kalender_vdir) else {
+            self.toast_zeigen("Kein Kalender eingestellt (kalender_vdir in einstellungen.toml)".to_string());
+            return;
+        };
+        if !kalender.is_dir() {
+            self.toast_zeigen(format!("Kalender nicht gefunden: {}", kalender.display()));
+            return;
+        }
+        let ort_folge = if self.naechstes_ort.is_empty() { self.ort.clone() } else { self.naechstes_ort.clone() };
+        let termine = [
+            (self.datum_text.clone(), self.ort.clone(), self.ueber_meeting.clone()),
+            (self.naechstes_datum.clone(), ort_folge, self.naechste_agenda.clone()),
+        ];
+        let mut eingetragen = 0;
+        let mut fehler = false;
+        for (termin, ort, beschreibung) in termine {
+            let Some((datum, _)) = termin_parsen(&termin) else {
+                continue;
+            };
+            let name = self.termin_schluessel(datum);
+            let uid = format!("{}@mzprotokoll", name);
+            let titel = if self.titel.is_empty() { "Meeting" } else { self.titel.as_str() };
+            let Some(ics) = self.ics_termin(&uid, titel, &termin, &ort, beschreibung.trim(), &self.protokoll_link()) else {
+                continue;
+            };
+            if std::fs::write(kalender.join(format!("{}.ics", name)), ics).is_ok() {
+                eingetragen += 1;
+            } else {
+                fehler = true;
+            }
+        }
+        let name = kalender_name(&kalender);
+        let meldung = match (eingetragen, fehler) {
+            (_, true) => format!("Kalender „{}“ ist nicht beschreibbar", name),
+            (0, false) => "Kein gültiger Termin eingetragen".to_string(),
+            (1, false) => format!("1 Termin in „{}“ eingetragen", name),
+            (n, false) => format!("{} Termine in „{}“ eingetragen", n, name),
+        };
+        self.toast_zeigen(meldung);
+    }
+
+    /// „Archivieren": verschiebt das freigegebene Protokoll samt PDF (und ggf. XML-Begleitdatei)
+    /// in den Unterordner `archiv_schema` unter `archiv_verzeichnis`, merkt den neuen Pfad
+    /// und setzt die Dateien schreibgeschützt. Liegt neben der Markdown-Datei kein PDF
+    /// gleichen Namens, wird es im Archiv neu erzeugt.
+    fn archivieren(&mut self) {
+        let Some(archiv) = einstellungspfad(&self.einstellungen.archiv_verzeichnis) else {
+            self.toast_zeigen("Kein Archiv eingestellt (archiv_verzeichnis in einstellungen.toml)".to_string());
+            return;
+        };
+        let Some(alt) = self.save_path.clone() else {
+            self.toast_zeigen("Das Protokoll muss vor dem Archivieren gespeichert sein".to_string());
+            return;
+        };
+        if self.status != Status::Freigegeben {
+            self.toast_zeigen("Nur freigegebene Protokolle lassen sich archivieren".to_string());
+            return;
+        }
+        if self.protokollant.name.trim().is_empty() {
+            self.show_pflichtfeld_hinweis = true;
+            return;
+        }
+        let ordnername = |text: &str| -> String {
+            let name: String = text
+                .trim()
+                .chars()
+                .map(|c| if c.is_alphanumeric() || " -_.".contains(c) { c } else { '_' })
+                .collect();
+            name.trim_matches('.').trim().to_string()
+        };
+        let projekt = match ordnername(&self.projekt) {
+            name if name.is_empty() => "Ohne Projekt".to_string(),
+            name => name,
+        };
+        let datum = datum_aus_text(&self.datum_text).unwrap_or_else(|| Local::now().date_naive());
+        let unterordner = self
+            .einstellungen
+            .archiv_schema
+            .replace("{projekt}", &projekt)
+            .replace("{jahr}", &datum.format("%Y").to_string())
+            .replace("{monat}", &datum.format("%m").to_string());
+        let ordner = archiv.join(unterordner.trim_matches('/'));
+        let Some(dateiname) = alt.file_name() else {
+            return;
+        };
+        let neu = ordner.join(dateiname);
+        if neu == alt {
+            self.toast_zeigen("Das Protokoll liegt bereits im Archiv".to_string());
+            return;
+        }
+        if neu.exists() {
+            self.toast_zeigen(format!("Im Archiv existiert bereits {}", neu.display()));
+            return;
+        }
+        if std::fs::create_dir_all(&ordner).is_err() {
+            self.toast_zeigen(format!("Verzeichnis nicht verfügbar: {}", ordner.display()));
+            return;
+        }
+
+        // Status und Audit-Eintrag gehören in die archivierte Fassung; schlägt das
+        // Schreiben fehl, wird beides zurückgenommen.
+        let (status_vorher, audit_vorher) = (self.status, self.audit_log.len());
+        if self.einstellungen.status_zustaende.contains(&Status::Archiviert) {
+            self.status_setzen(Status::Archiviert);
+        }
+        self.audit_protokollieren(&format!("Archiviert nach {}", neu.display()));
+        self.save_path = Some(neu.clone());
+        self.speichern();
+        if !neu.is_file() {
+            self.status = status_vorher;
+            self.audit_log.truncate(audit_vorher);
+            self.save_path = Some(alt);
+            self.toast_zeigen(format!("Archivieren fehlgeschlagen: {}", neu.display()));
+            return;
+        }
+        let _ = std::fs::remove_file(&alt);
+
+        let mut pdf_fehler = None;
+        if alt.with_extension("pdf").is_file() {
+            for endung in ["pdf", "xml"] {
+                let quelle = alt.with_extension(endung);
+                if quelle.is_file() {
+                    let _ = datei_verschieben(&quelle, &neu.with_extension(endung));
+                }
+            }
+        } else if let Some(schrift) = self.schrift_laden() {
+            // Das PDF entsteht im Hintergrund und wird danach schreibgeschützt
+            match self.pdf_export_vorbereiten(&neu.with_extension("pdf"), schrift) {
+                Ok(export) => {
+                    let ziel = neu.clone();
+                    self.pdf_im_hintergrund(export, PdfDanach::Archiv, move || {
+                        for endung in ["pdf", "xml"] {
+                            schreibschutz_setzen(&ziel.with_extension(endung));
+                        }
+                    });
+                }
+                Err(fehler) => pdf_fehler = Some(fehler),
+            }
+        } else {
+            pdf_fehler = Some("Schrift fehlt".to_string());
+        }
+        for endung in ["md", "pdf", "xml"] {
+            schreibschutz_setzen(&neu.with_extension(endung));
+        }
+        if let Some(fehler) = pdf_fehler {
+            self.toast_zeigen(format!("Archiviert ohne PDF ({}): {}", fehler, neu.display()));
+        } else {
+            self.toast_zeigen(format!("Archiviert: {}", neu.display()));
+        }
+    }
+
+    /// Öffnet einen Datei-Speichern-Dialog (separater Thread) für die ICS-Einladung.
+    fn ics_exportieren(&mut self) {
+        let Some(ics) = self.ics_erstellen() else {
+            return;
+        };
+        let name_part: String = self.titel.chars().filter(|c| c.is_alphabetic()).collect();
+        let filename = format!("MZProtokoll_{}__Einladung.ics", name_part);
+        let (tx, rx) = mpsc::channel();
+        self.dialog_rx = Some(rx);
+        std::thread::spawn(move || {
+            if let Some(path) = rfd::FileDialog::new()
+                .set_file_name(&filename)
+                .add_filter("iCalendar", &["ics"])
+                .save_file()
+            {
+                let _ = std::fs::write(&path, &ics);
+                let _ = tx.send(DialogErgebnis::IcsExport);
+            }
+        });
+    }
+
+    /// Öffnet einen Datei-Dialog (separater Thread) zur Auswahl des Vorgängerprotokolls.
+    fn vorgaenger_verknuepfen(&mut self) {
+        let (tx, rx) = mpsc::channel();
+        self.dialog_rx = Some(rx);
+        std::thread::spawn(move || {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("Markdown", &["md"])
+                .pick_file()
+            {
+                let _ = tx.send(DialogErgebnis::Vorgaenger(path));
+            }
+        });
+    }
+
+    /// Generiert einen vorgeschlagenen Dateinamen für den PDF-Export.
+    /// Format: `MZProtokoll_<Titel>__<JJJJ-MM-TT>.pdf`
+    fn pdf_dateinamen_erstellen(&self) -> String {
+        let name_part: String = self.titel.chars().filter(|c| c.is_alphabetic()).collect();
+        let datum = Local::now().format("%Y-%m-%d").to_string();
+        format!("MZProtokoll_{}__{}.pdf", name_part, datum)
+    }
+
+    /// Sucht auf dem System nach einer passenden Schriftfamilie für den PDF-Export.
+    /// Probiert nacheinander Liberation Sans, Noto Sans und DejaVu Sans.
+    /// Gibt `None` zurück, wenn keine Schrift gefunden wird.
+    fn schrift_laden(&self) -> Option<genpdf::fonts::FontFamily<genpdf::fonts::FontData>> {
+        // Liest Schriften zur Laufzeit vom System – keine Schriften werden eingebettet.
+
+        // 1. Linux: Schriftfamilien mit Standard-Benennung (Name-Regular.ttf, Name-Bold.ttf, ...)
+        #[cfg(not(windows))]
+        {
+            let schrift_familien = [
+                ("/usr/share/fonts/liberation",          "LiberationSans"),
+                ("/usr/share/fonts/noto",                "NotoSans"),
+                ("/usr/share/fonts/TTF",                 "LiberationSans"),
+                ("/usr/share/fonts/TTF",                 "NotoSans"),
+                ("/usr/share/fonts/truetype/liberation", "LiberationSans"),
+                ("/usr/share/fonts/truetype/noto",       "NotoSans"),
+            ];
+            for (pfad, familie) in schrift_familien {
+                if let Ok(schrift) = genpdf::fonts::from_files(pfad, familie, None) {
+                    return Some(schrift);
+                }
+            }
+        }
+
+        // 2. Einzelne .ttf-Dateien (Windows-Systemschriften + Linux DejaVu als Fallback)
+        #[cfg(windows)]
+        let einzel_schriften = [
+            ("C:\\Windows\\Fonts\\arial.ttf",   "C:\\Windows\\Fonts\\arialbd.ttf"),
+            ("C:\\Windows\\Fonts\\verdana.ttf", "C:\\Windows\\Fonts\\verdanab.ttf"),
+            ("C:\\Windows\\Fonts\\calibri.ttf", "C:\\Windows\\Fonts\\calibrib.ttf"),
+            ("C:\\Windows\\Fonts\\segoeui.ttf", "C:\\Windows\\Fonts\\segoeuib.ttf"),
+        ];
+        #[cfg(not(windows))]
+        let einzel_schriften = [
+            ("/usr/share/fonts/TTF/DejaVuSans.ttf",                    "/usr/share/fonts/TTF/DejaVuSans-Bold.ttf"),
+            ("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",        "/usr/share/fonts/truetype/dejavu/DejaVuSans-Bold.ttf"),
+            ("/usr/share/fonts/TTF/DejaVuSans.ttf",                    "/usr/share/fonts/TTF/DejaVuSans-Bold.ttf"),
+            ("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",        "/usr/share/fonts/truetype/dejavu/DejaVuSans-Bold.ttf"),
+        ];
+        for (regular_path, bold_path) in einzel_schriften {
+            if let Ok(regular_data) = std::fs::read(regular_path) {
+                let bold_data = std::fs::read(bold_path).unwrap_or_else(|_| regular_data.clone());
+                if let (Ok(regular), Ok(bold), Ok(italic), Ok(bold_italic)) = (
+                    genpdf::fonts::FontData::new(regular_data.clone(), None),
+                    genpdf::fonts::FontData::new(bold_data.clone(), None),
+                    genpdf::fonts::FontData::new(regular_data, None),
+                    genpdf::fonts::FontData::new(bold_data, None),
+                ) {
+                    return Some(genpdf::fonts::FontFamily { regular, bold, italic, bold_italic });
+                }
+            }
+        }
+        None
+    }
+
+    /// Prüft mit den echten Metriken der PDF-Schrift, ob ein Text in seiner Tabellenspalte
+    /// (Index in `PdfSpalten`) umbricht oder ein einzelnes Wort breiter als die
+    /// Spalte ist und abgeschnitten würde. Gibt dann einen Hinweistext und `true` für
+    /// „abgeschnitten" zurück. TODO-Zeilen werden fett gesetzt (`fett`); weggelassene
+    /// Spalten erzeugen keine Warnung.
+    fn pdf_spaltenwarnung(&self, text: &str, spalte: usize, fett: bool) -> Option<(String, bool)> {
+        let gewichte = self.pdf_spalten().0;
+        if text.trim().is_empty() || gewichte[spalte] == 0 {
+            return None;
+        }
+        let metrik = self
+            .pdf_schriftmetrik
+            .get_or_init(|| self.schrift_laden().map(genpdf::fonts::FontCache::new))
+            .as_ref()?;
+        let mut stil = genpdf::style::Style::new().with_font_size(self.pdf_profil.schriftgroesse());
+        if fett {
+            stil = stil.bold();
+        }
+        // A4-Breite abzüglich Seitenränder, anteilig nach Gewicht, abzüglich Zellenabstand
+        let [_, rechts, _, links] = self.pdf_profil.raender_mm();
+        let inhalt = 210.0 - links - rechts;
+        let gesamt: usize = gewichte.iter().sum();
+        let innenabstand = if spalte == 0 { 2.0 } else { 4.0 };
+        let breite = inhalt * gewichte[spalte] as f64 / gesamt as f64 - innenabstand;
+        let leerzeichen: f64 = stil.str_width(metrik, " ").into();
+        let mut zeilen = 1;
+        let mut zeile = 0.0;
+        for wort in text.split_whitespace() {
+            let wortbreite: f64 = stil.str_width(metrik, wort).into();
+            if wortbreite > breite {
+                return Some((format!("„{}“ ist breiter als die PDF-Spalte und wird abgeschnitten", wort), true));
+            }
+            if zeile > 0.0 && zeile + leerzeichen + wortbreite > breite {
+                zeilen += 1;
+                zeile = wortbreite;
+            } else if zeile > 0.0 {
+                zeile += leerzeichen + wortbreite;
+            } else {
+                zeile = wortbreite;
+            }
+        }
+        (zeilen > 1).then(|| (format!("Bricht im PDF auf {} Zeilen um", zeilen), false))
+    }
+
+    /// Lädt eine Festbreitenschrift für Code in Notizen (Liberation Mono, DejaVu Sans Mono
+    /// bzw. Consolas/Courier New unter Windows). Gibt `None` zurück, wenn keine gefunden wird –
+    /// Code erscheint dann in der normalen Schrift.
+    fn mono_schrift_laden(&self) -> Option<genpdf::fonts::FontFamily<genpdf::fonts::FontData>> {
+        #[cfg(not(windows))]
+        {
+            let schrift_familien = [
+                ("/usr/share/fonts/liberation",          "LiberationMono"),
+                ("/usr/share/fonts/TTF",                 "LiberationMono"),
+                ("/usr/share/fonts/truetype/liberation", "LiberationMono"),
+            ];
+            for (pfad, familie) in schrift_familien {
+                if let Ok(schrift) = genpdf::fonts::from_files(pfad, familie, None) {
+                    return Some(schrift);
+                }
+            }
+        }
+
+        #[cfg(windows)]
+        let einzel_schriften = [
+            ("C:\\Windows\\Fonts\\consola.ttf", "C:\\Windows\\Fonts\\consolab.ttf"),
+            ("C:\\Windows\\Fonts\\cour.ttf",    "C:\\Windows\\Fonts\\courbd.ttf"),
+        ];
+        #[cfg(not(windows))]
+        let einzel_schriften = [
+            ("/usr/share/fonts/TTF/DejaVuSansMono.ttf",             "/usr/share/fonts/TTF/DejaVuSansMono-Bold.ttf"),
+            ("/usr/share/fonts/truetype/dejavu/DejaVuSansMono.ttf", "/usr/share/fonts/truetype/dejavu/DejaVuSansMono-Bold.ttf"),
+        ];
+        for (regular_path, bold_path) in einzel_schriften {
+            if let Ok(regular_data) = std::fs::read(regular_path) {
+                let bold_data = std::fs::read(bold_path).unwrap_or_else(|_| regular_data.clone());
+                if let (Ok(regular), Ok(bold), Ok(italic), Ok(bold_italic)) = (
+                    genpdf::fonts::FontData::new(regular_data.clone(), None),
+                    genpdf::fonts::FontData::new(bold_data.clone(), None),
+                    genpdf::fonts::FontData::new(regular_data, None),
+                    genpdf::fonts::FontData::new(bold_data, None),
+                ) {
+                    return Some(genpdf::fonts::FontFamily { regular, bold, italic, bold_italic });
+                }
+            }
+        }
+        None
+    }
+
+    /// Fügt den gesamten Protokollinhalt (Kopfdaten, Eintrags-Tabelle, Links)
+    /// in das übergebene genpdf-Dokument ein.
+    /// Wird zweimal aufgerufen: einmal für den Vorberechnungsdurchlauf
+    /// (Seitenanzahl ermitteln) und einmal für den eigentlichen Export.
+    /// `mono` ist die einmal je Export geladene Festbreitenschrift (`mono_schrift_laden`).
+    fn pdf_inhalt_hinzufuegen(
+        &self,
+        doc: &mut genpdf::Document,
+        mono: Option<genpdf::fonts::FontFamily<genpdf::fonts::FontData>>,
+    ) {
+        let profil = self.pdf_profil;
+        let small = genpdf::style::Style::new().with_font_size(profil.schriftgroesse());
+        let small_bold = genpdf::style::Style::new().bold().with_font_size(profil.schriftgroesse());
+        let heading_style = genpdf::style::Style::new().bold().with_font_size(profil.titelgroesse());
+        // Vertikale Abstände skalieren mit dem gewählten Dichte-Profil
+        let abstand = profil.abstand();
+        // Festbreitenschrift für `Code` und ```-Blöcke in Notizen (falls vorhanden)
+        let mono = mono.map(|f| doc.add_font_family(f));
+        let sprache = self.pdf_sprache;
+
+        if self.einstellungen.pdf_deckblatt {
+            self.pdf_deckblatt_hinzufuegen(doc);
+        }
+
+        // Projekt
+        if !self.projekt.is_empty() {
+            doc.push(
+                genpdf::elements::Paragraph::new(&self.projekt)
+                    .styled(small),
+            );
+        }
+
+        // Titel
+        doc.push(
+            genpdf::elements::Paragraph::new(&self.titel)
+                .styled(heading_style),
+        );
+        doc.push(genpdf::elements::Break::new(0.5 * abstand));
+
+        // Datum | Ort | Protokoll erstellt am (nur wenn abweichend vom Meetingtag)
+        let mut meta_parts = Vec::new();
+        if !self.datum_text.is_empty() {
+            meta_parts.push(format!("{}: {}", sprache.t("Datum"), sprache.datum_text(&self.datum_text)));
+        }
+        if !self.ort.is_empty() {
+            meta_parts.push(format!("{}: {}", sprache.t("Ort"), self.ort));
+        }
+        if self.protokoll_abweichend_erstellt() {
+            meta_parts.push(format!("{}: {}", sprache.t("Protokoll erstellt am"), sprache.datum_text(&self.protokoll_datum)));
+        }
+        if !meta_parts.is_empty() {
+            doc.push(genpdf::elements::Paragraph::new(meta_parts.join("  |  ")).styled(small));
+            doc.push(genpdf::elements::Break::new(0.5 * abstand));
+        }
+
+        // Trennlinie
+        doc.push(Trennlinie::new(
+            self.einstellungen.pdf_trennlinie_staerke,
+            self.einstellungen.pdf_trennlinie_farbe,
+        ));
+        doc.push(genpdf::elements::Break::new(0.5 * abstand));
+
+        // Protokollführer, Teilnehmer, Zur Kenntnis, Über dieses Meeting
+        // als zweispaltige Tabelle, damit die Werte bündig starten
+        {
+            let mut info_table = genpdf::elements::TableLayout::new(vec![3, 11]);
+
+            // Protokollführer
+            if !self.protokollant.name.is_empty() {
+                let name = self.pdf_person(&self.protokollant);
+                let _ = info_table.row()
+                    .element(genpdf::elements::Paragraph::new(sprache.t("Protokollführer")).styled(small_bold).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
+                    .element(genpdf::elements::Paragraph::new(name).styled(small).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
+                    .push();
+            }
+
+            // Teilnehmer
+            let tn: Vec<_> = self.teilnehmer.iter().filter(|t| !t.name.is_empty()).collect();
+            if !tn.is_empty() {
+                let namen: Vec<String> = tn.iter().map(|t| self.pdf_person(t)).collect();
+                let _ = info_table.row()
+                    .element(genpdf::elements::Paragraph::new(sprache.t("Teilnehmer")).styled(small_bold).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
+                    .element(genpdf::elements::Paragraph::new(namen.join(", ")).styled(small).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
+                    .push();
+            }
+
+            // Zur Kenntnis
+            let zk: Vec<_> = self.zur_kenntnis.iter().filter(|z| !z.name.is_empty()).collect();
+            if !zk.is_empty() {
+                let namen: Vec<String> = zk.iter().map(|z| self.pdf_person(z)).collect();
+                let _ = info_table.row()
+                    .element(genpdf::elements::Paragraph::new(sprache.t("Zur Kenntnis")).styled(small_bold).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
+                    .element(genpdf::elements::Paragraph::new(namen.join(", ")).styled(small).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
+                    .push();
+            }
+
+            // Über dieses Meeting
+            if !self.ueber_meeting.is_empty() {
+                let _ = info_table.row()
+                    .element(genpdf::elements::Paragraph::new(sprache.t("Über dieses Meeting")).styled(small_bold).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
+                    .element(genpdf::elements::Paragraph::new(self.pdf_text(&self.ueber_meeting)).styled(small).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
+                    .push();
+            }
+
+            // Status (angebotene Zustände, der aktuelle angekreuzt)
+            {
+                let zustaende = self.status_auswahl();
+                let spalten = zustaende.len().max(4);
+                let mut cb_table = genpdf::elements::TableLayout::new(vec![1; spalten]);
+                let mut zeile = cb_table.row();
+                for i in 0..spalten {
+                    let text = match zustaende.get(i) {
+                        Some(s) if *s == self.status => format!("[x] {}", sprache.t(s.label())),
+                        Some(s) => format!("[  ] {}", sprache.t(s.label())),
+                        None => String::new(),
+                    };
+                    zeile = zeile.element(genpdf::elements::Paragraph::new(text).styled(small));
+                }
+                let _ = zeile.push();
+                let _ = info_table.row()
+                    .element(genpdf::elements::Paragraph::new(sprache.t("Status")).styled(small_bold).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
+                    .element(cb_table.padded(genpdf::Margins::trbl(1, 0, 1, 0)))
+                    .push();
+            }
+
+            // Klassifizierung
+            {
+                let entries: Vec<String> = Sicherheit::all()
+                    .iter()
+                    .map(|s| {
+                        if *s == self.sicherheit {
+                            format!("[x] {}", sprache.t(s.label()))
+                        } else {
+                            format!("[  ] {}", sprache.t(s.label()))
+                        }
+                    })
+                    .collect();
+                let mut cb_table = genpdf::elements::TableLayout::new(vec![1, 1, 1, 1]);
+                let _ = cb_table.row()
+                    .element(genpdf::elements::Paragraph::new(entries[0].clone()).styled(small))
+                    .element(genpdf::elements::Paragraph::new(entries[1].clone()).styled(small))
+                    .element(genpdf::elements::Paragraph::new(entries[2].clone()).styled(small))
+                    .element(genpdf::elements::Paragraph::new(entries[3].clone()).styled(small))
+                    .push();
+                let _ = info_table.row()
+                    .element(genpdf::elements::Paragraph::new(sprache.t("Klassifizierung")).styled(small_bold).padded(genpdf::Margins::trbl(1, 0, 1, 0)))
+                    .element(cb_table.padded(genpdf::Margins::trbl(1, 0, 1, 0)))
+                    .push();
+            }
+
+            doc.push(info_table);
+            doc.push(genpdf::elements::Break::new(0.5 * abstand));
+        }
+
+        // Trennlinie
+        doc.push(Trennlinie::new(
+            self.einstellungen.pdf_trennlinie_staerke,
+            self.einstellungen.pdf_trennlinie_farbe,
+        ));
+        doc.push(genpdf::elements::Break::new(0.5 * abstand));
+
+        // Einträge als Tabelle
+        let nummern = top_nummern(&self.eintraege, self.einstellungen.top_nummerierung);
+        let entries: Vec<_> = self
+            .eintraege
+            .iter()
+            .zip(&nummern)
+            .filter(|(e, _)| !e.punkt.is_empty() || e.art != Art::Leer || !e.notiz.is_empty())
+            .filter(|(e, _)| self.pdf_art_exportieren(&e.art))
+            .collect();
+
+        // Links aller Notizen; `link_gruppen` merkt sich je Agenda-TOP den ersten Link
+        let mut all_links: Vec<(usize, String, String)> = Vec::new();
+        let mut link_gruppen: Vec<(usize, String)> = Vec::new();
+        if !entries.is_empty() {
+            let spalten = self.pdf_spalten();
+            let mut table = spalten.tabelle();
+            // Wortbeiträge, Seitenumbrüche und zusammengehaltene Einträge unterbrechen die
+            // Tabelle; danach beginnt ein neues Tabellensegment
+            let mut zeilen_im_segment = 0usize;
+            // Die Kopfzeile wird direkt über der ersten Tabellenzeile eingefügt
+            let mut kopf_ausstehend = true;
+
+            // Kopfzeile
+            let kopfzeile_hinzufuegen = |table: &mut genpdf::elements::TableLayout| {
+                let zelle = |titel: &str, links: i32| -> Box<dyn genpdf::Element> {
+                    Box::new(
+                        genpdf::elements::Paragraph::new(titel)
+                            .styled(small_bold)
+                            .padded(genpdf::Margins::trbl(1, 2, 1, links)),
+                    )
+                };
+                spalten.zeile(
+                    table,
+                    [zelle("", 0), zelle(sprache.t("Art"), 2), zelle(sprache.t("Notiz"), 2), zelle(sprache.t("Kümmerer"), 2), zelle(sprache.t("Bis"), 2)],
+                );
+            };
+
+            for (e, (_, nummer)) in &entries {
+                let punkt_nummeriert = nummer_voranstellen(nummer, &e.punkt);
+                if e.art == Art::Agenda {
+                    let titel = if punkt_nummeriert.is_empty() { self.pdf_art_label(&Art::Agenda) } else { punkt_nummeriert.clone() };
+                    link_gruppen.push((all_links.len(), titel));
+                }
+                if e.umbruch_davor {
+                    if zeilen_im_segment > 0 {
+                        doc.push(std::mem::replace(&mut table, spalten.tabelle()));
+                        zeilen_im_segment = 0;
+                    }
+                    doc.push(genpdf::elements::PageBreak::new());
+                }
+                if e.art == Art::Wortbeitrag {
+                    if zeilen_im_segment > 0 {
+                        doc.push(std::mem::replace(&mut table, spalten.tabelle()));
+                        zeilen_im_segment = 0;
+                    }
+                    // Wortbeitrag als Absatz „Name: Text"
+                    let sprecher = self.pdf_sprecher(&e.kuemmerer);
+                    let mut layout = genpdf::elements::LinearLayout::vertical();
+                    if !punkt_nummeriert.is_empty() {
+                        layout.push(genpdf::elements::Paragraph::new(&punkt_nummeriert).styled(small_bold));
+                    }
+                    for (n, line) in self.pdf_text(&e.notiz).split('\n').enumerate() {
+                        let (replaced, new_links) =
+                            markdown_links_extrahieren(line, all_links.len() + 1);
+                        all_links.extend(new_links);
+                        let mut absatz = genpdf::elements::Paragraph::default();
+                        if n == 0 && !sprecher.is_empty() {
+                            absatz.push_styled(format!("{}: ", sprecher), small_bold);
+                        }
+                        code_spans_anhaengen(&mut absatz, &replaced, small, mono);
+                        layout.push(absatz);
+                    }
+                    // Weißer Hintergrund deckt etwaigen Grau-Überlauf der Tabellenzeile darüber ab
+                    doc.push(ZellenHintergrund::weiss(
+                        layout.padded(genpdf::Margins::trbl(1.75, 0, 2.25, 0)),
+                        40.0,
+                    ));
+                    continue;
+                }
+
+                // Sehr lange Notizen auf Fortsetzungszeilen verteilen; solche Einträge
+                // passen ohnehin nicht auf eine Seite und werden nicht zusammengehalten
+                let abschnitte = notiz_abschnitte(&self.pdf_text(&e.notiz), PDF_NOTIZ_ABSCHNITT_ZEILEN);
+                let zusammenhalten = e.zusammenhalten && abschnitte.len() == 1;
+
+                // Zusammengehaltene Einträge werden als eigene einzeilige Tabelle gerendert,
+                // damit `Zusammenhalten` die ganze Zeile auf die nächste Seite schieben kann
+                let mut einzel = spalten.tabelle();
+                if zusammenhalten && zeilen_im_segment > 0 {
+                    doc.push(std::mem::replace(&mut table, spalten.tabelle()));
+                    zeilen_im_segment = 0;
+                }
+                let ziel = if zusammenhalten {
+                    &mut einzel
+                } else {
+                    zeilen_im_segment += 1;
+                    &mut table
+                };
+                if kopf_ausstehend {
+                    kopfzeile_hinzufuegen(ziel);
+                    kopf_ausstehend = false;
+                }
+
+                let art_str = if e.art == Art::Leer {
+                    String::new()
+                } else {
+                    self.pdf_art_label(&e.art)
+                };
+                let is_todo = e.art == Art::Todo;
+                let row_style = if is_todo { small_bold } else { small };
+                let bis_text = e.bis_text(self.pdf_sprache);
+
+                for (n, abschnitt) in abschnitte.iter().enumerate() {
+                    let letzter = n + 1 == abschnitte.len();
+                    // Fortsetzungszeilen tragen nur Art und Notiz, die Punkt-Spalte weist sie aus
+                    let (punkt, punkt_stil, kuemmerer, bis) = if n == 0 {
+                        (punkt_nummeriert.as_str(), row_style, e.kuemmerer.as_str(), bis_text.as_str())
+                    } else {
+                        (sprache.t("Notiz (Fortsetzung)"), row_style.italic(), "", "")
+                    };
+                    let notiz_cell = {
+                        let mut layout = notiz_pdf_layout(abschnitt, row_style, mono, &mut all_links);
+                        if letzter && e.review == Review::Strittig && !e.klaerung.trim().is_empty() {
+                            layout.push(
+                                genpdf::elements::Paragraph::new(format!("{}: {}", sprache.t("Klärung"), e.klaerung.trim()))
+                                    .styled(small.italic().with_color(genpdf::style::Color::Rgb(231, 76, 60))),
+                            );
+                        }
+                        layout.padded(genpdf::Margins::trbl(1, 2, 1, 2))
+                    };
+
+                    // Art-Zelle: bei Entscheidungen die Beschlussnummer hervorgehoben darunter
+                    let art_cell = {
+                        let mut layout = genpdf::elements::LinearLayout::vertical();
+                        layout.push(genpdf::elements::Paragraph::new(art_str.as_str()).styled(row_style));
+                        if n == 0 && e.art == Art::Entscheidung && !e.beschluss_nr.is_empty() {
+                            layout.push(
+                                genpdf::elements::Paragraph::new(&e.beschluss_nr)
+                                    .styled(small_bold.with_color(genpdf::style::Color::Rgb(52, 152, 219))),
+                            );
+                        }
+                        if n == 0 && e.review != Review::Offen {
+                            let farbe = e.review.color();
+                            layout.push(
+                                genpdf::elements::Paragraph::new(sprache.t(e.review.label()))
+                                    .styled(small_bold.with_color(genpdf::style::Color::Rgb(farbe.r(), farbe.g(), farbe.b()))),
+                            );
+                        }
+                        layout
+                    };
+
+                    if is_todo {
+                        // Großzügiger max_height — nächste Zeile mit weißem Hintergrund deckt Überlauf ab
+                        let notiz_lines = abschnitt.split('\n').count().max(1) as f64;
+                        let row_h = notiz_lines * 8.0 + 10.0;
+
+                        spalten.zeile(
+                            ziel,
+                            [
+                                Box::new(ZellenHintergrund::grau(
+                                    genpdf::elements::Paragraph::new(punkt)
+                                        .styled(punkt_stil)
+                                        .padded(genpdf::Margins::trbl(1.5, 2, 2.5, 0)),
+                                    row_h,
+                                )),
+                                Box::new(ZellenHintergrund::grau(
+                                    art_cell.padded(genpdf::Margins::trbl(1.5, 2, 2.5, 2)),
+                                    row_h,
+                                )),
+                                Box::new(ZellenHintergrund::grau(
+                                    notiz_cell.padded(genpdf::Margins::trbl(0.5, 0, 1.5, 0)),
+                                    row_h,
+                                )),
+                                Box::new(ZellenHintergrund::grau(
+                                    kuerzel_pdf_absatz(kuemmerer, row_style, &self.einstellungen.kuerzel_praefixe)
+                                        .padded(genpdf::Margins::trbl(1.5, 2, 2.5, 2)),
+                                    row_h,
+                                )),
+                                Box::new(ZellenHintergrund::grau(
+                                    genpdf::elements::Paragraph::new(bis)
+                                        .styled(row_style)
+                                        .padded(genpdf::Margins::trbl(1.5, 2, 2.5, 2)),
+                                    row_h,
+                                )),
+                            ],
+                        );
+                    } else {
+                        // Weißer Hintergrund deckt etwaigen Grau-Überlauf der Zeile darüber ab
+                        let white_h = 40.0;
+                        spalten.zeile(
+                            ziel,
+                            [
+                                Box::new(ZellenHintergrund::weiss(
+                                    genpdf::elements::Paragraph::new(punkt)
+                                        .styled(punkt_stil)
+                                        .padded(genpdf::Margins::trbl(1.75, 2, 2.25, 0)),
+                                    white_h,
+                                )),
+                                Box::new(ZellenHintergrund::weiss(
+                                    art_cell.padded(genpdf::Margins::trbl(1.75, 2, 2.25, 2)),
+                                    white_h,
+                                )),
+                                Box::new(ZellenHintergrund::weiss(
+                                    notiz_cell.padded(genpdf::Margins::trbl(0.75, 0, 1.25, 0)),
+                                    white_h,
+                                )),
+                                Box::new(ZellenHintergrund::weiss(
+                                    kuerzel_pdf_absatz(kuemmerer, row_style, &self.einstellungen.kuerzel_praefixe)
+                                        .padded(genpdf::Margins::trbl(1.75, 2, 2.25, 2)),
+                                    white_h,
+                                )),
+                                Box::new(ZellenHintergrund::weiss(
+                                    genpdf::elements::Paragraph::new(bis)
+                                        .styled(row_style)
+                                        .padded(genpdf::Margins::trbl(1.75, 2, 2.25, 2)),
+                                    white_h,
+                                )),
+                            ],
+                        );
+                    }
+                }
+
+                if zusammenhalten {
+                    let notiz_zeilen: usize = e
+                        .notiz
+                        .split('\n')
+                        .map(|l| l.chars().count().div_ceil(55).max(1))
+                        .sum();
+                    // Geschätzte Zeilenhöhe: ca. 4 mm pro Textzeile plus Zellenabstände
+                    let hoehe = notiz_zeilen as f64 * 4.0 + 5.0;
+                    doc.push(Zusammenhalten::new(einzel, hoehe));
+                }
+            }
+
+            if zeilen_im_segment > 0 {
+                doc.push(table);
+            }
+
+            if !all_links.is_empty() && !self.pdf_quellenseite {
+                let tiny = genpdf::style::Style::new().with_font_size(7);
+                let tiny_bold = genpdf::style::Style::new().bold().with_font_size(9);
+                doc.push(genpdf::elements::Break::new(1.0 * abstand));
+                doc.push(
+                    genpdf::elements::Paragraph::new("Links")
+                        .styled(tiny_bold),
+                );
+                doc.push(genpdf::elements::Break::new(0.3 * abstand));
+                for (num, label, url) in &all_links {
+                    doc.push(pdf_link_layout(*num, label, url, tiny));
+                }
+            }
+        }
+
+        // Nächstes Meeting
+        if !self.naechstes_datum.is_empty() || !self.naechstes_ort.is_empty() || !self.naechste_agenda.trim().is_empty() {
+            doc.push(genpdf::elements::Break::new(1.0 * abstand));
+            doc.push(genpdf::elements::Paragraph::new(sprache.t("Nächstes Meeting")).styled(small_bold));
+            doc.push(genpdf::elements::Break::new(0.3 * abstand));
+            let mut termin = Vec::new();
+            if !self.naechstes_datum.is_empty() {
+                termin.push(format!("{}: {}", sprache.t("Datum"), sprache.datum_text(&self.naechstes_datum)));
+            }
+            if !self.naechstes_ort.is_empty() {
+                termin.push(format!("{}: {}", sprache.t("Ort"), self.naechstes_ort));
+            }
+            if !termin.is_empty() {
+                doc.push(genpdf::elements::Paragraph::new(termin.join("  |  ")).styled(small));
+            }
+            let mut agenda = genpdf::elements::UnorderedList::new();
+            let mut agenda_leer = true;
+            for punkt in self.naechste_agenda.lines().filter(|l| !l.trim().is_empty()) {
+                agenda.push(genpdf::elements::Paragraph::new(punkt.trim()).styled(small));
+                agenda_leer = false;
+            }
+            if !agenda_leer {
+                doc.push(genpdf::elements::Break::new(0.3 * abstand));
+                doc.push(agenda);
+            }
+        }
+
+        // Optionales Kapitel: Änderungen seit dem letzten Export; entfällt, wenn das
+        // Exportprofil nur bestimmte Arten zeigt, da es sonst die übrigen verrät
+        if self.pdf_aenderungen_aufnehmen && self.exportprofil_aktiv.as_ref().is_none_or(|p| p.arten.is_empty()) {
+            if let Some(diff) = self.pdf_aenderungen.as_ref().filter(|d| !d.aenderungen.is_empty()) {
+                doc.push(genpdf::elements::Break::new(1.0 * abstand));
+                doc.push(
+                    genpdf::elements::Paragraph::new(format!("{} {}", sprache.t("Änderungen seit Version"), diff.version))
+                        .styled(small_bold),
+                );
+                doc.push(genpdf::elements::Break::new(0.3 * abstand));
+                let mut liste = genpdf::elements::UnorderedList::new();
+                for a in &diff.aenderungen {
+                    let mut absatz = genpdf::elements::Paragraph::default();
+                    absatz.push_styled(format!("{}: ", sprache.t(a.art.label())), small_bold);
+                    absatz.push_styled(a.titel.clone(), small_bold);
+                    if let Some(zeile) = self.pdf_text(&a.notiz).lines().find(|l| !l.trim().is_empty()) {
+                        absatz.push_styled(format!(" – {}", zeile.trim()), small);
+                    }
+                    liste.push(absatz);
+                }
+                doc.push(liste);
+            }
+        }
+
+        // Optionale Anlage: konsolidierte Offene-Punkte-Liste inkl. Vorgängerprotokolle
+        if self.einstellungen.pdf_offene_punkte && self.pdf_art_exportieren(&Art::Todo) {
+            let punkte = self.offene_punkte_sammeln();
+            if !punkte.is_empty() {
+                let heute = Local::now().date_naive();
+                doc.push(genpdf::elements::PageBreak::new());
+                doc.push(
+                    genpdf::elements::Paragraph::new(sprache.t("Anlage: Offene-Punkte-Liste"))
+                        .styled(genpdf::style::Style::new().bold().with_font_size(14)),
+                );
+                doc.push(genpdf::elements::Break::new(0.3 * abstand));
+                doc.push(
+                    genpdf::elements::Paragraph::new(format!(
+                        "{} {} {}",
+                        punkte.len(),
+                        sprache.t("offene Punkte, Stand"),
+                        sprache.datum(heute)
+                    ))
+                    .styled(small),
+                );
+                doc.push(genpdf::elements::Break::new(0.5 * abstand));
+
+                let mut table = genpdf::elements::TableLayout::new(vec![11, 4, 3, 6, 2]);
+                let mut kopf = table.row();
+                for titel in ["Notiz", "Kümmerer", "Bis", "Aus Protokoll", "Alter"] {
+                    kopf = kopf.element(
+                        genpdf::elements::Paragraph::new(sprache.t(titel))
+                            .styled(small_bold)
+                            .padded(genpdf::Margins::trbl(1, 2, 1, 2)),
+                    );
+                }
+                let _ = kopf.push();
+                for p in &punkte {
+                    let alter = p
+                        .seit
+                        .map(|d| format!("{} {}", (heute - d).num_days().max(0), sprache.t("T.")))
+                        .unwrap_or_else(|| "–".to_string());
+                    let herkunft = match p.seit {
+                        Some(d) => format!("{} ({})", p.protokoll, sprache.datum(d)),
+                        None => p.protokoll.clone(),
+                    };
+                    let mut notiz = genpdf::elements::LinearLayout::vertical();
+                    for line in self.pdf_text(&p.notiz).split('\n') {
+                        notiz.push(genpdf::elements::Paragraph::new(line).styled(small));
+                    }
+                    let _ = table
+                        .row()
+                        .element(notiz.padded(genpdf::Margins::trbl(1, 2, 1, 2)))
+                        .element(genpdf::elements::Paragraph::new(&p.kuemmerer).styled(small).padded(genpdf::Margins::trbl(1, 2, 1, 2)))
+                        .element(genpdf::elements::Paragraph::new(sprache.kurzdatum_text(&p.bis)).styled(small).padded(genpdf::Margins::trbl(1, 2, 1, 2)))
+                        .element(genpdf::elements::Paragraph::new(herkunft).styled(small).padded(genpdf::Margins::trbl(1, 2, 1, 2)))
+                        .element(genpdf::elements::Paragraph::new(alter).styled(small_bold).padded(genpdf::Margins::trbl(1, 2, 1, 2)))
+                        .push();
+                }
+                doc.push(table);
+            }
+        }
+
+        // Anhang „Nur intern" – entfällt in der Fassung für externe Empfänger
+        if self.pdf_zielgruppe == Zielgruppe::Intern && !self.interner_anhang.trim().is_empty() {
+            doc.push(genpdf::elements::PageBreak::new());
+            doc.push(
+                genpdf::elements::Paragraph::new(sprache.t("Anhang: Nur intern"))
+                    .styled(genpdf::style::Style::new().bold().with_font_size(14)),
+            );
+            doc.push(genpdf::elements::Break::new(0.5 * abstand));
+            for zeile in self.interner_anhang.trim().lines() {
+                if zeile.trim().is_empty() {
+                    doc.push(genpdf::elements::Break::new(0.5 * abstand));
+                } else {
+                    doc.push(genpdf::elements::Paragraph::new(zeile).styled(small));
+                }
+            }
+        }
+
+        // Optionale Schlussseite: Links nummeriert und nach Agenda-TOPs gruppiert
+        if self.pdf_quellenseite && !all_links.is_empty() {
+            doc.push(genpdf::elements::PageBreak::new());
+            doc.push(
+                genpdf::elements::Paragraph::new(sprache.t("Quellen & Referenzen"))
+                    .styled(genpdf::style::Style::new().bold().with_font_size(14)),
+            );
+            // Links vor dem ersten TOP stehen unter „Allgemein“
+            if link_gruppen.first().is_none_or(|(start, _)| *start > 0) {
+                link_gruppen.insert(0, (0, sprache.t("Allgemein").to_string()));
+            }
+            for (g, (start, titel)) in link_gruppen.iter().enumerate() {
+                let ende = link_gruppen.get(g + 1).map(|(s, _)| *s).unwrap_or(all_links.len());
+                if ende <= *start {
+                    continue;
+                }
+                doc.push(genpdf::elements::Break::new(0.8 * abstand));
+                doc.push(genpdf::elements::Paragraph::new(titel.as_str()).styled(small_bold));
+                doc.push(genpdf::elements::Break::new(0.3 * abstand));
+                for (num, label, url) in &all_links[*start..ende] {
+                    doc.push(pdf_link_layout(*num, label, url, small));
+                }
+            }
+        }
+    }
+
+    /// Setzt die konfigurierte Kopfzeile für die Folgeseiten zusammen. Leere Felder
+    /// fallen samt ihrem Trenner „ – " weg.
+    fn kopfzeile_erstellen(&self) -> String {
+        let vorlage = self
+            .exportprofil_aktiv
+            .as_ref()
+            .and_then(|p| p.kopfzeile.as_deref())
+            .unwrap_or(&self.einstellungen.pdf_kopfzeile);
+        let text = vorlage
+            .replace("{projekt}", &self.projekt)
+            .replace("{titel}", &self.titel)
+            .replace("{datum}", &self.pdf_sprache.datum_text(&self.datum_text))
+            .replace("{ort}", &self.ort)
+            .replace("{klassifizierung}", self.pdf_sprache.t(self.sicherheit.label()));
+        text.split(" – ")
+            .map(str::trim)
+            .filter(|teil| !teil.is_empty())
+            .collect::<Vec<_>>()
+            .join(" – ")
+    }
+
+    /// Fügt das optionale Deckblatt (Logo, Projekt, Titel, Datum, Klassifizierung,
+    /// Verteiler) ein und schließt es mit einem Seitenumbruch ab.
+    fn pdf_deckblatt_hinzufuegen(&self, doc: &mut genpdf::Document) {
+        let zentriert = genpdf::Alignment::Center;
+        let normal = genpdf::style::Style::new().with_font_size(11);
+        let fett = genpdf::style::Style::new().bold().with_font_size(11);
+        let sprache = self.pdf_sprache;
+
+        doc.push(genpdf::elements::Break::new(4.0));
+        let logo_pfad = self
+            .exportprofil_aktiv
+            .as_ref()
+            .and_then(|p| p.logo.as_deref())
+            .unwrap_or(&self.einstellungen.pdf_logo);
+        if !logo_pfad.is_empty() {
+            if let Some(logo) = pdf_logo_laden(logo_pfad, 25.0) {
+                doc.push(logo);
+                doc.push(genpdf::elements::Break::new(3.0));
+            }
+        }
+        if !self.projekt.is_empty() {
+            doc.push(genpdf::elements::Paragraph::new(&self.projekt).aligned(zentriert).styled(normal));
+            doc.push(genpdf::elements::Break::new(0.5));
+        }
+        doc.push(
+            genpdf::elements::Paragraph::new(&self.titel)
+                .aligned(zentriert)
+                .styled(genpdf::style::Style::new().bold().with_font_size(26)),
+        );
+        doc.push(genpdf::elements::Break::new(1.0));
+        let mut meta_parts = Vec::new();
+        if !self.datum_text.is_empty() {
+            meta_parts.push(sprache.datum_text(&self.datum_text));
+        }
+        if !self.ort.is_empty() {
+            meta_parts.push(self.ort.clone());
+        }
+        if !meta_parts.is_empty() {
+            doc.push(genpdf::elements::Paragraph::new(meta_parts.join("  |  ")).aligned(zentriert).styled(normal));
+        }
+        doc.push(genpdf::elements::Break::new(2.0));
+        doc.push(
+            genpdf::elements::Paragraph::new(format!("{}: {}", sprache.t("Klassifizierung"), sprache.t(self.sicherheit.label())))
+                .aligned(zentriert)
+                .styled(fett),
+        );
+        doc.push(genpdf::elements::Break::new(4.0));
+
+        // Verteiler
+        let verteiler = |personen: &[Person]| -> Vec<String> {
+            personen
+                .iter()
+                .filter(|p| !p.name.is_empty())
+                .map(|p| self.pdf_person(p))
+                .collect()
+        };
+        for (titel, namen) in [
+            (sprache.t("Teilnehmer"), verteiler(&self.teilnehmer)),
+            (sprache.t("Zur Kenntnis"), verteiler(&self.zur_kenntnis)),
+        ] {
+            if namen.is_empty() {
+                continue;
+            }
+            doc.push(genpdf::elements::Paragraph::new(titel).aligned(zentriert).styled(fett));
+            doc.push(genpdf::elements::Break::new(0.3));
+            for name in namen {
+                doc.push(genpdf::elements::Paragraph::new(name).aligned(zentriert).styled(normal));
+            }
+            doc.push(genpdf::elements::Break::new(1.5));
+        }
+        doc.push(genpdf::elements::PageBreak::new());
+    }
+
+    /// Startet den PDF-Export-Prozess:
+    /// 1. Personen sortieren und Pflichtfelder prüfen.
+    /// 2. Änderungen seit dem letzten Export ermitteln.
+    /// 3. Exportdialog mit Auswahl des Dichte-Profils öffnen.
+    ///
+    /// Nach Bestätigung setzt `pdf_export_starten` den Export fort.
+    fn pdf_exportieren(&mut self) {
+        self.sort_personen();
+        if self.protokollant.name.trim().is_empty() {
+            self.show_pflichtfeld_hinweis = true;
+            return;
+        }
+        self.pdf_aenderungen = self.versand_diff();
+        self.show_pdf_export_dialog = true;
+    }
+
+    /// Setzt den PDF-Export nach dem Exportdialog fort:
+    /// 1. Markdown automatisch speichern (falls Pfad bekannt).
+    /// 2. Schriftart laden (Fehler → Fehlerdialog).
+    /// 3. Datei-Speichern-Dialog in separatem Thread öffnen.
+    /// 4. Bei Bestätigung: `pdf_generieren` aufrufen.
+    fn pdf_export_starten(&mut self) {
+        self.beschluesse_nummerieren();
+        self.eintraege_protokollieren();
+        // Vor PDF-Erzeugung automatisch speichern
+        if let Some(path) = self.save_path.clone() {
+            self.erstellung_vermerken();
+            let content = self.markdown_erstellen();
+            match atomar_schreiben(&path, content) {
+                Ok(()) => self.stand_merken(),
+                Err(e) => self.toast_zeigen(format!("Speichern fehlgeschlagen: {}", e)),
+            }
+        }
+        let font_family = match self.schrift_laden() {
+            Some(f) => f,
+            None => {
+                self.show_pdf_error = true;
+                return;
+            }
+        };
+
+        self.pending_pdf_font = Some(font_family);
+        self.pending_exportprofil = self.pdf_exportprofil.and_then(|i| self.einstellungen.export_profile.get(i).cloned());
+        let pdf_filename = self.pdf_dateinamen_erstellen();
+        let (tx, rx) = mpsc::channel();
+        self.dialog_rx = Some(rx);
+        std::thread::spawn(move || {
+            if let Some(path) = rfd::FileDialog::new()
+                .set_file_name(&pdf_filename)
+                .add_filter("PDF", &["pdf"])
+                .save_file()
+            {
+                let _ = tx.send(DialogErgebnis::PdfExport(path));
+            }
+        });
+    }
+
+    /// Spaltengewichte der PDF-Tabelle: die des Protokolls, sonst `pdf_spalten`.
+    fn pdf_spalten(&self) -> PdfSpalten {
+        self.pdf_spalten.unwrap_or(self.einstellungen.pdf_spalten)
+    }
+
+    /// Das in `pdf_backend` eingestellte PDF-Backend; genpdf nutzt `schriftfamilie`.
+    fn pdf_backend(&self, schriftfamilie: genpdf::fonts::FontFamily<genpdf::fonts::FontData>) -> Box<dyn PdfBackend> {
+        match self.einstellungen.pdf_backend {
+            PdfBackendArt::Genpdf => Box::new(GenpdfBackend { schriftfamilie }),
+            PdfBackendArt::Html => Box::new(HtmlBackend { kommando: self.einstellungen.pdf_html_kommando.clone() }),
+            PdfBackendArt::Typst => Box::new(TypstBackend { kommando: self.einstellungen.typst_kommando.clone() }),
+        }
+    }
+
+    /// Bereitet den PDF-Export nach `path` vor: das eingestellte Backend rendert bzw.
+    /// liefert die Quelle, dazu kommen Nachbearbeitung gemäß Exportprofil, Versandstand
+    /// und optional die XML-Begleitdatei. Ausgeführt wird er mit `pdf_im_hintergrund`.
+    fn pdf_export_vorbereiten(
+        &self,
+        path: &std::path::Path,
+        schriftfamilie: genpdf::fonts::FontFamily<genpdf::fonts::FontData>,
+    ) -> Result<PdfExport, String> {
+        let auftrag = self.pdf_backend(schriftfamilie).pdf_vorbereiten(self)?;
+        let profil = self.exportprofil_aktiv.as_ref();
+        // Fassungen eines Exportprofils sind gefiltert bzw. anonymisiert: weder
+        // Vergleichsstand noch die vollständige XML-Begleitdatei ablegen
+        let vollstaendig = profil.is_none();
+        let xml = (vollstaendig && self.einstellungen.xml_begleitdatei).then(|| {
+            let pdf_datei = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            self.xml_erstellen(&pdf_datei)
+        });
+        Ok(PdfExport {
+            auftrag,
+            pdfa_kommando: profil.filter(|p| p.pdfa).map(|_| self.einstellungen.pdfa_kommando.clone()),
+            verschluesselung: profil
+                .filter(|p| p.passwortschutz)
+                .map(|_| (self.einstellungen.qpdf_programm.clone(), self.pdf_passwort.clone())),
+            ziel: path.to_path_buf(),
+            versand_stand: if vollstaendig { self.versand_stand() } else { None },
+            xml,
+        })
+    }
+
+    /// Führt einen vorbereiteten PDF-Export in einem eigenen Thread aus; `abschluss` läuft
+    /// dort nach erfolgreichem Schreiben. Das Ergebnis meldet `pdf_fertig` gemäß `danach`.
+    fn pdf_im_hintergrund(&mut self, export: PdfExport, danach: PdfDanach, abschluss: impl FnOnce() + Send + 'static) {
+        let tx = self.pdf_tx.clone();
+        self.pdf_laufend += 1;
+        std::thread::spawn(move || {
+            let pfad = export.ziel.clone();
+            let ergebnis = export.ausfuehren();
+            if ergebnis.is_ok() {
+                abschluss();
+            }
+            let _ = tx.send(PdfFertig { danach, pfad, ergebnis });
+        });
+    }
+
+    /// Meldet ein im Hintergrund erzeugtes PDF; bei der Freigabe-Pipeline wird erst
+    /// jetzt archiviert und das Ergebnis an den wartenden Schritt weitergegeben.
+    fn pdf_fertig(&mut self, ctx: &egui::Context, fertig: PdfFertig) {
+        self.pdf_laufend = self.pdf_laufend.saturating_sub(1);
+        let PdfFertig { danach, pfad, ergebnis } = fertig;
+        match (danach, ergebnis) {
+            (PdfDanach::Export, Ok(())) if self.beenden_nach_dialog => self.beenden(ctx),
+            (PdfDanach::Export | PdfDanach::Archiv, Ok(())) => {}
+            (PdfDanach::Export | PdfDanach::Schnellexport, Err(fehler)) => {
+                self.toast_zeigen(format!("PDF-Export fehlgeschlagen: {}", fehler));
+            }
+            (PdfDanach::Schnellexport | PdfDanach::Typst, Ok(())) => self.toast_zeigen(format!("Exportiert: {}", pfad.display())),
+            (PdfDanach::Typst, Err(fehler)) => self.toast_zeigen(format!("Typst-Kompilierung fehlgeschlagen: {}", fehler)),
+            (PdfDanach::Archiv, Err(fehler)) => self.toast_zeigen(format!("Archiviert ohne PDF ({}): {}", fehler, pfad.display())),
+            (PdfDanach::Freigabe { alt, tx }, Ok(())) => {
+                self.archivieren();
+                // `archivieren` meldet sich per Toast; die Meldung wandert in die Pipeline
+                let meldung = self.toast.take().map(|(text, _)| text).unwrap_or_default();
+                let _ = tx.send(match &self.save_path {
+                    Some(neu) if *neu != alt && neu.is_file() => Ok(meldung),
+                    _ => Err(meldung),
+                });
+            }
+            (PdfDanach::Freigabe { tx, .. }, Err(fehler)) => {
+                let _ = tx.send(Err(format!("PDF-Export fehlgeschlagen: {}", fehler)));
+            }
+        }
+    }
+
+    /// Öffnet einen Datei-Speichern-Dialog (separater Thread) für die Anwesenheitsliste.
+    fn anwesenheitsliste_exportieren(&mut self) {
+        self.sort_personen();
+        let Some(schrift) = self.schrift_laden() else {
+            self.show_pdf_error = true;
+            return;
+        };
+        self.pending_pdf_font = Some(schrift);
+        let name_part: String = self.titel.chars().filter(|c| c.is_alphabetic()).collect();
+        let filename = format!("MZProtokoll_{}__Anwesenheitsliste.pdf", name_part);
+        let (tx, rx) = mpsc::channel();
+        self.dialog_rx = Some(rx);
+        std::thread::spawn(move || {
+            if let Some(path) = rfd::FileDialog::new()
+                .set_file_name(&filename)
+                .add_filter("PDF", &["pdf"])
+                .save_file()
+            {
+                let _ = tx.send(DialogErgebnis::Anwesenheitsliste(path));
+            }
+        });
+    }
+
+    /// Rendert die Anwesenheitsliste zum Unterschreiben: Kopf mit Projekt, Titel,
+    /// Datum und Ort, darunter eine gerahmte Tabelle (Name, Organisation, Unterschrift)
+    /// mit allen Teilnehmern und `ANWESENHEIT_LEERZEILEN` freien Zeilen für Gäste.
+    fn anwesenheitsliste_generieren(&self, path: &std::path::Path, schriftfamilie: genpdf::fonts::FontFamily<genpdf::fonts::FontData>) -> bool {
+        let profil = self.pdf_profil;
+        let small = genpdf::style::Style::new().with_font_size(profil.schriftgroesse());
+        let small_bold = genpdf::style::Style::new().bold().with_font_size(profil.schriftgroesse());
+        let heading_style = genpdf::style::Style::new().bold().with_font_size(profil.titelgroesse());
+        let abstand = profil.abstand();
+
+        let mut dok = genpdf::Document::new(schriftfamilie);
+        dok.set_title(if self.titel.is_empty() {
+            "Anwesenheitsliste".to_string()
+        } else {
+            format!("Anwesenheitsliste – {}", self.titel)
+        });
+        let mut dekorator = genpdf::SimplePageDecorator::new();
+        dekorator.set_margins(profil.raender());
+        dok.set_page_decorator(dekorator);
+        dok.set_line_spacing(profil.zeilenabstand());
+
+        if !self.projekt.is_empty() {
+            dok.push(genpdf::elements::Paragraph::new(&self.projekt).styled(small));
+        }
+        dok.push(genpdf::elements::Paragraph::new("Anwesenheitsliste").styled(heading_style));
+        if !self.titel.is_empty() {
+            dok.push(genpdf::elements::Paragraph::new(&self.titel).styled(small_bold));
+        }
+        let mut meta_parts = Vec::new();
+        if !self.datum_text.is_empty() {
+            meta_parts.push(format!("Datum: {}", self.datum_text));
+        }
+        if !self.ort.is_empty() {
+            meta_parts.push(format!("Ort: {}", self.ort));
+        }
+        if !meta_parts.is_empty() {
+            dok.push(genpdf::elements::Paragraph::new(meta_parts.join("  |  ")).styled(small));
+        }
+        dok.push(genpdf::elements::Break::new(abstand));
+
+        // Großzügige Zeilenhöhe, damit Platz für die Unterschrift bleibt
+        let zelle = genpdf::Margins::trbl(4, 2, 4, 2);
+        let mut table = genpdf::elements::TableLayout::new(vec![6, 5, 6]);
+        table.set_cell_decorator(genpdf::elements::FrameCellDecorator::new(true, true, false));
+        let mut kopf = table.row();
+        for titel in ["Name", "Organisation", "Unterschrift"] {
+            kopf = kopf.element(
+                genpdf::elements::Paragraph::new(titel)
+                    .styled(small_bold)
+                    .padded(genpdf::Margins::trbl(1, 2, 1, 2)),
+            );
+        }
+        let _ = kopf.push();
+        let namen = self
+            .teilnehmer
+            .iter()
+            .map(|t| t.name.trim())
+            .filter(|name| !name.is_empty())
+            .chain(std::iter::repeat_n("", ANWESENHEIT_LEERZEILEN));
+        for name in namen {
+            let _ = table
+                .row()
+                .element(genpdf::elements::Paragraph::new(name).styled(small).padded(zelle))
+                .element(genpdf::elements::Paragraph::new("").styled(small).padded(zelle))
+                .element(genpdf::elements::Paragraph::new("").styled(small).padded(zelle))
+                .push();
+        }
+        dok.push(table);
+        dok.render_to_file(path).is_ok()
+    }
+
+    /// „Nachtrag exportieren": prüft, ob es seit dem letzten PDF-Export Änderungen gibt,
+    /// und öffnet dann einen Datei-Speichern-Dialog (separater Thread) für das Korrektur-PDF.
+    fn nachtrag_exportieren(&mut self) {
+        let Some(diff) = self.versand_diff() else {
+            self.toast_zeigen("Noch kein exportierter Stand – ein Nachtrag setzt einen PDF-Export voraus".to_string());
+            return;
+        };
+        if diff.aenderungen.is_empty() {
+            self.toast_zeigen(format!("Keine Änderungen seit Version {}", diff.version));
+            return;
+        }
+        let Some(schrift) = self.schrift_laden() else {
+            self.show_pdf_error = true;
+            return;
+        };
+        self.pending_pdf_font = Some(schrift);
+        let name_part: String = self.titel.chars().filter(|c| c.is_alphabetic()).collect();
+        let filename = format!("MZProtokoll_{}__Nachtrag_{}.pdf", name_part, diff.version + 1);
+        let (tx, rx) = mpsc::channel();
+        self.dialog_rx = Some(rx);
+        std::thread::spawn(move || {
+            if let Some(path) = rfd::FileDialog::new()
+                .set_file_name(&filename)
+                .add_filter("PDF", &["pdf"])
+                .save_file()
+            {
+                let _ = tx.send(DialogErgebnis::Nachtrag(path));
+            }
+        });
+    }
+
+    /// Rendert den Nachtrag: Kopf mit Verweis auf das Originalprotokoll (Titel, Datum,
+    /// Dateiname des Original-PDFs und verglichene Version), darunter eine gerahmte
+    /// Tabelle nur mit den neuen, geänderten und entfernten Einträgen. Das Original-PDF
+    /// bleibt unangetastet; der aktuelle Stand wird wie beim PDF-Export als neue
+    /// Version abgelegt, sodass ein weiterer Nachtrag nur spätere Änderungen enthält.
+    fn nachtrag_generieren(&self, path: &std::path::Path, schriftfamilie: genpdf::fonts::FontFamily<genpdf::fonts::FontData>) -> bool {
+        let Some(diff) = self.versand_diff() else {
+            return false;
+        };
+        let profil = self.pdf_profil;
+        let small = genpdf::style::Style::new().with_font_size(profil.schriftgroesse());
+        let small_bold = genpdf::style::Style::new().bold().with_font_size(profil.schriftgroesse());
+        let heading_style = genpdf::style::Style::new().bold().with_font_size(profil.titelgroesse());
+        let abstand = profil.abstand();
+
+        let mut dok = genpdf::Document::new(schriftfamilie);
+        dok.set_title(if self.titel.is_empty() {
+            "Nachtrag".to_string()
+        } else {
+            format!("Nachtrag – {}", self.titel)
+        });
+        let mut dekorator = genpdf::SimplePageDecorator::new();
+        dekorator.set_margins(profil.raender());
+        dok.set_page_decorator(dekorator);
+        dok.set_line_spacing(profil.zeilenabstand());
+
+        if !self.projekt.is_empty() {
+            dok.push(genpdf::elements::Paragraph::new(&self.projekt).styled(small));
+        }
+        dok.push(genpdf::elements::Paragraph::new("Nachtrag / Korrekturblatt").styled(heading_style));
+        let mut bezug = if self.titel.is_empty() { "zum Protokoll".to_string() } else { format!("zum Protokoll „{}“", self.titel) };
+        if !self.datum_text.is_empty() {
+            bezug.push_str(&format!(" vom {}", self.datum_text));
+        }
+        dok.push(genpdf::elements::Paragraph::new(bezug).styled(small_bold));
+        let mut meta_parts = vec![format!("Änderungen gegenüber Version {}", diff.version)];
+        if let Some(original) = self
+            .save_path
+            .as_ref()
+            .map(|p| p.with_extension("pdf"))
+            .filter(|p| p.exists())
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        {
+            meta_parts.push(format!("Original: {}", original));
+        }
+        meta_parts.push(format!("Erstellt am {}", Local::now().format("%d.%m.%Y")));
+        dok.push(genpdf::elements::Paragraph::new(meta_parts.join("  |  ")).styled(small));
+        dok.push(genpdf::elements::Paragraph::new("Alle übrigen Einträge gelten unverändert fort.").styled(small));
+        dok.push(genpdf::elements::Break::new(abstand));
+
+        let zelle = genpdf::Margins::trbl(1, 2, 1, 2);
+        let mut table = genpdf::elements::TableLayout::new(vec![2, 4, 8]);
+        table.set_cell_decorator(genpdf::elements::FrameCellDecorator::new(true, true, false));
+        let mut kopf = table.row();
+        for titel in ["Änderung", "Eintrag", "Notiz"] {
+            kopf = kopf.element(genpdf::elements::Paragraph::new(titel).styled(small_bold).padded(zelle));
+        }
+        let _ = kopf.push();
+        for a in &diff.aenderungen {
+            let mut notiz = genpdf::elements::LinearLayout::vertical();
+            for zeile in a.notiz.lines() {
+                notiz.push(genpdf::elements::Paragraph::new(zeile).styled(small));
+            }
+            let _ = table
+                .row()
+                .element(genpdf::elements::Paragraph::new(a.art.label()).styled(small_bold).padded(zelle))
+                .element(genpdf::elements::Paragraph::new(a.titel.as_str()).styled(small).padded(zelle))
+                .element(notiz.padded(zelle))
+                .push();
+        }
+        dok.push(table);
+        let erfolgreich = dok.render_to_file(path).is_ok();
+        if erfolgreich {
+            self.versand_stand_sichern();
+        }
+        erfolgreich
+    }
+
+    /// „Schnell exportieren": schreibt Markdown und PDF ohne Dateidialog nach
+    /// `export_verzeichnis` (bzw. in den Ordner der geöffneten Datei), benannt nach
+    /// `export_namensmuster`. Das Ergebnis erscheint als Toast.
+    fn schnell_exportieren(&mut self) {
+        self.sort_personen();
+        if self.protokollant.name.trim().is_empty() {
+            self.show_pflichtfeld_hinweis = true;
+            return;
+        }
+        let verzeichnis = if self.einstellungen.export_verzeichnis.is_empty() {
+            self.save_path.as_ref().and_then(|p| p.parent()).map(|p| p.to_path_buf())
+        } else {
+            Some(std::path::PathBuf::from(&self.einstellungen.export_verzeichnis))
+        };
+        let Some(verzeichnis) = verzeichnis else {
+            self.toast_zeigen("Kein Exportverzeichnis konfiguriert (export_verzeichnis)".to_string());
+            return;
+        };
+        let Some(schrift) = self.schrift_laden() else {
+            self.show_pdf_error = true;
+            return;
+        };
+        let name_bereinigen = |text: &str| -> String { text.chars().filter(|c| c.is_alphanumeric()).collect() };
+        let name = self
+            .einstellungen
+            .export_namensmuster
+            .replace("{projekt}", &name_bereinigen(&self.projekt))
+            .replace("{titel}", &name_bereinigen(&self.titel))
+            .replace("{datum}", &Local::now().format("%Y-%m-%d").to_string());
+        if std::fs::create_dir_all(&verzeichnis).is_err() {
+            self.toast_zeigen(format!("Verzeichnis nicht verfügbar: {}", verzeichnis.display()));
+            return;
+        }
+        self.erstellung_vermerken();
+        self.beschluesse_nummerieren();
+        self.eintraege_protokollieren();
+        let md_pfad = verzeichnis.join(format!("{}.md", name));
+        let pdf_pfad = verzeichnis.join(format!("{}.pdf", name));
+        if let Err(e) = atomar_schreiben(&md_pfad, self.markdown_erstellen()) {
+            self.toast_zeigen(format!("Speichern fehlgeschlagen: {}: {}", md_pfad.display(), e));
+            return;
+        }
+        if self.save_path.is_none() {
+            self.save_path = Some(md_pfad);
+            self.stand_merken();
+        }
+        self.pdf_aenderungen = self.versand_diff();
+        match self.pdf_export_vorbereiten(&pdf_pfad, schrift) {
+            Ok(export) => self.pdf_im_hintergrund(export, PdfDanach::Schnellexport, || {}),
+            Err(fehler) => self.toast_zeigen(format!("PDF-Export fehlgeschlagen: {}", fehler)),
+        }
+    }
+
+    /// Indizes der Einträge, die im Präsentationsmodus gezeigt werden (ohne leere Zeilen).
+    fn praesentations_eintraege(&self) -> Vec<usize> {
+        self.eintraege
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.art != Art::Leer || !e.punkt.trim().is_empty() || !e.notiz.trim().is_empty())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Startet bzw. beendet den Präsentationsmodus (Vollbild, schreibgeschützt).
+    fn praesentation_umschalten(&mut self, ctx: &egui::Context) {
+        if self.praesentation.take().is_none() {
+            self.sort_personen();
+            self.praesentation = Some(0);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(true));
+        } else {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(false));
+            // Ende der Präsentation = Ende des Meetings
+            self.finalisierung_erinnern();
+        }
+    }
+
+    /// Zeigt den aktuellen Eintrag groß und ohne Bedienelemente an.
+    /// Pfeiltasten, Leertaste und Bild auf/ab blättern, `Esc` beendet den Modus.
+    /// `B`/`S` markieren den Eintrag als bestätigt bzw. strittig.
+    fn praesentation_anzeigen(&mut self, ctx: &egui::Context) {
+        let liste = self.praesentations_eintraege();
+        let letzte = liste.len().saturating_sub(1);
+        let mut pos = self.praesentation.unwrap_or(0).min(letzte);
+        // Während der Klärungsvermerk getippt wird, gelten die Tasten dem Textfeld
+        let tastatur_frei = ctx.memory(|m| m.focused().is_none());
+        let (vor, zurueck, anfang, ende, beenden, bestaetigen, strittig) = ctx.input(|i| {
+            if !tastatur_frei {
+                return (false, false, false, false, i.key_pressed(egui::Key::Escape), false, false);
+            }
+            (
+                i.key_pressed(egui::Key::ArrowRight)
+                    || i.key_pressed(egui::Key::ArrowDown)
+                    || i.key_pressed(egui::Key::Space)
+                    || i.key_pressed(egui::Key::PageDown),
+                i.key_pressed(egui::Key::ArrowLeft) || i.key_pressed(egui::Key::ArrowUp) || i.key_pressed(egui::Key::PageUp),
+                i.key_pressed(egui::Key::Home),
+                i.key_pressed(egui::Key::End),
+                i.key_pressed(egui::Key::Escape),
+                i.key_pressed(egui::Key::B),
+                i.key_pressed(egui::Key::S),
+            )
+        });
+        if vor {
+            pos = (pos + 1).min(letzte);
+        }
+        if zurueck {
+            pos = pos.saturating_sub(1);
+        }
+        if anfang {
+            pos = 0;
+        }
+        if ende {
+            pos = letzte;
+        }
+        self.praesentation = Some(pos);
+        if beenden {
+            if !tastatur_frei {
+                // Esc im Klärungsvermerk verlässt nur das Textfeld
+                ctx.memory_mut(|m| m.stop_text_input());
+                return;
+            }
+            self.praesentation_umschalten(ctx);
+            return;
+        }
+        if let Some(&idx) = liste.get(pos) {
+            let eintrag = &mut self.eintraege[idx];
+            for (gedrueckt, markierung) in [(bestaetigen, Review::Bestaetigt), (strittig, Review::Strittig)] {
+                if gedrueckt {
+                    eintrag.review = if eintrag.review == markierung { Review::Offen } else { markierung };
+                }
+            }
+        }
+
+        let textfarbe = self.input_text_color;
+        egui::CentralPanel::default()
+            .frame(egui::Frame::central_panel(&ctx.style()).inner_margin(egui::Margin::same(48)))
+            .show(ctx, |ui| {
+                let kopf: Vec<&str> = [self.projekt.as_str(), self.titel.as_str(), self.datum_text.as_str()]
+                    .into_iter()
+                    .filter(|t| !t.trim().is_empty())
+                    .collect();
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(kopf.join(" – ")).size(20.0).weak());
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if !liste.is_empty() {
+                            ui.label(RichText::new(format!("{} / {}", pos + 1, liste.len())).size(20.0).weak());
+                        }
+                    });
+                });
+                ui.separator();
+                ui.add_space(24.0);
+
+                let Some(eintrag) = liste.get(pos).map(|&i| &mut self.eintraege[i]) else {
+                    ui.label(RichText::new("Keine Einträge vorhanden.").size(28.0).weak());
+                    return;
+                };
+                ui.horizontal(|ui| {
+                    if eintrag.art != Art::Leer {
+                        ui.label(RichText::new(art_beschriftung(&self.art_labels, &eintrag.art)).font(fette_schrift(26.0)).color(self.tabellen_farben.art(&eintrag.art)));
+                    }
+                    // Review-Markierung: Klick oder Taste B/S schaltet um
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        for (markierung, text) in [(Review::Strittig, "Strittig (S)"), (Review::Bestaetigt, "Bestätigt (B)")] {
+                            let aktiv = eintrag.review == markierung;
+                            let mut rt = RichText::new(text).size(20.0);
+                            if aktiv {
+                                rt = rt.strong().color(markierung.color());
+                            }
+                            let knopf = egui::Button::new(rt).stroke(if aktiv {
+                                egui::Stroke::new(2.0, markierung.color())
+                            } else {
+                                ui.visuals().widgets.inactive.bg_stroke
+                            });
+                            if ui.add(knopf).clicked() {
+                                eintrag.review = if aktiv { Review::Offen } else { markierung };
+                            }
+                        }
+                    });
+                });
+                ui.add_space(8.0);
+                if !eintrag.punkt.trim().is_empty() {
+                    let mut rt = RichText::new(eintrag.punkt.trim()).font(fette_schrift(40.0));
+                    if let Some(c) = textfarbe { rt = rt.color(c); }
+                    ui.label(rt);
+                    ui.add_space(16.0);
+                }
+                let mut angaben = Vec::new();
+                if !eintrag.kuemmerer.trim().is_empty() {
+                    let rolle = if eintrag.art == Art::Wortbeitrag { "Sprecher" } else { "Kümmerer" };
+                    angaben.push(format!("{}: {}", rolle, eintrag.kuemmerer.trim()));
+                }
+                if !eintrag.bis.trim().is_empty() {
+                    angaben.push(format!("Bis: {}", eintrag.bis.trim()));
+                }
+                let fusszeile_hoehe = 40.0
+                    + if angaben.is_empty() { 0.0 } else { 48.0 }
+                    + if eintrag.review == Review::Strittig { 56.0 } else { 0.0 };
+                egui::ScrollArea::vertical()
+                    .max_height((ui.available_height() - fusszeile_hoehe).max(100.0))
+                    .show(ui, |ui| {
+                        ui.set_width(ui.available_width());
+                        let mut rt = RichText::new(eintrag.notiz.trim()).size(30.0);
+                        if let Some(c) = textfarbe { rt = rt.color(c); }
+                        ui.label(rt);
+                    });
+                if !angaben.is_empty() {
+                    ui.add_space(16.0);
+                    ui.label(RichText::new(angaben.join("   ·   ")).size(24.0).strong());
+                }
+                if eintrag.review == Review::Strittig {
+                    ui.add_space(16.0);
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new("Klärung:").size(22.0).color(Review::Strittig.color()));
+                        ui.add(
+                            egui::TextEdit::singleline(&mut eintrag.klaerung)
+                                .font(egui::FontId::proportional(22.0))
+                                .hint_text("Was ist offen, wer klärt es?")
+                                .desired_width(f32::INFINITY),
+                        );
+                    });
+                }
+                ui.with_layout(egui::Layout::bottom_up(egui::Align::Center), |ui| {
+                    ui.label(RichText::new("← / → blättern · B bestätigt · S strittig · Esc oder F5 beendet die Präsentation").size(14.0).weak());
+                });
+            });
+    }
+
+    /// Zeigt eine Kurzmeldung für einige Sekunden unten rechts an.
+    fn toast_zeigen(&mut self, text: String) {
+        self.toast = Some((text, std::time::Instant::now()));
+    }
+
+    /// Baut das fertige genpdf-Dokument (inkl. Fußzeile mit Gesamtseitenzahl) auf,
+    /// ohne es zu schreiben (`GenpdfBackend`). Gerendert wird in zwei Durchläufen:
+    /// - **Durchlauf 1**: Inhalt in einen In-Memory-Puffer rendern, um die Gesamtseitenzahl
+    ///   zu ermitteln (genpdf kennt diese erst nach dem Rendern).
+    /// - **Durchlauf 2**: Inhalt erneut rendern, diesmal mit `FusszeileDekorator`, der
+    ///   die korrekte Gesamtseitenzahl in die Fußzeile schreibt.
+    fn pdf_dokument_erstellen(&self, schriftfamilie: genpdf::fonts::FontFamily<genpdf::fonts::FontData>) -> genpdf::Document {
+        // Festbreitenschrift nur einmal von der Platte lesen, für beide Durchläufe
+        let mono = self.mono_schrift_laden();
+
+        // Durchlauf 1: Gesamtseitenzahl durch In-Memory-Rendering ermitteln
+        let gesamtseiten = {
+            let seitenanzahl = std::rc::Rc::new(std::cell::Cell::new(0usize));
+            let zaehler = seitenanzahl.clone();
+
+            let mut vorberechnungs_dok = genpdf::Document::new(schriftfamilie.clone());
+            let mut dekorator = genpdf::SimplePageDecorator::new();
+            dekorator.set_margins(self.pdf_profil.raender());
+            // Callback wird pro Seite aufgerufen – speichert die letzte Seitennummer
+            dekorator.set_header(move |seite| {
+                zaehler.set(seite);
+                genpdf::elements::Break::new(0.0)
+            });
+            vorberechnungs_dok.set_page_decorator(dekorator);
+            vorberechnungs_dok.set_line_spacing(self.pdf_profil.zeilenabstand());
+            self.pdf_inhalt_hinzufuegen(&mut vorberechnungs_dok, mono.clone());
+            let mut puffer = Vec::new();
+            let _ = vorberechnungs_dok.render(&mut puffer);
+            seitenanzahl.get()
+        };
+
+        // Durchlauf 2: Echtes PDF mit Fußzeile und korrekter Gesamtseitenzahl erstellen
+        let mut dok = genpdf::Document::new(schriftfamilie);
+        let pdf_titel = if self.titel.is_empty() {
+            "MZProtokoll".to_string()
+        } else {
+            format!("{} — MZProtokoll von Marcel Zimmer (www.marcelzimmer.de)", self.titel)
+        };
+        dok.set_title(&pdf_titel);
+        dok.set_page_decorator(
+            FusszeileDekorator::new(gesamtseiten)
+                .mit_raendern(self.pdf_profil.raender())
+                .mit_kopfzeile(self.kopfzeile_erstellen(), self.einstellungen.pdf_kopfzeile_ausrichtung)
+                .mit_sprache(self.pdf_sprache),
+        );
+        dok.set_line_spacing(self.pdf_profil.zeilenabstand());
+        self.pdf_inhalt_hinzufuegen(&mut dok, mono);
+        dok
+    }
+
+    /// Öffnet den Dialog „An Gerät senden" und startet im Hintergrund
+    /// die mDNS-Suche nach IPP-Druckern im lokalen Netz.
+    fn druck_dialog_oeffnen(&mut self) {
+        self.sort_personen();
+        if self.protokollant.name.trim().is_empty() {
+            self.show_pflichtfeld_hinweis = true;
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || ipp_drucker_suchen(tx));
+        self.druck_dialog = Some(DruckDialog {
+            gefundene_drucker: Vec::new(),
+            suche_rx: Some(rx),
+            uri: String::new(),
+            seiten: 0,
+            fach: 0,
+            kopien: 1,
+            status: None,
+            auftrag_rx: None,
+        });
+    }
+
+    /// Bereitet das PDF vor, erzeugt es in einem separaten Thread und sendet es
+    /// per IPP (`Print-Job`) an die im Druckdialog gewählte Ziel-URI.
+    fn drucken(&mut self) {
+        let font_family = match self.schrift_laden() {
+            Some(f) => f,
+            None => {
+                self.show_pdf_error = true;
+                return;
+            }
+        };
+        let auftrag = match self.pdf_backend(font_family).pdf_vorbereiten(self) {
+            Ok(auftrag) => auftrag,
+            Err(e) => {
+                if let Some(dialog) = self.druck_dialog.as_mut() {
+                    dialog.status = Some(format!("PDF konnte nicht erzeugt werden: {}", e));
+                }
+                return;
+            }
+        };
+        let benutzer = self.benutzer_name();
+        let auftragsname = if self.titel.is_empty() { "MZProtokoll".to_string() } else { self.titel.clone() };
+        let Some(dialog) = self.druck_dialog.as_mut() else {
+            return;
+        };
+        let uri = dialog.uri.trim().to_string();
+        let seiten = IPP_SEITEN[dialog.seiten].0;
+        let fach = IPP_FAECHER[dialog.fach].0;
+        let kopien = dialog.kopien.max(1);
+        let (tx, rx) = mpsc::channel();
+        dialog.auftrag_rx = Some(rx);
+        dialog.status = Some("Wird gesendet …".to_string());
+        std::thread::spawn(move || {
+            let ergebnis = auftrag
+                .ausfuehren()
+                .map_err(|e| format!("PDF konnte nicht erzeugt werden: {}", e))
+                .and_then(|pdf| ipp_drucken(&uri, &benutzer, &auftragsname, seiten, fach, kopien, &pdf));
+            let _ = tx.send(ergebnis);
+        });
+    }
+
+    /// Vergibt für alle Entscheidungen ohne Nummer die nächste freie Beschlussnummer
+    /// des Meetingjahres (`B-<Jahr>-<NN>`) und trägt sämtliche Beschlüsse dieses
+    /// Protokolls ins archivweite Beschlussregister ein bzw. aktualisiert sie dort.
+    fn beschluesse_nummerieren(&mut self) {
+        let mut register = beschlussregister_laden();
+        let jahr = datum_aus_text(&self.datum_text)
+            .map(|d| d.year())
+            .unwrap_or_else(|| Local::now().year());
+        let praefix = format!("B-{}-", jahr);
+        let mut naechste = register
+            .iter()
+            .filter_map(|b| b.nr.strip_prefix(&praefix)?.parse::<u32>().ok())
+            .chain(
+                self.eintraege
+                    .iter()
+                    .filter_map(|e| e.beschluss_nr.strip_prefix(&praefix)?.parse::<u32>().ok()),
+            )
+            .max()
+            .unwrap_or(0)
+            + 1;
+
+        let mut geaendert = false;
+        for e in self.eintraege.iter_mut().filter(|e| e.art == Art::Entscheidung) {
+            if e.beschluss_nr.is_empty() {
+                e.beschluss_nr = format!("{}{:02}", praefix, naechste);
+                naechste += 1;
+            }
+            let beschluss = if e.punkt.is_empty() {
+                e.notiz.replace('\n', " ")
+            } else {
+                format!("{}: {}", e.punkt, e.notiz.replace('\n', " "))
+            };
+            let neu = BeschlussEintrag {
+                nr: e.beschluss_nr.clone(),
+                datum: self.datum_text.clone(),
+                titel: self.titel.clone(),
+                beschluss,
+            };
+            match register.iter_mut().find(|b| b.nr == neu.nr) {
+                Some(b) => {
+                    if b.datum != neu.datum || b.titel != neu.titel || b.beschluss != neu.beschluss {
+                        *b = neu;
+                        geaendert = true;
+                    }
+                }
+                None => {
+                    register.push(neu);
+                    geaendert = true;
+                }
+            }
+        }
+        if geaendert {
+            beschlussregister_speichern(&register);
+        }
+    }
+
+    /// Öffnet die Vorlage `pfad`, ersetzt alle automatisch auflösbaren Platzhalter
+    /// und öffnet für die übrigen den Platzhalter-Dialog. Sind keine interaktiven
+    /// Platzhalter vorhanden, wird das neue Protokoll direkt angelegt.
+    fn vorlage_anwenden(&mut self, pfad: &std::path::Path) {
+        let Ok(mut inhalt) = std::fs::read_to_string(pfad) else {
+            return;
+        };
+        let heute = Local::now().date_naive();
+
+        // {{letzte_offene_todos}}: offene TODOs des aktuell geöffneten Protokolls übernehmen
+        let offene_todos: Vec<&Eintrag> = self
+            .eintraege
+            .iter()
+            .filter(|e| e.art == Art::Todo && !e.notiz.trim().is_empty())
+            .collect();
+        if inhalt.contains("{{letzte_offene_todos}}") {
+            let mut ersetzt = String::new();
+            for line in inhalt.lines() {
+                if line.contains("{{letzte_offene_todos}}") {
+                    if line.trim_start().starts_with('|') {
+                        for e in &offene_todos {
+                            ersetzt.push_str(&eintrag_markdown_zeile(e, &self.art_label(&e.art), ""));
+                        }
+                    } else {
+                        let liste: Vec<String> = offene_todos
+                            .iter()
+                            .map(|e| e.notiz.replace('\n', " "))
+                            .collect();
+                        ersetzt.push_str(&line.replace("{{letzte_offene_todos}}", &liste.join(", ")));
+                        ersetzt.push('\n');
+                    }
+                } else {
+                    ersetzt.push_str(line);
+                    ersetzt.push('\n');
+                }
+            }
+            inhalt = ersetzt;
+        }
+
+        let mut werte = Vec::new();
+        for name in platzhalter_finden(&inhalt) {
+            let wert = platzhalter_automatisch(&name, heute)
+                .or_else(|| self.einstellungen.platzhalter.get(&name).cloned());
+            match wert {
+                Some(wert) => inhalt = platzhalter_einsetzen(&inhalt, &name, &wert),
+                None => werte.push((name, String::new())),
+            }
+        }
+
+        if werte.is_empty() {
+            self.protokoll_aus_vorlage(&inhalt);
+        } else {
+            self.platzhalter_dialog = Some(PlatzhalterDialog { inhalt, werte });
+        }
+    }
+
+    /// Verwirft das aktuelle Protokoll und beginnt ein leeres.
+    fn neu_anlegen(&mut self, ctx: &egui::Context) {
+        *self = self.leeres_protokoll(ctx);
+    }
+
+    /// Baut ein leeres Protokoll, in das der sitzungsweite Zustand wandert: Theme,
+    /// Omarchy-Erkennung, Icon, Touch-Modus, der Empfänger der Einzelinstanz und die
+    /// Eintrags-Zwischenablage.
+    fn leeres_protokoll(&mut self, ctx: &egui::Context) -> ProtokollApp {
+        let mut neu = ProtokollApp::new(ctx);
+        neu.theme = self.theme;
+        neu.has_omarchy = self.has_omarchy;
+        neu.icon_texture = self.icon_texture.take();
+        neu.touch_modus = self.touch_modus;
+        neu.instanz_rx = self.instanz_rx.take();
+        neu.eintrag_ablage = std::mem::take(&mut self.eintrag_ablage);
+        // Beschlussnummern gehören zum bisherigen Protokoll und werden im neuen neu vergeben
+        for e in &mut neu.eintrag_ablage {
+            e.beschluss_nr.clear();
+        }
+        touch_stil_anwenden(ctx, neu.touch_modus);
+        neu
+    }
+
+    /// `true`, wenn „Protokoll erstellt am" gesetzt ist und vom Meetingdatum abweicht.
+    fn protokoll_abweichend_erstellt(&self) -> bool {
+        let erstellt = datum_aus_text(&self.protokoll_datum);
+        erstellt.is_some() && erstellt != datum_aus_text(&self.datum_text)
+    }
+
+    /// Hält beim ersten Speichern Zeitpunkt und Person der Erstellung fest und belegt
+    /// ein leeres „Protokoll erstellt am" mit dem heutigen Datum vor.
+    fn erstellung_vermerken(&mut self) {
+        if self.erstellt_am.is_empty() {
+            self.erstellt_am = Local::now().format("%d.%m.%Y %H:%M").to_string();
+            self.erstellt_von = self.benutzer_name();
+        }
+        if self.protokoll_datum.is_empty() {
+            self.protokoll_datum = Local::now().format("%d.%m.%Y").to_string();
+        }
+    }
+
+    /// Fokus-Steuerung eines Kopffelds: setzt den angeforderten Fokus (`kopf_fokus`)
+    /// und leitet Tab strikt an das nächste Kopffeld weiter – Hinweis-Schaltflächen
+    /// zwischen Ort und Protokollführer werden so übersprungen.
+    fn kopf_feld_fokus(&mut self, ui: &egui::Ui, antwort: &egui::Response, feld: KopfFeld) {
+        if self.kopf_fokus == Some(feld) {
+            antwort.request_focus();
+            self.kopf_fokus = None;
+        } else if antwort.has_focus() && ui.input(|i| i.key_pressed(egui::Key::Tab) && !i.modifiers.shift) {
+            self.kopf_fokus = feld.naechstes();
+        }
+    }
+
+    /// Wechselt das Profil (leer = Standardprofil) und lädt Einstellungen und
+    /// Benutzerprofil neu. Das geöffnete Protokoll bleibt erhalten; ein leerer
+    /// Protokollführer wird aus dem neuen Benutzerprofil vorbelegt.
+    fn profil_wechseln(&mut self, ctx: &egui::Context, name: &str) {
+        if let Err(e) = aktives_profil_setzen(name) {
+            self.toast_zeigen(format!("Profil konnte nicht aktiviert werden: {}", e));
+            return;
+        }
+        self.konfig_profil = name.to_string();
+        self.einstellungen = Einstellungen::laden();
+        self.profil = Benutzerprofil::laden();
+        self.pdf_profil = self.einstellungen.pdf_profil;
+        self.pdf_sprache = self.einstellungen.pdf_sprache;
+        self.pdf_quellenseite = self.einstellungen.pdf_quellenseite;
+        self.touch_modus = self.einstellungen.touch_modus;
+        touch_stil_anwenden(ctx, self.touch_modus);
+        let zeilenhoehe = self.einstellungen.mausrad_zeilenhoehe;
+        ctx.options_mut(|o| o.line_scroll_speed = zeilenhoehe);
+        if self.protokollant.name.trim().is_empty() {
+            self.protokollant = self.profil.als_person();
+        }
+        let anzeige = if name.is_empty() { "Standard" } else { name };
+        self.toast_zeigen(format!("Profil „{}“ aktiv", anzeige));
+    }
+
+    /// Übernimmt die Angaben des Erste-Schritte-Assistenten in ein neues Protokoll.
+    fn assistent_abschliessen(&mut self, ctx: &egui::Context, assistent: ErsteSchritteAssistent) {
+        self.neu_anlegen(ctx);
+        let (typ_titel, typ_beschreibung) = MEETINGTYPEN[assistent.meetingtyp];
+        self.projekt = assistent.projekt.trim().to_string();
+        self.titel = if assistent.titel.trim().is_empty() {
+            typ_titel.to_string()
+        } else {
+            assistent.titel.trim().to_string()
+        };
+        self.ort = assistent.ort.trim().to_string();
+        self.ueber_meeting = typ_beschreibung.to_string();
+        let mut teilnehmer: Vec<Person> = assistent
+            .teilnehmer
+            .lines()
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .map(|name| Person {
+                name: name.to_string(),
+                kuerzel: self.einstellungen.kuerzel_erzeugen(name, ""),
+                kuerzel_manuell: false,
+            })
+            .collect();
+        teilnehmer.push(Person::new());
+        self.teilnehmer = teilnehmer;
+        let mut eintraege: Vec<Eintrag> = assistent
+            .agenda
+            .lines()
+            .map(|z| z.trim().trim_start_matches(['-', '*']).trim())
+            .filter(|z| !z.is_empty())
+            .map(|punkt| Eintrag {
+                punkt: punkt.to_string(),
+                art: Art::Agenda,
+                ..Eintrag::new()
+            })
+            .collect();
+        eintraege.push(Eintrag::new());
+        self.eintraege = eintraege;
+        self.sort_personen();
+    }
+
+    /// Legt aus dem aufgelösten Vorlageninhalt ein neues, noch ungespeichertes Protokoll an.
+    fn protokoll_aus_vorlage(&mut self, inhalt: &str) {
+        self.sicherung_anlegen();
+        self.markdown_parsen(inhalt);
+        self.sort_personen();
+        self.save_path = None;
+        self.entwurf_pfad = None;
+        self.protokoll_datum.clear();
+        self.erstellt_am.clear();
+        self.erstellt_von.clear();
+        self.audit_log.clear();
+        self.vorgaenger.clear();
+        self.bearbeitungszeit = std::time::Duration::ZERO;
+        self.bearbeitungszeit_datei = None;
+        self.naechste_eintrag_id = 0;
+        for e in &mut self.eintraege {
+            e.beschluss_nr.clear();
+        }
+        // Nur TOPs ohne eigene Einträge vorbelegen; von hinten, damit die Indizes gelten
+        let leer = |e: &Eintrag| e.art == Art::Leer && e.notiz.trim().is_empty();
+        for i in (0..self.eintraege.len()).rev() {
+            let ohne_inhalt = self.eintraege[i + 1..].iter().take_while(|e| e.art != Art::Agenda).all(leer);
+            if self.eintraege[i].art == Art::Agenda && ohne_inhalt {
+                self.top_vorbelegen(i);
+            }
+        }
+    }
+
+    /// Legt am Ende des Agenda-TOPs `i` die Kind-Einträge aus `top_vorbelegung` an
+    /// (vor einer leeren Schlusszeile).
+    fn top_vorbelegen(&mut self, i: usize) {
+        let kinder: Vec<Eintrag> = self
+            .einstellungen
+            .top_vorbelegung(&self.projekt)
+            .iter()
+            .map(|(art, notiz)| Eintrag {
+                art: art.clone(),
+                notiz: notiz.clone(),
+                ..Eintrag::new()
+            })
+            .collect();
+        let mut ende = i + 1;
+        while ende < self.eintraege.len() && self.eintraege[ende].art != Art::Agenda {
+            ende += 1;
+        }
+        while ende > i + 1 && self.eintraege[ende - 1].art == Art::Leer && self.eintraege[ende - 1].notiz.trim().is_empty() {
+            ende -= 1;
+        }
+        self.eintraege.splice(ende..ende, kinder);
+    }
+
+    /// Speichert das aktuelle Protokoll als Vorlage im Vorlagenverzeichnis.
+    /// Platzhalter wie `{{datum+7}}` können danach direkt in der Datei ergänzt werden.
+    fn als_vorlage_speichern(&mut self) {
+        let Some(dir) = vorlagen_verzeichnis() else {
+            return;
+        };
+        let _ = std::fs::create_dir_all(&dir);
+        let content = self.markdown_erstellen();
+        let name_part: String = self.titel.chars().filter(|c| c.is_alphabetic()).collect();
+        let filename = format!("{}.md", if name_part.is_empty() { "Vorlage".to_string() } else { name_part });
+        let (tx, rx) = mpsc::channel();
+        self.dialog_rx = Some(rx);
+        std::thread::spawn(move || {
+            if let Some(path) = rfd::FileDialog::new()
+                .set_directory(&dir)
+                .set_file_name(&filename)
+                .add_filter("Markdown", &["md"])
+                .save_file()
+            {
+                let _ = std::fs::write(&path, &content);
+                let _ = tx.send(DialogErgebnis::VorlageGespeichert);
+            }
+        });
+    }
+
+    /// Beschriftung einer Art im aktuellen Projekt (siehe `Einstellungen::art_label`).
+    fn art_label(&self, art: &Art) -> String {
+        self.einstellungen.art_label(&self.projekt, art)
+    }
+
+    /// Beschriftung einer Art im PDF: Standardbeschriftungen in `pdf_sprache`,
+    /// eigene Beschriftungen des Projekts unverändert.
+    fn pdf_art_label(&self, art: &Art) -> String {
+        self.pdf_sprache.t(&self.art_label(art)).to_string()
+    }
+
+    /// Wählt die Hilfeseite passend zur aktuellen Ansicht (für F1):
+    /// offener Dialog vor fokussiertem Notizfeld vor „Erste Schritte".
+    fn hilfe_kontext(&self) -> usize {
+        let titel = if self.show_pdf_export_dialog || self.druck_dialog.is_some() {
+            "PDF-Export"
+        } else if self.profil_dialog.is_some() || self.zugangsdaten_dialog.is_some() || self.profil_wechsel.is_some() {
+            "Einstellungen"
+        } else if self.notiz_had_focus.is_some() {
+            "Eintragsarten"
+        } else {
+            "Erste Schritte"
+        };
+        HILFE_SEITEN.iter().position(|(t, _)| *t == titel).unwrap_or(0)
+    }
+
+    /// Öffnet die Offline-Hilfe auf der zur Ansicht passenden Seite.
+    fn hilfe_oeffnen(&mut self) {
+        self.hilfe = Some(HilfeFenster { seite: self.hilfe_kontext(), suche: String::new() });
+    }
+
+    /// Name der Person, die die App bedient: aus dem Benutzerprofil, ersatzweise
+    /// der Protokollführer des geöffneten Protokolls.
+    fn benutzer_name(&self) -> String {
+        if self.profil.name.trim().is_empty() {
+            self.protokollant.name.clone()
+        } else {
+            self.profil.name.clone()
+        }
+    }
+
+    /// Hängt eine Änderung mit aktuellem Zeitstempel und dem Benutzer
+    /// (siehe `benutzer_name`) an das Audit-Log an.
+    fn audit_protokollieren(&mut self, aktion: &str) {
+        self.audit_log.push(AuditEintrag {
+            zeitpunkt: Local::now().format("%d.%m.%Y %H:%M").to_string(),
+            benutzer: self.benutzer_name(),
+            aktion: aktion.replace('\n', " "),
+        });
+    }
+
+    /// Automatische Prüfung vor dem Versand: Freigabe, TODOs ohne Kümmerer oder gültiges
+    /// Bis-Datum bzw. an arbeitsfreien Tagen, leerer Verteiler (leer = keine Auffälligkeiten).
+    fn versand_hinweise(&self) -> Vec<String> {
+        let mut hinweise = Vec::new();
+        if !matches!(self.status, Status::Freigegeben | Status::Archiviert) {
+            hinweise.push("Das Protokoll ist noch nicht freigegeben.".to_string());
+        }
+        let unvollstaendig = self
+            .eintraege
+            .iter()
+            .filter(|e| {
+                e.art == Art::Todo
+                    && (e.kuemmerer.trim().is_empty()
+                        || NaiveDate::parse_from_str(&e.bis, "%d.%m.%Y").is_err())
+            })
+            .count();
+        if unvollstaendig > 0 {
+            hinweise.push(format!("{} TODO(s) ohne Kümmerer oder gültiges Bis-Datum.", unvollstaendig));
+        }
+        let arbeitsfrei = self
+            .eintraege
+            .iter()
+            .filter(|e| e.art == Art::Todo && self.einstellungen.feiertage.bis_hinweis(&e.bis).is_some())
+            .count();
+        if arbeitsfrei > 0 {
+            hinweise.push(format!("{} TODO(s) an einem Wochenende oder Feiertag fällig.", arbeitsfrei));
+        }
+        let meeting = datum_aus_text(&self.datum_text);
+        let unplausibel = self
+            .eintraege
+            .iter()
+            .filter(|e| e.art == Art::Todo && bis_plausibilitaet(&e.bis, meeting).is_some())
+            .count();
+        if unplausibel > 0 {
+            hinweise.push(format!("{} TODO(s) vor dem Meeting oder mehr als zwei Jahre danach fällig.", unplausibel));
+        }
+        if self.zur_kenntnis.iter().all(|z| z.name.trim().is_empty()) {
+            hinweise.push("Der Verteiler „Zur Kenntnis“ ist leer.".to_string());
+        }
+        hinweise
+    }
+
+    /// Öffnet den Abschluss-Assistenten mit den konfigurierten Checklistenpunkten
+    /// und den Ergebnissen der automatischen Prüfung.
+    fn versand_checkliste_oeffnen(&mut self) {
+        self.versand_checkliste = Some(VersandCheckliste {
+            punkte: self.einstellungen.checkliste.iter().map(|p| (p.clone(), false)).collect(),
+            hinweise: self.versand_hinweise(),
+        });
+    }
+
+    /// Startet die Freigabe-Pipeline (`freigabe_pipeline`) für das freigegebene Protokoll.
+    fn freigabe_pipeline_starten(&mut self) {
+        if self.freigabe_pipeline.is_some() {
+            return;
+        }
+        if self.einstellungen.freigabe_pipeline.is_empty() {
+            self.toast_zeigen("Keine Freigabe-Pipeline eingestellt (freigabe_pipeline in einstellungen.toml)".to_string());
+            return;
+        }
+        if self.status != Status::Freigegeben {
+            self.toast_zeigen("Die Freigabe-Pipeline läuft nur für freigegebene Protokolle".to_string());
+            return;
+        }
+        let schritte = self.einstellungen.freigabe_pipeline.clone();
+        let namen: Vec<&str> = schritte.iter().map(|s| s.label()).collect();
+        self.audit_protokollieren(&format!("Freigabe-Pipeline gestartet: {}", namen.join(" → ")));
+        self.freigabe_pipeline = Some(FreigabePipeline { schritte, erledigt: Vec::new(), fehler: None, laeuft: None });
+    }
+
+    /// Führt einen Schritt der Freigabe-Pipeline aus. Das Ergebnis (Meldung oder Fehler)
+    /// kommt über den zurückgegebenen Kanal – sofort bzw. bei Matrix und Webhook aus
+    /// einem eigenen Thread.
+    fn freigabe_schritt_ausfuehren(&mut self, schritt: PipelineSchritt) -> mpsc::Receiver<Result<String, String>> {
+        let (tx, rx) = mpsc::channel();
+        let ergebnis = match schritt {
+            PipelineSchritt::Pruefen => {
+                let hinweise = self.versand_hinweise();
+                if hinweise.is_empty() {
+                    Ok("Keine Auffälligkeiten".to_string())
+                } else {
+                    Err(hinweise.join(" "))
+                }
+            }
+            PipelineSchritt::Archiv => match self.freigabe_archivieren(tx.clone()) {
+                // Ergebnis kommt nach PDF-Export und Archivierung über `pdf_fertig`
+                Ok(()) => return rx,
+                Err(fehler) => Err(fehler),
+            },
+            PipelineSchritt::Matrix if !self.matrix_eingerichtet() => {
+                Err("Matrix ist nicht eingerichtet (matrix_homeserver, matrix_raum)".to_string())
+            }
+            PipelineSchritt::Matrix => {
+                let (text, html) = self.matrix_zusammenfassung();
+                let homeserver = self.einstellungen.matrix_homeserver.trim().to_string();
+                let raum = self.einstellungen.matrix_raum.trim().to_string();
+                // Der Schlüsselbund (bzw. `passwort_kommando`) kann blockieren
+                std::thread::spawn(move || {
+                    let ergebnis = match zugangsdaten_laden("matrix") {
+                        None => Err("Kein Matrix-Zugangstoken hinterlegt (Menü „Zugangsdaten“)".to_string()),
+                        Some(token) => matrix_nachricht_senden(&homeserver, &raum, &token, &text, &html),
+                    };
+                    let _ = tx.send(ergebnis.map(|()| "Zusammenfassung gepostet".to_string()));
+                });
+                return rx;
+            }
+            PipelineSchritt::Email => {
+                self.per_email_versenden();
+                Ok("E-Mail im Mailprogramm geöffnet".to_string())
+            }
+            PipelineSchritt::Webhook => {
+                let url = self.einstellungen.freigabe_webhook.trim().to_string();
+                if url.is_empty() {
+                    Err("Kein Webhook eingestellt (freigabe_webhook in einstellungen.toml)".to_string())
+                } else {
+                    let koerper = self.webhook_koerper();
+                    std::thread::spawn(move || {
+                        let _ = tx.send(webhook_senden(&url, &koerper).map(|()| format!("An {} gesendet", url)));
+                    });
+                    return rx;
+                }
+            }
+            PipelineSchritt::Schreibschutz => match self.save_path.clone() {
+                Some(pfad) => {
+                    for endung in ["md", "pdf", "xml"] {
+                        schreibschutz_setzen(&pfad.with_extension(endung));
+                    }
+                    Ok("Dateien schreibgeschützt".to_string())
+                }
+                None => Err("Das Protokoll ist nicht gespeichert".to_string()),
+            },
+        };
+        let _ = tx.send(ergebnis);
+        rx
+    }
+
+    /// Pipeline-Schritt „PDF-Export ins Archiv": speichert, erzeugt das PDF im Hintergrund
+    /// neu (damit kein veralteter Stand mitwandert) und archiviert danach in `pdf_fertig`
+    /// wie „Archivieren". Das Ergebnis geht über `tx` an die Pipeline.
+    fn freigabe_archivieren(&mut self, tx: mpsc::Sender<Result<String, String>>) -> Result<(), String> {
+        let Some(alt) = self.save_path.clone() else {
+            return Err("Das Protokoll muss vor dem Archivieren gespeichert sein".to_string());
+        };
+        if schreibgeschuetzt(&alt) {
+            return Err("Das Protokoll ist bereits archiviert".to_string());
+        }
+        self.speichern();
+        let schrift = self.schrift_laden().ok_or_else(|| "Keine Schrift für den PDF-Export gefunden".to_string())?;
+        let export = self
+            .pdf_export_vorbereiten(&alt.with_extension("pdf"), schrift)
+            .map_err(|e| format!("PDF-Export fehlgeschlagen: {}", e))?;
+        self.pdf_im_hintergrund(export, PdfDanach::Freigabe { alt, tx }, || {});
+        Ok(())
+    }
+
+    /// JSON für den Webhook der Freigabe-Pipeline: Kopfdaten, Status und Dateipfade.
+    fn webhook_koerper(&self) -> String {
+        let pfad = |endung: &str| {
+            self.save_path
+                .as_ref()
+                .map(|p| p.with_extension(endung))
+                .filter(|p| p.is_file())
+                .map(|p| p.display().to_string())
+                .unwrap_or_default()
+        };
+        format!(
+            "{{\"ereignis\":\"freigabe\",\"titel\":\"{}\",\"projekt\":\"{}\",\"datum\":\"{}\",\"status\":\"{}\",\"protokollant\":\"{}\",\"datei\":\"{}\",\"pdf\":\"{}\"}}",
+            json_escapen(&self.titel),
+            json_escapen(&self.projekt),
+            json_escapen(&self.datum_text),
+            self.status.label(),
+            json_escapen(&self.protokollant.name),
+            json_escapen(&pfad("md")),
+            json_escapen(&pfad("pdf"))
+        )
+    }
+
+    /// Setzt eine neue Klassifizierung. Wird ein bereits gespeichertes bzw. geladenes
+    /// Protokoll ab „Vertraulich" herabgestuft, wird stattdessen der Bestätigungsdialog
+    /// mit Begründungsfeld geöffnet.
+    fn sicherheit_setzen(&mut self, neu: Sicherheit) {
+        let ist_herabstufung = self.save_path.is_some()
+            && self.sicherheit.stufe() >= Sicherheit::Vertraulich.stufe()
+            && neu.stufe() < self.sicherheit.stufe();
+        if ist_herabstufung {
+            self.herabstufung = Some(neu);
+            self.herabstufung_begruendung.clear();
+        } else {
+            self.sicherheit = neu;
+        }
+    }
+
+    /// Zustände, die in UI, Markdown und PDF angeboten werden: die aus `status_zustaende`
+    /// und – auch wenn dort nicht aufgeführt – der aktuelle.
+    fn status_auswahl(&self) -> Vec<Status> {
+        Status::alle()
+            .iter()
+            .copied()
+            .filter(|s| *s == self.status || self.einstellungen.status_zustaende.contains(s))
+            .collect()
+    }
+
+    /// Prüft einen Statuswechsel gegen das Zustandsmodell. Ist „In Prüfung" aktiv,
+    /// führt der Weg vom Entwurf zur Freigabe über die Prüfung.
+    fn status_wechsel_erlaubt(&self, ziel: Status) -> bool {
+        if ziel == self.status {
+            return true;
+        }
+        let zustaende = &self.einstellungen.status_zustaende;
+        zustaende.contains(&ziel)
+            && self.status.nachfolger().contains(&ziel)
+            && !(self.status == Status::Entwurf && ziel == Status::Freigegeben && zustaende.contains(&Status::InPruefung))
+    }
+
+    /// Wechselt den Status und hält den Übergang im Audit-Log fest. Bei der Freigabe
+    /// startet die Freigabe-Pipeline bzw. ohne Pipeline ggf. die Nachfrage zum Matrix-Post.
+    fn status_setzen(&mut self, neu: Status) {
+        if neu == self.status || !self.status_wechsel_erlaubt(neu) {
+            return;
+        }
+        // Pflichtfelder spätestens bei der Freigabe
+        if neu == Status::Freigegeben && self.protokollant.name.trim().is_empty() {
+            self.show_pflichtfeld_hinweis = true;
+            return;
+        }
+        let aktion = format!("Status: {} → {}", self.status.label(), neu.label());
+        self.status = neu;
+        self.audit_protokollieren(&aktion);
+        if neu == Status::Freigegeben {
+            if self.einstellungen.freigabe_pipeline.is_empty() {
+                self.matrix_nachfrage = self.matrix_eingerichtet();
+            } else {
+                self.freigabe_pipeline_starten();
+            }
+        }
+    }
+
+    /// Generiert einen vorgeschlagenen Dateinamen für den Tabellen-Export.
+    /// Format: `MZProtokoll_<Titel>__<JJJJ-MM-TT>.xlsx`
+    fn xlsx_dateinamen_erstellen(&self) -> String {
+        let name_part: String = self.titel.chars().filter(|c| c.is_alphabetic()).collect();
+        let datum = Local::now().format("%Y-%m-%d").to_string();
+        format!("MZProtokoll_{}__{}.xlsx", name_part, datum)
+    }
+
+    /// Öffnet einen Datei-Speichern-Dialog (separater Thread) für den
+    /// Export der Einträge als Excel-Tabelle.
+    fn xlsx_exportieren(&mut self) {
+        let filename = self.xlsx_dateinamen_erstellen();
+        let (tx, rx) = mpsc::channel();
+        self.dialog_rx = Some(rx);
+        std::thread::spawn(move || {
+            if let Some(path) = rfd::FileDialog::new()
+                .set_file_name(&filename)
+                .add_filter("Excel-Tabelle", &["xlsx"])
+                .save_file()
+            {
+                let _ = tx.send(DialogErgebnis::XlsxExport(path));
+            }
+        });
+    }
+
+    /// Öffnet einen Datei-Speichern-Dialog (separater Thread) für den
+    /// Export als Typst-Quelltext.
+    fn typst_exportieren(&mut self) {
+        let filename = self.xlsx_dateinamen_erstellen().replace(".xlsx", ".typ");
+        let (tx, rx) = mpsc::channel();
+        self.dialog_rx = Some(rx);
+        std::thread::spawn(move || {
+            if let Some(path) = rfd::FileDialog::new()
+                .set_file_name(&filename)
+                .add_filter("Typst", &["typ"])
+                .save_file()
+            {
+                let _ = tx.send(DialogErgebnis::TypstExport(path));
+            }
+        });
+    }
+
+    /// Schreibt den Typst-Quelltext und kompiliert ihn bei `typst_kompilieren`
+    /// daneben zum PDF (im Hintergrund). Ergebnis oder Fehler erscheinen als Toast.
+    fn typst_generieren(&mut self, path: &std::path::Path) {
+        let quelle = self.typst_erstellen();
+        if let Err(e) = std::fs::write(path, &quelle) {
+            self.toast_zeigen(format!("Typst-Export fehlgeschlagen: {}", e));
+            return;
+        }
+        if !self.einstellungen.typst_kompilieren {
+            self.toast_zeigen(format!("Exportiert: {}", path.display()));
+            return;
+        }
+        let export = PdfExport {
+            auftrag: PdfAuftrag::Extern { kommando: self.einstellungen.typst_kommando.clone(), endung: "typ", quelle },
+            pdfa_kommando: None,
+            verschluesselung: None,
+            ziel: path.with_extension("pdf"),
+            versand_stand: None,
+            xml: None,
+        };
+        self.pdf_im_hintergrund(export, PdfDanach::Typst, || {});
+    }
+
+    /// Schreibt die Einträge als XLSX-Arbeitsmappe:
+    /// - Kopfzeile fixiert und mit Filterzeile (AutoFilter),
+    /// - gültige Bis-Daten als echte Excel-Datumswerte (TT.MM.JJJJ; vor 1900 als Text),
+    /// - bedingte Formatierung: überfällige TODOs (Bis < HEUTE()) werden rot hervorgehoben.
+    fn xlsx_generieren(&self, path: &std::path::Path) -> Result<(), rust_xlsxwriter::XlsxError> {
+        use rust_xlsxwriter::{Color, ConditionalFormatFormula, ExcelDateTime, Format, Workbook};
+
+        let mut arbeitsmappe = Workbook::new();
+        let blatt = arbeitsmappe.add_worksheet();
+        blatt.set_name("Einträge")?;
+
+        let kopf_format = Format::new().set_bold().set_background_color(Color::RGB(0xDCDCDC));
+        let text_format = Format::new().set_text_wrap();
+        let datum_format = Format::new().set_num_format("dd.mm.yyyy");
+        let ueberfaellig_format = Format::new()
+            .set_font_color(Color::RGB(0x9C0006))
+            .set_background_color(Color::RGB(0xFFC7CE));
+
+        let spalten = [("Punkt", 24.0), ("Art", 16.0), ("Notiz", 60.0), ("Kümmerer", 12.0), ("Bis", 12.0)];
+        for (spalte, (titel, breite)) in spalten.iter().enumerate() {
+            blatt.write_string_with_format(0, spalte as u16, *titel, &kopf_format)?;
+            blatt.set_column_width(spalte as u16, *breite)?;
+        }
+
+        let nummern = top_nummern(&self.eintraege, self.einstellungen.top_nummerierung);
+        let entries: Vec<_> = self
+            .eintraege
+            .iter()
+            .zip(&nummern)
+            .filter(|(e, _)| !e.punkt.is_empty() || e.art != Art::Leer || !e.notiz.is_empty())
+            .collect();
+
+        for (i, (e, (_, nummer))) in entries.iter().enumerate() {
+            let zeile = i as u32 + 1;
+            let art_str = if e.art == Art::Leer { "" } else { e.art.label() };
+            blatt.write_string(zeile, 0, nummer_voranstellen(nummer, &e.punkt))?;
+            blatt.write_string(zeile, 1, art_str)?;
+            blatt.write_string_with_format(zeile, 2, &e.notiz, &text_format)?;
+            blatt.write_string(zeile, 3, &e.kuemmerer)?;
+            // Excel kennt nur Daten von 1900 bis 9999; alles andere bleibt Text
+            let excel_datum = NaiveDate::parse_from_str(&e.bis, "%d.%m.%Y").ok().and_then(|datum| {
+                let jahr = u16::try_from(datum.year()).ok()?;
+                ExcelDateTime::from_ymd(jahr, datum.month() as u8, datum.day() as u8).ok()
+            });
+            match excel_datum {
+                Some(excel_datum) => {
+                    blatt.write_datetime_with_format(zeile, 4, &excel_datum, &datum_format)?;
+                }
+                None => {
+                    blatt.write_string(zeile, 4, &e.bis)?;
+                }
+            }
+        }
+
+        let letzte_zeile = (entries.len() as u32).max(1);
+        blatt.set_freeze_panes(1, 0)?;
+        blatt.autofilter(0, 0, letzte_zeile, 4)?;
+        let ueberfaellig = ConditionalFormatFormula::new()
+            .set_rule("=AND($B2=\"TODO\",ISNUMBER($E2),$E2<TODAY())")
+            .set_format(ueberfaellig_format);
+        blatt.add_conditional_format(1, 0, letzte_zeile, 4, &ueberfaellig)?;
+
+        arbeitsmappe.save(path)
+    }
+
+    /// Löst das Sprecher-Kürzel eines Wortbeitrags in den vollen Namen auf
+    /// (Protokollführer oder Teilnehmer). Unbekannte Kürzel bleiben unverändert.
+    fn sprecher_name(&self, kuerzel: &str) -> String {
+        std::iter::once(&self.protokollant)
+            .chain(self.teilnehmer.iter())
+            .find(|p| !p.name.is_empty() && (p.kuerzel == kuerzel || p.name == kuerzel))
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| kuerzel.to_string())
+    }
+
+    /// `true`, wenn der laufende Export Personen anonymisiert (Exportprofil).
+    fn pdf_anonym(&self) -> bool {
+        self.exportprofil_aktiv.as_ref().is_some_and(|p| p.anonymisieren)
+    }
+
+    /// Person im Export: „Name [Kürzel]", anonymisiert nur das Kürzel bzw. die Initialen.
+    fn pdf_person(&self, person: &Person) -> String {
+        if self.pdf_anonym() {
+            if person.kuerzel.is_empty() {
+                Person::auto_kuerzel(&person.name)
+            } else {
+                person.kuerzel.clone()
+            }
+        } else if person.kuerzel.is_empty() {
+            person.name.clone()
+        } else {
+            format!("{} [{}]", person.name, person.kuerzel)
+        }
+    }
+
+    /// Sprecher eines Wortbeitrags im Export; anonymisiert bleibt es beim Kürzel.
+    fn pdf_sprecher(&self, kuerzel: &str) -> String {
+        if !self.pdf_anonym() {
+            return self.sprecher_name(kuerzel);
+        }
+        std::iter::once(&self.protokollant)
+            .chain(self.teilnehmer.iter())
+            .find(|p| !p.name.is_empty() && (p.kuerzel == kuerzel || p.name == kuerzel))
+            .map(|p| self.pdf_person(p))
+            .unwrap_or_else(|| kuerzel.to_string())
+    }
+
+    /// Freitext im Export; anonymisiert werden die vollen Namen aller Beteiligten
+    /// durch ihr Kürzel ersetzt (längere Namen zuerst).
+    fn pdf_text(&self, text: &str) -> String {
+        if !self.pdf_anonym() {
+            return text.to_string();
+        }
+        let mut personen: Vec<&Person> = std::iter::once(&self.protokollant)
+            .chain(self.teilnehmer.iter())
+            .chain(self.zur_kenntnis.iter())
+            .filter(|p| !p.name.trim().is_empty())
+            .collect();
+        personen.sort_by_key(|p| std::cmp::Reverse(p.name.trim().chars().count()));
+        let mut ergebnis = text.to_string();
+        for person in personen {
+            ergebnis = ergebnis.replace(person.name.trim(), &self.pdf_person(person));
+        }
+        ergebnis
+    }
+
+    /// `true`, wenn Einträge dieser Art im laufenden Export erscheinen.
+    fn pdf_art_exportieren(&self, art: &Art) -> bool {
+        self.exportprofil_aktiv.as_ref().is_none_or(|p| p.arten.is_empty() || p.arten.contains(art))
+    }
+
+    /// Gibt alle bekannten Kürzel (Protokollant + Teilnehmer + Zur-Kenntnis)
+    /// sortiert und dedupliziert zurück. Wird für das Kümmerer-Dropdown in TODO-Zeilen verwendet.
+    fn alle_kuerzel(&self) -> Vec<String> {
+        let mut k = Vec::new();
+        if !self.protokollant.kuerzel.is_empty() {
+            k.push(self.protokollant.kuerzel.clone());
+        }
+        for t in &self.teilnehmer {
+            if !t.kuerzel.is_empty() {
+                k.push(t.kuerzel.clone());
+            }
+        }
+        for z in &self.zur_kenntnis {
+            if !z.kuerzel.is_empty() {
+                k.push(z.kuerzel.clone());
+            }
+        }
+        k.sort();
+        k.dedup();
+        k
+    }
+
+    /// Alle Personen des Protokolls (Protokollführer, Teilnehmer, Zur Kenntnis).
+    fn alle_personen(&self) -> Vec<&Person> {
+        std::iter::once(&self.protokollant)
+            .chain(self.teilnehmer.iter())
+            .chain(self.zur_kenntnis.iter())
+            .collect()
+    }
+
+    /// `true`, wenn der Eintrag bei den aktiven Filtern „Meine Aufgaben“ und
+    /// „fällig zwischen“ angezeigt wird (ohne Filter immer).
+    fn eintrag_sichtbar(&self, i: usize) -> bool {
+        let e = &self.eintraege[i];
+        // Bei Fragen steht im Bis-Feld das Antwortdatum, keine Fälligkeit
+        let faellig = if e.art == Art::Frage { "" } else { e.bis.as_str() };
+        (!self.nur_meine_aufgaben
+            || (e.art == Art::Todo && kuerzel_zustaendig(&e.kuemmerer, &self.profil.kuerzel, &self.profil.name)))
+            && self.faellig_filter.passt(faellig)
+    }
+
+    /// Öffnet die Liste „Meine Aufgaben im Archiv“ (alle Protokolle unter `archiv_verzeichnis`).
+    fn archiv_aufgaben_oeffnen(&mut self) {
+        if self.profil.kuerzel.trim().is_empty() && self.profil.name.trim().is_empty() {
+            self.toast_zeigen("Kein eigenes Kürzel im Benutzerprofil hinterlegt".to_string());
+            return;
+        }
+        let Some(archiv) = einstellungspfad(&self.einstellungen.archiv_verzeichnis) else {
+            self.toast_zeigen("Kein Archiv eingestellt (archiv_verzeichnis in einstellungen.toml)".to_string());
+            return;
+        };
+        self.archiv_aufgaben = Some(archiv_aufgaben_laden(&archiv, &self.profil, &self.einstellungen));
+    }
+
+    /// Unbekannte Kürzel aller TODOs mit den Indizes der betroffenen Einträge,
+    /// nach Kürzel sortiert.
+    fn unbekannte_kuemmerer(&self) -> Vec<(String, Vec<usize>)> {
+        let personen = self.alle_personen();
+        let mut treffer: Vec<(String, Vec<usize>)> = Vec::new();
+        for (i, e) in self.eintraege.iter().enumerate().filter(|(_, e)| e.art == Art::Todo) {
+            for k in unbekannte_kuerzel(&e.kuemmerer, &personen) {
+                match treffer.iter_mut().find(|(t, _)| *t == k) {
+                    Some((_, indizes)) => indizes.push(i),
+                    None => treffer.push((k, vec![i])),
+                }
+            }
+        }
+        treffer.sort_by(|a, b| a.0.cmp(&b.0));
+        treffer
+    }
+
+    /// Öffnet „Kümmerer prüfen" und schlägt je Kürzel das ähnlichste bekannte vor.
+    fn kuerzel_pruefung_oeffnen(&mut self) {
+        let bekannte = self.alle_kuerzel();
+        let korrekturen = self
+            .unbekannte_kuemmerer()
+            .into_iter()
+            .map(|(k, _)| {
+                let vorschlag = bekannte.iter().min_by_key(|b| editierabstand(&k, b)).cloned().unwrap_or_default();
+                (k, vorschlag)
+            })
+            .collect();
+        self.kuerzel_pruefung = Some(KuerzelPruefung { namen: HashMap::new(), korrekturen });
+    }
+
+    /// Ersetzt ein Kürzel in den Kümmerer-Feldern aller TODOs.
+    fn kuerzel_ersetzen(&mut self, alt: &str, neu: &str) {
+        for e in self.eintraege.iter_mut().filter(|e| e.art == Art::Todo) {
+            let teile: Vec<&str> = e.kuemmerer.split([',', '/', '+', '&']).map(str::trim).collect();
+            if teile.contains(&alt) {
+                e.kuemmerer = if teile.len() == 1 {
+                    neu.to_string()
+                } else {
+                    teile.iter().map(|t| if *t == alt { neu } else { t }).collect::<Vec<_>>().join(", ")
+                };
+            }
+        }
+    }
+
+    /// Legt für ein unbekanntes Kürzel einen Teilnehmer an (mit manuell gesetztem Kürzel).
+    fn person_fuer_kuerzel_anlegen(&mut self, kuerzel: &str, name: &str) {
+        let mut person = Person::new();
+        person.name = name.trim().to_string();
+        person.kuerzel = kuerzel.to_string();
+        person.kuerzel_manuell = true;
+        match self.teilnehmer.last_mut() {
+            Some(letzte) if letzte.name.trim().is_empty() && letzte.kuerzel.trim().is_empty() => *letzte = person,
+            _ => self.teilnehmer.push(person),
+        }
+    }
+
+    /// Zeichnet die Fälligkeits-Zeitleiste: jedes TODO mit gültigem Bis-Datum als Punkt
+    /// über `zeitleiste_wochen` Wochen ab der Woche des Meetings (ohne Datum ab heute);
+    /// TODOs am selben Tag stapeln sich, frühere und spätere sammeln sich am Rand.
+    /// Hover zeigt Aufgabe, Kümmerer und Datum, ein Klick springt zur Notiz der Zeile.
+    fn faelligkeits_zeitleiste(&mut self, ui: &mut egui::Ui) {
+        let todos: Vec<(usize, NaiveDate)> = self
+            .eintraege
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.art == Art::Todo)
+            .filter_map(|(i, e)| NaiveDate::parse_from_str(e.bis.trim(), "%d.%m.%Y").ok().map(|d| (i, d)))
+            .collect();
+        if todos.is_empty() {
+            ui.label(RichText::new("Kein TODO mit gültigem Bis-Datum.").weak());
+            return;
+        }
+        let basis = datum_aus_text(&self.datum_text).unwrap_or_else(|| Local::now().date_naive());
+        let start = basis - chrono::Duration::days(basis.weekday().num_days_from_monday() as i64);
+        let tage = self.einstellungen.zeitleiste_wochen as i64 * 7;
+        let ende = start + chrono::Duration::days(tage - 1);
+
+        // Tag je TODO (-1 = früher, `tage` = später) und Stapelhöhe darauf
+        let mut stapel: HashMap<i64, usize> = HashMap::new();
+        let punkte: Vec<(usize, NaiveDate, i64, usize)> = todos
+            .iter()
+            .map(|&(i, datum)| {
+                let tag = (datum - start).num_days().clamp(-1, tage);
+                let ebene = stapel.entry(tag).or_insert(0);
+                *ebene += 1;
+                (i, datum, tag, *ebene - 1)
+            })
+            .collect();
+        let hoehe_max = stapel.values().copied().max().unwrap_or(1);
+
+        let farbe = self.tabellen_farben.art(&Art::Todo);
+        let schwach = ui.visuals().weak_text_color();
+        let rand = 18.0;
+        let breite = ui.available_width().max(200.0);
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(breite, 26.0 + hoehe_max as f32 * 11.0), egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        let achse_y = rect.bottom() - 16.0;
+        let links = rect.left() + rand;
+        let tagbreite = (rect.width() - 2.0 * rand) / tage as f32;
+        let tag_x = |tag: i64| match tag {
+            t if t < 0 => rect.left() + rand / 2.0,
+            t if t >= tage => rect.right() - rand / 2.0,
+            t => links + (t as f32 + 0.5) * tagbreite,
+        };
+
+        // Wochenenden hinterlegen, Wochen mit Kalenderwoche beschriften
+        for tag in 0..tage {
+            if tag % 7 >= 5 {
+                let x = links + tag as f32 * tagbreite;
+                painter.rect_filled(
+                    egui::Rect::from_min_max(egui::pos2(x, rect.top()), egui::pos2(x + tagbreite, achse_y)),
+                    0.0,
+                    schwach.gamma_multiply(0.08),
+                );
+            }
+        }
+        painter.hline(links..=rect.right() - rand, achse_y, egui::Stroke::new(1.0, schwach));
+        for woche in 0..=self.einstellungen.zeitleiste_wochen as i64 {
+            let x = links + (woche * 7) as f32 * tagbreite;
+            painter.vline(x, (achse_y - 3.0)..=(achse_y + 3.0), egui::Stroke::new(1.0, schwach));
+            // Beschriftung je nach verfügbarer Wochenbreite kürzen
+            let montag = start + chrono::Duration::days(woche * 7);
+            let beschriftung = match 7.0 * tagbreite {
+                _ if woche == tage / 7 => None,
+                w if w >= 70.0 => Some(format!("KW {} · {}", montag.iso_week().week(), montag.format("%d.%m."))),
+                w if w >= 34.0 => Some(format!("KW {}", montag.iso_week().week())),
+                _ => None,
+            };
+            if let Some(beschriftung) = beschriftung {
+                painter.text(egui::pos2(x + 2.0, achse_y + 3.0), egui::Align2::LEFT_TOP, beschriftung, egui::FontId::proportional(10.0), schwach);
+            }
+        }
+        let heute = (Local::now().date_naive() - start).num_days();
+        if (0..tage).contains(&heute) {
+            let x = tag_x(heute);
+            painter.vline(x, rect.top()..=achse_y, egui::Stroke::new(1.0, spaltenwarnung_farbe(true).gamma_multiply(0.6)));
+        }
+
+        let mut sprung = None;
+        for (i, datum, tag, ebene) in punkte {
+            let mitte = egui::pos2(tag_x(tag), achse_y - 7.0 - ebene as f32 * 11.0);
+            let punkt_farbe = if tag < 0 {
+                spaltenwarnung_farbe(true)
+            } else if tag >= tage {
+                schwach
+            } else {
+                farbe
+            };
+            let antwort = ui
+                .interact(egui::Rect::from_center_size(mitte, egui::vec2(11.0, 11.0)), ui.id().with(("zeitleiste", i)), egui::Sense::click())
+                .on_hover_cursor(egui::CursorIcon::PointingHand);
+            let radius = if antwort.hovered() { 5.5 } else { 4.5 };
+            painter.circle_filled(mitte, radius, punkt_farbe);
+            let e = &self.eintraege[i];
+            let mut text = format!("{}. {}", i + 1, eintrag_titel(e));
+            if !e.kuemmerer.trim().is_empty() {
+                text.push_str(&format!("\nKümmerer: {}", e.kuemmerer.trim()));
+            }
+            text.push_str(&format!("\nBis: {}, {}", wochentag_name(datum), datum.format("%d.%m.%Y")));
+            if tag < 0 {
+                text.push_str(&format!("\n(vor dem {})", start.format("%d.%m.%Y")));
+            } else if tag >= tage {
+                text.push_str(&format!("\n(nach dem {})", ende.format("%d.%m.%Y")));
+            }
+            if antwort.on_hover_text(text).clicked() {
+                sprung = Some(i);
+            }
+        }
+        if let Some(i) = sprung {
+            // Ausgefilterte Zeilen erst sichtbar machen
+            if !self.eintrag_sichtbar(i) {
+                self.nur_meine_aufgaben = false;
+                self.faellig_filter = FaelligFilter::default();
+            }
+            self.focus_notiz = Some(i);
+        }
+    }
+
+    /// Zeichnet die Einträge im Touch-Modus als einspaltige Karten.
+    /// Links an jeder Karte sitzt ein Griff: nach links wischen löscht den Eintrag,
+    /// nach oben oder unten ziehen verschiebt ihn um entsprechend viele Karten.
+    fn eintraege_karten(
+        &mut self,
+        ui: &mut egui::Ui,
+        textfarbe: Option<egui::Color32>,
+        alle_kuerzel: &[String],
+        sprecher_auswahl: &[(String, String)],
+    ) {
+        // Mindeststrecke in Pixeln, ab der ein Wischen nach links als Löschen gilt
+        const LOESCH_SCHWELLE: f32 = 120.0;
+        let anzahl = self.eintraege.len();
+        let mut entfernen: Option<usize> = None;
+        let mut verschieben: Option<(usize, usize)> = None;
+        let top_nummern = top_nummern(&self.eintraege, self.einstellungen.top_nummerierung);
+
+        for i in 0..anzahl {
+            if !self.eintrag_sichtbar(i) {
+                continue;
+            }
+            let is_todo = self.eintraege[i].art == Art::Todo;
+            let is_wortbeitrag = self.eintraege[i].art == Art::Wortbeitrag;
+            let is_frage = self.eintraege[i].art == Art::Frage;
+
+            let karte = ui.horizontal_top(|ui| {
+                let (griff_rect, griff) = ui.allocate_exact_size(egui::vec2(36.0, 80.0), egui::Sense::drag());
+                ui.vertical(|ui| {
+                    // Art und Punkt
+                    ui.horizontal(|ui| {
+                        let sel = RichText::new(self.eintraege[i].art.selected_label(&self.art_labels))
+                            .color(self.tabellen_farben.art(&self.eintraege[i].art))
+                            .font(fette_schrift(16.0));
+                        egui::ComboBox::from_id_salt(format!("touch_art_{i}"))
+                            .selected_text(sel)
+                            .width(160.0)
+                            .show_ui(ui, |ui| {
+                                let prev_art = self.eintraege[i].art.clone();
+                                for art in Art::all() {
+                                    let txt = RichText::new(art_beschriftung(&self.art_labels, art)).color(self.tabellen_farben.art(art)).font(fette_schrift(16.0));
+                                    ui.selectable_value(&mut self.eintraege[i].art, art.clone(), txt);
+                                }
+                                if self.eintraege[i].art == Art::Todo && prev_art != Art::Todo {
+                                    self.eintraege[i].punkt.clear();
+                                }
+                            });
+                        if !is_todo {
+                            let mut punkt_edit = egui::TextEdit::singleline(&mut self.eintraege[i].punkt)
+                                .hint_text(RichText::new("Punkt").font(egui::FontId::proportional(16.0)))
+                                .desired_width(ui.available_width())
+                                .font(fette_schrift(16.0));
+                            if let Some(c) = textfarbe { punkt_edit = punkt_edit.text_color(c); }
+                            ui.add(punkt_edit);
+                        }
+                    });
+                    if self.eintraege[i].art == Art::Entscheidung && !self.eintraege[i].beschluss_nr.is_empty() {
+                        ui.label(
+                            RichText::new(&self.eintraege[i].beschluss_nr)
+                                .font(fette_schrift(14.0))
+                                .color(self.tabellen_farben.art(&Art::Entscheidung)),
+                        );
+                    }
+                    if let Some((_, nummer)) = top_nummern.get(i).filter(|(_, n)| !n.is_empty()) {
+                        ui.label(
+                            RichText::new(nummer)
+                                .font(fette_schrift(14.0))
+                                .color(self.tabellen_farben.art(&Art::Agenda)),
+                        );
+                    }
+                    redmine_badges_anzeigen(ui, &self.eintraege[i].notiz, &self.einstellungen.redmine_url, &self.redmine_status, &self.tabellen_farben, 14.0);
+
+                    // Notiz in voller Breite
+                    let notiz_rows = self.eintraege[i].notiz.lines().count().max(2);
+                    let mut notiz_edit = egui::TextEdit::multiline(&mut self.eintraege[i].notiz)
+                        .id(egui::Id::new(("notiz", i)))
+                        .hint_text(RichText::new("Notiz").font(egui::FontId::proportional(16.0)))
+                        .desired_width(ui.available_width())
+                        .desired_rows(notiz_rows)
+                        .font(fette_schrift(16.0));
+                    let mut notiz_layouter = |ui: &egui::Ui, text: &str, wrap_width: f32| {
+                        let farbe = textfarbe.unwrap_or(ui.visuals().text_color());
+                        let mut job = notiz_layout_job(text, fette_schrift(16.0), farbe, ui.visuals().code_bg_color);
+                        job.wrap.max_width = wrap_width;
+                        ui.fonts(|f| f.layout_job(job))
+                    };
+                    notiz_edit = notiz_edit.layouter(&mut notiz_layouter);
+                    let notiz_resp = ui.add(notiz_edit);
+                    if self.focus_notiz == Some(i) {
+                        notiz_resp.request_focus();
+                        notiz_resp.scroll_to_me(None);
+                        self.focus_notiz = None;
+                    }
+
+                    // Kümmerer und Bis (TODO) bzw. Sprecher (Wortbeitrag)
+                    if is_todo {
+                        ui.horizontal(|ui| {
+                            let kum_unbekannt = !unbekannte_kuerzel(&self.eintraege[i].kuemmerer, &self.alle_personen()).is_empty();
+                            let mut kum_edit = egui::TextEdit::singleline(&mut self.eintraege[i].kuemmerer)
+                                .hint_text(RichText::new("Wer?").font(egui::FontId::proportional(16.0)))
+                                .desired_width(140.0)
+                                .font(fette_schrift(16.0));
+                            if let Some(c) = textfarbe { kum_edit = kum_edit.text_color(c); }
+                            if kum_unbekannt {
+                                kum_edit = kum_edit.text_color(egui::Color32::from_rgb(231, 76, 60));
+                            }
+                            ui.add(kum_edit);
+                            egui::ComboBox::from_id_salt(format!("touch_kum_{i}"))
+                                .selected_text("")
+                                .width(48.0)
+                                .show_ui(ui, |ui| {
+                                    if alle_kuerzel.is_empty() {
+                                        ui.label("Keine Kürzel");
+                                    }
+                                    for k in alle_kuerzel {
+                                        if ui.selectable_label(self.eintraege[i].kuemmerer == *k, k).clicked() {
+                                            self.eintraege[i].kuemmerer = k.clone();
+                                        }
+                                    }
+                                });
+                            let bis_valid = self.eintraege[i].bis.is_empty()
+                                || NaiveDate::parse_from_str(&self.eintraege[i].bis, "%d.%m.%Y").is_ok();
+                            let feiertag = self.einstellungen.feiertage.bis_hinweis(&self.eintraege[i].bis);
+                            let unplausibel = bis_plausibilitaet(&self.eintraege[i].bis, datum_aus_text(&self.datum_text));
+                            let bis_color = if !bis_valid {
+                                egui::Color32::from_rgb(231, 76, 60)
+                            } else if unplausibel.is_some() || feiertag.is_some() {
+                                spaltenwarnung_farbe(false)
+                            } else {
+                                textfarbe.unwrap_or(ui.visuals().text_color())
+                            };
+                            let bis_resp = ui.add(
+                                egui::TextEdit::singleline(&mut self.eintraege[i].bis)
+                                    .hint_text(RichText::new("TT.MM.JJJJ").font(egui::FontId::proportional(16.0)))
+                                    .desired_width(120.0)
+                                    .text_color(bis_color)
+                                    .font(fette_schrift(16.0)),
+                            );
+                            let hinweise: Vec<String> = unplausibel.into_iter().chain(feiertag).collect();
+                            if !hinweise.is_empty() {
+                                bis_resp.on_hover_text(hinweise.join("\n"));
+                            }
+                            let basis = datum_aus_text(&self.datum_text).unwrap_or_else(|| Local::now().date_naive());
+                            ui.menu_button(RichText::new("+").font(fette_schrift(16.0)), |ui| {
+                                bis_vorschlaege_anzeigen(ui, &mut self.eintraege[i].bis, basis, &self.einstellungen.feiertage);
+                            })
+                            .response
+                            .on_hover_text("Bis-Datum in Arbeitstagen vorschlagen");
+                        });
+                    } else if is_wortbeitrag || is_frage {
+                        let sprecher = self.sprecher_name(&self.eintraege[i].kuemmerer);
+                        let platzhalter = if is_frage { "Gestellt von" } else { "Sprecher wählen" };
+                        ui.horizontal(|ui| {
+                            egui::ComboBox::from_id_salt(format!("touch_kum_{i}"))
+                                .selected_text(if sprecher.is_empty() { platzhalter.to_string() } else { sprecher })
+                                .width(220.0)
+                                .show_ui(ui, |ui| {
+                                    if sprecher_auswahl.is_empty() {
+                                        ui.label("Keine Teilnehmer");
+                                    }
+                                    for (kuerzel, name) in sprecher_auswahl {
+                                        if ui.selectable_label(self.eintraege[i].kuemmerer == *kuerzel, name).clicked() {
+                                            self.eintraege[i].kuemmerer = kuerzel.clone();
+                                        }
+                                    }
+                                });
+                            if is_frage {
+                                let bis_valid = self.eintraege[i].bis.is_empty()
+                                    || NaiveDate::parse_from_str(&self.eintraege[i].bis, "%d.%m.%Y").is_ok();
+                                let bis_color = if bis_valid {
+                                    textfarbe.unwrap_or(ui.visuals().text_color())
+                                } else {
+                                    egui::Color32::from_rgb(231, 76, 60)
+                                };
+                                ui.label("beantwortet am");
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut self.eintraege[i].bis)
+                                        .hint_text(RichText::new("offen").font(egui::FontId::proportional(16.0)))
+                                        .desired_width(120.0)
+                                        .text_color(bis_color)
+                                        .font(fette_schrift(16.0)),
+                                );
+                            }
+                        });
+                    }
+
+                    // PDF-Seitenumbruch-Optionen
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut self.eintraege[i].umbruch_davor, "Umbruch davor");
+                        ui.checkbox(&mut self.eintraege[i].zusammenhalten, "Zusammenhalten");
+                    });
+                });
+                (griff_rect, griff)
+            });
+            let (griff_rect, griff) = karte.inner;
+
+            // Wischgeste am Griff auswerten (Strecke seit Beginn der Berührung)
+            let versatz = if griff.dragged() || griff.drag_stopped() {
+                ui.input(|inp| {
+                    inp.pointer
+                        .press_origin()
+                        .zip(inp.pointer.interact_pos())
+                        .map(|(start, jetzt)| jetzt - start)
+                })
+                .unwrap_or_default()
+            } else {
+                egui::Vec2::ZERO
+            };
+            let waagerecht = versatz.x.abs() > versatz.y.abs();
+            let loeschen = anzahl > 1 && waagerecht && versatz.x < -LOESCH_SCHWELLE;
+
+            let griff_farbe = if loeschen {
+                egui::Color32::from_rgb(231, 76, 60)
+            } else if griff.dragged() {
+                ui.visuals().selection.bg_fill
+            } else {
+                ui.visuals().widgets.inactive.bg_fill
+            };
+            ui.painter().rect_filled(griff_rect, 6.0, griff_farbe);
+            ui.painter().text(
+                griff_rect.center(),
+                egui::Align2::CENTER_CENTER,
+                if loeschen { "×" } else { "≡" },
+                egui::FontId::proportional(22.0),
+                ui.visuals().text_color(),
+            );
+            let griff = griff.on_hover_text("Nach links wischen: löschen · nach oben/unten ziehen: verschieben");
+
+            if griff.drag_stopped() {
+                if loeschen {
+                    entfernen = Some(i);
+                } else if !waagerecht {
+                    let karten_hoehe = karte.response.rect.height().max(1.0);
+                    let schritte = (versatz.y / karten_hoehe).round() as isize;
+                    let ziel = (i as isize + schritte).clamp(0, anzahl as isize - 1) as usize;
+                    if ziel != i {
+                        verschieben = Some((i, ziel));
+                    }
+                }
+            }
+            ui.separator();
+        }
+
+        if let Some((von, nach)) = verschieben {
+            let eintrag = self.eintraege.remove(von);
+            self.eintraege.insert(nach, eintrag);
+        }
+        if let Some(idx) = entfernen {
+            self.eintraege.remove(idx);
+        }
+    }
+}
+
+// -- Parse-Helfer --
+
+/// Serialisiert einen Eintrag als Zeile der Markdown-Eintragstabelle mit der
+/// übergebenen Beschriftung der Art (Standard oder projektbezogen angepasst) und
+/// stellt dem Punkt die TOP-Nummer voran (leer = keine).
+/// Zeilenumbrüche werden als ` <br> `, Pipe-Zeichen als `\|` codiert.
+fn eintrag_markdown_zeile(e: &Eintrag, art_label: &str, nummer: &str) -> String {
+    let art_str = if e.art == Art::Leer {
+        ""
+    } else {
+        art_label
+    };
+    let notiz = e.notiz.replace('\n', " <br> ").replace('|', "\\|");
+    let punkt = nummer_voranstellen(nummer, &e.punkt).replace('|', "\\|");
+    let kuemmerer = e.kuemmerer.replace('|', "\\|");
+    let mut umbruch = Vec::new();
+    if e.umbruch_davor {
+        umbruch.push("davor");
+    }
+    if e.zusammenhalten {
+        umbruch.push("zusammen");
+    }
+    // Review-Spalte: „BESTÄTIGT" bzw. „STRITTIG: Klärungsvermerk"
+    let review = if e.review == Review::Strittig && !e.klaerung.trim().is_empty() {
+        format!("{}: {}", e.review.label(), e.klaerung.trim().replace('\n', " ").replace('|', "\\|"))
+    } else {
+        e.review.label().to_string()
+    };
+    let id = if e.id == 0 { String::new() } else { e.id.to_string() };
+    format!(
+        "| {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
+        punkt, art_str, notiz, kuemmerer, e.bis, e.beschluss_nr, umbruch.join(", "), review, id
+    )
+}
+
+/// Zeigt den Verlauf eines Eintrags: alle Audit-Log-Zeilen mit `Eintrag #<id>`,
+/// neueste zuerst, dazu einen Hinweis auf noch nicht gespeicherte Änderungen.
+fn eintrag_verlauf_anzeigen(ui: &mut egui::Ui, audit_log: &[AuditEintrag], id: u32, ungespeichert: bool) {
+    let praefix = format!("Eintrag #{} ", id);
+    let verlauf: Vec<(&AuditEintrag, &str)> = audit_log
+        .iter()
+        .filter_map(|a| a.aktion.strip_prefix(&praefix).map(|aktion| (a, aktion.trim())))
+        .collect();
+    ui.label(RichText::new(format!("Verlauf von Eintrag #{}", id)).strong());
+    ui.separator();
+    if ungespeichert {
+        ui.label(RichText::new("Ungespeicherte Änderungen").italics());
+    }
+    if verlauf.is_empty() && !ungespeichert {
+        ui.label(RichText::new("Noch keine Änderungen protokolliert.").weak());
+    }
+    for (a, aktion) in verlauf.iter().rev() {
+        ui.label(format!("{} · {}", a.zeitpunkt, a.benutzer));
+        ui.label(RichText::new(*aktion).weak());
+        ui.add_space(4.0);
+    }
+}
+
+/// Vergleichbare Felder eines Eintrags als `(Feldname, Wert)` für die Eintragshistorie.
+fn eintrag_felder(e: &Eintrag) -> Vec<(&'static str, String)> {
+    vec![
+        ("Punkt", e.punkt.trim().to_string()),
+        ("Art", if e.art == Art::Leer { String::new() } else { e.art.label().to_string() }),
+        ("Notiz", e.notiz.trim().to_string()),
+        ("Kümmerer", e.kuemmerer.trim().to_string()),
+        ("Bis", e.bis.trim().to_string()),
+        ("Review", e.review.label().to_string()),
+    ]
+}
+
+/// Kurzbezeichnung eines Eintrags für Audit-Log und Verlauf (Punkt bzw. erste Notizzeile).
+fn eintrag_titel(e: &Eintrag) -> String {
+    let titel = if e.punkt.trim().is_empty() {
+        e.notiz.lines().find(|z| !z.trim().is_empty()).unwrap_or("").trim()
+    } else {
+        e.punkt.trim()
+    };
+    if titel.chars().count() > 60 {
+        format!("{}…", titel.chars().take(60).collect::<String>())
+    } else {
+        titel.to_string()
+    }
+}
+
+/// Trennt einen Personeneintrag der Form `"Name [Kürzel]"` in Name und Kürzel auf.
+/// Wenn kein Kürzel in eckigen Klammern vorhanden ist, wird ein leerer Kürzel-String zurückgegeben.
+fn name_kuerzel_parsen(s: &str) -> (String, String) {
+    let trimmed = s.trim();
+    if let Some(bracket_start) = trimmed.rfind('[') {
+        if let Some(bracket_end) = trimmed.rfind(']') {
+            if bracket_end > bracket_start {
+                let name = trimmed[..bracket_start].trim().to_string();
+                let kuerzel = trimmed[bracket_start + 1..bracket_end].trim().to_string();
+                return (name, kuerzel);
+            }
+        }
+    }
+    (trimmed.to_string(), String::new())
+}
+
+/// Alternative Bezeichnungen der Abschnittsüberschriften (`## …`), damit auch
+/// Protokolle mit englischen Überschriften eingelesen werden. Der erste Name jeder
+/// Gruppe ist die deutsche Überschrift, unter der der Parser den Abschnitt führt.
+const ABSCHNITT_ALIASE: &[&[&str]] = &[
+    &["Protokollführer", "Protokollant", "Minutes taker", "Minute taker", "Note taker", "Recorder"],
+    &["Teilnehmer", "Participants", "Attendees", "Present"],
+    &["Zur Kenntnis", "For information", "Distribution", "CC"],
+    &["Über dieses Meeting", "About this meeting"],
+    &["Status"],
+    &["Klassifizierung", "Classification"],
+    &["Einträge", "Entries", "Items", "Minutes"],
+    &["Nächstes Meeting", "Next meeting"],
+    &["Nur intern", "Interner Anhang", "Internal only", "Internal"],
+    &["Audit-Log", "Audit log", "Audit trail"],
+];
+
+/// Alternative Bezeichnungen der fett gesetzten Kopffelder (`**Datum:** …`).
+const FELD_ALIASE: &[&[&str]] = &[
+    &["Projekt", "Project"],
+    &["Datum", "Date"],
+    &["Protokoll erstellt am", "Minutes written on", "Written on"],
+    &["Ort", "Location", "Place", "Venue"],
+    &["Vorgänger", "Previous minutes", "Predecessor"],
+    &["PDF-Spalten", "PDF columns"],
+];
+
+/// Alternative Bezeichnungen der Status-Checkboxen.
+const STATUS_ALIASE: &[&[&str]] = &[
+    &["Entwurf", "Draft"],
+    &["In Prüfung", "In Review", "Review"],
+    &["Freigegeben", "Approved", "Released", "Final"],
+    &["Abgelehnt", "Rejected"],
+    &["Archiviert", "Archived"],
+];
+
+/// Alternative Bezeichnungen der Klassifizierungsstufen.
+const KLASSIFIZIERUNG_ALIASE: &[&[&str]] = &[
+    &["Öffentlich", "Public"],
+    &["Intern", "Internal"],
+    &["Vertraulich", "Confidential"],
+    &["Streng vertraulich", "Strictly confidential", "Secret"],
+];
+
+/// Sucht `text` in einer Alias-Tabelle (Groß-/Kleinschreibung egal, Präfixvergleich)
+/// und gibt den deutschen Namen der passenden Gruppe zurück.
+fn alias_aufloesen(text: &str, tabelle: &[&[&'static str]]) -> Option<&'static str> {
+    let text = text.trim().to_lowercase();
+    tabelle
+        .iter()
+        .find(|namen| namen.iter().any(|n| text.starts_with(&n.to_lowercase())))
+        .map(|namen| namen[0])
+}
+
+/// Zerlegt ein fett gesetztes Feld `**Name:** Wert` in `(Name, Wert)`.
+fn fett_feld(text: &str) -> Option<(&str, &str)> {
+    let rest = text.trim().strip_prefix("**")?;
+    let (name, wert) = rest.split_once(":**")?;
+    Some((name.trim(), wert.trim()))
+}
+
+/// Baut einen Eintrag aus den Zellen einer Zeile der Eintrags-Tabelle
+/// (mindestens fünf Zellen; Beschluss-, Umbruch-, Review- und ID-Spalte sind optional).
+fn eintrag_aus_zellen(cells: &[String], einstellungen: &Einstellungen) -> Eintrag {
+    let mut e = Eintrag::new();
+    e.punkt = top_nummer_entfernen(&cells[0], einstellungen.top_nummerierung).to_string();
+    e.art = art_parsen(&cells[1]);
+    if e.art == Art::Leer {
+        // Eigene Beschriftung aus den Einstellungen (z. B. „BESCHLUSS")
+        if let Some(art) = einstellungen.art_aus_label(&cells[1]) {
+            e.art = art;
+        }
+    }
+    e.notiz = cells[2].replace(" <br> ", "\n");
+    e.kuemmerer = cells[3].clone();
+    e.bis = cells[4].clone();
+    if let Some(nr) = cells.get(5) {
+        e.beschluss_nr = nr.clone();
+    }
+    if let Some(umbruch) = cells.get(6) {
+        e.umbruch_davor = umbruch.contains("davor");
+        e.zusammenhalten = umbruch.contains("zusammen");
+    }
+    if let Some(review) = cells.get(7) {
+        let (markierung, vermerk) = review.split_once(':').unwrap_or((review.as_str(), ""));
+        e.review = match markierung.trim() {
+            "BESTÄTIGT" => Review::Bestaetigt,
+            "STRITTIG" => Review::Strittig,
+            _ => Review::Offen,
+        };
+        if e.review == Review::Strittig {
+            e.klaerung = vermerk.trim().to_string();
+        }
+    }
+    if let Some(id) = cells.get(8) {
+        e.id = id.trim().parse().unwrap_or(0);
+    }
+    if e.art == Art::Todo {
+        e.punkt.clear();
+    }
+    e
+}
+
+/// Wandelt den Text einer Markdown-Tabellenzelle in die zugehörige `Art`-Variante um.
+/// Unbekannte Strings werden als `Art::Leer` interpretiert.
+fn art_parsen(s: &str) -> Art {
+    match s.trim() {
+        "ABGEBROCHEN" => Art::Abgebrochen,
+        "AGENDA" => Art::Agenda,
+        "ENTSCHEIDUNG" => Art::Entscheidung,
+        "FERTIG" => Art::Fertig,
+        "FRAGE" => Art::Frage,
+        "IDEE" => Art::Idee,
+        "INFO" => Art::Info,
+        "TODO" => Art::Todo,
+        "WORTBEITRAG" => Art::Wortbeitrag,
+        _ => Art::Leer,
+    }
+}
+
+/// Teilt eine Markdown-Tabellenzeile (`| A | B | C |`) in einzelne Zellen auf.
+/// Berücksichtigt escaped Pipe-Zeichen (`\|`), die innerhalb von Zellen vorkommen dürfen.
+fn tabellenzeile_aufteilen(row: &str) -> Vec<String> {
+    let trimmed = row.trim().trim_start_matches('|').trim_end_matches('|');
+    let mut cells = Vec::new();
+    let mut current = String::new();
+    let mut chars = trimmed.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(&next) = chars.peek() {
+                if next == '|' {
+                    current.push('|');
+                    chars.next();
+                    continue;
+                }
+            }
+            current.push(c);
+        } else if c == '|' {
+            cells.push(current.trim().to_string());
+            current = String::new();
+        } else {
+            current.push(c);
+        }
+    }
+    cells.push(current.trim().to_string());
+    cells
+}
+
+/// Ersetzt Markdown-Links der Form `[Text](URL)` durch `Text [N]` und
+/// gibt eine Liste der gefundenen Links als Tupel `(Nummer, Text, URL)` zurück.
+/// `start_num` gibt die erste Fußnotennummer an (1-basiert).
+/// Wird für den PDF-Export verwendet, da genpdf keine Hyperlinks unterstützt.
+fn markdown_links_extrahieren(text: &str, start_num: usize) -> (String, Vec<(usize, String, String)>) {
+    let mut result = String::new();
+    let mut links: Vec<(usize, String, String)> = Vec::new();
+    let mut num = start_num;
+    let mut pos = 0;
+
+    while pos < text.len() {
+        if text.as_bytes()[pos] == b'[' {
+            let after_bracket = pos + 1;
+            if after_bracket < text.len() {
+                if let Some(rel_close) = text[after_bracket..].find(']') {
+                    let close_bracket = after_bracket + rel_close;
+                    let label = &text[after_bracket..close_bracket];
+                    let after_close = close_bracket + 1;
+                    if after_close < text.len() && text.as_bytes()[after_close] == b'(' {
+                        let after_paren = after_close + 1;
+                        if after_paren < text.len() {
+                            if let Some(rel_end) = text[after_paren..].find(')') {
+                                let close_paren = after_paren + rel_end;
+                                let url = &text[after_paren..close_paren];
+                                if !label.is_empty() && !url.is_empty() {
+                                    result.push_str(&format!("{} [{}]", label, num));
+                                    links.push((num, label.to_string(), url.to_string()));
+                                    num += 1;
+                                    pos = close_paren + 1;
+                                    continue;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            result.push('[');
+            pos += 1;
+        } else {
+            let ch = text[pos..].chars().next().unwrap();
+            result.push(ch);
+            pos += ch.len_utf8();
+        }
+    }
+
+    (result, links)
+}
+
+/// Hängt eine Notizzeile an einen Absatz an: Abschnitte in einfachen Backticks
+/// (`` `Code` ``) erscheinen in der Festbreitenschrift, die Backticks selbst entfallen.
+/// Ohne Festbreitenschrift wird die Zeile unverändert übernommen.
+fn code_spans_anhaengen(
+    absatz: &mut genpdf::elements::Paragraph,
+    zeile: &str,
+    stil: genpdf::style::Style,
+    mono: Option<genpdf::fonts::FontFamily<genpdf::fonts::Font>>,
+) {
+    let Some(mono) = mono else {
+        absatz.push_styled(zeile.to_string(), stil);
+        return;
+    };
+    let mono_stil = stil.with_font_family(mono);
+    for (n, teil) in zeile.split('`').enumerate() {
+        if teil.is_empty() {
+            continue;
+        }
+        // Ungerade Abschnitte liegen zwischen zwei Backticks
+        let s = if n % 2 == 1 { mono_stil } else { stil };
+        absatz.push_styled(teil.to_string(), s);
+    }
+}
+
+/// Erkennt einen Markdown-Listenpunkt (`- `, `* `, `+ ` oder `1. `) und gibt
+/// `(Ebene, Bullet, Text)` zurück. Die Ebene ergibt sich aus dem Einzug
+/// (zwei Leerzeichen bzw. ein Tab pro Ebene).
+fn listenpunkt_erkennen(zeile: &str) -> Option<(usize, String, &str)> {
+    let inhalt = zeile.trim_start();
+    let einzug: usize = zeile[..zeile.len() - inhalt.len()]
+        .chars()
+        .map(|c| if c == '\t' { 2 } else { 1 })
+        .sum();
+    let ebene = einzug / 2;
+    for zeichen in ["- ", "* ", "+ "] {
+        if let Some(text) = inhalt.strip_prefix(zeichen) {
+            let bullet = if ebene == 0 { "•" } else { "–" };
+            return Some((ebene, bullet.to_string(), text.trim_start()));
+        }
+    }
+    let ziffern = inhalt.chars().take_while(|c| c.is_ascii_digit()).count();
+    if ziffern > 0 {
+        if let Some(text) = inhalt[ziffern..].strip_prefix(". ") {
+            return Some((ebene, format!("{}.", &inhalt[..ziffern]), text.trim_start()));
+        }
+    }
+    None
+}
+
+/// Teilt eine Notiz für den PDF-Export an Zeilengrenzen in Abschnitte von höchstens
+/// `max_zeilen` geschätzten Textzeilen (ca. 55 Zeichen je Zeile wie bei `Zusammenhalten`).
+/// Ein ```-Block über eine Abschnittsgrenze wird geschlossen und im nächsten Abschnitt
+/// wieder geöffnet. Kurze Notizen ergeben genau einen Abschnitt.
+fn notiz_abschnitte(notiz: &str, max_zeilen: usize) -> Vec<String> {
+    let mut abschnitte = Vec::new();
+    let mut aktuell: Vec<&str> = Vec::new();
+    let mut zeilen = 0usize;
+    let mut im_code = false;
+    for line in notiz.split('\n') {
+        let hoehe = line.chars().count().div_ceil(55).max(1);
+        if zeilen + hoehe > max_zeilen && !aktuell.is_empty() {
+            if im_code {
+                aktuell.push("```");
+            }
+            abschnitte.push(aktuell.join("\n"));
+            aktuell.clear();
+            zeilen = 0;
+            if im_code {
+                aktuell.push("```");
+            }
+        }
+        if line.trim_start().starts_with("```") {
+            im_code = !im_code;
+        }
+        aktuell.push(line);
+        zeilen += hoehe;
+    }
+    abschnitte.push(aktuell.join("\n"));
+    abschnitte
+}
+
+/// Setzt einen Link für die Linkliste bzw. die Quellenseite: `[N] Text:` und darunter
+/// die eingerückte URL, an `/` aufgeteilt, damit genpdf sie umbrechen kann.
+fn pdf_link_layout(num: usize, label: &str, url: &str, stil: genpdf::style::Style) -> genpdf::elements::LinearLayout {
+    let mut layout = genpdf::elements::LinearLayout::vertical();
+    layout.push(genpdf::elements::Paragraph::new(format!("[{}] {}:", num, label)).styled(stil));
+    let mut url_lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for ch in url.chars() {
+        current.push(ch);
+        if ch == '/' && current.len() > 100 {
+            url_lines.push(current);
+            current = String::new();
+        }
+    }
+    if !current.is_empty() {
+        url_lines.push(current);
+    }
+    for chunk in &url_lines {
+        layout.push(
+            genpdf::elements::Paragraph::new(chunk.as_str())
+                .styled(stil)
+                .padded(genpdf::Margins::trbl(0, 0, 0, 3.5)),
+        );
+    }
+    layout
+}
+
+/// Baut die Notiz-Zelle für den PDF-Export: Markdown-Links werden zu Fußnoten,
+/// Inline-Code erhält die Festbreitenschrift, ```-Blöcke werden als grau
+/// hinterlegter Kasten gesetzt und Listenpunkte als eingerückte Aufzählung.
+fn notiz_pdf_layout(
+    notiz: &str,
+    stil: genpdf::style::Style,
+    mono: Option<genpdf::fonts::FontFamily<genpdf::fonts::Font>>,
+    links: &mut Vec<(usize, String, String)>,
+) -> genpdf::elements::LinearLayout {
+    let mut layout = genpdf::elements::LinearLayout::vertical();
+    let mono_stil = mono.map(|m| stil.with_font_family(m)).unwrap_or(stil);
+    let mut code_block: Option<Vec<&str>> = None;
+
+    // Schließt einen Code-Block ab und fügt ihn als hinterlegten Kasten ein
+    let block_einfuegen = |layout: &mut genpdf::elements::LinearLayout, zeilen: Vec<&str>| {
+        let mut block = genpdf::elements::LinearLayout::vertical();
+        for z in &zeilen {
+            block.push(genpdf::elements::Paragraph::new(z.to_string()).styled(mono_stil));
+        }
+        // genpdf kann Elemente nicht vorab messen – Höhe aus Zeilenzahl schätzen (1 pt ≈ 0,353 mm)
+        let hoehe = zeilen.len().max(1) as f64 * f64::from(stil.font_size()) * 0.353 * 1.25 + 2.0;
+        layout.push(
+            genpdf::elements::PaddedElement::new(
+                ZellenHintergrund::code(block.padded(genpdf::Margins::trbl(1, 1, 1, 1)), hoehe),
+                genpdf::Margins::trbl(0.5, 0, 0.5, 0),
+            ),
+        );
+    };
+
+    for line in notiz.split('\n') {
+        if line.trim_start().starts_with("```") {
+            match code_block.take() {
+                Some(zeilen) => block_einfuegen(&mut layout, zeilen),
+                None => code_block = Some(Vec::new()),
+            }
+            continue;
+        }
+        if let Some(zeilen) = code_block.as_mut() {
+            zeilen.push(line);
+            continue;
+        }
+        let listenpunkt = listenpunkt_erkennen(line);
+        let text = listenpunkt.as_ref().map(|(_, _, t)| *t).unwrap_or(line);
+        let (replaced, new_links) = markdown_links_extrahieren(text, links.len() + 1);
+        links.extend(new_links);
+        let mut absatz = genpdf::elements::Paragraph::default();
+        code_spans_anhaengen(&mut absatz, &replaced, stil, mono);
+        match listenpunkt {
+            // Jeder Punkt als eigene einelementige Liste: so erhält jede Ebene ihren
+            // eigenen Einzug und umbrochene Zeilen bleiben hinter dem Bullet ausgerichtet
+            Some((ebene, bullet, _)) => {
+                let mut liste = genpdf::elements::UnorderedList::with_bullet(bullet);
+                liste.push(absatz);
+                layout.push(genpdf::elements::PaddedElement::new(
+                    liste,
+                    genpdf::Margins::trbl(0, 0, 0, 4 * ebene as i32),
+                ));
+            }
+            None => layout.push(absatz),
+        }
+    }
+    // Nicht geschlossener Block: bis zum Notizende als Code darstellen
+    if let Some(zeilen) = code_block {
+        block_einfuegen(&mut layout, zeilen);
+    }
+    layout
+}
+
+/// Erzeugt das egui-Layout für das Notizfeld: Inline-Code (`` `…` ``) und
+/// ```-Blöcke erscheinen in Festbreitenschrift mit Hintergrund. Alle Zeichen
+/// (auch Backticks) bleiben erhalten, damit der Cursor korrekt positioniert wird.
+fn notiz_layout_job(
+    text: &str,
+    schrift: egui::FontId,
+    farbe: egui::Color32,
+    code_hintergrund: egui::Color32,
+) -> egui::text::LayoutJob {
+    let mut job = egui::text::LayoutJob::default();
+    let normal = egui::TextFormat { font_id: schrift.clone(), color: farbe, ..Default::default() };
+    let code = egui::TextFormat {
+        font_id: egui::FontId::monospace(schrift.size - 1.0),
+        color: farbe,
+        background: code_hintergrund,
+        ..Default::default()
+    };
+    let mut im_block = false;
+    for zeile in text.split_inclusive('\n') {
+        if zeile.trim_start().starts_with("```") {
+            im_block = !im_block;
+            job.append(zeile, 0.0, code.clone());
+            continue;
+        }
+        if im_block {
+            job.append(zeile, 0.0, code.clone());
+            continue;
+        }
+        let mut start = 0;
+        let mut im_code = false;
+        for (pos, c) in zeile.char_indices() {
+            if c != '`' {
+                continue;
+            }
+            if im_code {
+                job.append(&zeile[start..=pos], 0.0, code.clone());
+                start = pos + 1;
+            } else {
+                job.append(&zeile[start..pos], 0.0, normal.clone());
+                start = pos;
+            }
+            im_code = !im_code;
+        }
+        job.append(&zeile[start..], 0.0, if im_code { code.clone() } else { normal.clone() });
+    }
+    job
+}
+
+/// Erzeugt das egui-Layout für Kürzel: erkannte Organisations-Präfixe (`EXT-`)
+/// erscheinen kleiner und abgeschwächt vor den Initialen.
+fn kuerzel_layout_job(
+    text: &str,
+    schrift: egui::FontId,
+    farbe: egui::Color32,
+    praefix_farbe: egui::Color32,
+    praefixe: &[(String, String)],
+) -> egui::text::LayoutJob {
+    let mut job = egui::text::LayoutJob::default();
+    let normal = egui::TextFormat { font_id: schrift.clone(), color: farbe, ..Default::default() };
+    let praefix = egui::TextFormat {
+        font_id: egui::FontId::proportional(schrift.size - 3.0),
+        color: praefix_farbe,
+        valign: egui::Align::Center,
+        ..Default::default()
+    };
+    for (teil, ist_praefix) in kuerzel_segmente(text, praefixe) {
+        job.append(teil, 0.0, if ist_praefix { praefix.clone() } else { normal.clone() });
+    }
+    job
+}
+
+/// PDF-Absatz für Kürzel: erkannte Organisations-Präfixe in Grau, der Rest in `stil`.
+fn kuerzel_pdf_absatz(text: &str, stil: genpdf::style::Style, praefixe: &[(String, String)]) -> genpdf::elements::Paragraph {
+    let mut absatz = genpdf::elements::Paragraph::default();
+    for (teil, ist_praefix) in kuerzel_segmente(text, praefixe) {
+        let teil_stil = if ist_praefix { stil.with_color(genpdf::style::Color::Greyscale(120)) } else { stil };
+        absatz.push_styled(teil, teil_stil);
+    }
+    absatz
+}
+
+/// Maskiert die in XML reservierten Zeichen (`&`, `<`, `>`, `"`, `'`) und entfernt
+/// Steuerzeichen unter U+0020 außer Tab, LF und CR, die in XML 1.0 unzulässig sind.
+fn xml_escapen(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\t' | '\n' | '\r' => result.push(ch),
+            c if c < ' ' => {}
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&apos;"),
+            other => result.push(other),
+        }
+    }
+    result
+}
+
+// -- E-Mail-Import --
+
+/// Eine Nachricht aus einem eingelesenen E-Mail-Verlauf.
+struct EmailNachricht {
+    /// Anzeigename des Absenders (ersatzweise aus der Adresse abgeleitet).
+    absender: String,
+    /// Betreff ohne Antwort- und Weiterleitungspräfixe.
+    betreff: String,
+    datum: Option<NaiveDate>,
+    /// Absätze des eigenen Texts ohne Zitate, Anrede, Grußformel und Signatur.
+    absaetze: Vec<String>,
+}
+
+/// Zustand des Dialogs „Aus E-Mail importieren": Vorschläge für Titel, Datum,
+/// Teilnehmer und Einträge, je mit Auswahl.
+struct EmailImport {
+    titel: String,
+    datum: Option<NaiveDate>,
+    teilnehmer: Vec<(Person, bool)>,
+    eintraege: Vec<(Eintrag, bool)>,
+}
+
+/// Kopfzeilen, die im Text eines Verlaufs eine weitergeleitete oder zitierte
+/// Nachricht einleiten (Outlook und Thunderbird, deutsch und englisch).
+const EMAIL_ABSENDER_KOPF: &[&str] = &["Von:", "From:"];
+
+/// Anfänge von Grußformeln; ab hier endet der eigene Text einer Nachricht.
+const EMAIL_GRUSSFORMELN: &[&str] = &[
+    "viele grüße",
+    "viele grüsse",
+    "beste grüße",
+    "liebe grüße",
+    "freundliche grüße",
+    "mit freundlichen grüßen",
+    "gruß",
+    "grüße",
+    "vg",
+    "lg",
+    "best regards",
+    "kind regards",
+    "regards",
+    "cheers",
+    "best",
+];
+
+/// Entfernt wiederholt Antwort- und Weiterleitungspräfixe wie „AW:", „Re:" oder „WG:".
+fn betreff_bereinigen(betreff: &str) -> String {
+    let mut rest = betreff.trim();
+    while let Some((praefix, danach)) = rest.split_once(':') {
+        if !["re", "aw", "wg", "fw", "fwd", "antw"].contains(&praefix.trim().to_lowercase().as_str()) {
+            break;
+        }
+        rest = danach.trim();
+    }
+    rest.to_string()
+}
+
+/// Zerlegt einen Absender wie `Anna Muster <anna@example.org>` in den Namen; fehlt er,
+/// wird er aus der Adresse abgeleitet („anna.muster@…" → „Anna Muster").
+fn absender_name(text: &str) -> String {
+    let text = text.trim();
+    let (name, adresse) = match text.split_once('<') {
+        Some((name, rest)) => (name.trim(), rest.trim_end_matches('>').trim()),
+        None if text.contains('@') => ("", text.trim_matches(['[', ']', '(', ')'])),
+        None => (text, ""),
+    };
+    let name = name.trim_matches(['"', '\'', ' ']);
+    if !name.is_empty() {
+        // „Muster, Anna" → „Anna Muster"
+        return match name.split_once(", ") {
+            Some((nachname, vorname)) if !vorname.contains(' ') => format!("{} {}", vorname, nachname),
+            _ => name.to_string(),
+        };
+    }
+    adresse
+        .split('@')
+        .next()
+        .unwrap_or("")
+        .split(['.', '_', '-'])
+        .filter(|t| !t.is_empty())
+        .map(|t| {
+            let mut zeichen = t.chars();
+            zeichen.next().map(|c| c.to_uppercase().chain(zeichen).collect::<String>()).unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Liest das Datum aus einem Datumskopf (RFC 2822, „05.02.2026 10:00" oder
+/// „Donnerstag, 5. Februar 2026 10:00").
+fn email_datum_parsen(text: &str) -> Option<NaiveDate> {
+    if let Ok(zeit) = chrono::DateTime::parse_from_rfc2822(text.trim()) {
+        return Some(zeit.date_naive());
+    }
+    let woerter: Vec<&str> = text.split([' ', ',']).filter(|w| !w.is_empty()).collect();
+    if let Some(datum) = woerter.iter().find_map(|w| NaiveDate::parse_from_str(w, "%d.%m.%Y").ok()) {
+        return Some(datum);
+    }
+    const MONATE: &[&str] = &["januar", "februar", "märz", "april", "mai", "juni", "juli", "august", "september", "oktober", "november", "dezember"];
+    woerter.windows(3).find_map(|w| {
+        let tag: u32 = w[0].trim_end_matches('.').parse().ok()?;
+        let monat = MONATE.iter().position(|m| *m == w[1].to_lowercase())? as u32 + 1;
+        let jahr: i32 = w[2].parse().ok()?;
+        NaiveDate::from_ymd_opt(jahr, monat, tag)
+    })
+}
+
+/// Dekodiert Base64 (Zeilenumbrüche und ungültige Zeichen werden übergangen).
+fn base64_dekodieren(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut puffer = 0u32;
+    let mut bits = 0;
+    for c in text.bytes() {
+        let wert = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            _ => continue,
+        };
+        puffer = (puffer << 6) | wert as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((puffer >> bits) as u8);
+        }
+    }
+    bytes
+}
+
+/// Dekodiert Quoted-Printable; `kopf` = Variante aus MIME-Kopfzeilen („_" = Leerzeichen).
+fn quoted_printable_dekodieren(text: &str, kopf: bool) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let eingabe = text.as_bytes();
+    let mut i = 0;
+    while i < eingabe.len() {
+        match eingabe[i] {
+            b'=' if eingabe.get(i + 1) == Some(&b'\n') => i += 2,
+            b'=' if eingabe.get(i + 1) == Some(&b'\r') && eingabe.get(i + 2) == Some(&b'\n') => i += 3,
+            b'=' => match std::str::from_utf8(eingabe.get(i + 1..i + 3).unwrap_or(b"")).ok().and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                Some(b) => {
+                    bytes.push(b);
+                    i += 3;
+                }
+                None => {
+                    bytes.push(b'=');
+                    i += 1;
+                }
+            },
+            b'_' if kopf => {
+                bytes.push(b' ');
+                i += 1;
+            }
+            b => {
+                bytes.push(b);
+                i += 1;
+            }
+        }
+    }
+    bytes
+}
+
+/// Wandelt Bytes im angegebenen Zeichensatz in Text um (UTF-8, sonst Latin-1/Windows-1252).
+fn zeichensatz_dekodieren(bytes: &[u8], zeichensatz: &str) -> String {
+    if zeichensatz.is_empty() || zeichensatz.eq_ignore_ascii_case("utf-8") || zeichensatz.eq_ignore_ascii_case("us-ascii") {
+        String::from_utf8_lossy(bytes).to_string()
+    } else {
+        bytes.iter().map(|&b| b as char).collect()
+    }
+}
+
+/// Dekodiert MIME-kodierte Wörter in Kopfzeilen (`=?UTF-8?Q?...?=` bzw. `?B?`).
+fn kopf_dekodieren(text: &str) -> String {
+    let mut ergebnis = String::new();
+    let mut rest = text;
+    let mut nach_kodiertem = false;
+    while let Some(start) = rest.find("=?") {
+        let teile: Vec<&str> = rest[start + 2..].splitn(3, '?').collect();
+        let (Some(zeichensatz), Some(verfahren), Some(ende)) = (teile.first(), teile.get(1), teile.get(2).and_then(|t| t.find("?="))) else {
+            break;
+        };
+        let davor = &rest[..start];
+        // Leerraum zwischen zwei kodierten Wörtern entfällt
+        if !(nach_kodiertem && davor.trim().is_empty()) {
+            ergebnis.push_str(davor);
+        }
+        let inhalt = &teile[2][..ende];
+        let bytes = if verfahren.eq_ignore_ascii_case("b") { base64_dekodieren(inhalt) } else { quoted_printable_dekodieren(inhalt, true) };
+        ergebnis.push_str(&zeichensatz_dekodieren(&bytes, zeichensatz));
+        nach_kodiertem = true;
+        rest = &teile[2][ende + 2..];
+    }
+    ergebnis.push_str(rest);
+    ergebnis
+}
+
+/// Liest einen Kopfblock (`Name: Wert`, Folgezeilen eingerückt) in Schlüssel/Wert-Paare
+/// mit kleingeschriebenem Schlüssel.
+fn kopfzeilen_parsen(zeilen: &[&str]) -> Vec<(String, String)> {
+    let mut kopf: Vec<(String, String)> = Vec::new();
+    for zeile in zeilen {
+        if zeile.starts_with([' ', '\t']) {
+            if let Some((_, wert)) = kopf.last_mut() {
+                wert.push(' ');
+                wert.push_str(zeile.trim());
+            }
+        } else if let Some((name, wert)) = zeile.split_once(':') {
+            kopf.push((name.trim().to_lowercase(), wert.trim().to_string()));
+        }
+    }
+    kopf
+}
+
+/// Wert eines MIME-Parameters wie `boundary` oder `charset` aus einer Kopfzeile.
+fn mime_parameter(kopf: &str, name: &str) -> String {
+    kopf.split(';')
+        .filter_map(|teil| teil.trim().split_once('='))
+        .find(|(schluessel, _)| schluessel.trim().eq_ignore_ascii_case(name))
+        .map(|(_, wert)| wert.trim().trim_matches('"').to_string())
+        .unwrap_or_default()
+}
+
+/// Liefert den Textteil eines MIME-Körpers: bei `multipart/*` den ersten `text/plain`-Teil
+/// (verschachtelt), sonst den Körper selbst, jeweils nach Transfer-Kodierung und Zeichensatz
+/// dekodiert.
+fn mime_text(kopf: &[(String, String)], koerper: &str) -> Option<String> {
+    let wert = |name: &str| kopf.iter().find(|(k, _)| k == name).map(|(_, w)| w.as_str()).unwrap_or("");
+    let typ = wert("content-type").to_lowercase();
+    if typ.starts_with("multipart/") {
+        let grenze = format!("--{}", mime_parameter(wert("content-type"), "boundary"));
+        return koerper.split(grenze.as_str()).skip(1).find_map(|teil| {
+            let teil = teil.strip_prefix('\n').unwrap_or(teil);
+            let (teil_kopf, teil_koerper) = teil.split_once("\n\n")?;
+            mime_text(&kopfzeilen_parsen(&teil_kopf.lines().collect::<Vec<_>>()), teil_koerper)
+        });
+    }
+    if !typ.is_empty() && !typ.starts_with("text/plain") {
+        return None;
+    }
+    let bytes = match wert("content-transfer-encoding").to_lowercase().as_str() {
+        "base64" => base64_dekodieren(koerper),
+        "quoted-printable" => quoted_printable_dekodieren(koerper, false),
+        _ => koerper.as_bytes().to_vec(),
+    };
+    Some(zeichensatz_dekodieren(&bytes, &mime_parameter(wert("content-type"), "charset")))
+}
+
+/// Liest eine E-Mail-Diskussion (EML-Datei oder als Text gespeicherter Verlauf) in
+/// einzelne Nachrichten, älteste zuerst. Zitierte Vorgänger („Am … schrieb …:",
+/// `>`-Zeilen) und eingebettete Kopfblöcke („Von:/Gesendet:/Betreff:") werden als
+/// eigene Nachrichten erkannt.
+fn email_verlauf_parsen(text: &str) -> Vec<EmailNachricht> {
+    let text = text.replace("\r\n", "\n");
+    let mut nachrichten: Vec<EmailNachricht> = Vec::new();
+    let mut aktuell = EmailNachricht { absender: String::new(), betreff: String::new(), datum: None, absaetze: Vec::new() };
+
+    // EML: Kopf der äußeren Nachricht und dekodierter Textteil
+    let erste = text.lines().next().unwrap_or("");
+    let koerper = match text.split_once("\n\n") {
+        Some((kopf_text, koerper)) if erste.split_once(':').is_some_and(|(n, _)| !n.is_empty() && !n.contains(' ')) => {
+            let kopf = kopfzeilen_parsen(&kopf_text.lines().collect::<Vec<_>>());
+            if kopf.iter().any(|(k, _)| k == "from") {
+                for (name, wert) in &kopf {
+                    match name.as_str() {
+                        "from" => aktuell.absender = absender_name(&kopf_dekodieren(wert)),
+                        "subject" => aktuell.betreff = betreff_bereinigen(&kopf_dekodieren(wert)),
+                        "date" => aktuell.datum = email_datum_parsen(wert),
+                        _ => {}
+                    }
+                }
+                mime_text(&kopf, koerper).unwrap_or_default()
+            } else {
+                text.clone()
+            }
+        }
+        _ => text.clone(),
+    };
+
+    let mut zeilen: Vec<String> = Vec::new();
+    let mut kopfblock: Option<Vec<String>> = None;
+    let mut signatur = false;
+    let abschliessen = |aktuell: &mut EmailNachricht, zeilen: &mut Vec<String>, nachrichten: &mut Vec<EmailNachricht>| {
+        let mut absaetze = Vec::new();
+        for absatz in zeilen.join("\n").split("\n\n") {
+            let absatz = absatz.lines().map(str::trim).filter(|z| !z.is_empty()).collect::<Vec<_>>().join(" ");
+            if absatz.is_empty() {
+                continue;
+            }
+            let klein = absatz.to_lowercase();
+            if EMAIL_GRUSSFORMELN.iter().any(|g| klein == *g || klein.starts_with(&format!("{},", g)) || klein.starts_with(&format!("{} ", g))) {
+                break;
+            }
+            // Anrede wie „Hallo zusammen," entfällt
+            if absaetze.is_empty() && absatz.ends_with(',') && absatz.split_whitespace().count() <= 4 {
+                continue;
+            }
+            absaetze.push(absatz);
+        }
+        zeilen.clear();
+        let nachricht = std::mem::replace(aktuell, EmailNachricht { absender: String::new(), betreff: String::new(), datum: None, absaetze: Vec::new() });
+        if !absaetze.is_empty() || !nachricht.absender.is_empty() {
+            nachrichten.push(EmailNachricht { absaetze, ..nachricht });
+        }
+    };
+    for roh in koerper.lines() {
+        let zeile = roh.trim_start_matches(['>', ' ']).trim_end();
+        if let Some(kopf) = kopfblock.as_mut() {
+            if !zeile.is_empty() {
+                kopf.push(zeile.to_string());
+                continue;
+            }
+            let kopf = kopfzeilen_parsen(&kopfblock.take().unwrap_or_default().iter().map(String::as_str).collect::<Vec<_>>());
+            for (name, wert) in kopf {
+                match name.as_str() {
+                    "von" | "from" => aktuell.absender = absender_name(&wert),
+                    "betreff" | "subject" => aktuell.betreff = betreff_bereinigen(&wert),
+                    "gesendet" | "sent" | "datum" | "date" => aktuell.datum = email_datum_parsen(&wert),
+                    _ => {}
+                }
+            }
+            continue;
+        }
+        if zeile.starts_with("-----") || zeile.starts_with("----------") {
+            continue;
+        }
+        let zitat_einleitung = zeile.ends_with(':')
+            && ((zeile.starts_with("Am ") && zeile.contains(" schrieb ")) || (zeile.starts_with("On ") && zeile.contains(" wrote")));
+        if zitat_einleitung {
+            abschliessen(&mut aktuell, &mut zeilen, &mut nachrichten);
+            signatur = false;
+            let ohne_ende = zeile.trim_end_matches(':').trim_end_matches(" wrote");
+            aktuell.datum = email_datum_parsen(ohne_ende);
+            aktuell.absender = match ohne_ende.split_once(" schrieb ") {
+                Some((_, name)) => absender_name(name),
+                // „On Mon, Feb 5, 2026 at 10:00 AM Anna Muster <anna@…>"
+                None => {
+                    let woerter: Vec<&str> = ohne_ende.split_whitespace().collect();
+                    let start = woerter.iter().rposition(|w| w.contains(':') || *w == "AM" || *w == "PM").map_or(0, |i| i + 1);
+                    absender_name(&woerter[start..].join(" "))
+                }
+            };
+            continue;
+        }
+        if EMAIL_ABSENDER_KOPF.iter().any(|k| zeile.starts_with(k)) {
+            abschliessen(&mut aktuell, &mut zeilen, &mut nachrichten);
+            signatur = false;
+            kopfblock = Some(vec![zeile.to_string()]);
+            continue;
+        }
+        if zeile == "--" && roh.trim_start_matches(['>', ' ']).starts_with("--") {
+            signatur = true;
+        }
+        if !signatur {
+            zeilen.push(zeile.to_string());
+        }
+    }
+    abschliessen(&mut aktuell, &mut zeilen, &mut nachrichten);
+
+    // Zitierte Nachrichten stehen unter der Antwort: älteste zuerst
+    nachrichten.reverse();
+    let betreff = nachrichten.iter().map(|n| n.betreff.clone()).find(|b| !b.is_empty()).unwrap_or_default();
+    for n in nachrichten.iter_mut().filter(|n| n.betreff.is_empty()) {
+        n.betreff = betreff.clone();
+    }
+    nachrichten
+}
+
+/// Schlägt aus den Nachrichten eines Verlaufs das Protokoll vor: Betreff → Titel,
+/// Absender → Teilnehmer, Absätze → INFO-Einträge (der erste je Nachricht mit
+/// Absender und Datum als Punkt). Als Datum gilt die jüngste Nachricht.
+fn email_import_vorschlagen(nachrichten: &[EmailNachricht], einstellungen: &Einstellungen) -> EmailImport {
+    let mut teilnehmer: Vec<(Person, bool)> = Vec::new();
+    let mut eintraege = Vec::new();
+    for n in nachrichten {
+        if !n.absender.is_empty() && !teilnehmer.iter().any(|(p, _)| p.name.eq_ignore_ascii_case(&n.absender)) {
+            let mut person = Person::new();
+            person.name = n.absender.clone();
+            person.kuerzel = einstellungen.kuerzel_erzeugen(&n.absender, "");
+            teilnehmer.push((person, true));
+        }
+        for (i, absatz) in n.absaetze.iter().enumerate() {
+            let mut e = Eintrag::new();
+            e.art = Art::Info;
+            e.notiz = absatz.clone();
+            if i == 0 {
+                e.punkt = match n.datum {
+                    Some(datum) => format!("{}, {}", n.absender, datum.format("%d.%m.%Y")),
+                    None => n.absender.clone(),
+                };
+            }
+            eintraege.push((e, true));
+        }
+    }
+    EmailImport {
+        titel: nachrichten.first().map(|n| n.betreff.clone()).unwrap_or_default(),
+        datum: nachrichten.iter().filter_map(|n| n.datum).max(),
+        teilnehmer,
+        eintraege,
+    }
+}
+
+// -- Druck-Helfer (IPP) --
+
+/// Sucht per mDNS etwa drei Sekunden lang nach IPP-Druckern (`_ipp._tcp`)
+/// und sendet jeden gefundenen Drucker als `(Name, IPP-URI)` über `tx`.
+fn ipp_drucker_suchen(tx: mpsc::Sender<(String, String)>) {
+    let Ok(daemon) = mdns_sd::ServiceDaemon::new() else {
+        return;
+    };
+    let dienst = "_ipp._tcp.local.";
+    let Ok(empfaenger) = daemon.browse(dienst) else {
+        return;
+    };
+    let ende = std::time::Instant::now() + std::time::Duration::from_secs(3);
+    while let Some(rest) = ende.checked_duration_since(std::time::Instant::now()) {
+        match empfaenger.recv_timeout(rest) {
+            Ok(mdns_sd::ServiceEvent::ServiceResolved(info)) => {
+                let Some(adresse) = info.get_addresses().iter().find(|a| a.is_ipv4()).copied() else {
+                    continue;
+                };
+                let pfad = info.get_property_val_str("rp").unwrap_or("ipp/print");
+                let uri = format!("ipp://{}:{}/{}", adresse, info.get_port(), pfad.trim_start_matches('/'));
+                let name = info
+                    .get_property_val_str("ty")
+                    .map(|ty| ty.to_string())
+                    .unwrap_or_else(|| info.get_fullname().trim_end_matches(dienst).trim_end_matches('.').to_string());
+                if tx.send((name, uri)).is_err() {
+                    break;
+                }
+            }
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+    let _ = daemon.shutdown();
+}
+
+/// Hängt ein IPP-Attribut (Wert-Tag, Name, Wert) gemäß RFC 8010 an `puffer` an.
+fn ipp_attribut(puffer: &mut Vec<u8>, tag: u8, name: &str, wert: &[u8]) {
+    puffer.push(tag);
+    puffer.extend_from_slice(&(name.len() as u16).to_be_bytes());
+    puffer.extend_from_slice(name.as_bytes());
+    puffer.extend_from_slice(&(wert.len() as u16).to_be_bytes());
+    puffer.extend_from_slice(wert);
+}
+
+/// Sendet ein PDF per IPP `Print-Job` direkt an einen Netzwerkdrucker
+/// (ohne CUPS-Kommandozeile). Unterstützt werden `ipp://`- und `http://`-URIs;
+/// verschlüsseltes `ipps://` ist nicht möglich.
+fn ipp_drucken(
+    uri: &str,
+    benutzer: &str,
+    auftragsname: &str,
+    seiten: &str,
+    fach: &str,
+    kopien: u32,
+    pdf: &[u8],
+) -> Result<(), String> {
+    use std::io::{Read, Write};
+
+    let rest = uri
+        .strip_prefix("ipp://")
+        .or_else(|| uri.strip_prefix("http://"))
+        .ok_or_else(|| "Nur ipp://- und http://-Adressen werden unterstützt.".to_string())?;
+    let (host_port, pfad) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/ipp/print"),
+    };
+    let host_port = if host_port.contains(':') { host_port.to_string() } else { format!("{}:631", host_port) };
+
+    // IPP-Anfrage: Version 2.0, Operation Print-Job (0x0002), Request-ID 1
+    let mut anfrage = vec![0x02, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x01];
+    anfrage.push(0x01); // operation-attributes-tag
+    ipp_attribut(&mut anfrage, 0x47, "attributes-charset", b"utf-8");
+    ipp_attribut(&mut anfrage, 0x48, "attributes-natural-language", b"de");
+    ipp_attribut(&mut anfrage, 0x45, "printer-uri", uri.as_bytes());
+    ipp_attribut(&mut anfrage, 0x42, "requesting-user-name", benutzer.as_bytes());
+    ipp_attribut(&mut anfrage, 0x42, "job-name", auftragsname.as_bytes());
+    ipp_attribut(&mut anfrage, 0x49, "document-format", b"application/pdf");
+    anfrage.push(0x02); // job-attributes-tag
+    ipp_attribut(&mut anfrage, 0x21, "copies", &(kopien as i32).to_be_bytes());
+    ipp_attribut(&mut anfrage, 0x44, "sides", seiten.as_bytes());
+    if fach != "auto" {
+        // media-col { media-source = <fach> }
+        ipp_attribut(&mut anfrage, 0x34, "media-col", b"");
+        ipp_attribut(&mut anfrage, 0x4A, "", b"media-source");
+        ipp_attribut(&mut anfrage, 0x44, "", fach.as_bytes());
+        ipp_attribut(&mut anfrage, 0x37, "", b"");
+    }
+    anfrage.push(0x03); // end-of-attributes-tag
+    anfrage.extend_from_slice(pdf);
+
+    let mut verbindung = std::net::TcpStream::connect(&host_port)
+        .map_err(|e| format!("Drucker {} nicht erreichbar: {}", host_port, e))?;
+    let _ = verbindung.set_read_timeout(Some(std::time::Duration::from_secs(30)));
+    let kopf = format!(
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/ipp\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        pfad,
+        host_port,
+        anfrage.len()
+    );
+    verbindung
+        .write_all(kopf.as_bytes())
+        .and_then(|_| verbindung.write_all(&anfrage))
+        .map_err(|e| format!("Senden fehlgeschlagen: {}", e))?;
+
+    let mut antwort = Vec::new();
+    let _ = verbindung.read_to_end(&mut antwort);
+    let mut rest = antwort.as_slice();
+    // Vorläufige Antworten (`100 Continue`) überspringen
+    let (kopf, koerper) = loop {
+        let kopf_ende = rest
+            .windows(4)
+            .position(|w| w == b"\r\n\r\n")
+            .ok_or_else(|| "Ungültige Antwort des Druckers.".to_string())?;
+        let kopf = String::from_utf8_lossy(&rest[..kopf_ende]).to_string();
+        let statuszeile = kopf.lines().next().unwrap_or_default().to_string();
+        let code = statuszeile.split_whitespace().nth(1).and_then(|c| c.parse::<u16>().ok());
+        rest = &rest[kopf_ende + 4..];
+        match code {
+            Some(100) => continue,
+            Some(200) => break (kopf, rest),
+            _ => return Err(format!("Drucker antwortet mit „{}“.", statuszeile)),
+        }
+    };
+    let chunked = kopf.lines().skip(1).any(|zeile| {
+        zeile.split_once(':').is_some_and(|(name, wert)| {
+            name.trim().eq_ignore_ascii_case("transfer-encoding") && wert.to_ascii_lowercase().contains("chunked")
+        })
+    });
+    let koerper = if chunked {
+        http_chunks_zusammensetzen(koerper).ok_or_else(|| "Unvollständige IPP-Antwort.".to_string())?
+    } else {
+        koerper.to_vec()
+    };
+    // IPP-Antwort: Version (2 Byte), Status-Code (2 Byte), Request-ID (4 Byte)
+    if koerper.len() < 8 {
+        return Err("Unvollständige IPP-Antwort.".to_string());
+    }
+    let status = u16::from_be_bytes([koerper[2], koerper[3]]);
+    match status {
+        // successful-ok, ggf. mit ignorierten bzw. ersetzten Attributen
+        0x0000..=0x00FF => Ok(()),
+        0x0400..=0x04FF => Err(format!("Drucker hat den Auftrag abgelehnt (IPP-Status 0x{:04X}, Anfragefehler).", status)),
+        0x0500..=0x05FF => Err(format!("Drucker hat den Auftrag abgelehnt (IPP-Status 0x{:04X}, Druckerfehler).", status)),
+        _ => Err(format!("Unerwarteter IPP-Status 0x{:04X}.", status)),
+    }
+}
+
+/// Setzt einen HTTP-Körper mit `Transfer-Encoding: chunked` zusammen
+/// (`None` = unvollständig oder fehlerhaft).
+fn http_chunks_zusammensetzen(mut daten: &[u8]) -> Option<Vec<u8>> {
+    let mut koerper = Vec::new();
+    loop {
+        let zeilen_ende = daten.windows(2).position(|w| w == b"\r\n")?;
+        let groesse_text = String::from_utf8_lossy(&daten[..zeilen_ende]).to_string();
+        // Chunk-Erweiterungen nach `;` ignorieren
+        let groesse = usize::from_str_radix(groesse_text.split(';').next()?.trim(), 16).ok()?;
+        daten = &daten[zeilen_ende + 2..];
+        if groesse == 0 {
+            return Some(koerper);
+        }
+        koerper.extend_from_slice(daten.get(..groesse)?);
+        daten = daten.get(groesse + 2..)?;
+    }
+}
+
+// -- Zugangsdaten (Secret Service / Windows-Tresor) --
+
+/// Dienstname, unter dem alle Zugangsdaten im Schlüsselbund abgelegt werden.
+const SCHLUESSELBUND_DIENST: &str = "mzprotokoll";
+
+/// Integrationen mit hinterlegbaren Zugangsdaten als `(Konto, Anzeigename)`.
+/// Integrationen lesen ihr Geheimnis ausschließlich über `zugangsdaten_laden`;
+/// Passwörter und Tokens gehören nie in `einstellungen.toml`.
+const ZUGANGSDATEN_KONTEN: &[(&str, &str)] = &[
+    ("smtp", "SMTP-Passwort"),
+    ("jira", "Jira-API-Token"),
+    ("github", "GitHub-Token"),
+    ("webdav", "WebDAV-Passwort"),
+    ("caldav", "CalDAV-Passwort"),
+    ("matrix", "Matrix-Zugangstoken"),
+    ("redmine", "Redmine-API-Key"),
+    ("export", "Exportpasswort (PDF/Markdown)"),
+];
+
+/// Führt ein Schlüsselbund-Kommando aus und übergibt `eingabe` über stdin
+/// (Geheimnisse erscheinen so nie in der Prozessliste). Gibt stdout zurück.
+fn schluesselbund_kommando(programm: &str, args: &[&str], eingabe: Option<&str>) -> Result<String, String> {
+    let mut befehl = std::process::Command::new(programm);
+    befehl.args(args);
+    schluesselbund_ausfuehren(befehl, eingabe)
+}
+
+/// Wie `schluesselbund_kommando`, aber für ein vorbereitetes Kommando (etwa mit
+/// zusätzlichen Umgebungsvariablen).
+fn schluesselbund_ausfuehren(mut befehl: std::process::Command, eingabe: Option<&str>) -> Result<String, String> {
+    use std::io::Write;
+    let mut kind = befehl
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("{} konnte nicht gestartet werden: {}", befehl.get_program().to_string_lossy(), e))?;
+    if let Some(mut stdin) = kind.stdin.take() {
+        let _ = stdin.write_all(eingabe.unwrap_or("").as_bytes());
+    }
+    let ausgabe = kind.wait_with_output().map_err(|e| e.to_string())?;
+    if ausgabe.status.success() {
+        Ok(String::from_utf8_lossy(&ausgabe.stdout).to_string())
+    } else {
+        Err(String::from_utf8_lossy(&ausgabe.stderr).trim().to_string())
+    }
+}
+
+/// Führt ein PowerShell-Skript gegen den Windows-PasswordVault (`$v`) aus. Dienst und
+/// Konto erhält das Skript nur als `$env:MZP_DIENST` und `$env:MZP_KONTO`; sie werden
+/// nie in den Skripttext eingesetzt, Anführungszeichen im Profilnamen bleiben so harmlos.
+#[cfg(windows)]
+fn passwordvault_skript(skript: &str, konto: &str, eingabe: Option<&str>) -> Result<String, String> {
+    let mut befehl = std::process::Command::new("powershell");
+    befehl
+        .args(["-NoProfile", "-NonInteractive", "-Command"])
+        .arg(format!(
+            "[void][Windows.Security.Credentials.PasswordVault,Windows.Security.Credentials,ContentType=WindowsRuntime]; \
+             $v = New-Object Windows.Security.Credentials.PasswordVault; {}",
+            skript
+        ))
+        .env("MZP_DIENST", SCHLUESSELBUND_DIENST)
+        .env("MZP_KONTO", konto);
+    schluesselbund_ausfuehren(befehl, eingabe)
+}
+
+/// Schlüsselbund-Konto im aktiven Profil: beim Standardprofil der Kontoname selbst,
+/// sonst `<Profil>/<Konto>`, damit jedes Profil eigene Zugangsdaten hat.
+fn schluesselbund_konto(konto: &str) -> String {
+    let profil = aktives_profil();
+    if profil.is_empty() {
+        konto.to_string()
+    } else {
+        format!("{}/{}", profil, konto)
+    }
+}
+
+/// Führt `passwort_kommando` für ein Konto aus (`{konto}` wird ersetzt) und gibt die
+/// erste Zeile der Ausgabe zurück – wie bei `pass` üblich steht dort das Passwort.
+/// Das Kommando wird ohne Shell an Leerzeichen zerlegt.
+fn passwort_kommando_ausfuehren(kommando: &str, konto: &str) -> Option<String> {
+    let kommando = kommando.replace("{konto}", konto);
+    let mut teile = kommando.split_whitespace();
+    let programm = teile.next()?;
+    let args: Vec<&str> = teile.collect();
+    let ausgabe = schluesselbund_kommando(programm, &args, None).ok()?;
+    let geheimnis = ausgabe.lines().next().unwrap_or("").trim_end_matches('\r').to_string();
+    if geheimnis.is_empty() { None } else { Some(geheimnis) }
+}
+
+/// Liest ein Geheimnis (`None` = nicht hinterlegt). Ist `passwort_kommando` gesetzt,
+/// wird zuerst dieses gefragt (etwa `pass` mit Team-Passwörtern), sonst bzw. ohne
+/// Ergebnis der Schlüsselbund.
+/// Linux: Secret Service über `secret-tool` (libsecret); Windows: PasswordVault.
+fn zugangsdaten_laden(konto: &str) -> Option<String> {
+    let konto = schluesselbund_konto(konto);
+    let kommando = Einstellungen::laden().passwort_kommando;
+    if !kommando.trim().is_empty() {
+        if let Some(geheimnis) = passwort_kommando_ausfuehren(&kommando, &konto) {
+            return Some(geheimnis);
+        }
+    }
+    #[cfg(not(windows))]
+    let ergebnis = schluesselbund_kommando(
+        "secret-tool",
+        &["lookup", "service", SCHLUESSELBUND_DIENST, "account", &konto],
+        None,
+    );
+    #[cfg(windows)]
+    let ergebnis = passwordvault_skript(
+        "$c = $v.Retrieve($env:MZP_DIENST, $env:MZP_KONTO); $c.RetrievePassword(); [Console]::Out.Write($c.Password)",
+        &konto,
+        None,
+    );
+    let geheimnis = ergebnis.ok()?.trim_end_matches(['\r', '\n']).to_string();
+    if geheimnis.is_empty() { None } else { Some(geheimnis) }
+}
+
+/// `true`, wenn im Schlüsselbund ein Geheimnis für das Konto liegt. Sucht nur nach
+/// dem Eintrag, ohne das Geheimnis zu laden oder zu entschlüsseln; `passwort_kommando`
+/// wird dafür nicht ausgeführt.
+/// Linux: `SearchItems` des Secret Service über `gdbus`; Windows: `FindAllByResource`.
+fn zugangsdaten_hinterlegt(konto: &str) -> bool {
+    let konto = schluesselbund_konto(konto);
+    // Antwort `([entsperrte], [gesperrte])`: jeder Objektpfad ist ein Treffer
+    #[cfg(not(windows))]
+    let hinterlegt = schluesselbund_kommando(
+        "gdbus",
+        &[
+            "call", "--session", "--dest", "org.freedesktop.secrets", "--object-path", "/org/freedesktop/secrets",
+            "--method", "org.freedesktop.Secret.Service.SearchItems",
+            &format!("{{'service': '{}', 'account': '{}'}}", SCHLUESSELBUND_DIENST, konto.replace('\\', "\\\\").replace('\'', "\\'")),
+        ],
+        None,
+    )
+    .is_ok_and(|ausgabe| ausgabe.contains("/org/freedesktop/secrets/"));
+    #[cfg(windows)]
+    let hinterlegt = passwordvault_skript(
+        "try { $v.FindAllByResource($env:MZP_DIENST) | Where-Object { $_.UserName -eq $env:MZP_KONTO } | \
+         ForEach-Object { [Console]::Out.Write('1') } } catch { }",
+        &konto,
+        None,
+    )
+    .is_ok_and(|ausgabe| !ausgabe.trim().is_empty());
+    hinterlegt
+}
+
+/// Legt ein Geheimnis im Schlüsselbund ab (ein vorhandenes wird ersetzt).
+fn zugangsdaten_speichern(konto: &str, geheimnis: &str) -> Result<(), String> {
+    let konto = schluesselbund_konto(konto);
+    #[cfg(not(windows))]
+    let ergebnis = schluesselbund_kommando(
+        "secret-tool",
+        &["store", &format!("--label=MZProtokoll: {}", konto), "service", SCHLUESSELBUND_DIENST, "account", &konto],
+        Some(geheimnis),
+    );
+    #[cfg(windows)]
+    let ergebnis = passwordvault_skript(
+        "$p = [Console]::In.ReadToEnd(); \
+         $v.Add((New-Object Windows.Security.Credentials.PasswordCredential($env:MZP_DIENST, $env:MZP_KONTO, $p)))",
+        &konto,
+        Some(geheimnis),
+    );
+    ergebnis.map(|_| ())
+}
+
+/// Entfernt ein Geheimnis aus dem Schlüsselbund.
+fn zugangsdaten_loeschen(konto: &str) -> Result<(), String> {
+    let konto = schluesselbund_konto(konto);
+    #[cfg(not(windows))]
+    let ergebnis = schluesselbund_kommando(
+        "secret-tool",
+        &["clear", "service", SCHLUESSELBUND_DIENST, "account", &konto],
+        None,
+    );
+    #[cfg(windows)]
+    let ergebnis = passwordvault_skript("$v.Remove($v.Retrieve($env:MZP_DIENST, $env:MZP_KONTO))", &konto, None);
+    ergebnis.map(|_| ())
+}
+
+// -- Aufgaben-Synchronisation (CalDAV-VTODO) --
+
+/// Zugang zur CalDAV-Aufgabenliste (URL endet auf `/`).
+struct CaldavZugang {
+    url: String,
+    benutzer: String,
+    passwort: Option<String>,
+}
+
+/// Ein TODO des Protokolls in der Form, in der es als VTODO übertragen wird.
+struct CaldavAufgabe {
+    /// Eintrags-ID im Protokoll (für den Rückkanal).
+    id: u32,
+    uid: String,
+    /// `true` = Art::Todo, `false` = lokal erledigt oder abgebrochen.
+    offen: bool,
+    zusammenfassung: String,
+    beschreibung: String,
+    /// Projekt als Kategorie (leer = keine).
+    kategorie: String,
+    faellig: Option<NaiveDate>,
+    /// `file://`-Link auf das Protokoll (leer = keiner).
+    link: String,
+}
+
+/// Ergebnis eines Abgleichs mit der Aufgabenliste.
+struct AufgabenErgebnis {
+    /// IDs der Einträge, deren VTODO auf dem Server erledigt ist.
+    erledigt: Vec<u32>,
+    /// Anzahl der geschriebenen VTODOs.
+    hochgeladen: usize,
+}
+
+/// Erzeugt einen VTODO für eine Aufgabe (RFC 5545).
+fn vtodo_erstellen(aufgabe: &CaldavAufgabe, erledigt: bool) -> String {
+    let jetzt = Local::now().naive_utc().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut ics = String::from("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//MZProtokoll//DE\r\nBEGIN:VTODO\r\n");
+    ics.push_str(&format!("UID:{}@mzprotokoll\r\n", aufgabe.uid));
+    ics.push_str(&format!("DTSTAMP:{}\r\n", jetzt));
+    ics.push_str(&format!("SUMMARY:{}\r\n", ics_escapen(&aufgabe.zusammenfassung)));
+    ics.push_str(&format!("DESCRIPTION:{}\r\n", ics_escapen(&aufgabe.beschreibung)));
+    if !aufgabe.kategorie.is_empty() {
+        ics.push_str(&format!("CATEGORIES:{}\r\n", ics_escapen(&aufgabe.kategorie)));
+    }
+    if let Some(faellig) = aufgabe.faellig {
+        ics.push_str(&format!("DUE;VALUE=DATE:{}\r\n", faellig.format("%Y%m%d")));
+    }
+    if !aufgabe.link.is_empty() {
+        ics.push_str(&format!("URL:{}\r\n", aufgabe.link));
+    }
+    if erledigt {
+        ics.push_str(&format!("STATUS:COMPLETED\r\nCOMPLETED:{}\r\nPERCENT-COMPLETE:100\r\n", jetzt));
+    } else {
+        ics.push_str("STATUS:NEEDS-ACTION\r\n");
+    }
+    ics.push_str("END:VTODO\r\nEND:VCALENDAR\r\n");
+    ics_falten(&ics)
+}
+
+/// Maskiert einen Wert für eine curl-Konfigurationsdatei (in Anführungszeichen).
+fn curl_escapen(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
+}
+
+/// Führt eine HTTP-Anfrage an den CalDAV-Server über `curl` aus (HTTPS ohne eigene
+/// TLS-Abhängigkeit). Zugangsdaten und Inhalt gehen als curl-Konfiguration über stdin,
+/// damit das Passwort nie in der Prozessliste erscheint. Gibt HTTP-Status und Antwort zurück.
+fn caldav_anfrage(zugang: &CaldavZugang, methode: &str, url: &str, koerper: Option<&str>) -> Result<(u16, String), String> {
+    let mut konfig = String::new();
+    if !zugang.benutzer.is_empty() {
+        konfig.push_str(&format!(
+            "user = \"{}:{}\"\n",
+            curl_escapen(&zugang.benutzer),
+            curl_escapen(zugang.passwort.as_deref().unwrap_or(""))
+        ));
+    }
+    if let Some(koerper) = koerper {
+        konfig.push_str("header = \"Content-Type: text/calendar; charset=utf-8\"\n");
+        konfig.push_str(&format!("data-binary = \"{}\"\n", curl_escapen(koerper)));
+    }
+    curl_anfrage(methode, url, &konfig).map_err(|e| format!("CalDAV-Server nicht erreichbar: {}", e))
+}
+
+/// Führt eine HTTP-Anfrage über `curl` aus; `konfig` enthält Kopfzeilen, Zugangsdaten
+/// und Inhalt als curl-Konfiguration (über stdin). Gibt HTTP-Status und Antwort zurück.
+fn curl_anfrage(methode: &str, url: &str, konfig: &str) -> Result<(u16, String), String> {
+    let ausgabe = schluesselbund_kommando(
+        "curl",
+        &["--silent", "--show-error", "--max-time", "30", "--request", methode, "--write-out", "\n%{http_code}", "--config", "-", url],
+        Some(konfig),
+    )?;
+    let (antwort, status) = ausgabe.trim_end().rsplit_once('\n').unwrap_or(("", ausgabe.trim()));
+    let status = status.trim().parse().map_err(|_| "Ungültige Antwort des Servers.".to_string())?;
+    Ok((status, antwort.to_string()))
+}
+
+/// Maskiert einen Text für einen JSON-String.
+fn json_escapen(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Sendet eine Nachricht (`m.text` mit HTML-Formatierung) über die Client-Server-API
+/// in einen Matrix-Raum. Die Transaktions-ID aus dem Zeitstempel verhindert Doppelposts
+/// bei Wiederholungen derselben Anfrage.
+fn matrix_nachricht_senden(homeserver: &str, raum: &str, token: &str, text: &str, html: &str) -> Result<(), String> {
+    let url = format!(
+        "{}/_matrix/client/v3/rooms/{}/send/m.room.message/mzprotokoll{}",
+        homeserver,
+        url_kodieren(raum),
+        Local::now().format("%Y%m%d%H%M%S%f")
+    );
+    let koerper = format!(
+        "{{\"msgtype\":\"m.text\",\"body\":\"{}\",\"format\":\"org.matrix.custom.html\",\"formatted_body\":\"{}\"}}",
+        json_escapen(text),
+        json_escapen(html)
+    );
+    let konfig = format!(
+        "header = \"Authorization: Bearer {}\"\nheader = \"Content-Type: application/json\"\ndata-binary = \"{}\"\n",
+        curl_escapen(token),
+        curl_escapen(&koerper)
+    );
+    let (status, antwort) = curl_anfrage("PUT", &url, &konfig).map_err(|e| format!("Matrix-Server nicht erreichbar: {}", e))?;
+    if (200..300).contains(&status) {
+        Ok(())
+    } else if status == 401 || status == 403 {
+        Err(format!("Matrix verweigert den Zugriff (HTTP {}): Token und Raummitgliedschaft prüfen.", status))
+    } else {
+        Err(format!("Matrix-Fehler (HTTP {}): {}", status, antwort.trim()))
+    }
+}
+
+/// Sendet `json` per `POST` an einen Webhook (über `curl_anfrage`).
+fn webhook_senden(url: &str, json: &str) -> Result<(), String> {
+    let konfig = format!("header = \"Content-Type: application/json\"\ndata-binary = \"{}\"\n", curl_escapen(json));
+    let (status, antwort) = curl_anfrage("POST", url, &konfig).map_err(|e| format!("Webhook nicht erreichbar: {}", e))?;
+    if (200..300).contains(&status) {
+        Ok(())
+    } else {
+        Err(format!("Webhook-Fehler (HTTP {}): {}", status, antwort.trim()))
+    }
+}
+
+/// Gleicht die Aufgaben mit dem Server ab: liest zuerst jeden VTODO (Rückkanal) und
+/// schreibt danach – nur mit `hochladen` – offene und frisch erledigte Aufgaben.
+/// Bereits auf dem Server erledigte Aufgaben werden nicht überschrieben.
+fn aufgaben_abgleichen(zugang: &CaldavZugang, aufgaben: &[CaldavAufgabe], hochladen: bool) -> Result<AufgabenErgebnis, String> {
+    let mut ergebnis = AufgabenErgebnis { erledigt: Vec::new(), hochgeladen: 0 };
+    for aufgabe in aufgaben {
+        let url = format!("{}{}.ics", zugang.url, aufgabe.uid);
+        let (status, inhalt) = caldav_anfrage(zugang, "GET", &url, None)?;
+        if status == 401 || status == 403 {
+            return Err(format!("CalDAV-Server verweigert den Zugriff (HTTP {}).", status));
+        }
+        let vorhanden = status == 200;
+        let server_erledigt = vorhanden && inhalt.lines().any(|l| l.trim() == "STATUS:COMPLETED");
+        if server_erledigt {
+            if aufgabe.offen {
+                ergebnis.erledigt.push(aufgabe.id);
+            }
+            continue;
+        }
+        // Lokal erledigte Aufgaben nur abschließen, wenn sie schon übertragen wurden
+        if !hochladen || (!aufgabe.offen && !vorhanden) {
+            continue;
+        }
+        let (status, _) = caldav_anfrage(zugang, "PUT", &url, Some(&vtodo_erstellen(aufgabe, !aufgabe.offen)))?;
+        if !(200..300).contains(&status) {
+            return Err(format!("Aufgabe konnte nicht gespeichert werden (HTTP {}).", status));
+        }
+        ergebnis.hochgeladen += 1;
+    }
+    Ok(ergebnis)
+}
+
+/// Liest den ersten String-Wert zu `schluessel` aus einer JSON-Antwort (ohne JSON-Parser;
+/// genügt für die flachen Antworten der Chat-Endpunkte).
+fn json_string_lesen(json: &str, schluessel: &str) -> Option<String> {
+    let muster = format!("\"{}\"", schluessel);
+    let start = json.find(&muster)? + muster.len();
+    let rest = json[start..].trim_start().strip_prefix(':')?.trim_start().strip_prefix('"')?;
+    let mut wert = String::new();
+    let mut zeichen = rest.chars();
+    while let Some(c) = zeichen.next() {
+        match c {
+            '"' => return Some(wert),
+            '\\' => match zeichen.next()? {
+                'n' => wert.push('\n'),
+                'r' => {}
+                't' => wert.push('\t'),
+                'u' => {
+                    let code: String = zeichen.by_ref().take(4).collect();
+                    wert.push(u32::from_str_radix(&code, 16).ok().and_then(char::from_u32).unwrap_or('\u{FFFD}'));
+                }
+                anderes => wert.push(anderes),
+            },
+            c => wert.push(c),
+        }
+    }
+    None
+}
+
+/// Liest die erste Zahl zu `schluessel` aus einer JSON-Antwort (wie `json_string_lesen`).
+fn json_zahl_lesen(json: &str, schluessel: &str) -> Option<u64> {
+    let muster = format!("\"{}\"", schluessel);
+    let start = json.find(&muster)? + muster.len();
+    let rest = json[start..].trim_start().strip_prefix(':')?.trim_start();
+    let ende = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    rest[..ende].parse().ok()
+}
+
+/// Fragt einen OpenAI-kompatiblen Chat-Endpunkt (z. B. Ollama, llama.cpp) nach einer
+/// Zusammenfassung des Protokolls. Gibt den Antworttext zurück.
+fn llm_zusammenfassung(endpunkt: &str, modell: &str, protokoll: &str) -> Result<String, String> {
+    let anweisung = "Fasse das folgende Besprechungsprotokoll für den Abschnitt „Über dieses Meeting“ \
+                     in zwei bis vier sachlichen Sätzen auf Deutsch zusammen. Nenne die wichtigsten Themen, \
+                     Entscheidungen und offenen Aufgaben. Antworte nur mit dem Text.";
+    let koerper = format!(
+        "{{\"model\":\"{}\",\"stream\":false,\"messages\":[{{\"role\":\"system\",\"content\":\"{}\"}},{{\"role\":\"user\",\"content\":\"{}\"}}]}}",
+        json_escapen(modell),
+        json_escapen(anweisung),
+        json_escapen(protokoll)
+    );
+    let konfig = format!(
+        "header = \"Content-Type: application/json\"\ndata-binary = \"{}\"\nmax-time = 120\n",
+        curl_escapen(&koerper)
+    );
+    let (status, antwort) = curl_anfrage("POST", endpunkt, &konfig).map_err(|e| format!("Sprachmodell nicht erreichbar: {}", e))?;
+    if !(200..300).contains(&status) {
+        return Err(format!("Sprachmodell antwortet mit HTTP {}", status));
+    }
+    json_string_lesen(&antwort, "content")
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+        .ok_or_else(|| "Leere Antwort des Sprachmodells".to_string())
+}
+
+// -- Redmine --
+
+/// Status eines Redmine-Tickets, wie er als Badge in der Zeile erscheint.
+struct RedmineStatus {
+    name: String,
+    /// `true` bei geschlossenen Status (z. B. Erledigt, Abgewiesen).
+    geschlossen: bool,
+}
+
+/// Ergebnis einer Redmine-Anfrage aus dem Hintergrund-Thread.
+enum RedmineErgebnis {
+    /// Ticket zum Eintrag mit der ID `eintrag` angelegt, samt Status (falls abrufbar).
+    Angelegt { eintrag: u32, ticket: u32, status: Vec<(u32, RedmineStatus)> },
+    /// Abgefragte Status; `manuell` = über das Menü angestoßen.
+    Status { status: Vec<(u32, RedmineStatus)>, manuell: bool },
+    Fehler(String),
+}
+
+/// Findet die Redmine-Tickets, auf die eine Notiz verweist: `#123` (nicht mitten in
+/// einem Wort, ohne direkt folgende Buchstaben wie in `#1e90ff`) und Links der Form
+/// `<redmine_url>/issues/123`. Reihenfolge wie im Text, ohne Doppelte.
+fn redmine_tickets(notiz: &str, url: &str) -> Vec<u32> {
+    let mut tickets = Vec::new();
+    let mut kandidaten: Vec<&str> = Vec::new();
+    for (pos, _) in notiz.match_indices('#') {
+        if notiz[..pos].chars().next_back().is_none_or(|c| !c.is_alphanumeric() && c != '&') {
+            kandidaten.push(&notiz[pos + 1..]);
+        }
+    }
+    let url = url.trim().trim_end_matches('/');
+    if !url.is_empty() {
+        let muster = format!("{}/issues/", url);
+        kandidaten.extend(notiz.match_indices(&muster).map(|(pos, _)| &notiz[pos + muster.len()..]));
+    }
+    for rest in kandidaten {
+        let ende = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        // Nur ganze Zahlen: „#1e90ff" (Farbe) oder „#12abc" sind keine Tickets
+        if rest[ende..].chars().next().is_some_and(char::is_alphanumeric) {
+            continue;
+        }
+        if let Ok(ticket) = rest[..ende].parse::<u32>() {
+            if ticket > 0 && !tickets.contains(&ticket) {
+                tickets.push(ticket);
+            }
+        }
+    }
+    tickets
+}
+
+/// curl-Konfiguration mit dem API-Key (ohne Key nur öffentlich sichtbare Tickets).
+fn redmine_konfig(schluessel: Option<&str>) -> String {
+    let mut konfig = String::from("header = \"Content-Type: application/json\"\n");
+    if let Some(schluessel) = schluessel {
+        konfig.push_str(&format!("header = \"X-Redmine-API-Key: {}\"\n", curl_escapen(schluessel)));
+    }
+    konfig
+}
+
+/// Liest den Status aus der Antwort von `GET /issues/<id>.json`
+/// (`"status":{"id":1,"name":"Neu","is_closed":false}`; `is_closed` erst ab Redmine 5.1).
+fn redmine_status_lesen(json: &str) -> Option<RedmineStatus> {
+    let rest = &json[json.find("\"status\"")?..];
+    let objekt = &rest[..rest.find('}').unwrap_or(rest.len())];
+    Some(RedmineStatus {
+        name: json_string_lesen(objekt, "name")?,
+        geschlossen: objekt.replace(' ', "").contains("\"is_closed\":true"),
+    })
+}
+
+/// Fragt den Status der angegebenen Tickets ab. Nicht (mehr) vorhandene oder nicht
+/// sichtbare Tickets werden übersprungen.
+fn redmine_status_laden(url: &str, schluessel: Option<&str>, tickets: &[u32]) -> Result<Vec<(u32, RedmineStatus)>, String> {
+    let konfig = redmine_konfig(schluessel);
+    let mut ergebnis = Vec::new();
+    for &ticket in tickets {
+        let (status, antwort) = curl_anfrage("GET", &format!("{}/issues/{}.json", url, ticket), &konfig)
+            .map_err(|e| format!("Redmine-Server nicht erreichbar: {}", e))?;
+        if status == 401 {
+            return Err("Redmine verweigert den Zugriff (HTTP 401): API-Key prüfen.".to_string());
+        }
+        if let Some(ticket_status) = (status == 200).then(|| redmine_status_lesen(&antwort)).flatten() {
+            ergebnis.push((ticket, ticket_status));
+        }
+    }
+    Ok(ergebnis)
+}
+
+/// Legt über `POST /issues.json` ein Ticket an und gibt dessen Nummer zurück.
+fn redmine_ticket_erstellen(
+    url: &str,
+    schluessel: &str,
+    projekt: &str,
+    betreff: &str,
+    beschreibung: &str,
+    faellig: Option<NaiveDate>,
+) -> Result<u32, String> {
+    let mut felder = format!(
+        "\"project_id\":\"{}\",\"subject\":\"{}\",\"description\":\"{}\"",
+        json_escapen(projekt),
+        json_escapen(betreff),
+        json_escapen(beschreibung)
+    );
+    if let Some(faellig) = faellig {
+        felder.push_str(&format!(",\"due_date\":\"{}\"", faellig.format("%Y-%m-%d")));
+    }
+    let konfig = format!(
+        "{}data-binary = \"{}\"\n",
+        redmine_konfig(Some(schluessel)),
+        curl_escapen(&format!("{{\"issue\":{{{}}}}}", felder))
+    );
+    let (status, antwort) = curl_anfrage("POST", &format!("{}/issues.json", url), &konfig)
+        .map_err(|e| format!("Redmine-Server nicht erreichbar: {}", e))?;
+    match status {
+        200..=299 => json_zahl_lesen(&antwort, "id")
+            .and_then(|id| u32::try_from(id).ok())
+            .ok_or_else(|| "Redmine hat keine Ticketnummer zurückgegeben.".to_string()),
+        401 | 403 => Err(format!("Redmine verweigert den Zugriff (HTTP {}): API-Key und Projektrechte prüfen.", status)),
+        _ => Err(format!("Redmine-Fehler (HTTP {}): {}", status, antwort.trim())),
+    }
+}
+
+/// Zeigt die in einer Notiz erwähnten Tickets mit bekanntem Status als Badges
+/// (offen in der TODO-, geschlossen in der FERTIG-Farbe).
+fn redmine_badges_anzeigen(
+    ui: &mut egui::Ui,
+    notiz: &str,
+    url: &str,
+    status: &HashMap<u32, RedmineStatus>,
+    farben: &TabellenFarben,
+    groesse: f32,
+) {
+    if status.is_empty() {
+        return;
+    }
+    for ticket in redmine_tickets(notiz, url) {
+        let Some(s) = status.get(&ticket) else { continue };
+        let farbe = farben.art(if s.geschlossen { &Art::Fertig } else { &Art::Todo });
+        ui.label(
+            RichText::new(format!("#{} {}", ticket, s.name))
+                .font(fette_schrift(groesse))
+                .color(egui::Color32::WHITE)
+                .background_color(farbe),
+        )
+        .on_hover_text(format!("Redmine-Ticket #{}", ticket));
+    }
+}
+
+// -- PDF-Backends --
+
+/// Auswahl des PDF-Backends in `einstellungen.toml` (`pdf_backend`).
+#[derive(Clone, Copy, PartialEq)]
+enum PdfBackendArt {
+    /// Eingebauter Satz mit genpdf (Standard).
+    Genpdf,
+    /// HTML-Ausgabe, die ein externes Programm wie weasyprint in PDF umwandelt.
+    Html,
+    /// Typst-Quelltext (`typst_erstellen`), den der Typst-Compiler setzt.
+    Typst,
+}
+
+impl PdfBackendArt {
+    /// Erkennt den Wert von `pdf_backend` (Unbekanntes = genpdf).
+    fn parsen(text: &str) -> PdfBackendArt {
+        match text.trim().to_lowercase().as_str() {
+            "html" => PdfBackendArt::Html,
+            "typst" => PdfBackendArt::Typst,
+            _ => PdfBackendArt::Genpdf,
+        }
+    }
+}
+
+/// Erzeugt das PDF eines Protokolls. Export, Schnellexport, Archivierung, Direktdruck
+/// und `--regenerate-pdf` holen das eingestellte Backend über `ProtokollApp::pdf_backend`.
+trait PdfBackend {
+    /// Rendert das Protokoll bzw. bereitet die Quelle für ein externes Programm vor.
+    /// Läuft im UI-Thread; externe Programme startet erst `PdfAuftrag::ausfuehren`.
+    fn pdf_vorbereiten(&self, app: &ProtokollApp) -> Result<PdfAuftrag, String>;
+
+    /// Rendert das Protokoll und gibt den Inhalt der PDF-Datei zurück (blockierend).
+    fn pdf_erzeugen(&self, app: &ProtokollApp) -> Result<Vec<u8>, String> {
+        self.pdf_vorbereiten(app)?.ausfuehren()
+    }
+}
+
+/// Vorbereitetes PDF: fertig gerendert oder als Quelle, die ein externes Programm
+/// noch umwandelt. Enthält keinen Verweis auf die App und kann daher in einem
+/// eigenen Thread ausgeführt werden.
+enum PdfAuftrag {
+    Fertig(Vec<u8>),
+    Extern { kommando: String, endung: &'static str, quelle: String },
+}
+
+impl PdfAuftrag {
+    /// Liefert den Inhalt der PDF-Datei; externe Programme laufen hier.
+    fn ausfuehren(self) -> Result<Vec<u8>, String> {
+        match self {
+            PdfAuftrag::Fertig(pdf) => Ok(pdf),
+            PdfAuftrag::Extern { kommando, endung, quelle } => pdf_extern_erzeugen(&kommando, endung, &quelle),
+        }
+    }
+}
+
+/// Vollständiger PDF-Export aus `ProtokollApp::pdf_export_vorbereiten`: Auftrag,
+/// Nachbearbeitung des Exportprofils und Begleitdateien.
+struct PdfExport {
+    auftrag: PdfAuftrag,
+    /// PDF/A-Umwandlung mit `pdfa_kommando` (`None` = keine).
+    pdfa_kommando: Option<String>,
+    /// Passwortschutz als `(qpdf-Programm, Passwort)` (`None` = keiner).
+    verschluesselung: Option<(String, String)>,
+    /// Zielpfad des PDFs.
+    ziel: std::path::PathBuf,
+    /// Versandstand für den nächsten Änderungsvergleich als `(Pfad, Inhalt)`.
+    versand_stand: Option<(std::path::PathBuf, String)>,
+    /// Inhalt der XML-Begleitdatei neben dem PDF.
+    xml: Option<String>,
+}
+
+impl PdfExport {
+    /// Erzeugt, bearbeitet und schreibt das PDF; erst bei Erfolg folgen Versandstand
+    /// und XML-Begleitdatei. Blockiert und läuft daher im eigenen Thread.
+    fn ausfuehren(self) -> Result<(), String> {
+        let mut pdf = self.auftrag.ausfuehren()?;
+        if let Some(kommando) = &self.pdfa_kommando {
+            pdf = extern_umwandeln(kommando, &pdf, "pdf", "{pdf}", "{ziel}").map_err(|e| format!("PDF/A-Umwandlung: {}", e))?;
+        }
+        if let Some((programm, passwort)) = &self.verschluesselung {
+            pdf = pdf_verschluesseln(programm, &pdf, passwort).map_err(|e| format!("Passwortschutz: {}", e))?;
+        }
+        std::fs::write(&self.ziel, pdf).map_err(|e| e.to_string())?;
+        if let Some((pfad, inhalt)) = &self.versand_stand {
+            versand_stand_schreiben(pfad, inhalt);
+        }
+        if let Some(xml) = &self.xml {
+            atomar_schreiben(&self.ziel.with_extension("xml"), xml)
+                .map_err(|e| format!("XML-Begleitdatei konnte nicht geschrieben werden: {}", e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Was nach einem im Hintergrund erzeugten PDF geschieht (`ProtokollApp::pdf_fertig`).
+enum PdfDanach {
+    /// Export-Dialog: Fehler als Toast, bei „… und beenden" anschließend beenden.
+    Export,
+    /// Schnellexport: Ergebnis als Toast.
+    Schnellexport,
+    /// Typst-Export mit Kompilierung: Ergebnis als Toast.
+    Typst,
+    /// Archivieren ohne vorhandenes PDF: nur Fehler als Toast.
+    Archiv,
+    /// Freigabe-Pipeline: archiviert `alt` und meldet das Ergebnis über `tx`.
+    Freigabe { alt: std::path::PathBuf, tx: mpsc::Sender<Result<String, String>> },
+}
+
+/// Ergebnis eines PDF-Threads.
+struct PdfFertig {
+    danach: PdfDanach,
+    pfad: std::path::PathBuf,
+    ergebnis: Result<(), String>,
+}
+
+/// Legt einen Versandstand ab (Verzeichnis wird bei Bedarf angelegt).
+fn versand_stand_schreiben(pfad: &std::path::Path, inhalt: &str) {
+    if let Some(dir) = pfad.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let _ = std::fs::write(pfad, inhalt);
+}
+
+/// Eingebautes Backend: genpdf mit der geladenen Schriftfamilie (`pdf_dokument_erstellen`).
+struct GenpdfBackend {
+    schriftfamilie: genpdf::fonts::FontFamily<genpdf::fonts::FontData>,
+}
+
+impl PdfBackend for GenpdfBackend {
+    fn pdf_vorbereiten(&self, app: &ProtokollApp) -> Result<PdfAuftrag, String> {
+        let mut pdf = Vec::new();
+        app.pdf_dokument_erstellen(self.schriftfamilie.clone())
+            .render(&mut pdf)
+            .map_err(|e| e.to_string())?;
+        Ok(PdfAuftrag::Fertig(pdf))
+    }
+}
+
+/// HTML→PDF über ein externes Programm: `html_erstellen` liefert das Dokument, das
+/// `kommando` (z. B. `weasyprint {html} {pdf}`) im temporären Verzeichnis umwandelt.
+struct HtmlBackend {
+    kommando: String,
+}
+
+impl PdfBackend for HtmlBackend {
+    fn pdf_vorbereiten(&self, app: &ProtokollApp) -> Result<PdfAuftrag, String> {
+        Ok(PdfAuftrag::Extern { kommando: self.kommando.clone(), endung: "html", quelle: app.html_erstellen() })
+    }
+}
+
+/// Typst-Backend: `typst_erstellen` liefert Vorlage und Daten, `kommando`
+/// (z. B. `typst compile {typ} {pdf}`) kompiliert im temporären Verzeichnis.
+struct TypstBackend {
+    kommando: String,
+}
+
+impl PdfBackend for TypstBackend {
+    fn pdf_vorbereiten(&self, app: &ProtokollApp) -> Result<PdfAuftrag, String> {
+        Ok(PdfAuftrag::Extern { kommando: self.kommando.clone(), endung: "typ", quelle: app.typst_erstellen() })
+    }
+}
+
+/// Schreibt `quelle` als temporäre Datei mit der Endung `endung`, führt `kommando` aus
+/// (Platzhalter `{<endung>}` für die Quelle und `{pdf}` für das Ziel, ohne Shell an
+/// Leerzeichen zerlegt) und liest das entstandene PDF ein.
+fn pdf_extern_erzeugen(kommando: &str, endung: &str, quelle: &str) -> Result<Vec<u8>, String> {
+    extern_umwandeln(kommando, quelle.as_bytes(), endung, &format!("{{{}}}", endung), "{pdf}")
+}
+
+/// Gemeinsamer Pfadstamm für temporäre Dateien externer Programme.
+fn temp_basis() -> std::path::PathBuf {
+    std::env::temp_dir().join(format!(
+        "mzprotokoll-{}-{}",
+        std::process::id(),
+        Local::now().format("%H%M%S%f")
+    ))
+}
+
+/// Schreibt `quelle` als temporäre Datei mit der Endung `endung`, führt `kommando` aus
+/// (`quell_platzhalter` und `ziel_platzhalter` werden durch die Pfade von Quelle und
+/// Ziel-PDF ersetzt) und liest das Ziel ein. Temporäre Dateien werden in jedem Fall
+/// entfernt.
+fn extern_umwandeln(
+    kommando: &str,
+    quelle: &[u8],
+    endung: &str,
+    quell_platzhalter: &str,
+    ziel_platzhalter: &str,
+) -> Result<Vec<u8>, String> {
+    let basis = temp_basis();
+    let quell_pfad = basis.with_extension(endung);
+    let pdf_pfad = basis.with_extension("ziel.pdf");
+    std::fs::write(&quell_pfad, quelle).map_err(|e| e.to_string())?;
+    let mut teile = kommando.split_whitespace().map(|teil| {
+        teil.replace(quell_platzhalter, &quell_pfad.to_string_lossy())
+            .replace(ziel_platzhalter, &pdf_pfad.to_string_lossy())
+    });
+    let ergebnis = match teile.next() {
+        None => Err("Kein Kommando eingestellt".to_string()),
+        Some(programm) => std::process::Command::new(&programm)
+            .args(teile)
+            .output()
+            .map_err(|e| format!("{} konnte nicht gestartet werden: {}", programm, e))
+            .and_then(|ausgabe| {
+                if ausgabe.status.success() {
+                    std::fs::read(&pdf_pfad).map_err(|e| e.to_string())
+                } else {
+                    Err(String::from_utf8_lossy(&ausgabe.stderr).trim().to_string())
+                }
+            }),
+    };
+    let _ = std::fs::remove_file(&quell_pfad);
+    let _ = std::fs::remove_file(&pdf_pfad);
+    ergebnis
+}
+
+/// Verschlüsselt `pdf` mit qpdf (AES-256, `passwort` als Benutzer- und
+/// Besitzerpasswort). Die Argumente gehen über eine nur für den Benutzer lesbare
+/// Argumentdatei an `programm`, damit das Passwort nicht in der Prozessliste steht.
+fn pdf_verschluesseln(programm: &str, pdf: &[u8], passwort: &str) -> Result<Vec<u8>, String> {
+    use std::io::Write;
+    if passwort.is_empty() {
+        return Err("Kein Passwort angegeben".to_string());
+    }
+    if passwort.contains(['\n', '\r']) {
+        return Err("Das Passwort darf keinen Zeilenumbruch enthalten".to_string());
+    }
+    let basis = temp_basis();
+    let quell_pfad = basis.with_extension("pdf");
+    let ziel_pfad = basis.with_extension("ziel.pdf");
+    let argument_pfad = basis.with_extension("args");
+    let argumente = format!(
+        "{}\n--encrypt\n{}\n{}\n256\n--\n{}\n",
+        quell_pfad.display(),
+        passwort,
+        passwort,
+        ziel_pfad.display()
+    );
+    let mut optionen = std::fs::OpenOptions::new();
+    optionen.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        optionen.mode(0o600);
+    }
+    let ergebnis = std::fs::write(&quell_pfad, pdf)
+        .and_then(|()| optionen.open(&argument_pfad))
+        .and_then(|mut datei| datei.write_all(argumente.as_bytes()))
+        .map_err(|e| e.to_string())
+        .and_then(|()| {
+            std::process::Command::new(programm)
+                .arg(format!("@{}", argument_pfad.display()))
+                .output()
+                .map_err(|e| format!("{} konnte nicht gestartet werden: {}", programm, e))
+        })
+        .and_then(|ausgabe| {
+            // Exit-Code 3: erfolgreich, aber mit Warnungen
+            if ausgabe.status.success() || ausgabe.status.code() == Some(3) {
+                std::fs::read(&ziel_pfad).map_err(|e| e.to_string())
+            } else {
+                Err(String::from_utf8_lossy(&ausgabe.stderr).trim().to_string())
+            }
+        });
+    let _ = std::fs::remove_file(&argument_pfad);
+    let _ = std::fs::remove_file(&quell_pfad);
+    let _ = std::fs::remove_file(&ziel_pfad);
+    ergebnis
+}
+
+/// Zerlegt Notiztext an Markdown-Links `[Label](URL)`: Textstücke gehen durch
+/// `text_umsetzen`, Links durch `link_umsetzen(label, url)`.
+fn links_umsetzen(text: &str, text_umsetzen: impl Fn(&str) -> String, link_umsetzen: impl Fn(&str, &str) -> String) -> String {
+    let mut ergebnis = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('[') {
+        let link = rest[start + 1..].split_once("](").and_then(|(label, danach)| {
+            let (url, danach) = danach.split_once(')')?;
+            (!label.is_empty() && !label.contains(']') && !url.is_empty()).then_some((label, url, danach))
+        });
+        match link {
+            Some((label, url, danach)) => {
+                ergebnis.push_str(&text_umsetzen(&rest[..start]));
+                ergebnis.push_str(&link_umsetzen(label, url));
+                rest = danach;
+            }
+            None => {
+                ergebnis.push_str(&text_umsetzen(&rest[..=start]));
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    ergebnis.push_str(&text_umsetzen(rest));
+    ergebnis
+}
+
+/// Wandelt Notiztext in HTML um: Sonderzeichen maskiert, Markdown-Links als `<a>`,
+/// Zeilenumbrüche als `<br>`.
+fn html_text(text: &str) -> String {
+    links_umsetzen(text, xml_escapen, |label, url| {
+        format!("<a href=\"{}\">{}</a>", xml_escapen(url), xml_escapen(label))
+    })
+    .replace('\n', "<br>\n")
+}
+
+/// Typst-Zeichenkettenliteral mit maskierten Sonderzeichen.
+fn typst_str(text: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '"' => literal.push_str("\\\""),
+            '\n' => literal.push_str("\\n"),
+            '\r' => {}
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// Wandelt Notiztext in einen Typst-Inhaltsblock um; Markdown-Links werden zu `link()`.
+/// Text wird als Zeichenkette eingefügt, damit Typst-Markup darin wirkungslos bleibt.
+fn typst_text(text: &str) -> String {
+    let inhalt = links_umsetzen(
+        text,
+        |stueck| if stueck.is_empty() { String::new() } else { format!("#{}", typst_str(stueck)) },
+        |label, url| format!("#link({})[#{}]", typst_str(url), typst_str(label)),
+    );
+    format!("[{}]", inhalt)
+}
+
+// -- PDF-Helfer --
+
+/// Seitendekorierer für den PDF-Export: fügt jeder Seite eine Fußzeile
+/// mit der aktuellen Seitenzahl und der Gesamtseitenanzahl hinzu sowie
+/// ab Seite 2 eine optionale Kopfzeile.
+struct FusszeileDekorator {
+    /// Seitenränder für den Inhaltsbereich (oben, rechts, unten, links in mm).
+    raender: genpdf::Margins,
+    /// Laufende Seitennummer (wird beim Rendern pro Seite hochgezählt).
+    aktuelle_seite: usize,
+    /// Gesamtanzahl der Seiten (aus dem ersten Render-Durchlauf).
+    gesamtseiten: usize,
+    /// Kopfzeilentext für die Folgeseiten (leer = keine Kopfzeile).
+    kopfzeile: String,
+    /// Ausrichtung der Kopfzeile.
+    kopfzeile_ausrichtung: genpdf::Alignment,
+    /// Sprache von „Seite x von y".
+    sprache: PdfSprache,
+}
+
+impl FusszeileDekorator {
+    /// Erstellt einen neuen Fußzeile-Dekorierer mit der bekannten Gesamtseitenzahl.
+    fn new(gesamtseiten: usize) -> Self {
+        Self {
+            raender: genpdf::Margins::trbl(20, 15, 20, 15),
+            aktuelle_seite: 0,
+            gesamtseiten,
+            kopfzeile: String::new(),
+            kopfzeile_ausrichtung: genpdf::Alignment::Left,
+            sprache: PdfSprache::Deutsch,
+        }
+    }
+
+    /// Setzt die Sprache der Seitenangabe.
+    fn mit_sprache(mut self, sprache: PdfSprache) -> Self {
+        self.sprache = sprache;
+        self
+    }
+
+    /// Ersetzt die Standard-Seitenränder (z. B. durch die des Dichte-Profils).
+    fn mit_raendern(mut self, raender: genpdf::Margins) -> Self {
+        self.raender = raender;
+        self
+    }
+
+    /// Ergänzt eine Kopfzeile, die ab Seite 2 gedruckt wird.
+    fn mit_kopfzeile(mut self, text: String, ausrichtung: genpdf::Alignment) -> Self {
+        self.kopfzeile = text;
+        self.kopfzeile_ausrichtung = ausrichtung;
+        self
+    }
+}
+
+impl genpdf::PageDecorator for FusszeileDekorator {
+    fn decorate_page<'a>(
+        &mut self,
+        context: &genpdf::Context,
+        area: genpdf::render::Area<'a>,
+        _style: genpdf::style::Style,
+    ) -> Result<genpdf::render::Area<'a>, genpdf::error::Error> {
+        self.aktuelle_seite += 1;
+
+        let mut area = area;
+
+        // Fußzeile auf der Rohseite platzieren, bevor die Seitenränder gesetzt werden
+        let rohseiten_groesse = area.size();
+        let rohseite_hoehe: f64 = rohseiten_groesse.height.into();
+        let rohseite_breite: f64 = rohseiten_groesse.width.into();
+
+        let fusszeilen_text = format!(
+            "{} {} {} {}",
+            self.sprache.t("Seite"),
+            self.aktuelle_seite,
+            self.sprache.t("von"),
+            self.gesamtseiten
+        );
+        let fusszeilen_stil = genpdf::style::Style::new().with_font_size(9);
+        // Textbreite bei 9pt: ca. 2.0 mm pro Zeichen (Näherungswert)
+        let text_breite = fusszeilen_text.len() as f64 * 2.0;
+        // Text bündig mit dem rechten Inhaltsrand ausrichten
+        let rechter_rand = 8.0;
+
+        let _ = area.print_str(
+            &context.font_cache,
+            genpdf::Position::new(rohseite_breite - rechter_rand - text_breite, rohseite_hoehe - 15.0),
+            fusszeilen_stil,
+            &fusszeilen_text,
+        );
+
+        // Kopfzeile ab Seite 2, damit einzelne Seiten zuordenbar bleiben
+        if self.aktuelle_seite > 1 && !self.kopfzeile.is_empty() {
+            let kopf_stil = genpdf::style::Style::new()
+                .with_font_size(8)
+                .with_color(genpdf::style::Color::Greyscale(110));
+            // Textbreite bei 8pt: ca. 1.8 mm pro Zeichen (Näherungswert)
+            let kopf_breite = self.kopfzeile.chars().count() as f64 * 1.8;
+            let x = match self.kopfzeile_ausrichtung {
+                genpdf::Alignment::Center => (rohseite_breite - kopf_breite) / 2.0,
+                genpdf::Alignment::Right => rohseite_breite - rechter_rand - kopf_breite,
+                _ => 15.0,
+            };
+            let _ = area.print_str(
+                &context.font_cache,
+                genpdf::Position::new(x.max(15.0), 10.0),
+                kopf_stil,
+                &self.kopfzeile,
+            );
+        }
+
+        // Seitenränder für den eigentlichen Inhaltsbereich anwenden
+        area.add_margins(self.raender);
+
+        Ok(area)
+    }
+}
+
+/// Wrapper-Element für genpdf: zeichnet zuerst einen farbigen Hintergrund (durch
+/// dichte horizontale Linien simuliert), danach wird der eigentliche Inhalt darüber gerendert.
+struct ZellenHintergrund<E: genpdf::Element> {
+    /// Das eingebettete genpdf-Element, das nach dem Hintergrund gerendert wird.
+    inhalt: E,
+    /// Hintergrundfarbe (Graustufe oder RGB).
+    farbe: genpdf::style::Color,
+    /// Zusätzlicher Überhang nach links in mm (aktuell immer 0).
+    erweiterung_links: f64,
+    /// Maximale Hintergrundhöhe in mm — verhindert, dass grauer Überlauf
+    /// auf die nächste weiße Zeile reicht.
+    max_hoehe: f64,
+}
+
+impl<E: genpdf::Element> ZellenHintergrund<E> {
+    /// Erstellt eine graue Hintergrundzeile (Graustufe 220).
+    fn grau(inhalt: E, max_hoehe: f64) -> Self {
+        Self {
+            inhalt,
+            farbe: genpdf::style::Color::Greyscale(220),
+            erweiterung_links: 0.0,
+            max_hoehe,
+        }
+    }
+    /// Erstellt einen hellgrauen Kasten für Code-Blöcke in Notizen (Graustufe 238).
+    fn code(inhalt: E, max_hoehe: f64) -> Self {
+        Self {
+            inhalt,
+            farbe: genpdf::style::Color::Greyscale(238),
+            erweiterung_links: 0.0,
+            max_hoehe,
+        }
+    }
+    /// Erstellt eine weiße Hintergrundzeile (deckt grauen Überlauf der Vorgängerzeile ab).
+    fn weiss(inhalt: E, max_hoehe: f64) -> Self {
+        Self {
+            inhalt,
+            farbe: genpdf::style::Color::Greyscale(255),
+            erweiterung_links: 0.0,
+            max_hoehe,
+        }
+    }
+}
+
+impl<E: genpdf::Element> genpdf::Element for ZellenHintergrund<E> {
+    fn render(
+        &mut self,
+        context: &genpdf::Context,
+        area: genpdf::render::Area<'_>,
+        stil: genpdf::style::Style,
+    ) -> Result<genpdf::RenderResult, genpdf::error::Error> {
+        let zellen_groesse = area.size();
+        let hintergrund_stil = genpdf::style::Style::new().with_color(self.farbe);
+        let breite: f64 = zellen_groesse.width.into();
+        let volle_hoehe: f64 = zellen_groesse.height.into();
+        // Hintergrund nur bis zur maximalen Höhe zeichnen
+        let hoehe: f64 = volle_hoehe.min(self.max_hoehe);
+        let x_start = -self.erweiterung_links;
+        let mut y = 0.0;
+        // Hintergrund durch sehr dichte horizontale Linien (0,15 mm Abstand) simulieren
+        while y <= hoehe - 0.5 {
+            area.draw_line(
+                vec![
+                    genpdf::Position::new(x_start, y),
+                    genpdf::Position::new(breite, y),
+                ],
+                hintergrund_stil,
+            );
+            y += 0.15;
+        }
+        // Inhalt über dem Hintergrund rendern
+        self.inhalt.render(context, area, stil)
+    }
+}
+
+/// Horizontale Trennlinie in voller Inhaltsbreite, gezeichnet mit `Area::draw_line`
+/// (unabhängig von Seitenformat und Schriftgröße). Die Stärke entsteht aus dicht
+/// gesetzten parallelen Linien, wie beim Zellenhintergrund.
+struct Trennlinie {
+    /// Linienstärke in mm.
+    staerke: f64,
+    /// Linienfarbe.
+    farbe: genpdf::style::Color,
+}
+
+impl Trennlinie {
+    /// Erstellt eine Trennlinie mit Stärke (mm) und Farbe.
+    fn new(staerke: f64, farbe: genpdf::style::Color) -> Self {
+        Self { staerke, farbe }
+    }
+}
+
+impl genpdf::Element for Trennlinie {
+    fn render(
+        &mut self,
+        _context: &genpdf::Context,
+        area: genpdf::render::Area<'_>,
+        _stil: genpdf::style::Style,
+    ) -> Result<genpdf::RenderResult, genpdf::error::Error> {
+        let groesse = area.size();
+        let breite: f64 = groesse.width.into();
+        // 1,5 mm Luft über der Linie, damit der Abstand dem früheren Unterstrich-Text entspricht
+        let hoehe = 1.5 + self.staerke;
+        let verfuegbar: f64 = groesse.height.into();
+        if verfuegbar < hoehe {
+            return Ok(genpdf::RenderResult {
+                size: genpdf::Size::new(0, 0),
+                has_more: true,
+            });
+        }
+        // genpdf 0.2 kennt keine Linienstärke: parallele Linien im Abstand von 0,1 mm
+        let stil = genpdf::style::Style::new().with_color(self.farbe);
+        let linien = (self.staerke / 0.1).round().max(1.0) as usize;
+        for n in 0..linien {
+            let y = 1.5 + 0.05 + n as f64 * 0.1;
+            area.draw_line(vec![genpdf::Position::new(0, y), genpdf::Position::new(breite, y)], stil);
+        }
+        Ok(genpdf::RenderResult {
+            size: genpdf::Size::new(breite, hoehe),
+            has_more: false,
+        })
+    }
+}
+
+/// Hält ein Element auf einer Seite zusammen: Passt es voraussichtlich nicht mehr
+/// in den verbleibenden Platz, wird es einmalig vollständig auf die nächste Seite
+/// verschoben. Die Höhe wird vorab geschätzt, da genpdf kein Vorab-Messen erlaubt.
+struct Zusammenhalten<E: genpdf::Element> {
+    /// Das eingebettete genpdf-Element.
+    inhalt: E,
+    /// Geschätzte Höhe des Elements in mm.
+    geschaetzte_hoehe: f64,
+    /// `true`, sobald das Element bereits einmal verschoben wurde (verhindert Endlosschleifen,
+    /// wenn es auch auf einer leeren Seite nicht passt).
+    verschoben: bool,
+}
+
+impl<E: genpdf::Element> Zusammenhalten<E> {
+    /// Erstellt den Wrapper mit der geschätzten Elementhöhe in mm.
+    fn new(inhalt: E, geschaetzte_hoehe: f64) -> Self {
+        Self {
+            inhalt,
+            geschaetzte_hoehe,
+            verschoben: false,
+        }
+    }
+}
+
+impl<E: genpdf::Element> genpdf::Element for Zusammenhalten<E> {
+    fn render(
+        &mut self,
+        context: &genpdf::Context,
+        area: genpdf::render::Area<'_>,
+        stil: genpdf::style::Style,
+    ) -> Result<genpdf::RenderResult, genpdf::error::Error> {
+        let verfuegbar: f64 = area.size().height.into();
+        // Auf einer (nahezu) leeren A4-Seite stehen ca. 257 mm zur Verfügung – dort nie verschieben
+        let seite_begonnen = verfuegbar < 250.0;
+        if !self.verschoben && seite_begonnen && verfuegbar < self.geschaetzte_hoehe {
+            self.verschoben = true;
+            // Nichts rendern und Fortsetzung anfordern → genpdf beginnt eine neue Seite
+            return Ok(genpdf::RenderResult {
+                size: genpdf::Size::new(0, 0),
+                has_more: true,
+            });
+        }
+        self.verschoben = true;
+        self.inhalt.render(context, area, stil)
+    }
+}
+
+// -- UI-Helfer --
+
+/// Rendert eine einzelne Personenzeile (Name + Kürzel in eckigen Klammern + optionaler Lösch-Button).
+/// Gibt `(wurde_gelöscht, Enter_gedrückt)` zurück, damit der Aufrufer reagieren kann.
+fn personen_zeile(
+    ui: &mut egui::Ui,
+    person: &mut Person,
+    show_delete: bool,
+    request_focus: bool,
+    text_color: Option<egui::Color32>,
+    einstellungen: &Einstellungen,
+) -> (bool, bool) {
+    let mut deleted = false;
+    let mut enter_pressed = false;
+    ui.horizontal(|ui| {
+        let available = ui.available_width();
+        // Kürzel mit Organisations-Präfix (EXT-AB) brauchen mehr Platz
+        let kuerzel_w = if einstellungen.kuerzel_praefixe.is_empty() { 45.0 } else { 60.0 };
+        let bracket_space = 50.0; // [ ] und Spacing
+        let delete_space = 28.0; // immer Platz reservieren
+        let name_w = (available - kuerzel_w - bracket_space - delete_space).max(100.0);
+
+        let mut name_edit = egui::TextEdit::singleline(&mut person.name)
+            .hint_text(RichText::new("Name").font(egui::FontId::proportional(14.0)))
+            .desired_width(name_w)
+            .font(fette_schrift(14.0));
+        if let Some(c) = text_color {
+            name_edit = name_edit.text_color(c);
+        }
+        let name_r = ui.add(name_edit);
+        if request_focus {
+            name_r.request_focus();
+        }
+        if name_r.changed() {
+            if person.name == "mz" {
+                person.name = "Marcel Zimmer".to_string();
+                // Cursor ans Ende setzen
+                if let Some(mut state) = egui::TextEdit::load_state(ui.ctx(), name_r.id) {
+                    let end = egui::text::CCursor::new(person.name.len());
+                    state.cursor.set_char_range(Some(egui::text::CCursorRange::one(end)));
+                    state.store(ui.ctx(), name_r.id);
+                }
+            }
+            if !person.kuerzel_manuell {
+                person.kuerzel = einstellungen.kuerzel_erzeugen(&person.name, &person.kuerzel);
+            }
+        }
+
+        ui.label("[");
+        let praefixe = &einstellungen.kuerzel_praefixe;
+        let mut k_layouter = |ui: &egui::Ui, text: &str, _wrap_width: f32| {
+            let farbe = text_color.unwrap_or(ui.visuals().text_color());
+            let job = kuerzel_layout_job(text, fette_schrift(14.0), farbe, ui.visuals().weak_text_color(), praefixe);
+            ui.fonts(|f| f.layout_job(job))
+        };
+        let k_praefix = einstellungen.kuerzel_praefix(&person.kuerzel).is_some();
+        let mut k_edit = egui::TextEdit::singleline(&mut person.kuerzel)
+            .desired_width(kuerzel_w)
+            .hint_text(RichText::new("Kürzel").font(egui::FontId::proportional(14.0)))
+            .horizontal_align(egui::Align::Center)
+            .font(fette_schrift(14.0));
+        if let Some(c) = text_color {
+            k_edit = k_edit.text_color(c);
+        }
+        if k_praefix {
+            k_edit = k_edit.layouter(&mut k_layouter);
+        }
+        let k_r = ui.add(k_edit);
+        if k_r.changed() {
+            person.kuerzel_manuell = !person.kuerzel.is_empty();
+        }
+        // Rechtsklick: Organisations-Präfix wählen, die Initialen bleiben erhalten
+        if !praefixe.is_empty() {
+            k_r.context_menu(|ui| {
+                let aktuell = einstellungen.kuerzel_praefix(&person.kuerzel).map(str::to_string);
+                let kern = match &aktuell {
+                    Some(praefix) => person.kuerzel.trim()[praefix.len() + 1..].to_string(),
+                    None if person.kuerzel.trim().is_empty() => kuerzel_nach_schema(&person.name, &einstellungen.kuerzel_schema),
+                    None => person.kuerzel.trim().to_string(),
+                };
+                if ui.selectable_label(aktuell.is_none(), "Ohne Präfix").clicked() {
+                    person.kuerzel = kern.clone();
+                    ui.close_menu();
+                }
+                for (praefix, beschreibung) in praefixe {
+                    let label = if beschreibung.is_empty() { praefix.clone() } else { format!("{} – {}", praefix, beschreibung) };
+                    if ui.selectable_label(aktuell.as_deref() == Some(praefix.as_str()), label).clicked() {
+                        person.kuerzel = format!("{}-{}", praefix, kern);
+                        ui.close_menu();
+                    }
+                }
+            });
+        }
+        ui.label("]");
+
+        if show_delete {
+            if ui
+                .add(
+                    egui::Button::new(
+                        RichText::new("×").color(egui::Color32::from_rgb(231, 76, 60)),
+                    )
+                    .small(),
+                )
+                .clicked()
+            {
+                deleted = true;
+            }
+        } else {
+            ui.allocate_space(egui::vec2(20.0, 0.0));
+        }
+
+        enter_pressed = (name_r.lost_focus() || k_r.lost_focus())
+            && ui.input(|i| i.key_pressed(egui::Key::Enter));
+    });
+    (deleted, enter_pressed)
+}
+
+/// Rendert eine linksbündige, fette Abschnittsüberschrift mit fixer Mindestbreite.
+/// Optionale `farbe` überschreibt die Theme-Standardfarbe (für Omarchy-Theme).
+fn abschnitts_beschriftung(ui: &mut egui::Ui, text: &str, label_w: f32, color: Option<egui::Color32>) {
+    ui.allocate_ui_with_layout(
+        egui::vec2(label_w, ui.spacing().interact_size.y),
+        egui::Layout::left_to_right(egui::Align::Center),
+        |ui| {
+            ui.set_min_width(label_w);
+            let mut rt = RichText::new(text).font(fette_schrift(14.0));
+            if let Some(c) = color { rt = rt.color(c); }
+            ui.label(rt);
+        },
+    );
+}
+
+/// Zeigt eine Zeile einer Hilfeseite an (vereinfachtes Markdown:
+/// `#`/`##`-Überschriften, `- `-Aufzählungen, sonst Fließtext).
+fn hilfe_zeile_anzeigen(ui: &mut egui::Ui, zeile: &str) {
+    if let Some(titel) = zeile.strip_prefix("# ") {
+        ui.label(RichText::new(titel).font(fette_schrift(20.0)));
+        ui.add_space(6.0);
+    } else if let Some(titel) = zeile.strip_prefix("## ") {
+        ui.add_space(8.0);
+        ui.label(RichText::new(titel).font(fette_schrift(15.0)));
+    } else if let Some(punkt) = zeile.strip_prefix("- ") {
+        ui.label(format!("•  {}", punkt));
+    } else if zeile.trim().is_empty() {
+        ui.add_space(4.0);
+    } else {
+        ui.label(zeile.trim());
+    }
+}
+
+/// Zeichnet die Eingaben des Filters „fällig zwischen" mit Schnellauswahl und
+/// Zurücksetzen; ungültige Daten werden rot markiert.
+fn faellig_filter_anzeigen(ui: &mut egui::Ui, filter: &mut FaelligFilter) {
+    ui.label(RichText::new("Fällig zwischen").size(12.0));
+    for text in [&mut filter.von, &mut filter.bis] {
+        let ungueltig = !text.trim().is_empty() && datum_aus_text(text).is_none();
+        let mut feld = egui::TextEdit::singleline(text).hint_text("TT.MM.JJJJ").desired_width(80.0);
+        if ungueltig {
+            feld = feld.text_color(egui::Color32::from_rgb(231, 76, 60));
+        }
+        ui.add(feld);
+    }
+    if ui.small_button("2 Wochen").on_hover_text("Von heute bis in 14 Tagen").clicked() {
+        filter.naechste_tage(14);
+    }
+    if filter.aktiv() && ui.small_button("✕").on_hover_text("Filter aufheben").clicked() {
+        *filter = FaelligFilter::default();
+    }
+}
+
+/// Zeichnet ein anklickbares Badge (Rahmen und Text in `farbe`); `aktiv` hebt es hervor.
+fn badge_anzeigen(ui: &mut egui::Ui, text: &str, farbe: egui::Color32, aktiv: bool) -> egui::Response {
+    egui::Frame::new()
+        .fill(farbe.gamma_multiply(if aktiv { 0.35 } else { 0.15 }))
+        .stroke(egui::Stroke::new(if aktiv { 2.0 } else { 1.0 }, farbe))
+        .corner_radius(10.0)
+        .inner_margin(egui::Margin::symmetric(8, 2))
+        .show(ui, |ui| {
+            ui.label(RichText::new(text).size(12.0).color(farbe));
+        })
+        .response
+        .interact(egui::Sense::click())
+        .on_hover_cursor(egui::CursorIcon::PointingHand)
+}
+
+/// Wie `abschnitts_beschriftung`, zeigt aber zusätzlich einen kleinen „+"-Button an.
+/// Gibt `true` zurück, wenn der Button geklickt wurde (zum Hinzufügen einer weiteren Zeile).
+fn abschnitts_beschriftung_mit_plus(ui: &mut egui::Ui, text: &str, label_w: f32, color: Option<egui::Color32>) -> bool {
+    let mut clicked = false;
+    ui.allocate_ui_with_layout(
+        egui::vec2(label_w, ui.spacing().interact_size.y),
+        egui::Layout::left_to_right(egui::Align::Center),
+        |ui| {
+            ui.set_min_width(label_w);
+            let mut rt = RichText::new(text).font(fette_schrift(14.0));
+            if let Some(c) = color { rt = rt.color(c); }
+            ui.label(rt);
+            if ui.small_button("+").clicked() {
+                clicked = true;
+            }
+        },
+    );
+    clicked
+}
+
+/// Passt die globalen Abstände an den Touch-Modus an: Schaltflächen und Eingabefelder
+/// werden mindestens 40 px hoch, damit sie sich mit dem Finger treffen lassen.
+/// Ohne Touch-Modus gelten die egui-Standardabstände.
+fn touch_stil_anwenden(ctx: &egui::Context, aktiv: bool) {
+    ctx.style_mut(|stil| {
+        stil.spacing = egui::style::Spacing::default();
+        if aktiv {
+            stil.spacing.interact_size = egui::vec2(48.0, 40.0);
+            stil.spacing.button_padding = egui::vec2(12.0, 8.0);
+            stil.spacing.item_spacing = egui::vec2(10.0, 10.0);
+            stil.spacing.icon_width = 24.0;
+            stil.spacing.icon_width_inner = 14.0;
+            stil.spacing.scroll.bar_width = 16.0;
+        }
+    });
+}
+
+// -- UI --
+
+impl eframe::App for ProtokollApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Event-Loop periodisch wecken für Wayland-Pings
+        // (vsync: false in NativeOptions verhindert das Blockieren von eglSwapBuffers)
+        ctx.request_repaint_after(std::time::Duration::from_secs(1));
+
+        // Tastenkombinationen
+        if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::N)) {
+            self.ersetzen_anfragen(ctx, Ersetzen::Neu);
+        }
+        if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::O)) {
+            self.oeffnen_dialog_oeffnen();
+        }
+        if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::S)) {
+            self.speichern();
+        }
+        if ctx.input(|i| i.modifiers.ctrl && i.modifiers.shift && i.key_pressed(egui::Key::P)) {
+            self.schnell_exportieren();
+        } else if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::P)) {
+            self.pdf_exportieren();
+        }
+        if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::E)) {
+            self.xlsx_exportieren();
+        }
+        if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::W)) {
+            self.show_quit_dialog = true;
+        }
+        // Fenster schließen: bei ungespeicherten Änderungen erst den Beenden-Dialog zeigen
+        if ctx.input(|i| i.viewport().close_requested()) && !self.beenden_bestaetigt && self.ungespeichert() {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.show_quit_dialog = true;
+        }
+        if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::T)) {
+            self.theme = self.theme.next(self.has_omarchy);
+        }
+        if ctx.input(|i| i.key_pressed(egui::Key::F1) || (i.modifiers.ctrl && i.key_pressed(egui::Key::H))) {
+            self.hilfe_oeffnen();
+        }
+        if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::I)) {
+            self.show_about_dialog = true;
+        }
+        if ctx.input(|i| i.key_pressed(egui::Key::F5)) {
+            self.praesentation_umschalten(ctx);
+        }
+        self.eintrag_ablage_tasten(ctx);
+        self.kopfdaten_tasten(ctx);
+
+        // Ergebnisse von Datei-Dialogen verarbeiten
+        if let Some(ref rx) = self.dialog_rx {
+            match rx.try_recv() {
+                Ok(result) => {
+                    match result {
+                        DialogErgebnis::Laden(path, content) => {
+                            self.datei_uebernehmen(path, &content);
+                        }
+                        DialogErgebnis::Speichern(path) => {
+                            self.save_path = Some(path);
+                            self.stand_merken();
+                            self.entwurf_entfernen();
+                            if self.beenden_nach_dialog {
+                                self.beenden(ctx);
+                            } else if let Some(aktion) = self.ersetzen_nach_speichern.take() {
+                                self.ersetzen_ausfuehren(ctx, aktion);
+                            }
+                        }
+                        DialogErgebnis::SpeichernFehlgeschlagen(fehler) => {
+                            self.beenden_nach_dialog = false;
+                            self.ersetzen_nach_speichern = None;
+                            self.toast_zeigen(format!("Speichern fehlgeschlagen: {}", fehler));
+                        }
+                        DialogErgebnis::PdfExport(path) => {
+                            if let Some(font) = self.pending_pdf_font.take() {
+                                self.exportprofil_aktiv = self.pending_exportprofil.take();
+                                let export = self.pdf_export_vorbereiten(&path, font);
+                                self.exportprofil_aktiv = None;
+                                self.pdf_passwort.clear();
+                                match export {
+                                    Ok(export) => self.pdf_im_hintergrund(export, PdfDanach::Export, || {}),
+                                    Err(fehler) => self.toast_zeigen(format!("PDF-Export fehlgeschlagen: {}", fehler)),
+                                }
+                            }
+                        }
+                        DialogErgebnis::XlsxExport(path) => {
+                            if let Err(fehler) = self.xlsx_generieren(&path) {
+                                self.toast_zeigen(format!("XLSX-Export fehlgeschlagen: {}", fehler));
+                            }
+                        }
+                        DialogErgebnis::TypstExport(path) => self.typst_generieren(&path),
+                        DialogErgebnis::EmailImport(text) => {
+                            let nachrichten = email_verlauf_parsen(&text);
+                            if nachrichten.iter().all(|n| n.absaetze.is_empty()) {
+                                self.toast_zeigen("In der Datei wurde keine E-Mail-Nachricht erkannt".to_string());
+                            } else {
+                                self.email_import = Some(email_import_vorschlagen(&nachrichten, &self.einstellungen));
+                            }
+                        }
+                        DialogErgebnis::VorlageGespeichert => {}
+                        DialogErgebnis::Vorgaenger(path) => {
+                            self.vorgaenger = path.to_string_lossy().to_string();
+                        }
+                        DialogErgebnis::IcsExport => {}
+                        DialogErgebnis::Anwesenheitsliste(path) => {
+                            if let Some(font) = self.pending_pdf_font.take() {
+                                if !self.anwesenheitsliste_generieren(&path, font) {
+                                    self.toast_zeigen(format!("PDF-Export fehlgeschlagen: {}", path.display()));
+                                }
+                            }
+                        }
+                        DialogErgebnis::Bericht(path) => {
+                            if let Some(bericht) = self.pending_bericht.take() {
+                                let ok = if path.extension().is_some_and(|e| e == "pdf") {
+                                    self.pending_pdf_font
+                                        .take()
+                                        .is_some_and(|font| self.bericht_pdf_generieren(&bericht, &path, font))
+                                } else {
+                                    std::fs::write(&path, bericht.markdown()).is_ok()
+                                };
+                                if !ok {
+                                    self.toast_zeigen(format!("Bericht konnte nicht geschrieben werden: {}", path.display()));
+                                }
+                            }
+                        }
+                        DialogErgebnis::Nachtrag(path) => {
+                            if let Some(font) = self.pending_pdf_font.take() {
+                                if self.nachtrag_generieren(&path, font) {
+                                    let datei = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                                    self.audit_protokollieren(&format!("Nachtrag exportiert: {}", datei));
+                                } else {
+                                    self.toast_zeigen(format!("PDF-Export fehlgeschlagen: {}", path.display()));
+                                }
+                            }
+                        }
+                    }
+                    self.dialog_rx = None;
+                }
+                // Dialog ohne Auswahl geschlossen
+                Err(mpsc::TryRecvError::Disconnected) => self.dialog_rx = None,
+                Err(mpsc::TryRecvError::Empty) => {}
+            }
+        }
+        // Im Hintergrund erzeugte PDFs melden
+        while let Ok(fertig) = self.pdf_rx.try_recv() {
+            self.pdf_fertig(ctx, fertig);
+        }
+        if self.pdf_laufend > 0 {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
+        // „… und beenden" abgebrochen: kein Dialog mehr offen, nichts geschrieben
+        if self.beenden_nach_dialog
+            && !self.beenden_bestaetigt
+            && self.dialog_rx.is_none()
+            && !self.show_pdf_export_dialog
+            && self.pdf_laufend == 0
+        {
+            self.beenden_nach_dialog = false;
+            self.toast_zeigen("Beenden abgebrochen".to_string());
+        }
+        // Speichern-Dialog vor Neu bzw. Überführen ohne Auswahl geschlossen
+        if self.ersetzen_nach_speichern.is_some() && self.dialog_rx.is_none() {
+            self.ersetzen_nach_speichern = None;
+        }
+
+        self.externe_aenderung_pruefen();
+        self.auto_speichern_pruefen();
+        if self.save_path.is_some() {
+            ctx.request_repaint_after(std::time::Duration::from_secs(2));
+        }
+
+        // Aufträge weiterer Programmstarts (Einzelinstanz)
+        if let Some(auftrag) = self.instanz_rx.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            self.instanz_auftrag_ausfuehren(ctx, auftrag);
+        }
+
+        // Ergebnis der Aufgaben-Synchronisation übernehmen
+        if let Some(ref rx) = self.aufgaben_rx {
+            if let Ok(ergebnis) = rx.try_recv() {
+                self.aufgaben_rx = None;
+                match ergebnis {
+                    Ok(ergebnis) => {
+                        for e in self.eintraege.iter_mut().filter(|e| e.art == Art::Todo && ergebnis.erledigt.contains(&e.id)) {
+                            e.art = Art::Fertig;
+                        }
+                        let mut teile = Vec::new();
+                        if ergebnis.hochgeladen > 0 {
+                            teile.push(format!("{} Aufgaben übertragen", ergebnis.hochgeladen));
+                        }
+                        if !ergebnis.erledigt.is_empty() {
+                            teile.push(format!("{} als erledigt übernommen", ergebnis.erledigt.len()));
+                        }
+                        if !teile.is_empty() {
+                            self.toast_zeigen(teile.join(", "));
+                        }
+                    }
+                    Err(fehler) => self.toast_zeigen(fehler),
+                }
+            }
+        }
+        if let Some(ref rx) = self.zusammenfassung_rx {
+            if let Ok(ergebnis) = rx.try_recv() {
+                self.zusammenfassung_rx = None;
+                match ergebnis {
+                    Ok(text) => self.zusammenfassung_vorschlag = Some(text),
+                    Err(fehler) => {
+                        // Ohne Sprachmodell wenigstens den regelbasierten Vorschlag anbieten
+                        self.toast_zeigen(fehler);
+                        self.zusammenfassung_vorschlag = Some(self.zusammenfassung_regelbasiert());
+                    }
+                }
+            }
+        }
+        if let Some(ergebnis) = self.redmine_rx.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            self.redmine_rx = None;
+            match ergebnis {
+                RedmineErgebnis::Angelegt { eintrag, ticket, status } => {
+                    if let Some(e) = self.eintraege.iter_mut().find(|e| e.id == eintrag) {
+                        e.notiz = format!("{} #{}", e.notiz.trim_end(), ticket);
+                    }
+                    self.redmine_status.extend(status);
+                    self.toast_zeigen(format!("Redmine-Ticket #{} angelegt", ticket));
+                }
+                RedmineErgebnis::Status { status, manuell } => {
+                    if manuell {
+                        self.toast_zeigen(format!("Status von {} Redmine-Tickets aktualisiert", status.len()));
+                    }
+                    self.redmine_status.extend(status);
+                }
+                RedmineErgebnis::Fehler(fehler) => self.toast_zeigen(fehler),
+            }
+        }
+        if let Some(ref rx) = self.matrix_rx {
+            if let Ok(ergebnis) = rx.try_recv() {
+                self.matrix_rx = None;
+                match ergebnis {
+                    Ok(()) => self.toast_zeigen("Zusammenfassung in Matrix gepostet".to_string()),
+                    Err(fehler) => self.toast_zeigen(fehler),
+                }
+            }
+        }
+
+        // Zeiterfassung: Abstände zwischen Eingaben zählen nur, solange das Fenster
+        // fokussiert ist und die Pause kürzer als LEERLAUF_GRENZE war
+        if ctx.input(|i| i.focused && !i.events.is_empty()) {
+            let jetzt = std::time::Instant::now();
+            if let Some(letzte) = self.letzte_aktivitaet {
+                let pause = jetzt.duration_since(letzte);
+                if pause <= LEERLAUF_GRENZE {
+                    self.bearbeitungszeit += pause;
+                }
+            }
+            self.letzte_aktivitaet = Some(jetzt);
+        }
+
+        // Scrollen je Eingabegerät: Mausräder melden Zeilen, Touchpads Punkte. Das zuletzt
+        // benutzte Gerät gilt weiter, weil geglättete Mausrad-Schritte über mehrere Frames
+        // ohne eigenes Ereignis auslaufen.
+        ctx.input(|i| {
+            for event in &i.events {
+                if let egui::Event::MouseWheel { unit, .. } = event {
+                    self.scroll_mausrad = *unit != egui::MouseWheelUnit::Point;
+                }
+            }
+        });
+        let scroll_faktor = if self.scroll_mausrad {
+            self.einstellungen.scroll_faktor_mausrad
+        } else {
+            self.einstellungen.scroll_faktor_touchpad
+        };
+        let scroll_glaetten = self.einstellungen.scroll_glaetten;
+        ctx.input_mut(|i| {
+            if !scroll_glaetten && !i.modifiers.command {
+                // Ohne Glättung die Bewegung dieses Frames direkt übernehmen
+                i.smooth_scroll_delta = i.raw_scroll_delta;
+            }
+            i.smooth_scroll_delta.y *= scroll_faktor;
+        });
+
+        self.input_text_color = None;
+        self.label_color = None;
+        self.tabellen_farben = TabellenFarben::default();
+        match self.theme {
+            Theme::Hell => ctx.set_visuals(egui::Visuals::light()),
+            Theme::Dunkel => {
+                let mut visuals = egui::Visuals::dark();
+                visuals.panel_fill = egui::Color32::BLACK;
+                visuals.window_fill = egui::Color32::BLACK;
+                visuals.extreme_bg_color = egui::Color32::BLACK;
+                let white_stroke = egui::Stroke::new(1.0, egui::Color32::WHITE);
+                visuals.widgets.noninteractive.fg_stroke = white_stroke;
+                visuals.widgets.inactive.fg_stroke = white_stroke;
+                visuals.widgets.hovered.fg_stroke = white_stroke;
+                visuals.widgets.active.fg_stroke = white_stroke;
+                ctx.set_visuals(visuals);
+            }
+            Theme::Omarchy => {
+                let mut visuals = egui::Visuals::dark();
+                if let Some(colors) = omarchy_farben_laden() {
+                    // Hintergrund voll deckend (wie Terminal)
+                    if let Some(bg) = colors.get("background") {
+                        visuals.panel_fill = *bg;
+                        visuals.window_fill = *bg;
+                        visuals.extreme_bg_color = *bg;
+                    }
+                    // Hints → cursor (über noninteractive, wird automatisch abgedunkelt)
+                    if let Some(cursor) = colors.get("cursor") {
+                        visuals.widgets.noninteractive.fg_stroke = egui::Stroke::new(1.0, *cursor);
+                    }
+                    // Buttons → accent
+                    if let Some(accent) = colors.get("accent") {
+                        let stroke = egui::Stroke::new(1.0, *accent);
+                        visuals.widgets.inactive.fg_stroke = stroke;
+                        visuals.widgets.hovered.fg_stroke = stroke;
                         visuals.widgets.active.fg_stroke = stroke;
                         visuals.selection.bg_fill = *accent;
                         visuals.hyperlink_color = *accent;
                         visuals.widgets.hovered.bg_fill = accent.linear_multiply(0.3);
                     }
-                    // Beschriftungen/Labels → color3
-                    if let Some(label) = colors.get("color3") {
-                        self.label_color = Some(*label);
+                    // Beschriftungen/Labels → color3
+                    if let Some(label) = colors.get("color3") {
+                        self.label_color = Some(*label);
+                    }
+                    // Eingegebener Text in Textfeldern → color2
+                    if let Some(text_color) = colors.get("color2") {
+                        self.input_text_color = Some(*text_color);
+                    }
+                    // Tabellenlinien, Zebra-Streifen und Art-Farben → Palette (kontrastgeprüft)
+                    self.tabellen_farben = TabellenFarben::aus_palette(&colors);
+                    if let Some(zebra) = self.tabellen_farben.zebra {
+                        visuals.faint_bg_color = zebra;
+                    }
+                } else {
+                    visuals.panel_fill = egui::Color32::from_rgb(30, 30, 30);
+                    visuals.window_fill = egui::Color32::from_rgb(30, 30, 30);
+                }
+                ctx.set_visuals(visuals);
+            }
+        }
+
+        self.eintrag_ids_vergeben();
+
+        // Art-Beschriftungen und -Farben des aktuellen Projekts
+        self.tabellen_farben.anpassen(&self.einstellungen, &self.projekt);
+        self.art_labels = Art::all().iter().map(|a| self.art_label(a)).collect();
+
+        // Präsentationsmodus ersetzt die gesamte Bearbeitungsansicht
+        if self.praesentation.is_some() {
+            self.praesentation_anzeigen(ctx);
+            return;
+        }
+
+        let alle_kuerzel = self.alle_kuerzel();
+        // Sprecherauswahl für Wortbeiträge: (Kürzel bzw. Name, Anzeigename)
+        let sprecher_auswahl: Vec<(String, String)> = std::iter::once(&self.protokollant)
+            .chain(self.teilnehmer.iter())
+            .filter(|p| !p.name.trim().is_empty())
+            .map(|p| {
+                if p.kuerzel.is_empty() {
+                    (p.name.clone(), p.name.clone())
+                } else {
+                    (p.kuerzel.clone(), format!("{} [{}]", p.name, p.kuerzel))
+                }
+            })
+            .collect();
+        // Feste Breite der linksseitigen Abschnittsbezeichnungen (in Pixeln)
+        let beschriftungs_breite = 160.0;
+
+        let panel_frame = egui::Frame::central_panel(&ctx.style())
+            .inner_margin(egui::Margin::same(10));
+        egui::CentralPanel::default().frame(panel_frame).show(ctx, |ui| {
+            // Toolbar oben rechts: Beenden-Button + Hamburger-Menü
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Min), |ui| {
+                ui.spacing_mut().item_spacing.x = 6.0;
+
+                if ui.button(RichText::new("X").size(14.0)).on_hover_text("Beenden (Strg+W)").clicked() {
+                    self.show_quit_dialog = true;
+                }
+
+                let menu_items: &[(&str, &str, i32)] = &[
+                    ("Neu", "Strg+N", 0),
+                    ("Neu mit Assistent", "", 0),
+                    ("Neu aus Vorlage", "", 0),
+                    ("Folgeprotokoll anlegen", "", 0),
+                    ("In neues Protokoll überführen", "", 0),
+                    ("Aus E-Mail importieren", "", 0),
+                    ("Öffnen", "Strg+O", 0),
+                    ("Neu laden", "", 0),
+                    ("Sicherung wiederherstellen", "", 0),
+                    ("Speichern", "Strg+S", 0),
+                    ("Als Entwurf zwischenspeichern", "", 0),
+                    ("Entwürfe öffnen", "", 0),
+                    ("Als Vorlage speichern", "", 0),
+                    ("PDF erzeugen", "Strg+P", 0),
+                    ("Schnell exportieren", "Strg+Shift+P", 0),
+                    ("Tabelle exportieren", "Strg+E", 0),
+                    ("Typst exportieren", "", 0),
+                    ("Anwesenheitsliste", "", 0),
+                    ("Nachtrag exportieren", "", 0),
+                    ("An Gerät senden", "", 0),
+                    ("Einladung exportieren", "", 0),
+                    ("In Kalender eintragen", "", 0),
+                    ("Aufgaben synchronisieren", "", 0),
+                    ("Redmine-Status aktualisieren", "", 0),
+                    ("Per E-Mail versenden", "", 0),
+                    ("Versandfertig prüfen", "", 0),
+                    ("Freigabe-Pipeline", "", 0),
+                    ("Archivieren", "", 0),
+                    ("", "", 1), // separator
+                    ("Vorgänger verknüpfen", "", 0),
+                    ("Offene TODOs übernehmen", "", 0),
+                    ("Kopfdaten kopieren", "", 0),
+                    ("Kopfdaten einfügen", "", 0),
+                    ("Kümmerer prüfen", "", 0),
+                    ("Beschlussregister", "", 0),
+                    ("Meine Aufgaben", "", 0),
+                    ("Bericht erstellen", "", 0),
+                    ("Statistik", "", 0),
+                    ("Präsentation", "F5", 0),
+                    ("", "", 1), // separator
+                    ("Benutzerprofil", "", 0),
+                    ("Profil wechseln", "", 0),
+                    ("Zugangsdaten", "", 0),
+                    ("Theme ändern", "Strg+T", 0),
+                    ("Touch-Modus", "", 0),
+                    ("", "", 1), // separator
+                    ("Hilfe", "F1", 0),
+                    ("Über", "Strg+I", 0),
+                ];
+                egui::menu::menu_button(ui, RichText::new("☰").size(14.0), |ui| {
+                    ui.set_width(200.0);
+                    for &(label, shortcut, is_sep) in menu_items {
+                        if is_sep == 1 {
+                            ui.separator();
+                            continue;
+                        }
+                        let w = ui.available_width();
+                        let (rect, response) = ui.allocate_exact_size(
+                            egui::vec2(w, 24.0),
+                            egui::Sense::click(),
+                        );
+                        if ui.is_rect_visible(rect) {
+                            // Hover-Highlight
+                            if response.hovered() {
+                                ui.painter().rect_filled(rect, 4.0, ui.visuals().widgets.hovered.bg_fill);
+                            }
+                            // Label links
+                            ui.painter().text(
+                                rect.left_center() + egui::vec2(8.0, 0.0),
+                                egui::Align2::LEFT_CENTER,
+                                label,
+                                egui::FontId::proportional(13.0),
+                                ui.visuals().text_color(),
+                            );
+                            // Shortcut rechts
+                            if !shortcut.is_empty() {
+                                ui.painter().text(
+                                    rect.right_center() - egui::vec2(8.0, 0.0),
+                                    egui::Align2::RIGHT_CENTER,
+                                    shortcut,
+                                    egui::FontId::proportional(12.0),
+                                    ui.visuals().weak_text_color(),
+                                );
+                            }
+                        }
+                        let clicked = response.clicked();
+                        if clicked {
+                            match label {
+                                "Neu" => self.ersetzen_anfragen(ctx, Ersetzen::Neu),
+                                "Neu mit Assistent" => self.assistent = Some(ErsteSchritteAssistent::new()),
+                                "Neu aus Vorlage" => self.vorlagen_auswahl = Some(vorlagen_auflisten()),
+                                "Folgeprotokoll anlegen" => self.folgeprotokoll_anlegen(ctx),
+                                "In neues Protokoll überführen" => self.ueberfuehrung = Some(self.ueberfuehrung_vorschlagen()),
+                                "Aus E-Mail importieren" => self.email_importieren(),
+                                "Einladung exportieren" => self.ics_exportieren(),
+                                "In Kalender eintragen" => self.kalender_eintragen(),
+                                "Aufgaben synchronisieren" => self.aufgaben_synchronisieren(true),
+                                "Redmine-Status aktualisieren" => self.redmine_status_aktualisieren(true),
+                                "Kümmerer prüfen" => self.kuerzel_pruefung_oeffnen(),
+                                "Per E-Mail versenden" => self.per_email_versenden(),
+                                "Archivieren" => self.archivieren(),
+                                "Als Vorlage speichern" => self.als_vorlage_speichern(),
+                                "Öffnen" => self.oeffnen_dialog_oeffnen(),
+                                "Neu laden" => self.datei_abgleichen(true),
+                                "Sicherung wiederherstellen" => self.sicherung_wiederherstellen(),
+                                "Speichern" => self.speichern(),
+                                "Als Entwurf zwischenspeichern" => self.entwurf_zwischenspeichern(),
+                                "Entwürfe öffnen" => self.entwuerfe_oeffnen(),
+                                "PDF erzeugen" => self.pdf_exportieren(),
+                                "Schnell exportieren" => self.schnell_exportieren(),
+                                "Tabelle exportieren" => self.xlsx_exportieren(),
+                                "Typst exportieren" => self.typst_exportieren(),
+                                "Anwesenheitsliste" => self.anwesenheitsliste_exportieren(),
+                                "Nachtrag exportieren" => self.nachtrag_exportieren(),
+                                "An Gerät senden" => self.druck_dialog_oeffnen(),
+                                "Versandfertig prüfen" => self.versand_checkliste_oeffnen(),
+                                "Freigabe-Pipeline" => self.freigabe_pipeline_starten(),
+                                "Vorgänger verknüpfen" => self.vorgaenger_verknuepfen(),
+                                "Offene TODOs übernehmen" => self.offene_todos_uebernehmen(),
+                                "Kopfdaten kopieren" => self.kopfdaten_kopieren(ctx),
+                                "Kopfdaten einfügen" => self.kopfdaten_einfuegen(ctx),
+                                "Statistik" => self.show_statistik = true,
+                                "Präsentation" => self.praesentation_umschalten(ctx),
+                                "Meine Aufgaben" => self.archiv_aufgaben_oeffnen(),
+                                "Bericht erstellen" => self.bericht_dialog_oeffnen(),
+                                "Beschlussregister" => {
+                                    self.beschlussregister = beschlussregister_laden();
+                                    self.show_beschlussregister = true;
+                                }
+                                "Theme ändern" => self.theme = self.theme.next(self.has_omarchy),
+                                "Benutzerprofil" => self.profil_dialog = Some(self.profil.clone()),
+                                "Profil wechseln" => {
+                                    self.profil_wechsel = Some(ProfilWechsel { profile: profile_auflisten(), neu: String::new() });
+                                }
+                                "Zugangsdaten" => {
+                                    // Schlüsselbund-Abfragen können dauern: Konto für Konto im Hintergrund prüfen
+                                    let (tx, rx) = mpsc::channel();
+                                    std::thread::spawn(move || {
+                                        for (i, (konto, _)) in ZUGANGSDATEN_KONTEN.iter().enumerate() {
+                                            if tx.send((i, zugangsdaten_hinterlegt(konto))).is_err() {
+                                                break;
+                                            }
+                                        }
+                                    });
+                                    self.zugangsdaten_dialog = Some(ZugangsdatenDialog {
+                                        eingaben: vec![String::new(); ZUGANGSDATEN_KONTEN.len()],
+                                        hinterlegt: vec![None; ZUGANGSDATEN_KONTEN.len()],
+                                        pruefung_rx: Some(rx),
+                                        status: None,
+                                    });
+                                }
+                                "Touch-Modus" => {
+                                    self.touch_modus = !self.touch_modus;
+                                    touch_stil_anwenden(ctx, self.touch_modus);
+                                }
+                                "Hilfe" => self.hilfe_oeffnen(),
+                                "Über" => self.show_about_dialog = true,
+                                _ => {}
+                            }
+                            ui.close_menu();
+                        }
+                    }
+                });
+                if !self.konfig_profil.is_empty() {
+                    ui.label(RichText::new(&self.konfig_profil).size(12.0).weak())
+                        .on_hover_text("Aktives Profil");
+                }
+            });
+
+            // Kurzreferenz auf die aktuellen Theme-Farben (für Textfelder und Labels)
+            let textfarbe = self.input_text_color;
+
+            // Header-Bereich (fixiert, scrollt nicht mit)
+            {
+                // 11: Projekt
+                let mut projekt_edit = egui::TextEdit::singleline(&mut self.projekt)
+                    .hint_text(RichText::new("Projektname").font(egui::FontId::proportional(13.0)))
+                    .desired_width(400.0)
+                    .font(fette_schrift(13.0));
+                if let Some(c) = textfarbe { projekt_edit = projekt_edit.text_color(c); }
+                let projekt_r = ui.add(projekt_edit);
+                self.kopf_feld_fokus(ui, &projekt_r, KopfFeld::Projekt);
+
+                ui.add_space(4.0);
+
+                // Titel
+                let mut titel_edit = egui::TextEdit::singleline(&mut self.titel)
+                    .font(fette_schrift(28.0))
+                    .hint_text(RichText::new("Titel").font(egui::FontId::proportional(28.0)))
+                    .desired_width(ui.available_width());
+                if let Some(c) = textfarbe { titel_edit = titel_edit.text_color(c); }
+                let titel_r = ui.add(titel_edit);
+                self.kopf_feld_fokus(ui, &titel_r, KopfFeld::Titel);
+
+                // Schnellstatistik „Fällig diese Woche“; ein Klick setzt den Fälligkeitsfilter
+                let heute = Local::now().date_naive();
+                let woche = FaelligFilter::diese_woche();
+                let sonntag = datum_aus_text(&woche.bis).unwrap_or(heute);
+                let (mut diese_woche, mut ueberfaellig) = (0, 0);
+                for datum in self.eintraege.iter().filter(|e| e.art == Art::Todo).filter_map(|e| datum_aus_text(&e.bis)) {
+                    if datum < heute {
+                        ueberfaellig += 1;
+                    } else if datum <= sonntag {
+                        diese_woche += 1;
+                    }
+                }
+                if diese_woche + ueberfaellig > 0 {
+                    ui.add_space(2.0);
+                    ui.horizontal(|ui| {
+                        let todos = if diese_woche == 1 { "TODO" } else { "TODOs" };
+                        let badges = [
+                            (
+                                diese_woche,
+                                format!("{} {} diese Woche fällig", diese_woche, todos),
+                                egui::Color32::from_rgb(230, 126, 34),
+                                woche,
+                            ),
+                            (
+                                ueberfaellig,
+                                format!("{} überfällig", ueberfaellig),
+                                egui::Color32::from_rgb(231, 76, 60),
+                                FaelligFilter::ueberfaellig(),
+                            ),
+                        ];
+                        for (anzahl, text, farbe, filter) in badges {
+                            if anzahl == 0 {
+                                continue;
+                            }
+                            let aktiv = self.faellig_filter == filter;
+                            let hinweis = if aktiv { "Filter aufheben" } else { "Nur diese Einträge anzeigen" };
+                            if badge_anzeigen(ui, &text, farbe, aktiv).on_hover_text(hinweis).clicked() {
+                                self.faellig_filter = if aktiv { FaelligFilter::default() } else { filter };
+                            }
+                        }
+                    });
+                }
+
+                ui.add_space(6.0);
+
+                // Datum + Ort
+                ui.horizontal(|ui| {
+                    let mut datum_edit = egui::TextEdit::singleline(&mut self.datum_text)
+                        .desired_width(250.0)
+                        .hint_text(RichText::new("Wochentag, TT.MM.JJJJ").font(egui::FontId::proportional(14.0)))
+                        .font(fette_schrift(14.0));
+                    if let Some(c) = textfarbe { datum_edit = datum_edit.text_color(c); }
+                    let datum_r = ui.add(datum_edit);
+                    self.kopf_feld_fokus(ui, &datum_r, KopfFeld::Datum);
+                    ui.label(RichText::new("|").size(15.0));
+                    let mut ort_edit = egui::TextEdit::singleline(&mut self.ort)
+                        .desired_width((ui.available_width() - 240.0).max(120.0))
+                        .hint_text(RichText::new("Ort").font(egui::FontId::proportional(14.0)))
+                        .font(fette_schrift(14.0));
+                    if let Some(c) = textfarbe { ort_edit = ort_edit.text_color(c); }
+                    let ort_r = ui.add(ort_edit);
+                    self.kopf_feld_fokus(ui, &ort_r, KopfFeld::Ort);
+                    ui.label(RichText::new("|").size(15.0));
+                    ui.label(RichText::new("Protokoll erstellt am").size(12.0).weak());
+                    let mut erstellt_edit = egui::TextEdit::singleline(&mut self.protokoll_datum)
+                        .desired_width(ui.available_width())
+                        .hint_text(RichText::new("TT.MM.JJJJ").font(egui::FontId::proportional(14.0)))
+                        .font(fette_schrift(14.0));
+                    if let Some(c) = textfarbe { erstellt_edit = erstellt_edit.text_color(c); }
+                    let erstellt_r = ui.add(erstellt_edit).on_hover_text(
+                        "Datum der Nachbereitung, falls abweichend vom Meetingtag (leer = beim ersten Speichern heute). Fälligkeiten beziehen sich auf das Meetingdatum.",
+                    );
+                    self.kopf_feld_fokus(ui, &erstellt_r, KopfFeld::ProtokollDatum);
+                });
+
+                // Verknüpftes Vorgängerprotokoll
+                if !self.vorgaenger.is_empty() {
+                    ui.horizontal(|ui| {
+                        let name = std::path::Path::new(&self.vorgaenger)
+                            .file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_else(|| self.vorgaenger.clone());
+                        ui.label(RichText::new(format!("Vorgänger: {}", name)).size(12.0).weak())
+                            .on_hover_text(&self.vorgaenger);
+                        if ui.small_button("×").on_hover_text("Verknüpfung entfernen").clicked() {
+                            self.vorgaenger.clear();
+                        }
+                    });
+                }
+
+                // Hinweis nach dem Laden: vorheriges Protokoll wurde gesichert
+                if self.sicherung.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new("Vorheriges Protokoll wurde gesichert.").size(12.0).weak());
+                        if ui.small_button("Rückgängig").clicked() {
+                            self.sicherung_wiederherstellen();
+                        }
+                        if ui.small_button("×").on_hover_text("Hinweis schließen").clicked() {
+                            self.sicherung = None;
+                        }
+                    });
+                }
+
+                ui.add_space(12.0);
+                ui.separator();
+                ui.add_space(8.0);
+            }
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                let beschriftungsfarbe = self.label_color;
+
+                // 12: Protokollführer (nebeneinander)
+                ui.horizontal_top(|ui| {
+                    abschnitts_beschriftung(ui, "Protokollführer", beschriftungs_breite,self.label_color);
+                    let fokus = self.kopf_fokus == Some(KopfFeld::Protokollfuehrer);
+                    if fokus {
+                        self.kopf_fokus = None;
+                    }
+                    personen_zeile(ui, &mut self.protokollant, false, fokus, self.input_text_color, &self.einstellungen);
+                });
+
+                ui.add_space(4.0);
+                ui.separator();
+                ui.add_space(4.0);
+
+                // 13: Teilnehmer (nebeneinander, Enter → neue Zeile)
+                let mut tn_add = false;
+                let mut tn_remove: Option<usize> = None;
+                ui.horizontal_top(|ui| {
+                    if abschnitts_beschriftung_mit_plus(ui, "Teilnehmer", beschriftungs_breite,self.label_color) {
+                        self.teilnehmer.push(Person::new());
+                    }
+                    let tn_len = self.teilnehmer.len();
+                    ui.vertical(|ui| {
+                        for i in 0..tn_len {
+                            let is_last = i == tn_len - 1;
+                            let focus = is_last && self.focus_new_teilnehmer;
+                            let (del, enter) =
+                                personen_zeile(ui, &mut self.teilnehmer[i], tn_len > 1, focus, self.input_text_color, &self.einstellungen);
+                            if focus {
+                                self.focus_new_teilnehmer = false;
+                            }
+                            if del {
+                                tn_remove = Some(i);
+                            }
+                            if enter {
+                                tn_add = true;
+                            }
+                        }
+                    });
+                });
+                if let Some(idx) = tn_remove {
+                    self.teilnehmer.remove(idx);
+                }
+                if tn_add {
+                    self.teilnehmer.push(Person::new());
+                    self.focus_new_teilnehmer = true;
+                }
+
+                ui.add_space(4.0);
+                ui.separator();
+                ui.add_space(4.0);
+
+                // 13: Zur Kenntnis (nebeneinander)
+                let mut zk_add = false;
+                let mut zk_remove: Option<usize> = None;
+                ui.horizontal_top(|ui| {
+                    if abschnitts_beschriftung_mit_plus(ui, "Zur Kenntnis", beschriftungs_breite,self.label_color) {
+                        self.zur_kenntnis.push(Person::new());
+                    }
+                    let zk_len = self.zur_kenntnis.len();
+                    ui.vertical(|ui| {
+                        for i in 0..zk_len {
+                            let is_last = i == zk_len - 1;
+                            let focus = is_last && self.focus_new_zur_kenntnis;
+                            let (del, enter) =
+                                personen_zeile(ui, &mut self.zur_kenntnis[i], zk_len > 1, focus, self.input_text_color, &self.einstellungen);
+                            if focus {
+                                self.focus_new_zur_kenntnis = false;
+                            }
+                            if del {
+                                zk_remove = Some(i);
+                            }
+                            if enter {
+                                zk_add = true;
+                            }
+                        }
+                    });
+                });
+                if let Some(idx) = zk_remove {
+                    self.zur_kenntnis.remove(idx);
+                }
+                if zk_add {
+                    self.zur_kenntnis.push(Person::new());
+                    self.focus_new_zur_kenntnis = true;
+                }
+
+                ui.add_space(4.0);
+                ui.separator();
+                ui.add_space(4.0);
+
+                // 14: Über dieses Meeting
+                ui.horizontal_top(|ui| {
+                    abschnitts_beschriftung(ui, "Über dieses Meeting", beschriftungs_breite,self.label_color);
+                    let mut meeting_edit = egui::TextEdit::multiline(&mut self.ueber_meeting)
+                        .hint_text(RichText::new("Informationen zum Meeting").font(egui::FontId::proportional(14.0)))
+                        .desired_width(ui.available_width())
+                        .desired_rows(3)
+                        .font(fette_schrift(14.0));
+                    if let Some(c) = textfarbe { meeting_edit = meeting_edit.text_color(c); }
+                    ui.add(meeting_edit);
+                });
+                ui.horizontal(|ui| {
+                    ui.add_space(beschriftungs_breite + ui.spacing().item_spacing.x);
+                    if self.zusammenfassung_rx.is_some() {
+                        ui.spinner();
+                        ui.label(RichText::new("Zusammenfassung wird erzeugt …").weak());
+                    } else if ui.small_button("Zusammenfassung erzeugen").clicked() {
+                        self.zusammenfassung_erzeugen();
+                    }
+                });
+
+                ui.add_space(4.0);
+                ui.separator();
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    abschnitts_beschriftung(ui, "Status", beschriftungs_breite,self.label_color);
+                    // Radiogruppe: nicht erlaubte Übergänge sind ausgegraut
+                    let cb_w = 140.0;
+                    let mut neuer_status = None;
+                    for s in self.status_auswahl() {
+                        let label = {
+                            let mut rt = RichText::new(s.label()).font(fette_schrift(14.0));
+                            if let Some(c) = textfarbe { rt = rt.color(c); }
+                            rt
+                        };
+                        let erlaubt = self.status_wechsel_erlaubt(s);
+                        ui.allocate_ui_with_layout(
+                            egui::vec2(cb_w, ui.spacing().interact_size.y),
+                            egui::Layout::left_to_right(egui::Align::Center),
+                            |ui| {
+                                ui.set_min_width(cb_w);
+                                let antwort = ui
+                                    .add_enabled(erlaubt, egui::RadioButton::new(self.status == s, label))
+                                    .on_disabled_hover_text(format!("Von „{}“ aus nicht erlaubt", self.status.label()));
+                                if antwort.clicked() && s != self.status {
+                                    neuer_status = Some(s);
+                                }
+                            },
+                        );
+                    }
+                    if let Some(s) = neuer_status {
+                        self.status_setzen(s);
+                    }
+                });
+
+                ui.add_space(4.0);
+                ui.separator();
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    abschnitts_beschriftung(ui, "Klassifizierung", beschriftungs_breite,self.label_color);
+                    let cb_w = 140.0;
+                    for s in Sicherheit::all() {
+                        let label = {
+                            let mut rt = RichText::new(s.label()).font(fette_schrift(14.0));
+                            if let Some(c) = textfarbe { rt = rt.color(c); }
+                            rt
+                        };
+                        let clicked = ui.allocate_ui_with_layout(
+                            egui::vec2(cb_w, ui.spacing().interact_size.y),
+                            egui::Layout::left_to_right(egui::Align::Center),
+                            |ui| {
+                                ui.set_min_width(cb_w);
+                                ui.radio(self.sicherheit == *s, label).clicked()
+                            },
+                        ).inner;
+                        if clicked && self.sicherheit != *s {
+                            self.sicherheit_setzen(s.clone());
+                        }
+                    }
+                });
+
+                ui.add_space(4.0);
+                ui.separator();
+                ui.add_space(4.0);
+
+                // Einträge-Tabelle
+                let mut entry_remove: Option<usize> = None;
+                let mut entry_swap: Option<(usize, usize)> = None;
+                let mut frist_eintragen: Option<usize> = None;
+                let mut redmine_anlegen: Option<usize> = None;
+                let mut vorbelegen: Option<usize> = None;
+                let mut ausschneiden: Option<usize> = None;
+                let mut einfuegen: Option<(usize, usize)> = None;
+                let entry_len = self.eintraege.len();
+
+                let available = ui.available_width();
+                let punkt_w: f32 = 160.0;
+                let art_w: f32 = 140.0;
+                let kum_text_w: f32 = 130.0;
+                let kum_dd_w: f32 = 35.0;
+                let bis_w: f32 = 88.0;
+                let action_w: f32 = 120.0;
+                let col_sp: f32 = 8.0;
+                let gaps = 5.0 * col_sp;
+                let notiz_w = (available
+                    - punkt_w
+                    - art_w
+                    - (kum_text_w + kum_dd_w + 4.0)
+                    - bis_w
+                    - action_w
+                    - gaps
+                    - 16.0)
+                    .max(150.0);
+
+                let mut header_line_y: f32 = 0.0;
+                // Oberkanten der Datenzeilen für die Rasterlinien
+                let mut zeilen_y: Vec<f32> = Vec::new();
+
+                ui.add_space(12.0);
+
+                // Live-Hinweis auf TODOs ohne Kümmerer oder Bis-Datum
+                let offen = todos_ohne_zuweisung(&self.eintraege);
+                if let Some(&erster) = offen.first() {
+                    let farbe = self.tabellen_farben.art(&Art::Todo);
+                    let hinweis = ui
+                        .horizontal(|ui| {
+                            egui::Frame::new()
+                                .fill(farbe.gamma_multiply(0.15))
+                                .stroke(egui::Stroke::new(1.0, farbe))
+                                .corner_radius(10.0)
+                                .inner_margin(egui::Margin::symmetric(8, 2))
+                                .show(ui, |ui| {
+                                    let text = if offen.len() == 1 {
+                                        "1 TODO ohne Kümmerer oder Bis-Datum".to_string()
+                                    } else {
+                                        format!("{} TODOs ohne Kümmerer oder Bis-Datum", offen.len())
+                                    };
+                                    ui.label(RichText::new(text).size(12.0).color(farbe));
+                                })
+                                .response
+                        })
+                        .inner
+                        .interact(egui::Sense::click())
+                        .on_hover_cursor(egui::CursorIcon::PointingHand);
+                    let liste: Vec<String> = offen
+                        .iter()
+                        .map(|&i| {
+                            let e = &self.eintraege[i];
+                            let titel = if e.punkt.trim().is_empty() {
+                                e.notiz.lines().next().unwrap_or("").to_string()
+                            } else {
+                                e.punkt.clone()
+                            };
+                            format!("{}. {}", i + 1, titel)
+                        })
+                        .collect();
+                    if hinweis.on_hover_text(liste.join("\n")).clicked() {
+                        self.focus_notiz = Some(erster);
+                    }
+                    ui.add_space(6.0);
+                }
+
+                // Live-Hinweis auf Kümmerer-Kürzel ohne passende Person
+                let unbekannt = self.unbekannte_kuemmerer();
+                if !unbekannt.is_empty() {
+                    let farbe = egui::Color32::from_rgb(231, 76, 60);
+                    let hinweis = ui
+                        .horizontal(|ui| {
+                            egui::Frame::new()
+                                .fill(farbe.gamma_multiply(0.15))
+                                .stroke(egui::Stroke::new(1.0, farbe))
+                                .corner_radius(10.0)
+                                .inner_margin(egui::Margin::symmetric(8, 2))
+                                .show(ui, |ui| {
+                                    let kuerzel: Vec<&str> = unbekannt.iter().map(|(k, _)| k.as_str()).collect();
+                                    let text = if kuerzel.len() == 1 {
+                                        format!("Unbekanntes Kürzel: {}", kuerzel[0])
+                                    } else {
+                                        format!("Unbekannte Kürzel: {}", kuerzel.join(", "))
+                                    };
+                                    ui.label(RichText::new(text).size(12.0).color(farbe));
+                                })
+                                .response
+                        })
+                        .inner
+                        .interact(egui::Sense::click())
+                        .on_hover_cursor(egui::CursorIcon::PointingHand);
+                    if hinweis.on_hover_text("Kümmerer gehören zu keiner Person im Protokoll – klicken zum Prüfen").clicked() {
+                        self.kuerzel_pruefung_oeffnen();
+                    }
+                    ui.add_space(6.0);
+                }
+
+                // Schnellfilter „Meine Aufgaben“ (nur mit eigenem Kürzel im Benutzerprofil)
+                if !self.profil.kuerzel.trim().is_empty() {
+                    let meine = (0..self.eintraege.len())
+                        .filter(|&i| {
+                            let e = &self.eintraege[i];
+                            e.art == Art::Todo && kuerzel_zustaendig(&e.kuemmerer, &self.profil.kuerzel, &self.profil.name)
+                        })
+                        .count();
+                    ui.horizontal(|ui| {
+                        let text = format!("Meine Aufgaben ({})", meine);
+                        if ui
+                            .selectable_label(self.nur_meine_aufgaben, RichText::new(text).size(12.0))
+                            .on_hover_text(format!("Nur TODOs mit Kümmerer {} anzeigen", self.profil.kuerzel.trim()))
+                            .clicked()
+                        {
+                            self.nur_meine_aufgaben = !self.nur_meine_aufgaben;
+                        }
+                        if ui.small_button("Im Archiv …").on_hover_text("Meine offenen TODOs aus allen archivierten Protokollen").clicked() {
+                            self.archiv_aufgaben_oeffnen();
+                        }
+                    });
+                    ui.add_space(6.0);
+                } else {
+                    self.nur_meine_aufgaben = false;
+                }
+
+                // Filter „fällig zwischen“ auf die Bis-Daten
+                ui.horizontal(|ui| {
+                    faellig_filter_anzeigen(ui, &mut self.faellig_filter);
+                    if self.faellig_filter.aktiv() {
+                        let treffer = (0..self.eintraege.len()).filter(|&i| self.eintrag_sichtbar(i)).count();
+                        ui.label(RichText::new(format!("{} Einträge", treffer)).size(12.0).weak());
+                    }
+                });
+                ui.add_space(6.0);
+
+                let line_x_range = ui.cursor().left()..=ui.available_rect_before_wrap().right();
+
+                let prev_notiz_focus = self.notiz_had_focus.take();
+                let mut new_notiz_focus: Option<(usize, usize)> = None;
+
+                // Touch-Modus: einspaltige Karten statt Tabelle
+                if self.touch_modus {
+                    self.eintraege_karten(ui, textfarbe, &alle_kuerzel, &sprecher_auswahl);
+                } else {
+                    let _grid_resp = egui::Grid::new("eintraege")
+                        .num_columns(6)
+                        .spacing([col_sp, 6.0])
+                        .striped(self.tabellen_farben.zebra.is_some())
+                        .show(ui, |ui| {
+                            // Kopfzeile — linksbündig, erzwingt Spaltenbreiten
+                            ui.with_layout(egui::Layout::left_to_right(egui::Align::Min), |ui| {
+                                ui.set_min_width(punkt_w);
+                                ui.label(RichText::new("").size(14.0));
+                            });
+                            ui.with_layout(egui::Layout::left_to_right(egui::Align::Min), |ui| {
+                                ui.set_min_width(art_w);
+                                let mut rt = RichText::new("Art").font(fette_schrift(14.0));
+                                if let Some(c) = beschriftungsfarbe { rt = rt.color(c); }
+                                ui.label(rt);
+                            });
+                            ui.with_layout(egui::Layout::left_to_right(egui::Align::Min), |ui| {
+                                ui.set_min_width(notiz_w);
+                                let mut rt = RichText::new("Notiz").font(fette_schrift(14.0));
+                                if let Some(c) = beschriftungsfarbe { rt = rt.color(c); }
+                                ui.label(rt);
+                            });
+                            ui.with_layout(egui::Layout::left_to_right(egui::Align::Min), |ui| {
+                                ui.set_min_width(kum_text_w + kum_dd_w + 4.0);
+                                let mut rt = RichText::new("Kümmerer").font(fette_schrift(14.0));
+                                if let Some(c) = beschriftungsfarbe { rt = rt.color(c); }
+                                ui.label(rt);
+                            });
+                            ui.with_layout(egui::Layout::left_to_right(egui::Align::Min), |ui| {
+                                ui.set_min_width(bis_w);
+                                let mut rt = RichText::new("Bis").font(fette_schrift(14.0));
+                                if let Some(c) = beschriftungsfarbe { rt = rt.color(c); }
+                                ui.label(rt);
+                            });
+                            ui.label("");
+                            ui.end_row();
+
+                            header_line_y = ui.cursor().top();
+
+                            // Spacer-Zeile für Abstand zwischen Linie und Daten
+                            ui.add_sized([0.0, 6.0], egui::Label::new(""));
+                            ui.add_sized([0.0, 6.0], egui::Label::new(""));
+                            ui.add_sized([0.0, 6.0], egui::Label::new(""));
+                            ui.add_sized([0.0, 6.0], egui::Label::new(""));
+                            ui.add_sized([0.0, 6.0], egui::Label::new(""));
+                            ui.label("");
+                            ui.end_row();
+
+                            let top_nummern = top_nummern(&self.eintraege, self.einstellungen.top_nummerierung);
+                            let mut zeile_gezeigt = false;
+                            for i in 0..entry_len {
+                                if !self.eintrag_sichtbar(i) {
+                                    continue;
+                                }
+                                let is_todo = self.eintraege[i].art == Art::Todo;
+                                let is_wortbeitrag = self.eintraege[i].art == Art::Wortbeitrag;
+                                let is_frage = self.eintraege[i].art == Art::Frage;
+                                if zeile_gezeigt {
+                                    zeilen_y.push(ui.cursor().top() - 3.0);
+                                }
+                                zeile_gezeigt = true;
+
+                                // 4: Punkt (oben ausgerichtet)
+                                ui.with_layout(egui::Layout::top_down(egui::Align::LEFT), |ui| {
+                                    let warnung = self.pdf_spaltenwarnung(&self.eintraege[i].punkt, 0, is_todo);
+                                    let mut punkt_edit = egui::TextEdit::singleline(&mut self.eintraege[i].punkt)
+                                        .hint_text(RichText::new(if is_todo { "" } else { "Punkt" }).font(egui::FontId::proportional(14.0)))
+                                        .font(fette_schrift(14.0))
+                                        .interactive(!is_todo)
+                                        .frame(!is_todo);
+                                    if let Some(c) = textfarbe { punkt_edit = punkt_edit.text_color(c); }
+                                    if let Some((_, abgeschnitten)) = &warnung {
+                                        punkt_edit = punkt_edit.text_color(spaltenwarnung_farbe(*abgeschnitten));
+                                    }
+                                    let punkt_resp = ui.add_sized([punkt_w, 20.0], punkt_edit);
+                                    if let Some((text, _)) = warnung {
+                                        punkt_resp.on_hover_text(text);
+                                    }
+                                });
+
+                                // 8: Art-Dropdown (oben ausgerichtet)
+                                ui.with_layout(egui::Layout::top_down(egui::Align::LEFT), |ui| {
+                                    let sel = RichText::new(self.eintraege[i].art.selected_label(&self.art_labels))
+                                        .color(self.tabellen_farben.art(&self.eintraege[i].art))
+                                        .font(fette_schrift(14.0));
+                                    egui::ComboBox::from_id_salt(format!("art_{i}"))
+                                        .selected_text(sel)
+                                        .width(art_w)
+                                        .show_ui(ui, |ui| {
+                                            let prev_art = self.eintraege[i].art.clone();
+                                            for art in Art::all() {
+                                                let txt = RichText::new(art_beschriftung(&self.art_labels, art)).color(self.tabellen_farben.art(art)).font(fette_schrift(14.0));
+                                                ui.selectable_value(
+                                                    &mut self.eintraege[i].art,
+                                                    art.clone(),
+                                                    txt,
+                                                );
+                                            }
+                                            if self.eintraege[i].art == Art::Todo && prev_art != Art::Todo {
+                                                self.eintraege[i].punkt.clear();
+                                            }
+                                        });
+                                    if self.eintraege[i].art == Art::Entscheidung && !self.eintraege[i].beschluss_nr.is_empty() {
+                                        ui.label(
+                                            RichText::new(&self.eintraege[i].beschluss_nr)
+                                                .font(fette_schrift(12.0))
+                                                .color(self.tabellen_farben.art(&Art::Entscheidung)),
+                                        );
+                                    }
+                                    if let Some((_, nummer)) = top_nummern.get(i).filter(|(_, n)| !n.is_empty()) {
+                                        ui.label(
+                                            RichText::new(nummer)
+                                                .font(fette_schrift(12.0))
+                                                .color(self.tabellen_farben.art(&Art::Agenda)),
+                                        );
+                                    }
+                                    redmine_badges_anzeigen(ui, &self.eintraege[i].notiz, &self.einstellungen.redmine_url, &self.redmine_status, &self.tabellen_farben, 12.0);
+                                });
+
+                                // 3: Notiz — dynamische Höhe + Cursor-Navigation
+                                let notiz_id = egui::Id::new(("notiz", i));
+                                let notiz_rows = self.eintraege[i].notiz.lines().count().max(1);
+                                let mut notiz_edit = egui::TextEdit::multiline(&mut self.eintraege[i].notiz)
+                                    .id(notiz_id)
+                                    .hint_text(RichText::new("Notiz").font(egui::FontId::proportional(14.0)))
+                                    .desired_width(notiz_w)
+                                    .desired_rows(notiz_rows)
+                                    .font(fette_schrift(14.0));
+                                if let Some(c) = textfarbe { notiz_edit = notiz_edit.text_color(c); }
+                                // Code (`…` und ```-Blöcke) in Festbreitenschrift mit Hintergrund
+                                let mut notiz_layouter = |ui: &egui::Ui, text: &str, wrap_width: f32| {
+                                    let farbe = textfarbe.unwrap_or(ui.visuals().text_color());
+                                    let mut job = notiz_layout_job(text, fette_schrift(14.0), farbe, ui.visuals().code_bg_color);
+                                    job.wrap.max_width = wrap_width;
+                                    ui.fonts(|f| f.layout_job(job))
+                                };
+                                notiz_edit = notiz_edit.layouter(&mut notiz_layouter);
+                                let notiz_resp = ui.add(notiz_edit);
+                                if self.focus_notiz == Some(i) {
+                                    notiz_resp.request_focus();
+                                    notiz_resp.scroll_to_me(None);
+                                    self.focus_notiz = None;
+                                }
+                                if notiz_resp.has_focus() {
+                                    if let Some(state) = egui::TextEdit::load_state(ui.ctx(), notiz_id) {
+                                        if let Some(range) = state.cursor.char_range() {
+                                            new_notiz_focus = Some((i, range.primary.index));
+                                        }
+                                    }
+                                }
+
+                                // 5+7+10: Kümmerer (oben ausgerichtet, nur bei TODO sichtbar;
+                                // bei Wortbeiträgen Auswahl des Sprechers aus den Teilnehmern)
+                                ui.with_layout(egui::Layout::top_down(egui::Align::LEFT), |ui| {
+                                    ui.horizontal(|ui| {
+                                        let kum_aktiv = is_todo || is_wortbeitrag || is_frage;
+                                        let kum_hinweis = if is_todo {
+                                            "Wer?"
+                                        } else if is_wortbeitrag {
+                                            "Sprecher"
+                                        } else if is_frage {
+                                            "Von?"
+                                        } else {
+                                            ""
+                                        };
+                                        let kum_unbekannt = is_todo
+                                            && !unbekannte_kuerzel(&self.eintraege[i].kuemmerer, &self.alle_personen()).is_empty();
+                                        let kum_warnung = self.pdf_spaltenwarnung(&self.eintraege[i].kuemmerer, 3, is_todo);
+                                        let praefixe = &self.einstellungen.kuerzel_praefixe;
+                                        let kum_praefix = kuerzel_segmente(&self.eintraege[i].kuemmerer, praefixe).iter().any(|(_, p)| *p);
+                                        // Organisations-Präfixe (EXT-) abgesetzt vor den Initialen
+                                        let mut kum_layouter = |ui: &egui::Ui, text: &str, _wrap_width: f32| {
+                                            let farbe = textfarbe.unwrap_or(ui.visuals().text_color());
+                                            let job = kuerzel_layout_job(text, fette_schrift(14.0), farbe, ui.visuals().weak_text_color(), praefixe);
+                                            ui.fonts(|f| f.layout_job(job))
+                                        };
+                                        let mut kum_edit = egui::TextEdit::singleline(
+                                                &mut self.eintraege[i].kuemmerer,
+                                            )
+                                            .hint_text(RichText::new(kum_hinweis).font(egui::FontId::proportional(14.0)))
+                                            .desired_width(kum_text_w)
+                                            .interactive(kum_aktiv)
+                                            .frame(kum_aktiv)
+                                            .font(fette_schrift(14.0));
+                                        if let Some(c) = textfarbe { kum_edit = kum_edit.text_color(c); }
+                                        let warnung = if kum_unbekannt {
+                                            kum_edit = kum_edit.text_color(egui::Color32::from_rgb(231, 76, 60));
+                                            Some("Kürzel gehört zu keiner Person im Protokoll".to_string())
+                                        } else if let Some((text, abgeschnitten)) = kum_warnung {
+                                            kum_edit = kum_edit.text_color(spaltenwarnung_farbe(abgeschnitten));
+                                            Some(text)
+                                        } else {
+                                            None
+                                        };
+                                        if warnung.is_none() && kum_praefix {
+                                            kum_edit = kum_edit.layouter(&mut kum_layouter);
+                                        }
+                                        let kum_resp = ui.add(kum_edit);
+                                        if let Some(text) = warnung {
+                                            kum_resp.on_hover_text(text);
+                                        }
+                                        if is_wortbeitrag || is_frage {
+                                            egui::ComboBox::from_id_salt(format!("kum_sel_{i}"))
+                                                .selected_text("")
+                                                .width(kum_dd_w)
+                                                .show_ui(ui, |ui| {
+                                                    if sprecher_auswahl.is_empty() {
+                                                        ui.label("Keine Teilnehmer");
+                                                    }
+                                                    for (kuerzel, name) in &sprecher_auswahl {
+                                                        if ui
+                                                            .selectable_label(
+                                                                self.eintraege[i].kuemmerer == *kuerzel,
+                                                                name,
+                                                            )
+                                                            .clicked()
+                                                        {
+                                                            self.eintraege[i].kuemmerer = kuerzel.clone();
+                                                        }
+                                                    }
+                                                });
+                                        } else if is_todo {
+                                            egui::ComboBox::from_id_salt(format!("kum_sel_{i}"))
+                                                .selected_text("")
+                                                .width(kum_dd_w)
+                                                .show_ui(ui, |ui| {
+                                                    if alle_kuerzel.is_empty() {
+                                                        ui.label("Keine Kürzel");
+                                                    }
+                                                    for k in &alle_kuerzel {
+                                                        if ui
+                                                            .selectable_label(
+                                                                self.eintraege[i].kuemmerer == *k,
+                                                                k,
+                                                            )
+                                                            .clicked()
+                                                        {
+                                                            self.eintraege[i].kuemmerer = k.clone();
+                                                        }
+                                                    }
+                                                });
+                                        } else {
+                                            ui.add_space(kum_dd_w + 4.0);
+                                        }
+                                    });
+                                });
+
+                                // 6: Bis (oben ausgerichtet, nur bei TODO und – als „beantwortet am“ – bei
+                                // Fragen sichtbar, mit Datumsvalidierung)
+                                ui.with_layout(egui::Layout::top_down(egui::Align::LEFT), |ui| {
+                                    let bis_valid = self.eintraege[i].bis.is_empty()
+                                        || NaiveDate::parse_from_str(
+                                            &self.eintraege[i].bis,
+                                            "%d.%m.%Y",
+                                        )
+                                        .is_ok();
+                                    let warnung = self.pdf_spaltenwarnung(&self.eintraege[i].bis, 4, is_todo);
+                                    let feiertag = if is_todo { self.einstellungen.feiertage.bis_hinweis(&self.eintraege[i].bis) } else { None };
+                                    let unplausibel = if is_todo { bis_plausibilitaet(&self.eintraege[i].bis, datum_aus_text(&self.datum_text)) } else { None };
+                                    let bis_color = if !bis_valid {
+                                        egui::Color32::from_rgb(231, 76, 60)
+                                    } else if unplausibel.is_some() {
+                                        spaltenwarnung_farbe(false)
+                                    } else if let Some((_, abgeschnitten)) = &warnung {
+                                        spaltenwarnung_farbe(*abgeschnitten)
+                                    } else if feiertag.is_some() {
+                                        spaltenwarnung_farbe(false)
+                                    } else if let Some(c) = textfarbe {
+                                        c
+                                    } else {
+                                        ui.visuals().text_color()
+                                    };
+                                    let bis_hinweis = if is_todo {
+                                        "TT.MM.JJJJ"
+                                    } else if is_frage {
+                                        "offen"
+                                    } else {
+                                        ""
+                                    };
+                                    let bis_resp = ui.add_sized(
+                                        [bis_w, 20.0],
+                                        egui::TextEdit::singleline(&mut self.eintraege[i].bis)
+                                            .hint_text(RichText::new(bis_hinweis).font(egui::FontId::proportional(14.0)))
+                                            .text_color(bis_color)
+                                            .interactive(is_todo || is_frage)
+                                            .frame(is_todo || is_frage)
+                                            .font(fette_schrift(14.0)),
+                                    );
+                                    let hinweise: Vec<String> = unplausibel
+                                        .into_iter()
+                                        .chain(warnung.map(|(text, _)| text))
+                                        .chain(feiertag)
+                                        .chain(is_frage.then(|| "Beantwortet am (TT.MM.JJJJ); leer = offene Frage".to_string()))
+                                        .collect();
+                                    let bis_resp = if hinweise.is_empty() { bis_resp } else { bis_resp.on_hover_text(hinweise.join("\n")) };
+                                    if is_todo {
+                                        let basis = datum_aus_text(&self.datum_text).unwrap_or_else(|| Local::now().date_naive());
+                                        bis_resp.context_menu(|ui| {
+                                            bis_vorschlaege_anzeigen(ui, &mut self.eintraege[i].bis, basis, &self.einstellungen.feiertage);
+                                        });
+                                    }
+                                });
+
+                                // Aktionen: Hoch / Runter / Löschen
+                                ui.with_layout(egui::Layout::top_down(egui::Align::LEFT), |ui| {
+                                    ui.horizontal(|ui| {
+                                        ui.spacing_mut().item_spacing.x = 0.0;
+                                        if i > 0 {
+                                            if ui.add_sized([20.0, 20.0], egui::Button::new("▲")).clicked() {
+                                                entry_swap = Some((i, i - 1));
+                                            }
+                                        } else {
+                                            ui.add_sized([20.0, 20.0], egui::Label::new(""));
+                                        }
+                                        ui.add_space(2.0);
+                                        if i + 1 < entry_len {
+                                            if ui.add_sized([20.0, 20.0], egui::Button::new("▼")).clicked() {
+                                                entry_swap = Some((i, i + 1));
+                                            }
+                                        } else {
+                                            ui.add_sized([20.0, 20.0], egui::Label::new(""));
+                                        }
+                                        ui.add_space(10.0);
+                                        if entry_len > 1 {
+                                            if ui.add_sized([20.0, 20.0], egui::Button::new(
+                                                RichText::new("×").color(egui::Color32::from_rgb(231, 76, 60)),
+                                            )).clicked() {
+                                                entry_remove = Some(i);
+                                            }
+                                        } else {
+                                            ui.add_sized([20.0, 20.0], egui::Label::new(""));
+                                        }
+                                        // PDF-Seitenumbruch-Optionen (hervorgehoben, wenn aktiv)
+                                        ui.add_space(4.0);
+                                        let layout_aktiv = self.eintraege[i].umbruch_davor || self.eintraege[i].zusammenhalten;
+                                        let mut layout_rt = RichText::new("⋯");
+                                        if layout_aktiv {
+                                            layout_rt = layout_rt.color(self.tabellen_farben.art(&Art::Agenda)).strong();
+                                        }
+                                        ui.menu_button(layout_rt, |ui| {
+                                            ui.checkbox(&mut self.eintraege[i].umbruch_davor, "Seitenumbruch vor diesem Eintrag");
+                                            ui.checkbox(&mut self.eintraege[i].zusammenhalten, "Eintrag nicht über Seiten umbrechen");
+                                            ui.separator();
+                                            if ui.button("Ausschneiden (Strg+X)").clicked() {
+                                                ausschneiden = Some(i);
+                                                ui.close_menu();
+                                            }
+                                            ui.add_enabled_ui(!self.eintrag_ablage.is_empty(), |ui| {
+                                                ui.menu_button("Darunter einfügen", |ui| {
+                                                    // Neuester zuerst; Strg+V fügt den obersten ein
+                                                    for (n, e) in self.eintrag_ablage.iter().enumerate().rev() {
+                                                        let zeile = e.notiz.lines().find(|l| !l.trim().is_empty()).unwrap_or(&e.punkt).trim();
+                                                        let text = format!("{}: {}", self.art_label(&e.art), zeile.chars().take(50).collect::<String>());
+                                                        if ui.button(text).clicked() {
+                                                            einfuegen = Some((i, n));
+                                                            ui.close_menu();
+                                                        }
+                                                    }
+                                                });
+                                            });
+                                            if self.eintraege[i].art == Art::Agenda {
+                                                ui.separator();
+                                                let vorbelegung = !self.einstellungen.top_vorbelegung(&self.projekt).is_empty();
+                                                if ui
+                                                    .add_enabled(vorbelegung, egui::Button::new("Vorbelegung einfügen"))
+                                                    .on_hover_text("Legt die Standard-Einträge aus top_vorbelegung unter diesem TOP an")
+                                                    .on_disabled_hover_text("Keine Vorbelegung eingestellt (top_vorbelegung in einstellungen.toml)")
+                                                    .clicked()
+                                                {
+                                                    vorbelegen = Some(i);
+                                                    ui.close_menu();
+                                                }
+                                            }
+                                            if self.eintraege[i].art == Art::Todo {
+                                                ui.separator();
+                                                let frist_gueltig = datum_aus_text(&self.eintraege[i].bis).is_some();
+                                                if ui
+                                                    .add_enabled(frist_gueltig, egui::Button::new("Fälligkeit in Kalender eintragen"))
+                                                    .on_disabled_hover_text("Kein gültiges Bis-Datum")
+                                                    .clicked()
+                                                {
+                                                    frist_eintragen = Some(i);
+                                                    ui.close_menu();
+                                                }
+                                                let verknuepft = !redmine_tickets(&self.eintraege[i].notiz, &self.einstellungen.redmine_url).is_empty();
+                                                if ui
+                                                    .add_enabled(self.redmine_eingerichtet() && !verknuepft, egui::Button::new("Als Redmine-Ticket anlegen"))
+                                                    .on_disabled_hover_text(if verknuepft { "Die Notiz verweist bereits auf ein Ticket" } else { "Kein Redmine-Server eingestellt" })
+                                                    .clicked()
+                                                {
+                                                    redmine_anlegen = Some(i);
+                                                    ui.close_menu();
+                                                }
+                                            }
+                                        })
+                                        .response
+                                        .on_hover_text("Ausschneiden/Einfügen, PDF-Seitenumbruch, Vorbelegung, Kalender und Redmine");
+                                        // Verlauf des Eintrags aus dem Audit-Log
+                                        let id = self.eintraege[i].id;
+                                        ui.menu_button("🕓", |ui| {
+                                            ui.set_min_width(320.0);
+                                            eintrag_verlauf_anzeigen(
+                                                ui,
+                                                &self.audit_log,
+                                                id,
+                                                self.eintrag_stand.get(&id).is_some_and(|alt| *alt != eintrag_felder(&self.eintraege[i])),
+                                            );
+                                        })
+                                        .response
+                                        .on_hover_text("Verlauf");
+                                    });
+                                });
+                                ui.end_row();
+                            }
+                        });
+
+                    // Cursor hoch/runter zwischen Notiz-Feldern
+                    {
+                        let up = ui.input(|inp| inp.key_pressed(egui::Key::ArrowUp));
+                        let down = ui.input(|inp| inp.key_pressed(egui::Key::ArrowDown));
+                        if let Some((prev_i, prev_cursor)) = prev_notiz_focus {
+                            if prev_i < self.eintraege.len() {
+                                let text = &self.eintraege[prev_i].notiz;
+                                let mut safe_idx = prev_cursor.min(text.len());
+                                while safe_idx > 0 && !text.is_char_boundary(safe_idx) {
+                                    safe_idx -= 1;
+                                }
+                                let on_first = !text[..safe_idx].contains('\n');
+                                let on_last = !text[safe_idx..].contains('\n');
+                                if up && on_first && prev_i > 0 {
+                                    self.focus_notiz = Some(prev_i - 1);
+                                } else if down && on_last && prev_i + 1 < self.eintraege.len() {
+                                    self.focus_notiz = Some(prev_i + 1);
+                                }
+                            }
+                        }
+                        self.notiz_had_focus = new_notiz_focus;
+                    }
+
+                    // 15: Linie unter Kopfzeile (gleiche Breite wie Separators)
+                    let kopflinie = self.tabellen_farben.kopflinie.unwrap_or(egui::Color32::from_rgb(180, 180, 180));
+                    ui.painter().hline(line_x_range.clone(), header_line_y - 1.0, egui::Stroke::new(1.5, kopflinie));
+                    // Rasterlinien zwischen den Zeilen (nur mit Theme-Palette)
+                    if let Some(raster) = self.tabellen_farben.raster {
+                        for y in zeilen_y {
+                            ui.painter().hline(line_x_range.clone(), y, egui::Stroke::new(1.0, raster));
+                        }
+                    }
+                }
+
+                if let Some((a, b)) = entry_swap {
+                    self.eintraege.swap(a, b);
+                }
+                if let Some(i) = frist_eintragen {
+                    self.frist_in_kalender_eintragen(i);
+                }
+                if let Some(i) = redmine_anlegen {
+                    self.redmine_ticket_anlegen(i);
+                }
+                if let Some(i) = vorbelegen {
+                    self.top_vorbelegen(i);
+                }
+                if let Some(i) = ausschneiden {
+                    self.eintrag_ausschneiden(ui.ctx(), i);
+                } else if let Some((nach, n)) = einfuegen {
+                    self.eintrag_einfuegen(ui.ctx(), Some(nach), n);
+                }
+                if let Some(idx) = entry_remove {
+                    self.eintraege.remove(idx);
+                }
+
+                ui.add_space(8.0);
+                if ui.button(RichText::new("+ Eintrag hinzufügen").strong()).clicked() {
+                    // Neue Einträge sind zunächst keine eigenen TODOs ohne Bis-Datum – Filter aufheben
+                    self.nur_meine_aufgaben = false;
+                    self.faellig_filter = FaelligFilter::default();
+                    self.eintraege.push(Eintrag::new());
+                }
+
+                // Fälligkeiten der TODOs als Zeitleiste
+                if self.einstellungen.zeitleiste_wochen > 0 && self.eintraege.iter().any(|e| e.art == Art::Todo && !e.bis.trim().is_empty()) {
+                    ui.add_space(12.0);
+                    ui.horizontal_top(|ui| {
+                        abschnitts_beschriftung(ui, "Fälligkeiten", beschriftungs_breite, self.label_color);
+                        ui.vertical(|ui| self.faelligkeits_zeitleiste(ui));
+                    });
+                }
+
+                // Nächstes Meeting (Termin, Ort, vorläufige Agenda)
+                ui.add_space(12.0);
+                ui.separator();
+                ui.add_space(4.0);
+                ui.horizontal_top(|ui| {
+                    abschnitts_beschriftung(ui, "Nächstes Meeting", beschriftungs_breite, self.label_color);
+                    ui.vertical(|ui| {
+                        ui.horizontal(|ui| {
+                            let termin_gueltig = self.naechstes_datum.is_empty()
+                                || termin_parsen(&self.naechstes_datum).is_some();
+                            let mut datum_edit = egui::TextEdit::singleline(&mut self.naechstes_datum)
+                                .hint_text(RichText::new("TT.MM.JJJJ HH:MM").font(egui::FontId::proportional(14.0)))
+                                .desired_width(250.0)
+                                .font(fette_schrift(14.0));
+                            if !termin_gueltig {
+                                datum_edit = datum_edit.text_color(egui::Color32::from_rgb(231, 76, 60));
+                            } else if let Some(c) = textfarbe {
+                                datum_edit = datum_edit.text_color(c);
+                            }
+                            ui.add(datum_edit);
+                            ui.label(RichText::new("|").size(15.0));
+                            let mut ort_edit = egui::TextEdit::singleline(&mut self.naechstes_ort)
+                                .hint_text(RichText::new("Ort").font(egui::FontId::proportional(14.0)))
+                                .desired_width(ui.available_width())
+                                .font(fette_schrift(14.0));
+                            if let Some(c) = textfarbe { ort_edit = ort_edit.text_color(c); }
+                            ui.add(ort_edit);
+                        });
+                        let mut agenda_edit = egui::TextEdit::multiline(&mut self.naechste_agenda)
+                            .hint_text(RichText::new("Vorläufige Agenda (ein Punkt pro Zeile)").font(egui::FontId::proportional(14.0)))
+                            .desired_width(ui.available_width())
+                            .desired_rows(3)
+                            .font(fette_schrift(14.0));
+                        if let Some(c) = textfarbe { agenda_edit = agenda_edit.text_color(c); }
+                        ui.add(agenda_edit);
+                    });
+                });
+
+                // Anhang „Nur intern" (nicht im PDF für externe Empfänger)
+                ui.add_space(12.0);
+                ui.separator();
+                ui.add_space(4.0);
+                ui.horizontal_top(|ui| {
+                    abschnitts_beschriftung(ui, "Nur intern", beschriftungs_breite, self.label_color);
+                    let mut anhang_edit = egui::TextEdit::multiline(&mut self.interner_anhang)
+                        .hint_text(RichText::new("Interner Anhang – entfällt im PDF für externe Empfänger").font(egui::FontId::proportional(14.0)))
+                        .desired_width(ui.available_width())
+                        .desired_rows(2)
+                        .font(fette_schrift(14.0));
+                    if let Some(c) = textfarbe { anhang_edit = anhang_edit.text_color(c); }
+                    ui.add(anhang_edit);
+                });
+            });
+        });
+
+        // Offline-Hilfe: Seitenliste bzw. Suchergebnisse links, Seite rechts
+        if let Some(hilfe) = self.hilfe.as_mut() {
+            let mut open = true;
+            egui::Window::new("Hilfe")
+                .open(&mut open)
+                .collapsible(false)
+                .default_size([760.0, 520.0])
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.horizontal_top(|ui| {
+                        ui.vertical(|ui| {
+                            ui.set_width(200.0);
+                            ui.add(egui::TextEdit::singleline(&mut hilfe.suche).hint_text("Suchen …"));
+                            ui.add_space(6.0);
+                            let suche = hilfe.suche.trim().to_lowercase();
+                            if suche.is_empty() {
+                                for (i, (titel, _)) in HILFE_SEITEN.iter().enumerate() {
+                                    if ui.selectable_label(hilfe.seite == i, *titel).clicked() {
+                                        hilfe.seite = i;
+                                    }
+                                }
+                            } else {
+                                let mut treffer = false;
+                                for (i, (titel, inhalt)) in HILFE_SEITEN.iter().enumerate() {
+                                    let zeilen: Vec<&str> = inhalt
+                                        .lines()
+                                        .filter(|z| z.to_lowercase().contains(&suche))
+                                        .take(3)
+                                        .collect();
+                                    if zeilen.is_empty() && !titel.to_lowercase().contains(&suche) {
+                                        continue;
+                                    }
+                                    treffer = true;
+                                    if ui.selectable_label(hilfe.seite == i, RichText::new(*titel).strong()).clicked() {
+                                        hilfe.seite = i;
+                                    }
+                                    for zeile in zeilen {
+                                        let text = zeile.trim_start_matches(['#', '-', ' ']);
+                                        if ui.link(RichText::new(text).size(11.0)).clicked() {
+                                            hilfe.seite = i;
+                                        }
+                                    }
+                                    ui.add_space(4.0);
+                                }
+                                if !treffer {
+                                    ui.label(RichText::new("Keine Treffer").weak());
+                                }
+                            }
+                            ui.add_space(12.0);
+                            if ui.link("Website öffnen").clicked() {
+                                url_oeffnen("https://www.marcelzimmer.de");
+                            }
+                        });
+                        ui.separator();
+                        egui::ScrollArea::vertical().id_salt("hilfe_seite").show(ui, |ui| {
+                            ui.set_min_width(480.0);
+                            for zeile in HILFE_SEITEN[hilfe.seite].1.lines() {
+                                hilfe_zeile_anzeigen(ui, zeile);
+                            }
+                        });
+                    });
+                });
+            if !open {
+                self.hilfe = None;
+            }
+        }
+
+        // Zugangsdaten im Schlüsselbund verwalten
+        if let Some(dialog) = self.zugangsdaten_dialog.as_mut() {
+            if let Some(rx) = &dialog.pruefung_rx {
+                loop {
+                    match rx.try_recv() {
+                        Ok((i, gesetzt)) => dialog.hinterlegt[i] = Some(gesetzt),
+                        Err(mpsc::TryRecvError::Empty) => {
+                            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                            break;
+                        }
+                        Err(mpsc::TryRecvError::Disconnected) => {
+                            dialog.pruefung_rx = None;
+                            break;
+                        }
+                    }
+                }
+            }
+            let mut open = true;
+            egui::Window::new("Zugangsdaten")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_min_width(460.0);
+                    ui.label(
+                        RichText::new("Tokens und Passwörter werden im System-Schlüsselbund gespeichert, nie in Konfigurationsdateien.")
+                            .size(11.0)
+                            .weak(),
+                    );
+                    ui.add_space(8.0);
+                    egui::Grid::new("zugangsdaten").num_columns(4).spacing([12.0, 8.0]).show(ui, |ui| {
+                        for (i, (konto, name)) in ZUGANGSDATEN_KONTEN.iter().enumerate() {
+                            ui.label(*name);
+                            let hinweis = match dialog.hinterlegt[i] {
+                                Some(true) => "gesetzt",
+                                Some(false) => "nicht gesetzt",
+                                None => "wird geprüft …",
+                            };
+                            ui.add(
+                                egui::TextEdit::singleline(&mut dialog.eingaben[i])
+                                    .password(true)
+                                    .hint_text(hinweis)
+                                    .desired_width(180.0),
+                            );
+                            if ui.add_enabled(!dialog.eingaben[i].is_empty(), egui::Button::new("Speichern")).clicked() {
+                                match zugangsdaten_speichern(konto, &dialog.eingaben[i]) {
+                                    Ok(()) => {
+                                        dialog.hinterlegt[i] = Some(true);
+                                        dialog.eingaben[i].clear();
+                                        dialog.status = Some(format!("{} gespeichert.", name));
+                                    }
+                                    Err(e) => dialog.status = Some(format!("Fehler: {}", e)),
+                                }
+                            }
+                            if ui.add_enabled(dialog.hinterlegt[i] == Some(true), egui::Button::new("Entfernen")).clicked() {
+                                match zugangsdaten_loeschen(konto) {
+                                    Ok(()) => {
+                                        dialog.hinterlegt[i] = Some(false);
+                                        dialog.status = Some(format!("{} entfernt.", name));
+                                    }
+                                    Err(e) => dialog.status = Some(format!("Fehler: {}", e)),
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+                    if let Some(status) = &dialog.status {
+                        ui.add_space(8.0);
+                        ui.label(status);
                     }
-                    // Eingegebener Text in Textfeldern → color2
-                    if let Some(text_color) = colors.get("color2") {
-                        self.input_text_color = Some(*text_color);
+                });
+            if !open {
+                self.zugangsdaten_dialog = None;
+            }
+        }
+
+        // Benutzerprofil bearbeiten
+        if self.profil_dialog.is_some() {
+            let mut speichern = false;
+            let mut abbrechen = false;
+            if let Some(profil) = self.profil_dialog.as_mut() {
+                egui::Window::new("Benutzerprofil")
+                    .collapsible(false)
+                    .resizable(false)
+                    .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                    .show(ctx, |ui| {
+                        ui.set_min_width(400.0);
+                        egui::Grid::new("benutzerprofil").num_columns(2).spacing([12.0, 8.0]).show(ui, |ui| {
+                            ui.label("Name");
+                            ui.add(egui::TextEdit::singleline(&mut profil.name).desired_width(260.0));
+                            ui.end_row();
+                            ui.label("Kürzel");
+                            ui.add(egui::TextEdit::singleline(&mut profil.kuerzel).desired_width(80.0));
+                            ui.end_row();
+                            ui.label("E-Mail");
+                            ui.add(egui::TextEdit::singleline(&mut profil.email).desired_width(260.0));
+                            ui.end_row();
+                            ui.label("Signatur");
+                            ui.add(egui::TextEdit::multiline(&mut profil.signatur).desired_width(260.0).desired_rows(3));
+                            ui.end_row();
+                        });
+                        ui.add_space(4.0);
+                        ui.label(
+                            RichText::new("Wird für „Erstellt von“, das Audit-Log und als Protokollführer neuer Protokolle verwendet.")
+                                .size(11.0)
+                                .weak(),
+                        );
+                        ui.add_space(8.0);
+                        ui.horizontal(|ui| {
+                            if ui.button("Speichern").clicked() {
+                                speichern = true;
+                            }
+                            if ui.button("Abbrechen").clicked() {
+                                abbrechen = true;
+                            }
+                        });
+                    });
+            }
+            if speichern {
+                if let Some(profil) = self.profil_dialog.take() {
+                    let _ = profil.speichern();
+                    if self.protokollant.name.trim().is_empty() {
+                        self.protokollant = profil.als_person();
                     }
-                } else {
-                    visuals.panel_fill = egui::Color32::from_rgb(30, 30, 30);
-                    visuals.window_fill = egui::Color32::from_rgb(30, 30, 30);
+                    self.profil = profil;
                 }
-                ctx.set_visuals(visuals);
+            } else if abbrechen {
+                self.profil_dialog = None;
             }
         }
 
-        let alle_kuerzel = self.alle_kuerzel();
-        // Feste Breite der linksseitigen Abschnittsbezeichnungen (in Pixeln)
-        let beschriftungs_breite = 160.0;
-
-        let panel_frame = egui::Frame::central_panel(&ctx.style())
-            .inner_margin(egui::Margin::same(10));
-        egui::CentralPanel::default().frame(panel_frame).show(ctx, |ui| {
-            // Toolbar oben rechts: Beenden-Button + Hamburger-Menü
-            ui.with_layout(egui::Layout::right_to_left(egui::Align::Min), |ui| {
-                ui.spacing_mut().item_spacing.x = 6.0;
-
-                if ui.button(RichText::new("X").size(14.0)).on_hover_text("Beenden (Strg+W)").clicked() {
-                    self.show_quit_dialog = true;
+        // Eigener Öffnen-Dialog: Protokolle im Archiv mit Titel, Datum, Projekt, Status und TODOs
+        if let Some(mut dialog) = self.oeffnen_dialog.take() {
+            let mut open = true;
+            let mut oeffnen: Option<std::path::PathBuf> = None;
+            let mut systemdialog = false;
+            egui::Window::new("Protokoll öffnen")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(true)
+                .default_size([760.0, 440.0])
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(dialog.verzeichnis.display().to_string()).size(11.0).weak());
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.button("Andere Datei …").clicked() {
+                                systemdialog = true;
+                            }
+                        });
+                    });
+                    ui.add(
+                        egui::TextEdit::singleline(&mut dialog.suche)
+                            .hint_text("Suchen (Titel, Projekt, Dateiname)")
+                            .desired_width(f32::INFINITY),
+                    );
+                    ui.add_space(8.0);
+                    let suche = dialog.suche.to_lowercase();
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        egui::Grid::new("oeffnen_dialog").num_columns(5).striped(true).spacing([12.0, 4.0]).show(ui, |ui| {
+                            ui.label(RichText::new("Datum").strong());
+                            ui.label(RichText::new("Titel").strong());
+                            ui.label(RichText::new("Projekt").strong());
+                            ui.label(RichText::new("Status").strong());
+                            ui.label(RichText::new("Offene TODOs").strong());
+                            ui.end_row();
+                            for d in dialog.dateien.iter().filter(|d| {
+                                suche.is_empty()
+                                    || d.titel.to_lowercase().contains(&suche)
+                                    || d.projekt.to_lowercase().contains(&suche)
+                                    || d.pfad.to_string_lossy().to_lowercase().contains(&suche)
+                            }) {
+                                ui.label(d.datum.map(|d| d.format("%d.%m.%Y").to_string()).unwrap_or_default());
+                                let titel = if d.titel.trim().is_empty() {
+                                    d.pfad.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+                                } else {
+                                    d.titel.clone()
+                                };
+                                if ui
+                                    .selectable_label(self.save_path.as_ref() == Some(&d.pfad), titel)
+                                    .on_hover_text(d.pfad.display().to_string())
+                                    .clicked()
+                                {
+                                    oeffnen = Some(d.pfad.clone());
+                                }
+                                ui.label(&d.projekt);
+                                ui.label(d.status.map(|s| s.label()).unwrap_or(""));
+                                if d.offene_todos > 0 {
+                                    ui.label(RichText::new(d.offene_todos.to_string()).color(self.tabellen_farben.art(&Art::Todo)));
+                                } else {
+                                    ui.label("–");
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    });
+                });
+            if let Some(pfad) = oeffnen {
+                match std::fs::read_to_string(&pfad) {
+                    Ok(content) => self.datei_uebernehmen(pfad, &content),
+                    Err(e) => self.toast_zeigen(format!("Datei konnte nicht gelesen werden: {}", e)),
                 }
+            } else if systemdialog {
+                self.laden();
+            } else if open {
+                self.oeffnen_dialog = Some(dialog);
+            }
+        }
 
-                let menu_items: &[(&str, &str, i32)] = &[
-                    ("Neu", "Strg+N", 0),
-                    ("Öffnen", "Strg+O", 0),
-                    ("Speichern", "Strg+S", 0),
-                    ("PDF erzeugen", "Strg+P", 0),
-                    ("", "", 1), // separator
-                    ("Theme ändern", "Strg+T", 0),
-                    ("", "", 1), // separator
-                    ("Hilfe", "Strg+H", 0),
-                    ("Über", "Strg+I", 0),
-                ];
-                egui::menu::menu_button(ui, RichText::new("☰").size(14.0), |ui| {
-                    ui.set_width(180.0);
-                    for &(label, shortcut, is_sep) in menu_items {
-                        if is_sep == 1 {
-                            ui.separator();
-                            continue;
+        // Profil wechseln: Standardprofil, angelegte Profile oder neues Profil
+        if let Some(mut wechsel) = self.profil_wechsel.take() {
+            let mut open = true;
+            let mut auswahl: Option<String> = None;
+            egui::Window::new("Profil wechseln")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_min_width(320.0);
+                    let standard = std::iter::once(("Standard", String::new()));
+                    let profile = wechsel.profile.iter().map(|p| (p.as_str(), p.clone()));
+                    for (label, name) in standard.chain(profile) {
+                        if ui.selectable_label(self.konfig_profil == name, label).clicked() {
+                            auswahl = Some(name);
                         }
-                        let w = ui.available_width();
-                        let (rect, response) = ui.allocate_exact_size(
-                            egui::vec2(w, 24.0),
-                            egui::Sense::click(),
+                    }
+                    ui.add_space(8.0);
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.add(egui::TextEdit::singleline(&mut wechsel.neu).hint_text("Neues Profil, z. B. Verein").desired_width(200.0));
+                        let name = wechsel.neu.trim();
+                        let gueltig = !name.is_empty()
+                            && !name.starts_with('.')
+                            && !name.contains(['/', '\\'])
+                            && !wechsel.profile.iter().any(|p| p == name);
+                        if ui.add_enabled(gueltig, egui::Button::new("Anlegen")).clicked() {
+                            auswahl = Some(name.to_string());
+                        }
+                    });
+                    ui.add_space(4.0);
+                    ui.label(
+                        RichText::new("Jedes Profil hat eigene Einstellungen, Vorlagen, Zugangsdaten und Archivpfade.")
+                            .size(11.0)
+                            .weak(),
+                    );
+                });
+            if let Some(name) = auswahl {
+                self.profil_wechseln(ctx, &name);
+            } else if open {
+                self.profil_wechsel = Some(wechsel);
+            }
+        }
+
+        // Statistik-Panel (Umfang und Bearbeitungszeit des Protokolls)
+        if self.show_statistik {
+            let mut open = true;
+            egui::Window::new("Statistik")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_min_width(320.0);
+                    let belegt: Vec<&Eintrag> = self
+                        .eintraege
+                        .iter()
+                        .filter(|e| e.art != Art::Leer || !e.notiz.trim().is_empty())
+                        .collect();
+                    let woerter: usize = belegt.iter().map(|e| e.notiz.split_whitespace().count()).sum();
+                    egui::Grid::new("statistik").num_columns(2).spacing([24.0, 6.0]).show(ui, |ui| {
+                        ui.label("Bearbeitungszeit");
+                        ui.label(RichText::new(dauer_formatieren(self.bearbeitungszeit)).strong());
+                        ui.end_row();
+                        ui.label("Einträge");
+                        ui.label(belegt.len().to_string());
+                        ui.end_row();
+                        for art in Art::all() {
+                            let anzahl = belegt.iter().filter(|e| e.art == *art).count();
+                            if anzahl > 0 && *art != Art::Leer {
+                                ui.label(RichText::new(art_beschriftung(&self.art_labels, art)).color(self.tabellen_farben.art(art)));
+                                ui.label(anzahl.to_string());
+                                ui.end_row();
+                            }
+                            if anzahl > 0 && *art == Art::Frage {
+                                ui.label("    davon unbeantwortet");
+                                ui.label(belegt.iter().filter(|e| e.offene_frage()).count().to_string());
+                                ui.end_row();
+                            }
+                        }
+                        ui.label("Wörter in Notizen");
+                        ui.label(woerter.to_string());
+                        ui.end_row();
+                    });
+                    ui.add_space(8.0);
+                    if !self.einstellungen.zeiterfassung_speichern {
+                        ui.label(
+                            RichText::new("Die Zeit wird nicht in der Datei gespeichert (zeiterfassung_speichern).")
+                                .size(11.0)
+                                .weak(),
                         );
-                        if ui.is_rect_visible(rect) {
-                            // Hover-Highlight
-                            if response.hovered() {
-                                ui.painter().rect_filled(rect, 4.0, ui.visuals().widgets.hovered.bg_fill);
+                    }
+                    // Buchungszeile für die Projektzeiterfassung
+                    if ui.button("Buchungszeile kopieren").clicked() {
+                        let minuten = self.bearbeitungszeit.as_secs() / 60;
+                        ui.ctx().copy_text(format!(
+                            "{}\t{}\t{}\t{}",
+                            self.projekt, self.titel, self.datum_text, minuten
+                        ));
+                    }
+                });
+            if !open {
+                self.show_statistik = false;
+            }
+        }
+
+        // Über-Dialog
+        if self.show_about_dialog {
+            let mut open = true;
+            egui::Window::new("Über MZProtokoll")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_min_width(350.0);
+                    ui.vertical_centered(|ui| {
+                        ui.add_space(8.0);
+
+                        // Logo
+                        let texture = self.icon_texture.get_or_insert_with(|| {
+                            let png_bytes = include_bytes!("../assets/icon.png");
+                            let image = image::load_from_memory(png_bytes).expect("Failed to load icon");
+                            let rgba = image.to_rgba8();
+                            let size = [rgba.width() as usize, rgba.height() as usize];
+                            let pixels = rgba.into_raw();
+                            let color_image = egui::ColorImage::from_rgba_unmultiplied(size, &pixels);
+                            ctx.load_texture("app-icon", color_image, egui::TextureOptions::LINEAR)
+                        });
+                        ui.image((texture.id(), egui::vec2(80.0, 80.0)));
+
+                        ui.add_space(12.0);
+
+                        // App-Name
+                        ui.label(RichText::new("MZProtokoll").strong().size(24.0).color(egui::Color32::WHITE));
+
+                        ui.add_space(4.0);
+                        ui.label(format!("Version {}", env!("CARGO_PKG_VERSION")));
+
+                        ui.add_space(20.0);
+
+                        if ui.add(egui::Button::new("Report an Issue").min_size(egui::vec2(200.0, 32.0))).clicked() {
+                            url_oeffnen("https://www.marcelzimmer.de");
+                        }
+                        ui.add_space(4.0);
+                        if ui.add(egui::Button::new("Follow on X").min_size(egui::vec2(200.0, 32.0))).clicked() {
+                            url_oeffnen("https://www.x.com/marcelzimmer");
+                        }
+
+                        ui.add_space(8.0);
+                    });
+                });
+            if !open {
+                self.show_about_dialog = false;
+            }
+        }
+
+        // PDF-Exportdialog (Exportprofil, Dichte-Profil, Zielgruppe)
+        if self.show_pdf_export_dialog {
+            let passwort_ok = !self
+                .pdf_exportprofil
+                .and_then(|i| self.einstellungen.export_profile.get(i))
+                .is_some_and(|p| p.passwortschutz && self.pdf_passwort.is_empty());
+            let mut exportieren = ctx.input(|i| i.key_pressed(egui::Key::Enter));
+            let mut abbrechen = ctx.input(|i| i.key_pressed(egui::Key::Escape));
+            egui::Window::new("PDF erzeugen")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_min_width(360.0);
+                    ui.label("Exportprofil:");
+                    ui.add_space(4.0);
+                    let mut auswahl = self.pdf_exportprofil;
+                    let profile = &self.einstellungen.export_profile;
+                    egui::ComboBox::from_id_salt("pdf_exportprofil")
+                        .selected_text(auswahl.and_then(|i| profile.get(i)).map_or("Keines", |p| p.name.as_str()))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut auswahl, None, "Keines");
+                            for (i, profil) in profile.iter().enumerate() {
+                                ui.selectable_value(&mut auswahl, Some(i), &profil.name);
                             }
-                            // Label links
-                            ui.painter().text(
-                                rect.left_center() + egui::vec2(8.0, 0.0),
-                                egui::Align2::LEFT_CENTER,
-                                label,
-                                egui::FontId::proportional(13.0),
-                                ui.visuals().text_color(),
-                            );
-                            // Shortcut rechts
-                            if !shortcut.is_empty() {
-                                ui.painter().text(
-                                    rect.right_center() - egui::vec2(8.0, 0.0),
-                                    egui::Align2::RIGHT_CENTER,
-                                    shortcut,
-                                    egui::FontId::proportional(12.0),
-                                    ui.visuals().weak_text_color(),
-                                );
+                        });
+                    if auswahl != self.pdf_exportprofil {
+                        // Vorgaben des Profils in die Auswahl unten übernehmen
+                        self.pdf_exportprofil = auswahl;
+                        if let Some(profil) = auswahl.and_then(|i| self.einstellungen.export_profile.get(i)) {
+                            if let Some(zielgruppe) = profil.zielgruppe {
+                                self.pdf_zielgruppe = zielgruppe;
+                            }
+                            if let Some(dichte) = profil.dichte {
+                                self.pdf_profil = dichte;
+                            }
+                            if let Some(sprache) = profil.sprache {
+                                self.pdf_sprache = sprache;
+                            }
+                            // Hinterlegtes Exportpasswort vorbelegen (Schlüsselbund ggf. langsam)
+                            if profil.passwortschutz && self.pdf_passwort.is_empty() {
+                                let (tx, rx) = mpsc::channel();
+                                self.pdf_passwort_rx = Some(rx);
+                                std::thread::spawn(move || {
+                                    let _ = tx.send(zugangsdaten_laden("export"));
+                                });
                             }
                         }
-                        let clicked = response.clicked();
-                        if clicked {
-                            match label {
-                                "Neu" => {
-                                    let theme = self.theme;
-                                    let has_omarchy = self.has_omarchy;
-                                    let icon_texture = self.icon_texture.take();
-                                    *self = ProtokollApp::new(ctx);
-                                    self.theme = theme;
-                                    self.has_omarchy = has_omarchy;
-                                    self.icon_texture = icon_texture;
+                    }
+                    if let Some(rx) = &self.pdf_passwort_rx {
+                        match rx.try_recv() {
+                            Ok(passwort) => {
+                                if let Some(passwort) = passwort.filter(|_| self.pdf_passwort.is_empty()) {
+                                    self.pdf_passwort = passwort;
                                 }
-                                "Öffnen" => self.laden(),
-                                "Speichern" => self.speichern(),
-                                "PDF erzeugen" => self.pdf_exportieren(),
-                                "Theme ändern" => self.theme = self.theme.next(self.has_omarchy),
-                                "Hilfe" => {
-                                    url_oeffnen("https://www.marcelzimmer.de");
+                                self.pdf_passwort_rx = None;
+                            }
+                            Err(mpsc::TryRecvError::Empty) => ui.ctx().request_repaint(),
+                            Err(mpsc::TryRecvError::Disconnected) => self.pdf_passwort_rx = None,
+                        }
+                    }
+                    if let Some(profil) = self.pdf_exportprofil.and_then(|i| self.einstellungen.export_profile.get(i)) {
+                        ui.label(RichText::new(profil.beschreibung()).weak());
+                        if profil.passwortschutz {
+                            ui.horizontal(|ui| {
+                                ui.label("Passwort:");
+                                ui.add(egui::TextEdit::singleline(&mut self.pdf_passwort).password(true));
+                                if self.pdf_passwort_rx.is_some() {
+                                    ui.spinner();
                                 }
-                                "Über" => self.show_about_dialog = true,
-                                _ => {}
+                            })
+                            .response
+                            .on_hover_text("Vorbelegt mit dem Exportpasswort aus „Zugangsdaten“, falls hinterlegt");
+                        }
+                    }
+                    ui.add_space(8.0);
+                    ui.label("Schriftgröße und Dichte:");
+                    ui.add_space(4.0);
+                    for profil in PdfProfil::all() {
+                        let beschreibung = format!("{} ({} pt)", profil.label(), profil.schriftgroesse());
+                        ui.radio_value(&mut self.pdf_profil, *profil, beschreibung);
+                    }
+                    ui.add_space(8.0);
+                    ui.label("Zielgruppe:");
+                    ui.add_space(4.0);
+                    for zielgruppe in [Zielgruppe::Intern, Zielgruppe::Extern] {
+                        ui.radio_value(&mut self.pdf_zielgruppe, zielgruppe, zielgruppe.label());
+                    }
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        ui.label("Sprache im PDF:");
+                        for sprache in PdfSprache::all() {
+                            ui.radio_value(&mut self.pdf_sprache, *sprache, sprache.label());
+                        }
+                    });
+                    ui.add_space(8.0);
+                    ui.checkbox(&mut self.pdf_quellenseite, "Links als Seite „Quellen & Referenzen“ am Ende");
+                    ui.add_space(8.0);
+                    ui.label("Spaltengewichte der Tabelle (0 = Spalte weglassen):");
+                    ui.add_space(4.0);
+                    let mut spalten = self.pdf_spalten();
+                    ui.horizontal(|ui| {
+                        for (i, name) in PdfSpalten::NAMEN.iter().enumerate() {
+                            ui.label(*name);
+                            // Punkt (TOP-Nummern) und Notiz lassen sich nicht weglassen
+                            let minimum = if PdfSpalten::PFLICHT.contains(&i) { 1 } else { 0 };
+                            ui.add(egui::DragValue::new(&mut spalten.0[i]).range(minimum..=40));
+                        }
+                    });
+                    if spalten != self.pdf_spalten() {
+                        self.pdf_spalten = Some(spalten);
+                    }
+                    if self.pdf_spalten.is_some() && ui.small_button("Standard aus den Einstellungen").clicked() {
+                        self.pdf_spalten = None;
+                    }
+                    ui.add_space(8.0);
+                    ui.separator();
+                    match &self.pdf_aenderungen {
+                        None => {
+                            ui.label(RichText::new("Erster Export – kein Vergleichsstand vorhanden.").weak());
+                        }
+                        Some(diff) => {
+                            ui.label(format!("Änderungen seit Version {}:", diff.version));
+                            if diff.aenderungen.is_empty() {
+                                ui.label(RichText::new("Keine Änderungen an den Einträgen.").weak());
                             }
-                            ui.close_menu();
+                            egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                                for a in &diff.aenderungen {
+                                    ui.horizontal(|ui| {
+                                        ui.label(RichText::new(a.art.label()).strong().color(a.art.color()));
+                                        ui.label(RichText::new(&a.titel).strong());
+                                        if let Some(zeile) = a.notiz.lines().find(|l| !l.trim().is_empty()) {
+                                            ui.label(RichText::new(zeile.trim()).weak());
+                                        }
+                                    });
+                                }
+                            });
+                            ui.add_enabled(
+                                !diff.aenderungen.is_empty(),
+                                egui::Checkbox::new(
+                                    &mut self.pdf_aenderungen_aufnehmen,
+                                    format!("Kapitel „Änderungen seit Version {}“ aufnehmen", diff.version),
+                                ),
+                            );
                         }
                     }
+                    ui.add_space(12.0);
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(passwort_ok, egui::Button::new(RichText::new("Exportieren").strong()).min_size(egui::vec2(120.0, 30.0)))
+                            .on_disabled_hover_text("Das Exportprofil verlangt ein Passwort.")
+                            .clicked()
+                        {
+                            exportieren = true;
+                        }
+                        if ui.add(egui::Button::new("Abbrechen").min_size(egui::vec2(120.0, 30.0))).clicked() {
+                            abbrechen = true;
+                        }
+                    });
                 });
-            });
-
-            // Kurzreferenz auf die aktuellen Theme-Farben (für Textfelder und Labels)
-            let textfarbe = self.input_text_color;
+            if abbrechen {
+                self.show_pdf_export_dialog = false;
+                self.pdf_passwort.clear();
+            } else if exportieren && passwort_ok {
+                self.show_pdf_export_dialog = false;
+                self.pdf_export_starten();
+            }
+        }
 
-            // Header-Bereich (fixiert, scrollt nicht mit)
-            {
-                // 11: Projekt
-                let mut projekt_edit = egui::TextEdit::singleline(&mut self.projekt)
-                    .hint_text(RichText::new("Projektname").font(egui::FontId::proportional(13.0)))
-                    .desired_width(400.0)
-                    .font(fette_schrift(13.0));
-                if let Some(c) = textfarbe { projekt_edit = projekt_edit.text_color(c); }
-                ui.add(projekt_edit);
+        // PDF-Fehler-Dialog
+        if self.show_pdf_error {
+            egui::Window::new("PDF-Export nicht möglich")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_min_width(400.0);
+                    ui.label("Keine passende Schriftart gefunden.");
+                    ui.add_space(4.0);
+                    ui.label("Bitte installiere eine der folgenden Schriftarten:");
+                    ui.label("  - Liberation Sans");
+                    ui.label("  - Noto Sans");
+                    ui.add_space(12.0);
+                    ui.vertical_centered(|ui| {
+                        if ui.add(egui::Button::new(RichText::new("OK").strong()).min_size(egui::vec2(120.0, 30.0))).clicked() {
+                            self.show_pdf_error = false;
+                        }
+                    });
+                });
+        }
 
-                ui.add_space(4.0);
+        // Pflichtfeld-Hinweis
+        if self.show_pflichtfeld_hinweis {
+            egui::Window::new("Pflichtfeld")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_min_width(400.0);
+                    ui.label("Bitte den Protokollführer eintragen.");
+                    ui.label(RichText::new("Ohne Protokollführer lässt sich das Protokoll als Entwurf zwischenspeichern.").weak());
+                    ui.add_space(12.0);
+                    ui.vertical_centered(|ui| {
+                        ui.horizontal(|ui| {
+                            if ui.add(egui::Button::new(RichText::new("OK").strong()).min_size(egui::vec2(120.0, 30.0))).clicked() {
+                                self.show_pflichtfeld_hinweis = false;
+                            }
+                            if ui.add(egui::Button::new("Als Entwurf zwischenspeichern").min_size(egui::vec2(120.0, 30.0))).clicked() {
+                                self.show_pflichtfeld_hinweis = false;
+                                self.entwurf_zwischenspeichern();
+                            }
+                        });
+                    });
+                });
+        }
 
-                // Titel
-                let mut titel_edit = egui::TextEdit::singleline(&mut self.titel)
-                    .font(fette_schrift(28.0))
-                    .hint_text(RichText::new("Titel").font(egui::FontId::proportional(28.0)))
-                    .desired_width(ui.available_width());
-                if let Some(c) = textfarbe { titel_edit = titel_edit.text_color(c); }
-                ui.add(titel_edit);
+        // Kümmerer prüfen: unbekannte Kürzel als Person anlegen oder korrigieren
+        if let Some(mut pruefung) = self.kuerzel_pruefung.take() {
+            let mut open = true;
+            let mut anlegen: Option<(String, String)> = None;
+            let mut korrigieren: Option<(String, String)> = None;
+            let unbekannt = self.unbekannte_kuemmerer();
+            let bekannte = self.alle_kuerzel();
+            egui::Window::new("Kümmerer prüfen")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_min_width(520.0);
+                    if unbekannt.is_empty() {
+                        ui.label("Alle Kümmerer gehören zu einer Person im Protokoll.");
+                        return;
+                    }
+                    egui::Grid::new("kuerzel_pruefung").num_columns(3).spacing([12.0, 8.0]).show(ui, |ui| {
+                        for (kuerzel, indizes) in &unbekannt {
+                            let eintraege: Vec<String> = indizes.iter().map(|i| (i + 1).to_string()).collect();
+                            ui.label(RichText::new(kuerzel).strong())
+                                .on_hover_text(format!("Einträge {}", eintraege.join(", ")));
+                            ui.horizontal(|ui| {
+                                let name = pruefung.namen.entry(kuerzel.clone()).or_default();
+                                ui.add(egui::TextEdit::singleline(name).hint_text("Name").desired_width(140.0));
+                                if ui.button("Person anlegen").clicked() {
+                                    anlegen = Some((kuerzel.clone(), name.clone()));
+                                }
+                            });
+                            ui.horizontal(|ui| {
+                                let ziel = pruefung.korrekturen.entry(kuerzel.clone()).or_default();
+                                egui::ComboBox::from_id_salt(format!("korrektur_{kuerzel}"))
+                                    .selected_text(ziel.as_str())
+                                    .width(60.0)
+                                    .show_ui(ui, |ui| {
+                                        for k in &bekannte {
+                                            ui.selectable_value(ziel, k.clone(), k);
+                                        }
+                                    });
+                                if ui.add_enabled(!ziel.is_empty(), egui::Button::new("Kürzel korrigieren")).clicked() {
+                                    korrigieren = Some((kuerzel.clone(), ziel.clone()));
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                });
+            if let Some((kuerzel, name)) = anlegen {
+                self.person_fuer_kuerzel_anlegen(&kuerzel, &name);
+            }
+            if let Some((alt, neu)) = korrigieren {
+                self.kuerzel_ersetzen(&alt, &neu);
+            }
+            if open {
+                self.kuerzel_pruefung = Some(pruefung);
+            }
+        }
 
-                ui.add_space(6.0);
+        // In neues Protokoll überführen: Titel, Personen, TOPs und offene Punkte auswählen
+        if let Some(mut auswahl) = self.ueberfuehrung.take() {
+            let mut open = true;
+            let mut ueberfuehren = false;
+            let mut abbrechen = false;
+            let titel = self.titel.clone();
+            egui::Window::new("In neues Protokoll überführen")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_width(480.0);
+                    let titel_label = if titel.is_empty() { "Titel übernehmen".to_string() } else { format!("Titel „{}“ übernehmen", titel) };
+                    ui.checkbox(&mut auswahl.titel, titel_label);
+                    ui.checkbox(&mut auswahl.personen, "Teilnehmer und „Zur Kenntnis“ übernehmen");
+                    egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                        for (ueberschrift, elemente) in [("Tagesordnung", &mut auswahl.tops), ("Offene Punkte", &mut auswahl.offene)] {
+                            ui.add_space(8.0);
+                            ui.label(RichText::new(ueberschrift).strong());
+                            if elemente.is_empty() {
+                                ui.label(RichText::new("Keine").weak());
+                            }
+                            for (e, gewaehlt) in elemente.iter_mut() {
+                                let erste_zeile = e.notiz.lines().find(|l| !l.trim().is_empty()).unwrap_or("").trim();
+                                let mut text = match (e.punkt.trim(), erste_zeile) {
+                                    ("", zeile) => zeile.to_string(),
+                                    (punkt, "") => punkt.to_string(),
+                                    (punkt, zeile) => format!("{} – {}", punkt, zeile),
+                                };
+                                let zusatz: Vec<&str> = [e.kuemmerer.trim(), e.bis.trim()].into_iter().filter(|t| !t.is_empty()).collect();
+                                if !zusatz.is_empty() {
+                                    text.push_str(&format!(" ({})", zusatz.join(", ")));
+                                }
+                                ui.checkbox(gewaehlt, text);
+                            }
+                        }
+                    });
+                    ui.add_space(12.0);
+                    ui.horizontal(|ui| {
+                        if ui.add(egui::Button::new(RichText::new("Überführen").strong()).min_size(egui::vec2(110.0, 30.0))).clicked() {
+                            ueberfuehren = true;
+                        }
+                        if ui.add(egui::Button::new("Abbrechen").min_size(egui::vec2(110.0, 30.0))).clicked() {
+                            abbrechen = true;
+                        }
+                    });
+                });
+            if ueberfuehren {
+                self.ersetzen_anfragen(ctx, Ersetzen::Ueberfuehren(auswahl));
+            } else if open && !abbrechen {
+                self.ueberfuehrung = Some(auswahl);
+            }
+        }
 
-                // Datum + Ort
-                ui.horizontal(|ui| {
-                    let mut datum_edit = egui::TextEdit::singleline(&mut self.datum_text)
-                        .desired_width(250.0)
-                        .hint_text(RichText::new("Wochentag, TT.MM.JJJJ").font(egui::FontId::proportional(14.0)))
-                        .font(fette_schrift(14.0));
-                    if let Some(c) = textfarbe { datum_edit = datum_edit.text_color(c); }
-                    ui.add(datum_edit);
-                    ui.label(RichText::new("|").size(15.0));
-                    let mut ort_edit = egui::TextEdit::singleline(&mut self.ort)
-                        .desired_width(ui.available_width())
-                        .hint_text(RichText::new("Ort").font(egui::FontId::proportional(14.0)))
-                        .font(fette_schrift(14.0));
-                    if let Some(c) = textfarbe { ort_edit = ort_edit.text_color(c); }
-                    ui.add(ort_edit);
+        // Rückfrage vor Neu bzw. Überführen bei ungespeicherten Änderungen
+        if let Some(aktion) = self.ersetzen_rueckfrage.take() {
+            let mut wahl: Option<&str> = None;
+            egui::Window::new("Ungespeicherte Änderungen")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label("Das Protokoll enthält ungespeicherte Änderungen.");
+                    ui.label(RichText::new("Beim Verwerfen bleibt der bisherige Stand über „Rückgängig“ erreichbar.").weak());
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        if ui.button(RichText::new("Speichern").strong()).clicked() {
+                            wahl = Some("speichern");
+                        }
+                        if ui.button("Verwerfen").clicked() {
+                            wahl = Some("verwerfen");
+                        }
+                        if ui.button("Abbrechen").clicked() {
+                            wahl = Some("abbrechen");
+                        }
+                    });
                 });
+            match wahl {
+                Some("speichern") => {
+                    self.speichern();
+                    if !self.ungespeichert() {
+                        self.ersetzen_ausfuehren(ctx, aktion);
+                    } else if self.save_path.is_none() && self.dialog_rx.is_some() {
+                        self.ersetzen_nach_speichern = Some(aktion);
+                    }
+                }
+                Some("verwerfen") => self.ersetzen_ausfuehren(ctx, aktion),
+                Some(_) => {}
+                None => self.ersetzen_rueckfrage = Some(aktion),
+            }
+        }
 
-                ui.add_space(12.0);
-                ui.separator();
-                ui.add_space(8.0);
+        // Aus E-Mail importieren: Titel, Teilnehmer und Einträge des Vorschlags auswählen
+        if let Some(mut import) = self.email_import.take() {
+            let mut open = true;
+            let mut uebernehmen = false;
+            let mut abbrechen = false;
+            egui::Window::new("Aus E-Mail importieren")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_width(520.0);
+                    ui.horizontal(|ui| {
+                        ui.label("Titel:");
+                        ui.add(egui::TextEdit::singleline(&mut import.titel).desired_width(f32::INFINITY));
+                    });
+                    if let Some(datum) = import.datum {
+                        ui.label(RichText::new(format!("Datum: {}, {}", wochentag_name(datum), datum.format("%d.%m.%Y"))).weak());
+                    }
+                    egui::ScrollArea::vertical().max_height(380.0).show(ui, |ui| {
+                        ui.add_space(8.0);
+                        ui.label(RichText::new("Teilnehmer").strong());
+                        for (person, gewaehlt) in import.teilnehmer.iter_mut() {
+                            ui.checkbox(gewaehlt, format!("{} [{}]", person.name, person.kuerzel));
+                        }
+                        ui.add_space(8.0);
+                        ui.label(RichText::new("Einträge (INFO)").strong());
+                        for (e, gewaehlt) in import.eintraege.iter_mut() {
+                            let mut text: String = e.notiz.chars().take(90).collect();
+                            if e.notiz.chars().count() > 90 {
+                                text.push('…');
+                            }
+                            if !e.punkt.is_empty() {
+                                text = format!("{} – {}", e.punkt, text);
+                            }
+                            ui.checkbox(gewaehlt, text).on_hover_text(&e.notiz);
+                        }
+                    });
+                    ui.add_space(12.0);
+                    ui.horizontal(|ui| {
+                        if ui.add(egui::Button::new(RichText::new("Protokoll anlegen").strong()).min_size(egui::vec2(110.0, 30.0))).clicked() {
+                            uebernehmen = true;
+                        }
+                        if ui.add(egui::Button::new("Abbrechen").min_size(egui::vec2(110.0, 30.0))).clicked() {
+                            abbrechen = true;
+                        }
+                    });
+                });
+            if uebernehmen {
+                self.email_import_uebernehmen(ctx, import);
+            } else if open && !abbrechen {
+                self.email_import = Some(import);
             }
+        }
 
-            egui::ScrollArea::vertical().show(ui, |ui| {
-                let beschriftungsfarbe = self.label_color;
+        // Vorschlag für „Über dieses Meeting" prüfen und übernehmen
+        if let Some(vorschlag) = self.zusammenfassung_vorschlag.as_mut() {
+            let mut open = true;
+            let mut aktion = None;
+            let mut verwerfen = false;
+            egui::Window::new("Zusammenfassung")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_width(480.0);
+                    ui.label(RichText::new("Vorschlag – vor dem Übernehmen bei Bedarf anpassen:").weak());
+                    ui.add_space(4.0);
+                    ui.add(egui::TextEdit::multiline(vorschlag).desired_width(f32::INFINITY).desired_rows(6));
+                    ui.add_space(12.0);
+                    ui.horizontal(|ui| {
+                        if ui.add(egui::Button::new(RichText::new("Ersetzen").strong()).min_size(egui::vec2(110.0, 30.0))).clicked() {
+                            aktion = Some(true);
+                        }
+                        if ui.add(egui::Button::new("Anhängen").min_size(egui::vec2(110.0, 30.0))).clicked() {
+                            aktion = Some(false);
+                        }
+                        if ui.add(egui::Button::new("Verwerfen").min_size(egui::vec2(110.0, 30.0))).clicked() {
+                            verwerfen = true;
+                        }
+                    });
+                });
+            if let Some(ersetzen) = aktion {
+                let text = vorschlag.trim().to_string();
+                if ersetzen || self.ueber_meeting.trim().is_empty() {
+                    self.ueber_meeting = text;
+                } else {
+                    self.ueber_meeting = format!("{}\n\n{}", self.ueber_meeting.trim_end(), text);
+                }
+                open = false;
+            }
+            if !open || verwerfen {
+                self.zusammenfassung_vorschlag = None;
+            }
+        }
 
-                // 12: Protokollführer (nebeneinander)
-                ui.horizontal_top(|ui| {
-                    abschnitts_beschriftung(ui, "Protokollführer", beschriftungs_breite,self.label_color);
-                    personen_zeile(ui, &mut self.protokollant, false, false, self.input_text_color);
+        // Nach der Freigabe: Zusammenfassung in den Matrix-Raum posten?
+        if self.matrix_nachfrage {
+            let mut posten = false;
+            let mut schliessen = false;
+            egui::Window::new("Zusammenfassung posten")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_width(460.0);
+                    ui.label(format!("In den Matrix-Raum {} posten?", self.einstellungen.matrix_raum));
+                    ui.add_space(8.0);
+                    egui::ScrollArea::vertical().max_height(260.0).show(ui, |ui| {
+                        ui.label(RichText::new(self.matrix_zusammenfassung().0).monospace());
+                    });
+                    ui.add_space(12.0);
+                    ui.horizontal(|ui| {
+                        if ui.add(egui::Button::new(RichText::new("Posten").strong()).min_size(egui::vec2(120.0, 30.0))).clicked() {
+                            posten = true;
+                        }
+                        if ui.add(egui::Button::new("Nicht posten").min_size(egui::vec2(120.0, 30.0))).clicked() {
+                            schliessen = true;
+                        }
+                    });
                 });
+            if posten {
+                self.matrix_posten();
+            }
+            if posten || schliessen {
+                self.matrix_nachfrage = false;
+            }
+        }
 
-                ui.add_space(4.0);
-                ui.separator();
-                ui.add_space(4.0);
-
-                // 13: Teilnehmer (nebeneinander, Enter → neue Zeile)
-                let mut tn_add = false;
-                let mut tn_remove: Option<usize> = None;
-                ui.horizontal_top(|ui| {
-                    if abschnitts_beschriftung_mit_plus(ui, "Teilnehmer", beschriftungs_breite,self.label_color) {
-                        self.teilnehmer.push(Person::new());
+        // Freigabe-Pipeline: pro Frame höchstens einen Schritt starten bzw. abholen
+        if let Some(mut pipeline) = self.freigabe_pipeline.take() {
+            let index = pipeline.erledigt.len();
+            if pipeline.fehler.is_none() && index < pipeline.schritte.len() {
+                if pipeline.laeuft.is_none() {
+                    pipeline.laeuft = Some(self.freigabe_schritt_ausfuehren(pipeline.schritte[index]));
+                }
+                let ergebnis = match pipeline.laeuft.as_ref().map(|rx| rx.try_recv()) {
+                    Some(Ok(ergebnis)) => Some(ergebnis),
+                    Some(Err(mpsc::TryRecvError::Disconnected)) => Some(Err("Schritt ohne Ergebnis beendet".to_string())),
+                    _ => None,
+                };
+                match ergebnis {
+                    Some(Ok(meldung)) => {
+                        pipeline.laeuft = None;
+                        pipeline.erledigt.push(Ok(meldung));
                     }
-                    let tn_len = self.teilnehmer.len();
-                    ui.vertical(|ui| {
-                        for i in 0..tn_len {
-                            let is_last = i == tn_len - 1;
-                            let focus = is_last && self.focus_new_teilnehmer;
-                            let (del, enter) =
-                                personen_zeile(ui, &mut self.teilnehmer[i], tn_len > 1, focus, self.input_text_color);
-                            if focus {
-                                self.focus_new_teilnehmer = false;
-                            }
-                            if del {
-                                tn_remove = Some(i);
+                    Some(Err(fehler)) => {
+                        pipeline.laeuft = None;
+                        pipeline.fehler = Some(fehler);
+                    }
+                    None => {}
+                }
+                ctx.request_repaint_after(std::time::Duration::from_millis(100));
+            }
+            let fertig = pipeline.erledigt.len() == pipeline.schritte.len();
+            let mut ueberspringen = false;
+            let mut schliessen = false;
+            egui::Window::new("Freigabe-Pipeline")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_width(460.0);
+                    ui.add(
+                        egui::ProgressBar::new(pipeline.erledigt.len() as f32 / pipeline.schritte.len() as f32)
+                            .text(format!("{} von {} Schritten", pipeline.erledigt.len(), pipeline.schritte.len())),
+                    );
+                    ui.add_space(8.0);
+                    for (i, schritt) in pipeline.schritte.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            match pipeline.erledigt.get(i) {
+                                Some(Ok(meldung)) => {
+                                    ui.label(RichText::new(format!("✔ {}", schritt.label())).color(Art::Fertig.color()));
+                                    ui.label(RichText::new(meldung).weak());
+                                }
+                                Some(Err(fehler)) => {
+                                    ui.label(RichText::new(format!("↷ {}", schritt.label())).color(Art::Todo.color()));
+                                    ui.label(RichText::new(format!("übersprungen: {}", fehler)).weak());
+                                }
+                                None if i == pipeline.erledigt.len() && pipeline.fehler.is_some() => {
+                                    ui.label(RichText::new(format!("✖ {}", schritt.label())).color(Art::Todo.color()));
+                                }
+                                None if i == pipeline.erledigt.len() => {
+                                    ui.spinner();
+                                    ui.label(schritt.label());
+                                }
+                                None => {
+                                    ui.label(RichText::new(format!("○ {}", schritt.label())).weak());
+                                }
                             }
-                            if enter {
-                                tn_add = true;
+                        });
+                    }
+                    if let Some(fehler) = &pipeline.fehler {
+                        ui.add_space(8.0);
+                        ui.label(RichText::new(fehler).color(Art::Todo.color()));
+                    }
+                    ui.add_space(12.0);
+                    ui.horizontal(|ui| {
+                        if fertig {
+                            if ui.add(egui::Button::new("Schließen").min_size(egui::vec2(120.0, 30.0))).clicked() {
+                                schliessen = true;
                             }
+                            return;
+                        }
+                        if pipeline.fehler.is_some()
+                            && ui.add(egui::Button::new("Überspringen").min_size(egui::vec2(120.0, 30.0))).clicked()
+                        {
+                            ueberspringen = true;
+                        }
+                        if ui.add(egui::Button::new("Abbrechen").min_size(egui::vec2(120.0, 30.0))).clicked() {
+                            schliessen = true;
                         }
                     });
                 });
-                if let Some(idx) = tn_remove {
-                    self.teilnehmer.remove(idx);
+            if ueberspringen {
+                if let Some(fehler) = pipeline.fehler.take() {
+                    pipeline.erledigt.push(Err(fehler));
                 }
-                if tn_add {
-                    self.teilnehmer.push(Person::new());
-                    self.focus_new_teilnehmer = true;
+            }
+            if schliessen {
+                if !fertig {
+                    let schritt = pipeline.schritte[pipeline.erledigt.len()];
+                    self.toast_zeigen(format!("Freigabe-Pipeline vor „{}“ abgebrochen", schritt.label()));
                 }
+            } else {
+                self.freigabe_pipeline = Some(pipeline);
+            }
+        }
 
-                ui.add_space(4.0);
-                ui.separator();
-                ui.add_space(4.0);
+        // Toast (verschwindet nach fünf Sekunden)
+        if let Some((text, seit)) = &self.toast {
+            let restzeit = std::time::Duration::from_secs(5).saturating_sub(seit.elapsed());
+            if restzeit.is_zero() {
+                self.toast = None;
+            } else {
+                egui::Area::new(egui::Id::new("toast"))
+                    .anchor(egui::Align2::RIGHT_BOTTOM, [-16.0, -16.0])
+                    .order(egui::Order::Foreground)
+                    .show(ctx, |ui| {
+                        egui::Frame::popup(ui.style()).show(ui, |ui| {
+                            ui.label(text.as_str());
+                        });
+                    });
+                ctx.request_repaint_after(restzeit);
+            }
+        }
 
-                // 13: Zur Kenntnis (nebeneinander)
-                let mut zk_add = false;
-                let mut zk_remove: Option<usize> = None;
-                ui.horizontal_top(|ui| {
-                    if abschnitts_beschriftung_mit_plus(ui, "Zur Kenntnis", beschriftungs_breite,self.label_color) {
-                        self.zur_kenntnis.push(Person::new());
-                    }
-                    let zk_len = self.zur_kenntnis.len();
-                    ui.vertical(|ui| {
-                        for i in 0..zk_len {
-                            let is_last = i == zk_len - 1;
-                            let focus = is_last && self.focus_new_zur_kenntnis;
-                            let (del, enter) =
-                                personen_zeile(ui, &mut self.zur_kenntnis[i], zk_len > 1, focus, self.input_text_color);
-                            if focus {
-                                self.focus_new_zur_kenntnis = false;
+        // Erste-Schritte-Assistent: Projekt → Meetingtyp → Teilnehmer → Agenda
+        if let Some(assistent) = self.assistent.as_mut() {
+            let mut open = true;
+            let mut fertig = false;
+            egui::Window::new("Neues Protokoll")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_width(420.0);
+                    ui.horizontal(|ui| {
+                        for (i, name) in ASSISTENT_SCHRITTE.iter().enumerate() {
+                            if i > 0 {
+                                ui.label(RichText::new("→").weak());
                             }
-                            if del {
-                                zk_remove = Some(i);
+                            let text = format!("{}. {}", i + 1, name);
+                            if i == assistent.schritt {
+                                ui.label(RichText::new(text).strong());
+                            } else {
+                                ui.label(RichText::new(text).weak());
                             }
-                            if enter {
-                                zk_add = true;
+                        }
+                    });
+                    ui.separator();
+                    match assistent.schritt {
+                        0 => {
+                            ui.label("Zu welchem Projekt gehört das Meeting?");
+                            ui.add(egui::TextEdit::singleline(&mut assistent.projekt).hint_text("Projekt").desired_width(f32::INFINITY));
+                            ui.add_space(6.0);
+                            ui.label("Wo findet es statt?");
+                            ui.add(egui::TextEdit::singleline(&mut assistent.ort).hint_text("Ort oder Videokonferenz").desired_width(f32::INFINITY));
+                        }
+                        1 => {
+                            ui.label("Welche Art von Meeting?");
+                            for (i, (typ, beschreibung)) in MEETINGTYPEN.iter().enumerate() {
+                                ui.radio_value(&mut assistent.meetingtyp, i, *typ).on_hover_text(*beschreibung);
+                            }
+                            ui.add_space(6.0);
+                            ui.label("Titel (leer = Name des Meetingtyps):");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut assistent.titel)
+                                    .hint_text(MEETINGTYPEN[assistent.meetingtyp].0)
+                                    .desired_width(f32::INFINITY),
+                            );
+                        }
+                        2 => {
+                            ui.label("Wer nimmt teil? Ein Name pro Zeile.");
+                            ui.add(egui::TextEdit::multiline(&mut assistent.teilnehmer).desired_rows(6).desired_width(f32::INFINITY));
+                            ui.label(RichText::new("Kürzel werden aus den Namen abgeleitet.").weak());
+                        }
+                        _ => {
+                            ui.label("Welche Punkte stehen auf der Agenda? Ein Punkt pro Zeile.");
+                            ui.add(egui::TextEdit::multiline(&mut assistent.agenda).desired_rows(6).desired_width(f32::INFINITY));
+                        }
+                    }
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(assistent.schritt > 0, egui::Button::new("Zurück")).clicked() {
+                            assistent.schritt -= 1;
+                        }
+                        if assistent.schritt + 1 < ASSISTENT_SCHRITTE.len() {
+                            if ui.button("Weiter").clicked() {
+                                assistent.schritt += 1;
                             }
+                        } else if ui.button("Protokoll anlegen").clicked() {
+                            fertig = true;
                         }
                     });
                 });
-                if let Some(idx) = zk_remove {
-                    self.zur_kenntnis.remove(idx);
+            if fertig {
+                if let Some(assistent) = self.assistent.take() {
+                    self.assistent_abschliessen(ctx, assistent);
                 }
-                if zk_add {
-                    self.zur_kenntnis.push(Person::new());
-                    self.focus_new_zur_kenntnis = true;
-                }
-
-                ui.add_space(4.0);
-                ui.separator();
-                ui.add_space(4.0);
-
-                // 14: Über dieses Meeting
-                ui.horizontal_top(|ui| {
-                    abschnitts_beschriftung(ui, "Über dieses Meeting", beschriftungs_breite,self.label_color);
-                    let mut meeting_edit = egui::TextEdit::multiline(&mut self.ueber_meeting)
-                        .hint_text(RichText::new("Informationen zum Meeting").font(egui::FontId::proportional(14.0)))
-                        .desired_width(ui.available_width())
-                        .desired_rows(3)
-                        .font(fette_schrift(14.0));
-                    if let Some(c) = textfarbe { meeting_edit = meeting_edit.text_color(c); }
-                    ui.add(meeting_edit);
-                });
+            } else if !open {
+                self.assistent = None;
+            }
+        }
 
-                ui.add_space(4.0);
-                ui.separator();
-                ui.add_space(4.0);
-                ui.horizontal(|ui| {
-                    abschnitts_beschriftung(ui, "Status", beschriftungs_breite,self.label_color);
-                    let prev_entwurf = self.ist_entwurf;
-                    let prev_freigegeben = self.ist_freigegeben;
-                    let entwurf_label = {
-                        let mut rt = RichText::new("Entwurf").font(fette_schrift(14.0));
-                        if let Some(c) = textfarbe { rt = rt.color(c); }
-                        rt
-                    };
-                    let freigegeben_label = {
-                        let mut rt = RichText::new("Freigegeben").font(fette_schrift(14.0));
-                        if let Some(c) = textfarbe { rt = rt.color(c); }
-                        rt
-                    };
-                    let cb_w = 140.0;
-                    ui.allocate_ui_with_layout(
-                        egui::vec2(cb_w, ui.spacing().interact_size.y),
-                        egui::Layout::left_to_right(egui::Align::Center),
-                        |ui| {
-                            ui.set_min_width(cb_w);
-                            ui.checkbox(&mut self.ist_entwurf, entwurf_label);
-                        },
-                    );
-                    ui.checkbox(&mut self.ist_freigegeben, freigegeben_label);
-                    if self.ist_entwurf && !prev_entwurf {
-                        self.ist_freigegeben = false;
-                    }
-                    if self.ist_freigegeben && !prev_freigegeben {
-                        self.ist_entwurf = false;
-                    }
-                    if !self.ist_entwurf && prev_entwurf {
-                        self.ist_freigegeben = true;
-                    }
-                    if !self.ist_freigegeben && prev_freigegeben {
-                        self.ist_entwurf = true;
+        // Vorlagen-Auswahl
+        if let Some(vorlagen) = self.vorlagen_auswahl.clone() {
+            let mut open = true;
+            let mut gewaehlt: Option<std::path::PathBuf> = None;
+            egui::Window::new("Neu aus Vorlage")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_min_width(350.0);
+                    if vorlagen.is_empty() {
+                        ui.label("Keine Vorlagen gefunden.");
+                        ui.label(RichText::new("Über „Als Vorlage speichern“ lassen sich Vorlagen anlegen.").weak());
                     }
-                });
-
-                ui.add_space(4.0);
-                ui.separator();
-                ui.add_space(4.0);
-                ui.horizontal(|ui| {
-                    abschnitts_beschriftung(ui, "Klassifizierung", beschriftungs_breite,self.label_color);
-                    let cb_w = 140.0;
-                    let sicherheiten = Sicherheit::all();
-                    let last_idx = sicherheiten.len() - 1;
-                    for (idx, s) in sicherheiten.iter().enumerate() {
-                        let mut checked = self.sicherheit == *s;
-                        let label = {
-                            let mut rt = RichText::new(s.label()).font(fette_schrift(14.0));
-                            if let Some(c) = textfarbe { rt = rt.color(c); }
-                            rt
-                        };
-                        if idx < last_idx {
-                            let clicked = ui.allocate_ui_with_layout(
-                                egui::vec2(cb_w, ui.spacing().interact_size.y),
-                                egui::Layout::left_to_right(egui::Align::Center),
-                                |ui| {
-                                    ui.set_min_width(cb_w);
-                                    ui.checkbox(&mut checked, label).clicked()
-                                },
-                            ).inner;
-                            if clicked {
-                                if checked { self.sicherheit = s.clone(); }
-                                else { self.sicherheit = Sicherheit::Intern; }
-                            }
-                        } else {
-                            if ui.checkbox(&mut checked, label).clicked() {
-                                if checked { self.sicherheit = s.clone(); }
-                                else { self.sicherheit = Sicherheit::Intern; }
-                            }
+                    for pfad in &vorlagen {
+                        let name = pfad.file_stem().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                        if ui.add(egui::Button::new(name).min_size(egui::vec2(330.0, 26.0))).clicked() {
+                            gewaehlt = Some(pfad.clone());
                         }
                     }
-                });
-
-                ui.add_space(4.0);
-                ui.separator();
-                ui.add_space(4.0);
-
-                // Einträge-Tabelle
-                let mut entry_remove: Option<usize> = None;
-                let mut entry_swap: Option<(usize, usize)> = None;
-                let entry_len = self.eintraege.len();
-
-                let available = ui.available_width();
-                let punkt_w: f32 = 160.0;
-                let art_w: f32 = 140.0;
-                let kum_text_w: f32 = 130.0;
-                let kum_dd_w: f32 = 35.0;
-                let bis_w: f32 = 88.0;
-                let action_w: f32 = 76.0;
-                let col_sp: f32 = 8.0;
-                let gaps = 5.0 * col_sp;
-                let notiz_w = (available
-                    - punkt_w
-                    - art_w
-                    - (kum_text_w + kum_dd_w + 4.0)
-                    - bis_w
-                    - action_w
-                    - gaps
-                    - 16.0)
-                    .max(150.0);
-
-                let mut header_line_y: f32 = 0.0;
-
-                ui.add_space(12.0);
-
-                let line_x_range = ui.cursor().left()..=ui.available_rect_before_wrap().right();
-
-                let prev_notiz_focus = self.notiz_had_focus.take();
-                let mut new_notiz_focus: Option<(usize, usize)> = None;
-
-                let _grid_resp = egui::Grid::new("eintraege")
-                    .num_columns(6)
-                    .spacing([col_sp, 6.0])
-                    .striped(false)
-                    .show(ui, |ui| {
-                        // Kopfzeile — linksbündig, erzwingt Spaltenbreiten
-                        ui.with_layout(egui::Layout::left_to_right(egui::Align::Min), |ui| {
-                            ui.set_min_width(punkt_w);
-                            ui.label(RichText::new("").size(14.0));
-                        });
-                        ui.with_layout(egui::Layout::left_to_right(egui::Align::Min), |ui| {
-                            ui.set_min_width(art_w);
-                            let mut rt = RichText::new("Art").font(fette_schrift(14.0));
-                            if let Some(c) = beschriftungsfarbe { rt = rt.color(c); }
-                            ui.label(rt);
-                        });
-                        ui.with_layout(egui::Layout::left_to_right(egui::Align::Min), |ui| {
-                            ui.set_min_width(notiz_w);
-                            let mut rt = RichText::new("Notiz").font(fette_schrift(14.0));
-                            if let Some(c) = beschriftungsfarbe { rt = rt.color(c); }
-                            ui.label(rt);
-                        });
-                        ui.with_layout(egui::Layout::left_to_right(egui::Align::Min), |ui| {
-                            ui.set_min_width(kum_text_w + kum_dd_w + 4.0);
-                            let mut rt = RichText::new("Kümmerer").font(fette_schrift(14.0));
-                            if let Some(c) = beschriftungsfarbe { rt = rt.color(c); }
-                            ui.label(rt);
-                        });
-                        ui.with_layout(egui::Layout::left_to_right(egui::Align::Min), |ui| {
-                            ui.set_min_width(bis_w);
-                            let mut rt = RichText::new("Bis").font(fette_schrift(14.0));
-                            if let Some(c) = beschriftungsfarbe { rt = rt.color(c); }
-                            ui.label(rt);
-                        });
-                        ui.label("");
-                        ui.end_row();
-
-                        header_line_y = ui.cursor().top();
-
-                        // Spacer-Zeile für Abstand zwischen Linie und Daten
-                        ui.add_sized([0.0, 6.0], egui::Label::new(""));
-                        ui.add_sized([0.0, 6.0], egui::Label::new(""));
-                        ui.add_sized([0.0, 6.0], egui::Label::new(""));
-                        ui.add_sized([0.0, 6.0], egui::Label::new(""));
-                        ui.add_sized([0.0, 6.0], egui::Label::new(""));
-                        ui.label("");
-                        ui.end_row();
-
-                        for i in 0..entry_len {
-                            let is_todo = self.eintraege[i].art == Art::Todo;
-
-                            // 4: Punkt (oben ausgerichtet)
-                            ui.with_layout(egui::Layout::top_down(egui::Align::LEFT), |ui| {
-                                let mut punkt_edit = egui::TextEdit::singleline(&mut self.eintraege[i].punkt)
-                                    .hint_text(RichText::new(if is_todo { "" } else { "Punkt" }).font(egui::FontId::proportional(14.0)))
-                                    .font(fette_schrift(14.0))
-                                    .interactive(!is_todo)
-                                    .frame(!is_todo);
-                                if let Some(c) = textfarbe { punkt_edit = punkt_edit.text_color(c); }
-                                ui.add_sized([punkt_w, 20.0], punkt_edit);
-                            });
-
-                            // 8: Art-Dropdown (oben ausgerichtet)
-                            ui.with_layout(egui::Layout::top_down(egui::Align::LEFT), |ui| {
-                                let sel = RichText::new(self.eintraege[i].art.selected_label())
-                                    .color(self.eintraege[i].art.color())
-                                    .font(fette_schrift(14.0));
-                                egui::ComboBox::from_id_salt(format!("art_{i}"))
-                                    .selected_text(sel)
-                                    .width(art_w)
-                                    .show_ui(ui, |ui| {
-                                        let prev_art = self.eintraege[i].art.clone();
-                                        for art in Art::all() {
-                                            let txt = RichText::new(art.label()).color(art.color()).font(fette_schrift(14.0));
-                                            ui.selectable_value(
-                                                &mut self.eintraege[i].art,
-                                                art.clone(),
-                                                txt,
-                                            );
-                                        }
-                                        if self.eintraege[i].art == Art::Todo && prev_art != Art::Todo {
-                                            self.eintraege[i].punkt.clear();
-                                        }
-                                    });
-                            });
-
-                            // 3: Notiz — dynamische Höhe + Cursor-Navigation
-                            let notiz_id = egui::Id::new(("notiz", i));
-                            let notiz_rows = self.eintraege[i].notiz.lines().count().max(1);
-                            let mut notiz_edit = egui::TextEdit::multiline(&mut self.eintraege[i].notiz)
-                                .id(notiz_id)
-                                .hint_text(RichText::new("Notiz").font(egui::FontId::proportional(14.0)))
-                                .desired_width(notiz_w)
-                                .desired_rows(notiz_rows)
-                                .font(fette_schrift(14.0));
-                            if let Some(c) = textfarbe { notiz_edit = notiz_edit.text_color(c); }
-                            let notiz_resp = ui.add(notiz_edit);
-                            if self.focus_notiz == Some(i) {
-                                notiz_resp.request_focus();
-                                self.focus_notiz = None;
-                            }
-                            if notiz_resp.has_focus() {
-                                if let Some(state) = egui::TextEdit::load_state(ui.ctx(), notiz_id) {
-                                    if let Some(range) = state.cursor.char_range() {
-                                        new_notiz_focus = Some((i, range.primary.index));
-                                    }
-                                }
-                            }
-
-                            // 5+7+10: Kümmerer (oben ausgerichtet, nur bei TODO sichtbar)
-                            ui.with_layout(egui::Layout::top_down(egui::Align::LEFT), |ui| {
-                                ui.horizontal(|ui| {
-                                    let mut kum_edit = egui::TextEdit::singleline(
-                                            &mut self.eintraege[i].kuemmerer,
-                                        )
-                                        .hint_text(RichText::new(if is_todo { "Wer?" } else { "" }).font(egui::FontId::proportional(14.0)))
-                                        .desired_width(kum_text_w)
-                                        .interactive(is_todo)
-                                        .frame(is_todo)
-                                        .font(fette_schrift(14.0));
-                                    if let Some(c) = textfarbe { kum_edit = kum_edit.text_color(c); }
-                                    ui.add(kum_edit);
-                                    if is_todo {
-                                        egui::ComboBox::from_id_salt(format!("kum_sel_{i}"))
-                                            .selected_text("")
-                                            .width(kum_dd_w)
-                                            .show_ui(ui, |ui| {
-                                                if alle_kuerzel.is_empty() {
-                                                    ui.label("Keine Kürzel");
-                                                }
-                                                for k in &alle_kuerzel {
-                                                    if ui
-                                                        .selectable_label(
-                                                            self.eintraege[i].kuemmerer == *k,
-                                                            k,
-                                                        )
-                                                        .clicked()
-                                                    {
-                                                        self.eintraege[i].kuemmerer = k.clone();
-                                                    }
-                                                }
-                                            });
-                                    } else {
-                                        ui.add_space(kum_dd_w + 4.0);
-                                    }
-                                });
-                            });
-
-                            // 6: Bis (oben ausgerichtet, nur bei TODO sichtbar, mit Datumsvalidierung)
-                            ui.with_layout(egui::Layout::top_down(egui::Align::LEFT), |ui| {
-                                let bis_valid = self.eintraege[i].bis.is_empty()
-                                    || NaiveDate::parse_from_str(
-                                        &self.eintraege[i].bis,
-                                        "%d.%m.%Y",
-                                    )
-                                    .is_ok();
-                                let bis_color = if !bis_valid {
-                                    egui::Color32::from_rgb(231, 76, 60)
-                                } else if let Some(c) = textfarbe {
-                                    c
-                                } else {
-                                    ui.visuals().text_color()
-                                };
-                                ui.add_sized(
-                                    [bis_w, 20.0],
-                                    egui::TextEdit::singleline(&mut self.eintraege[i].bis)
-                                        .hint_text(RichText::new(if is_todo { "TT.MM.JJJJ" } else { "" }).font(egui::FontId::proportional(14.0)))
-                                        .text_color(bis_color)
-                                        .interactive(is_todo)
-                                        .frame(is_todo)
-                                        .font(fette_schrift(14.0)),
-                                );
-                            });
+                });
+            if let Some(pfad) = gewaehlt {
+                self.vorlagen_auswahl = None;
+                self.vorlage_anwenden(&pfad);
+            } else if !open {
+                self.vorlagen_auswahl = None;
+            }
+        }
 
-                            // Aktionen: Hoch / Runter / Löschen
-                            ui.with_layout(egui::Layout::top_down(egui::Align::LEFT), |ui| {
+        // Zusammenführen-Dialog für mögliche Dubletten
+        if self.duplikat_dialog.is_some() {
+            let mut uebernehmen = false;
+            let mut abbrechen = false;
+            if let Some(paare) = self.duplikat_dialog.as_mut() {
+                egui::Window::new("Mögliche Dubletten")
+                    .collapsible(false)
+                    .resizable(false)
+                    .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                    .show(ctx, |ui| {
+                        ui.set_min_width(480.0);
+                        ui.label("Diese Einträge ähneln bereits vorhandenen Einträgen:");
+                        ui.add_space(8.0);
+                        egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                            for paar in paare.iter_mut() {
+                                let vorhanden = self
+                                    .eintraege
+                                    .iter()
+                                    .find(|e| e.id == paar.vorhanden)
+                                    .map(|e| e.notiz.as_str())
+                                    .unwrap_or("");
+                                ui.label(
+                                    RichText::new(format!("{} % ähnlich", (paar.aehnlichkeit * 100.0).round()))
+                                        .strong(),
+                                );
+                                ui.label(format!("Neu: {}", paar.neu.notiz.replace('\n', " ")));
+                                ui.label(RichText::new(format!("Vorhanden: {}", vorhanden.replace('\n', " "))).weak());
                                 ui.horizontal(|ui| {
-                                    ui.spacing_mut().item_spacing.x = 0.0;
-                                    if i > 0 {
-                                        if ui.add_sized([20.0, 20.0], egui::Button::new("▲")).clicked() {
-                                            entry_swap = Some((i, i - 1));
-                                        }
-                                    } else {
-                                        ui.add_sized([20.0, 20.0], egui::Label::new(""));
-                                    }
-                                    ui.add_space(2.0);
-                                    if i + 1 < entry_len {
-                                        if ui.add_sized([20.0, 20.0], egui::Button::new("▼")).clicked() {
-                                            entry_swap = Some((i, i + 1));
-                                        }
-                                    } else {
-                                        ui.add_sized([20.0, 20.0], egui::Label::new(""));
-                                    }
-                                    ui.add_space(10.0);
-                                    if entry_len > 1 {
-                                        if ui.add_sized([20.0, 20.0], egui::Button::new(
-                                            RichText::new("×").color(egui::Color32::from_rgb(231, 76, 60)),
-                                        )).clicked() {
-                                            entry_remove = Some(i);
-                                        }
-                                    }
+                                    ui.radio_value(&mut paar.aktion, DuplikatAktion::Zusammenfuehren, "Zusammenführen");
+                                    ui.radio_value(&mut paar.aktion, DuplikatAktion::BeideBehalten, "Beide behalten");
+                                    ui.radio_value(&mut paar.aktion, DuplikatAktion::Verwerfen, "Verwerfen");
                                 });
-                            });
-                            ui.end_row();
-                        }
+                                ui.separator();
+                            }
+                        });
+                        ui.add_space(8.0);
+                        ui.horizontal(|ui| {
+                            if ui.button("Übernehmen").clicked() {
+                                uebernehmen = true;
+                            }
+                            if ui.button("Abbrechen").clicked() {
+                                abbrechen = true;
+                            }
+                        });
                     });
+            }
+            if uebernehmen {
+                if let Some(paare) = self.duplikat_dialog.take() {
+                    self.duplikate_anwenden(paare);
+                }
+            } else if abbrechen {
+                self.duplikat_dialog = None;
+            }
+        }
 
-                // Cursor hoch/runter zwischen Notiz-Feldern
-                {
-                    let up = ui.input(|inp| inp.key_pressed(egui::Key::ArrowUp));
-                    let down = ui.input(|inp| inp.key_pressed(egui::Key::ArrowDown));
-                    if let Some((prev_i, prev_cursor)) = prev_notiz_focus {
-                        if prev_i < self.eintraege.len() {
-                            let text = &self.eintraege[prev_i].notiz;
-                            let mut safe_idx = prev_cursor.min(text.len());
-                            while safe_idx > 0 && !text.is_char_boundary(safe_idx) {
-                                safe_idx -= 1;
+        // Platzhalter-Dialog (interaktive Vorlagenfelder)
+        if self.platzhalter_dialog.is_some() {
+            let mut uebernehmen = false;
+            let mut abbrechen = false;
+            if let Some(dialog) = self.platzhalter_dialog.as_mut() {
+                egui::Window::new("Platzhalter ausfüllen")
+                    .collapsible(false)
+                    .resizable(false)
+                    .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                    .show(ctx, |ui| {
+                        ui.set_min_width(400.0);
+                        egui::Grid::new("platzhalter").num_columns(2).spacing([12.0, 8.0]).show(ui, |ui| {
+                            for (name, wert) in dialog.werte.iter_mut() {
+                                ui.label(name.as_str());
+                                ui.add(egui::TextEdit::singleline(wert).desired_width(260.0));
+                                ui.end_row();
+                            }
+                        });
+                        ui.add_space(12.0);
+                        ui.horizontal(|ui| {
+                            if ui.button("Übernehmen").clicked() {
+                                uebernehmen = true;
                             }
-                            let on_first = !text[..safe_idx].contains('\n');
-                            let on_last = !text[safe_idx..].contains('\n');
-                            if up && on_first && prev_i > 0 {
-                                self.focus_notiz = Some(prev_i - 1);
-                            } else if down && on_last && prev_i + 1 < self.eintraege.len() {
-                                self.focus_notiz = Some(prev_i + 1);
+                            if ui.button("Abbrechen").clicked() {
+                                abbrechen = true;
                             }
-                        }
+                        });
+                    });
+            }
+            if uebernehmen {
+                if let Some(dialog) = self.platzhalter_dialog.take() {
+                    let mut inhalt = dialog.inhalt;
+                    for (name, wert) in &dialog.werte {
+                        inhalt = platzhalter_einsetzen(&inhalt, name, wert);
                     }
-                    self.notiz_had_focus = new_notiz_focus;
+                    self.protokoll_aus_vorlage(&inhalt);
                 }
+            } else if abbrechen {
+                self.platzhalter_dialog = None;
+            }
+        }
 
-                // 15: Linie unter Kopfzeile (gleiche Breite wie Separators)
-                ui.painter().hline(
-                    line_x_range,
-                    header_line_y - 1.0,
-                    egui::Stroke::new(1.5, egui::Color32::from_rgb(180, 180, 180)),
-                );
+        // Beschlussregister (archivweit, mit Suche)
+        if self.show_beschlussregister {
+            let mut open = true;
+            egui::Window::new("Beschlussregister")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(true)
+                .default_size([700.0, 420.0])
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.beschluss_suche)
+                            .hint_text("Suchen (Nummer, Protokoll, Beschlusstext)")
+                            .desired_width(f32::INFINITY),
+                    );
+                    ui.add_space(8.0);
+                    let suche = self.beschluss_suche.to_lowercase();
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        egui::Grid::new("beschlussregister").num_columns(4).striped(true).spacing([12.0, 4.0]).show(ui, |ui| {
+                            ui.label(RichText::new("Nr.").strong());
+                            ui.label(RichText::new("Datum").strong());
+                            ui.label(RichText::new("Protokoll").strong());
+                            ui.label(RichText::new("Beschluss").strong());
+                            ui.end_row();
+                            for b in self.beschlussregister.iter().rev().filter(|b| {
+                                suche.is_empty()
+                                    || b.nr.to_lowercase().contains(&suche)
+                                    || b.titel.to_lowercase().contains(&suche)
+                                    || b.beschluss.to_lowercase().contains(&suche)
+                            }) {
+                                ui.label(RichText::new(&b.nr).color(Art::Entscheidung.color()).strong());
+                                ui.label(&b.datum);
+                                ui.label(&b.titel);
+                                ui.add(egui::Label::new(&b.beschluss).wrap());
+                                ui.end_row();
+                            }
+                        });
+                    });
+                });
+            if !open {
+                self.show_beschlussregister = false;
+            }
+        }
 
-                if let Some((a, b)) = entry_swap {
-                    self.eintraege.swap(a, b);
-                }
-                if let Some(idx) = entry_remove {
-                    self.eintraege.remove(idx);
+        // Starthinweis: heute bereits begonnene Protokolle öffnen statt neu anfangen
+        if !self.heute_begonnen.is_empty() {
+            let mut open = true;
+            let mut oeffnen: Option<std::path::PathBuf> = None;
+            let mut schliessen = false;
+            egui::Window::new("Heute bereits begonnen")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_min_width(360.0);
+                    ui.label("Für heute gibt es bereits ein Protokoll:");
+                    ui.add_space(6.0);
+                    for d in &self.heute_begonnen {
+                        ui.horizontal(|ui| {
+                            let mut text = if d.titel.trim().is_empty() {
+                                d.pfad.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+                            } else {
+                                d.titel.clone()
+                            };
+                            if !d.projekt.trim().is_empty() {
+                                text = format!("{} ({})", text, d.projekt.trim());
+                            }
+                            ui.label(RichText::new(text).strong()).on_hover_text(d.pfad.display().to_string());
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                if ui.button("Öffnen").clicked() {
+                                    oeffnen = Some(d.pfad.clone());
+                                }
+                            });
+                        });
+                    }
+                    ui.add_space(8.0);
+                    if ui.button("Neues Protokoll beginnen").clicked() {
+                        schliessen = true;
+                    }
+                });
+            if let Some(pfad) = oeffnen {
+                self.heute_begonnen.clear();
+                match std::fs::read_to_string(&pfad) {
+                    Ok(content) => self.datei_uebernehmen(pfad, &content),
+                    Err(e) => self.toast_zeigen(format!("Datei konnte nicht gelesen werden: {}", e)),
                 }
+            } else if schliessen || !open {
+                self.heute_begonnen.clear();
+            }
+        }
 
-                ui.add_space(8.0);
-                if ui.button(RichText::new("+ Eintrag hinzufügen").strong()).clicked() {
-                    self.eintraege.push(Eintrag::new());
-                }
-            });
-        });
+        // Datei extern geändert: Unterschiede je Kopffeld bzw. Abschnitt zur Auswahl
+        if let Some(mut aenderung) = self.externe_aenderung.take() {
+            let mut uebernehmen = false;
+            let mut verwerfen = false;
+            egui::Window::new("Datei wurde extern geändert")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_min_width(480.0);
+                    ui.label("Die Protokolldatei wurde außerhalb von MZProtokoll bearbeitet. Welche Änderungen sollen übernommen werden?");
+                    ui.add_space(8.0);
+                    egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                        for b in aenderung.bereiche.iter_mut() {
+                            ui.horizontal(|ui| {
+                                ui.checkbox(&mut b.uebernehmen, RichText::new(&b.name).strong());
+                                if b.konflikt {
+                                    ui.label(RichText::new("auch hier geändert").color(egui::Color32::from_rgb(231, 76, 60)));
+                                }
+                            });
+                            ui.indent(("extern", &b.name), |ui| {
+                                let leer = |t: &str| if t.is_empty() { "(leer)".to_string() } else { t.to_string() };
+                                if b.name == "Einträge" && !aenderung.eintraege.is_empty() {
+                                    for a in &aenderung.eintraege {
+                                        ui.horizontal(|ui| {
+                                            ui.label(RichText::new(a.art.label()).strong().color(a.art.color()));
+                                            ui.label(&a.titel);
+                                        });
+                                    }
+                                } else if b.kopffeld {
+                                    ui.label(format!("Datei: {}", leer(&b.datei)));
+                                    ui.label(RichText::new(format!("App: {}", leer(&b.lokal))).weak());
+                                } else {
+                                    ui.collapsing("Unterschied anzeigen", |ui| {
+                                        ui.label("Datei:");
+                                        ui.label(RichText::new(leer(&b.datei)).monospace());
+                                        ui.label("App:");
+                                        ui.label(RichText::new(leer(&b.lokal)).monospace().weak());
+                                    });
+                                }
+                            });
+                            ui.add_space(4.0);
+                        }
+                    });
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        if ui.button(RichText::new("Auswahl übernehmen").strong()).clicked() {
+                            uebernehmen = true;
+                        }
+                        if ui.button("Stand der App behalten").clicked() {
+                            verwerfen = true;
+                        }
+                    });
+                });
+            if uebernehmen {
+                self.externe_aenderung_uebernehmen(aenderung);
+            } else if verwerfen {
+                self.datei_zeit = aenderung.zeit;
+            } else {
+                self.externe_aenderung = Some(aenderung);
+            }
+        }
 
-        // Über-Dialog
-        if self.show_about_dialog {
+        // Bericht erstellen: Projekt und Zeitraum wählen, dann als Markdown oder PDF speichern
+        if let Some(mut dialog) = self.bericht_dialog.take() {
             let mut open = true;
-            egui::Window::new("Über MZProtokoll")
+            let mut export: Option<bool> = None;
+            egui::Window::new("Bericht erstellen")
                 .open(&mut open)
                 .collapsible(false)
                 .resizable(false)
                 .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
                 .show(ctx, |ui| {
-                    ui.set_min_width(350.0);
-                    ui.vertical_centered(|ui| {
-                        ui.add_space(8.0);
+                    ui.set_min_width(360.0);
+                    egui::Grid::new("bericht_dialog").num_columns(2).spacing([12.0, 8.0]).show(ui, |ui| {
+                        ui.label("Projekt");
+                        egui::ComboBox::from_id_salt("bericht_projekt")
+                            .selected_text(if dialog.projekt.is_empty() { "Ohne Projekt" } else { dialog.projekt.as_str() })
+                            .show_ui(ui, |ui| {
+                                for p in &dialog.projekte {
+                                    let label = if p.is_empty() { "Ohne Projekt" } else { p.as_str() };
+                                    ui.selectable_value(&mut dialog.projekt, p.clone(), label);
+                                }
+                            });
+                        ui.end_row();
+                        ui.label("Zeitraum");
+                        ui.horizontal(|ui| {
+                            let vorher = dialog.zeitraum;
+                            ui.radio_value(&mut dialog.zeitraum, BerichtZeitraum::Woche, "Woche");
+                            ui.radio_value(&mut dialog.zeitraum, BerichtZeitraum::Monat, "Monat");
+                            ui.radio_value(&mut dialog.zeitraum, BerichtZeitraum::Frei, "Frei");
+                            if dialog.zeitraum != vorher {
+                                bericht_zeitraum_setzen(&mut dialog);
+                            }
+                        });
+                        ui.end_row();
+                        ui.label("Von – Bis");
+                        ui.horizontal(|ui| {
+                            let frei = dialog.zeitraum == BerichtZeitraum::Frei;
+                            ui.add_enabled(frei, egui::TextEdit::singleline(&mut dialog.von).hint_text("TT.MM.JJJJ").desired_width(100.0));
+                            ui.label("–");
+                            ui.add_enabled(frei, egui::TextEdit::singleline(&mut dialog.bis).hint_text("TT.MM.JJJJ").desired_width(100.0));
+                        });
+                        ui.end_row();
+                    });
+                    ui.add_space(8.0);
+                    ui.label(
+                        RichText::new("Fasst Meetings, Entscheidungen sowie neue und erledigte TODOs aller Protokolle des Projekts im Zeitraum zusammen.")
+                            .size(11.0)
+                            .weak(),
+                    );
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        if ui.button("Als PDF …").clicked() {
+                            export = Some(true);
+                        }
+                        if ui.button("Als Markdown …").clicked() {
+                            export = Some(false);
+                        }
+                    });
+                });
+            if let Some(pdf) = export {
+                self.bericht_exportieren(&dialog, pdf);
+            } else if open {
+                self.bericht_dialog = Some(dialog);
+            }
+        }
 
-                        // Logo
-                        let texture = self.icon_texture.get_or_insert_with(|| {
-                            let png_bytes = include_bytes!("../assets/icon.png");
-                            let image = image::load_from_memory(png_bytes).expect("Failed to load icon");
-                            let rgba = image.to_rgba8();
-                            let size = [rgba.width() as usize, rgba.height() as usize];
-                            let pixels = rgba.into_raw();
-                            let color_image = egui::ColorImage::from_rgba_unmultiplied(size, &pixels);
-                            ctx.load_texture("app-icon", color_image, egui::TextureOptions::LINEAR)
+        // Meine Aufgaben im Archiv
+        if let Some(aufgaben) = self.archiv_aufgaben.take() {
+            let mut open = true;
+            let heute = Local::now().date_naive();
+            egui::Window::new("Meine Aufgaben im Archiv")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(true)
+                .default_size([700.0, 420.0])
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    if aufgaben.is_empty() {
+                        ui.label(format!("Keine TODOs mit Kümmerer {} im Archiv.", self.profil.kuerzel.trim()));
+                        return;
+                    }
+                    ui.horizontal(|ui| {
+                        faellig_filter_anzeigen(ui, &mut self.faellig_filter);
+                    });
+                    ui.separator();
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        egui::Grid::new("archiv_aufgaben").num_columns(3).striped(true).spacing([12.0, 4.0]).show(ui, |ui| {
+                            ui.label(RichText::new("Bis").strong());
+                            ui.label(RichText::new("Aufgabe").strong());
+                            ui.label(RichText::new("Protokoll").strong());
+                            ui.end_row();
+                            for a in aufgaben.iter().filter(|a| self.faellig_filter.passt(&a.bis)) {
+                                let ueberfaellig = datum_aus_text(&a.bis).is_some_and(|d| d < heute);
+                                let bis = RichText::new(&a.bis);
+                                ui.label(if ueberfaellig { bis.color(egui::Color32::from_rgb(231, 76, 60)).strong() } else { bis });
+                                ui.add(egui::Label::new(&a.aufgabe).wrap());
+                                ui.label(&a.protokoll).on_hover_text(a.pfad.display().to_string());
+                                ui.end_row();
+                            }
                         });
-                        ui.image((texture.id(), egui::vec2(80.0, 80.0)));
+                    });
+                });
+            if open {
+                self.archiv_aufgaben = Some(aufgaben);
+            }
+        }
 
-                        ui.add_space(12.0);
+        // Druckdialog (IPP)
+        if self.druck_dialog.is_some() {
+            let mut open = true;
+            let mut senden = false;
+            if let Some(dialog) = self.druck_dialog.as_mut() {
+                // Ergebnisse der Druckersuche und des Sende-Threads übernehmen
+                if let Some(ref rx) = dialog.suche_rx {
+                    loop {
+                        match rx.try_recv() {
+                            Ok(drucker) => {
+                                if dialog.uri.is_empty() {
+                                    dialog.uri = drucker.1.clone();
+                                }
+                                if !dialog.gefundene_drucker.contains(&drucker) {
+                                    dialog.gefundene_drucker.push(drucker);
+                                }
+                            }
+                            Err(mpsc::TryRecvError::Empty) => break,
+                            Err(mpsc::TryRecvError::Disconnected) => {
+                                dialog.suche_rx = None;
+                                break;
+                            }
+                        }
+                    }
+                }
+                if let Some(ref rx) = dialog.auftrag_rx {
+                    if let Ok(ergebnis) = rx.try_recv() {
+                        dialog.status = Some(match ergebnis {
+                            Ok(()) => "Druckauftrag wurde angenommen.".to_string(),
+                            Err(fehler) => fehler,
+                        });
+                        dialog.auftrag_rx = None;
+                    }
+                }
 
-                        // App-Name
-                        ui.label(RichText::new("MZProtokoll").strong().size(24.0).color(egui::Color32::WHITE));
+                egui::Window::new("An Gerät senden")
+                    .open(&mut open)
+                    .collapsible(false)
+                    .resizable(false)
+                    .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                    .show(ctx, |ui| {
+                        ui.set_min_width(420.0);
+                        egui::Grid::new("druck_optionen").num_columns(2).spacing([12.0, 8.0]).show(ui, |ui| {
+                            ui.label("Drucker");
+                            let auswahl = dialog
+                                .gefundene_drucker
+                                .iter()
+                                .find(|(_, uri)| *uri == dialog.uri)
+                                .map(|(name, _)| name.clone())
+                                .unwrap_or_default();
+                            egui::ComboBox::from_id_salt("druck_drucker")
+                                .selected_text(auswahl)
+                                .width(300.0)
+                                .show_ui(ui, |ui| {
+                                    if dialog.gefundene_drucker.is_empty() {
+                                        ui.label(if dialog.suche_rx.is_some() { "Suche läuft …" } else { "Keine Drucker gefunden" });
+                                    }
+                                    for (name, uri) in &dialog.gefundene_drucker {
+                                        ui.selectable_value(&mut dialog.uri, uri.clone(), name);
+                                    }
+                                });
+                            ui.end_row();
 
-                        ui.add_space(4.0);
-                        ui.label(format!("Version {}", env!("CARGO_PKG_VERSION")));
+                            ui.label("Adresse");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut dialog.uri)
+                                    .hint_text("ipp://drucker:631/ipp/print")
+                                    .desired_width(300.0),
+                            );
+                            ui.end_row();
 
-                        ui.add_space(20.0);
+                            ui.label("Duplex");
+                            egui::ComboBox::from_id_salt("druck_seiten")
+                                .selected_text(IPP_SEITEN[dialog.seiten].1)
+                                .width(300.0)
+                                .show_ui(ui, |ui| {
+                                    for (i, (_, label)) in IPP_SEITEN.iter().enumerate() {
+                                        ui.selectable_value(&mut dialog.seiten, i, *label);
+                                    }
+                                });
+                            ui.end_row();
 
-                        if ui.add(egui::Button::new("Report an Issue").min_size(egui::vec2(200.0, 32.0))).clicked() {
-                            url_oeffnen("https://www.marcelzimmer.de");
-                        }
-                        ui.add_space(4.0);
-                        if ui.add(egui::Button::new("Follow on X").min_size(egui::vec2(200.0, 32.0))).clicked() {
-                            url_oeffnen("https://www.x.com/marcelzimmer");
-                        }
+                            ui.label("Fach");
+                            egui::ComboBox::from_id_salt("druck_fach")
+                                .selected_text(IPP_FAECHER[dialog.fach].1)
+                                .width(300.0)
+                                .show_ui(ui, |ui| {
+                                    for (i, (_, label)) in IPP_FAECHER.iter().enumerate() {
+                                        ui.selectable_value(&mut dialog.fach, i, *label);
+                                    }
+                                });
+                            ui.end_row();
 
-                        ui.add_space(8.0);
+                            ui.label("Kopien");
+                            ui.add(egui::DragValue::new(&mut dialog.kopien).range(1..=99));
+                            ui.end_row();
+
+                            ui.label("Zielgruppe");
+                            ui.horizontal(|ui| {
+                                for zielgruppe in [Zielgruppe::Intern, Zielgruppe::Extern] {
+                                    ui.radio_value(&mut self.pdf_zielgruppe, zielgruppe, zielgruppe.label());
+                                }
+                            });
+                            ui.end_row();
+                        });
+
+                        if let Some(ref status) = dialog.status {
+                            ui.add_space(8.0);
+                            ui.label(status);
+                        }
+                        ui.add_space(12.0);
+                        ui.vertical_centered(|ui| {
+                            let bereit = !dialog.uri.trim().is_empty() && dialog.auftrag_rx.is_none();
+                            if ui
+                                .add_enabled(bereit, egui::Button::new(RichText::new("Drucken").strong()).min_size(egui::vec2(120.0, 30.0)))
+                                .clicked()
+                            {
+                                senden = true;
+                            }
+                        });
                     });
-                });
+            }
+            if senden {
+                self.drucken();
+            }
             if !open {
-                self.show_about_dialog = false;
+                self.druck_dialog = None;
             }
         }
 
-        // PDF-Fehler-Dialog
-        if self.show_pdf_error {
-            egui::Window::new("PDF-Export nicht möglich")
+        // Herabstufungs-Dialog (Klassifizierung senken nur mit Begründung)
+        if let Some(neu) = self.herabstufung.clone() {
+            let mut bestaetigt = false;
+            let mut abgebrochen = false;
+            egui::Window::new("Klassifizierung herabstufen")
                 .collapsible(false)
                 .resizable(false)
                 .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
                 .show(ctx, |ui| {
                     ui.set_min_width(400.0);
-                    ui.label("Keine passende Schriftart gefunden.");
-                    ui.add_space(4.0);
-                    ui.label("Bitte installiere eine der folgenden Schriftarten:");
-                    ui.label("  - Liberation Sans");
-                    ui.label("  - Noto Sans");
+                    ui.label(format!(
+                        "Das Protokoll wird von „{}“ auf „{}“ herabgestuft.",
+                        self.sicherheit.label(),
+                        neu.label()
+                    ));
+                    ui.add_space(8.0);
+                    ui.label("Begründung:");
+                    ui.add(
+                        egui::TextEdit::multiline(&mut self.herabstufung_begruendung)
+                            .desired_width(f32::INFINITY)
+                            .desired_rows(3),
+                    );
                     ui.add_space(12.0);
-                    ui.vertical_centered(|ui| {
-                        if ui.add(egui::Button::new(RichText::new("OK").strong()).min_size(egui::vec2(120.0, 30.0))).clicked() {
-                            self.show_pdf_error = false;
+                    ui.horizontal(|ui| {
+                        let hat_begruendung = !self.herabstufung_begruendung.trim().is_empty();
+                        if ui.add_enabled(hat_begruendung, egui::Button::new("Herabstufen")).clicked() {
+                            bestaetigt = true;
+                        }
+                        if ui.button("Abbrechen").clicked() {
+                            abgebrochen = true;
                         }
                     });
                 });
+            if bestaetigt {
+                let aktion = format!(
+                    "Klassifizierung herabgestuft: {} → {} (Begründung: {})",
+                    self.sicherheit.label(),
+                    neu.label(),
+                    self.herabstufung_begruendung.trim()
+                );
+                self.audit_protokollieren(&aktion);
+                self.sicherheit = neu;
+                self.herabstufung = None;
+            } else if abgebrochen {
+                self.herabstufung = None;
+            }
         }
 
-        // Pflichtfeld-Hinweis
-        if self.show_pflichtfeld_hinweis {
-            egui::Window::new("Pflichtfeld")
-                .collapsible(false)
-                .resizable(false)
-                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
-                .show(ctx, |ui| {
-                    ui.set_min_width(400.0);
-                    ui.label("Bitte den Protokollführer eintragen.");
-                    ui.add_space(12.0);
-                    ui.vertical_centered(|ui| {
-                        if ui.add(egui::Button::new(RichText::new("OK").strong()).min_size(egui::vec2(120.0, 30.0))).clicked() {
-                            self.show_pflichtfeld_hinweis = false;
+        // Abschluss-Assistent „Protokoll versandfertig"
+        if self.versand_checkliste.is_some() {
+            let mut abschliessen = false;
+            let mut abbrechen = false;
+            if let Some(checkliste) = self.versand_checkliste.as_mut() {
+                egui::Window::new("Protokoll versandfertig")
+                    .collapsible(false)
+                    .resizable(false)
+                    .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                    .show(ctx, |ui| {
+                        ui.set_min_width(400.0);
+                        for (punkt, erledigt) in checkliste.punkte.iter_mut() {
+                            ui.checkbox(erledigt, punkt.as_str());
                         }
+                        ui.add_space(8.0);
+                        ui.label(RichText::new("Automatische Prüfung").strong());
+                        if checkliste.hinweise.is_empty() {
+                            ui.label(RichText::new("Keine Auffälligkeiten.").color(Art::Fertig.color()));
+                        }
+                        for hinweis in &checkliste.hinweise {
+                            ui.label(RichText::new(hinweis).color(Art::Todo.color()));
+                        }
+                        ui.add_space(12.0);
+                        ui.horizontal(|ui| {
+                            if ui.button("Abschließen").clicked() {
+                                abschliessen = true;
+                            }
+                            if ui.button("Abbrechen").clicked() {
+                                abbrechen = true;
+                            }
+                        });
                     });
-                });
+            }
+            if abschliessen {
+                if let Some(checkliste) = self.versand_checkliste.take() {
+                    let offen: Vec<&str> = checkliste
+                        .punkte
+                        .iter()
+                        .filter(|(_, erledigt)| !erledigt)
+                        .map(|(punkt, _)| punkt.as_str())
+                        .collect();
+                    let erfuellt = checkliste.punkte.len() - offen.len();
+                    let aktion = if offen.is_empty() {
+                        format!("Versand-Checkliste abgeschlossen: alle {} Punkte erfüllt", erfuellt)
+                    } else {
+                        format!(
+                            "Versand-Checkliste abgeschlossen: {}/{} Punkte erfüllt, offen: {}",
+                            erfuellt,
+                            checkliste.punkte.len(),
+                            offen.join(", ")
+                        )
+                    };
+                    self.audit_protokollieren(&aktion);
+                }
+            } else if abbrechen {
+                self.versand_checkliste = None;
+            }
         }
 
         // Beenden-Dialog
         if self.show_quit_dialog {
+            let ungespeichert = self.ungespeichert();
+            let mut aktion: Option<&str> = None;
             egui::Window::new("Beenden")
                 .collapsible(false)
                 .resizable(false)
                 .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
                 .show(ctx, |ui| {
-                    ui.label("Möchten Sie die Anwendung wirklich beenden?");
+                    if ungespeichert {
+                        ui.label("Das Protokoll enthält ungespeicherte Änderungen.");
+                        if self.save_path.is_none() {
+                            ui.label(RichText::new("Beim PDF-Export ohne Speicherort wird nur das PDF geschrieben.").weak());
+                        }
+                    } else {
+                        ui.label("Alle Änderungen sind gespeichert. Möchten Sie die Anwendung beenden?");
+                    }
                     ui.add_space(8.0);
                     ui.horizontal(|ui| {
-                        if ui.button("Ja").clicked() {
-                            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                        if ungespeichert {
+                            if ui.button(RichText::new("Speichern und beenden").strong()).clicked() {
+                                aktion = Some("speichern");
+                            }
+                            if ui.button("PDF exportieren und beenden").clicked() {
+                                aktion = Some("pdf");
+                            }
+                            if ui.button("Ohne Speichern beenden").clicked() {
+                                aktion = Some("beenden");
+                            }
+                        } else {
+                            if ui.button(RichText::new("Beenden").strong()).clicked() {
+                                aktion = Some("beenden");
+                            }
+                            if ui.button("PDF exportieren und beenden").clicked() {
+                                aktion = Some("pdf");
+                            }
                         }
-                        if ui.button("Nein").clicked() {
-                            self.show_quit_dialog = false;
+                        if ui.button("Abbrechen").clicked() {
+                            aktion = Some("abbrechen");
                         }
                     });
                 });
+            if let Some(aktion) = aktion {
+                self.show_quit_dialog = false;
+                match aktion {
+                    "speichern" => self.speichern_und_beenden(ctx),
+                    "pdf" => {
+                        self.pdf_exportieren();
+                        self.beenden_nach_dialog = self.show_pdf_export_dialog;
+                    }
+                    "beenden" => self.beenden(ctx),
+                    _ => {}
+                }
+            }
         }
     }
 }